@@ -33,6 +33,59 @@ CREATE TYPE public.async_kind AS ENUM (
 
 ALTER TYPE public.async_kind OWNER TO mido;
 
+--
+-- Name: bracket_kind; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.bracket_kind AS ENUM (
+    'single_elimination',
+    'double_elimination'
+);
+
+
+ALTER TYPE public.bracket_kind OWNER TO mido;
+
+--
+-- Name: bracket_side; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.bracket_side AS ENUM (
+    'winners',
+    'losers',
+    'grand_final'
+);
+
+
+ALTER TYPE public.bracket_side OWNER TO mido;
+
+--
+-- Name: draft_action_source; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.draft_action_source AS ENUM (
+    'discord',
+    'racetime',
+    'web',
+    'timer'
+);
+
+
+ALTER TYPE public.draft_action_source OWNER TO mido;
+
+--
+-- Name: draft_step_kind; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.draft_step_kind AS ENUM (
+    'go_first',
+    'ban',
+    'pick',
+    'boolean_choice'
+);
+
+
+ALTER TYPE public.draft_step_kind OWNER TO mido;
+
 --
 -- Name: hash_icon; Type: TYPE; Schema: public; Owner: mido
 --
@@ -131,6 +184,35 @@ CREATE TYPE public.racetime_pronouns AS ENUM (
 
 ALTER TYPE public.racetime_pronouns OWNER TO mido;
 
+--
+-- Name: rating_category; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.rating_category AS ENUM (
+    'solo',
+    'co_op',
+    'multiworld'
+);
+
+
+ALTER TYPE public.rating_category OWNER TO mido;
+
+--
+-- Name: reschedule_request_state; Type: TYPE; Schema: public; Owner: mido
+--
+
+CREATE TYPE public.reschedule_request_state AS ENUM (
+    'pending',
+    'accepted',
+    'declined',
+    'withdrawn',
+    'superseded',
+    'expired'
+);
+
+
+ALTER TYPE public.reschedule_request_state OWNER TO mido;
+
 --
 -- Name: restream_ok; Type: TYPE; Schema: public; Owner: mido
 --
@@ -338,6 +420,108 @@ CREATE TABLE public.asyncs (
 
 ALTER TABLE public.asyncs OWNER TO mido;
 
+--
+-- Name: availability; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.availability (
+    user_id bigint NOT NULL,
+    weekday smallint NOT NULL,
+    hour smallint NOT NULL,
+    CONSTRAINT availability_hour_check CHECK (((hour >= 0) AND (hour <= 23))),
+    CONSTRAINT availability_weekday_check CHECK (((weekday >= 0) AND (weekday <= 6)))
+);
+
+
+ALTER TABLE public.availability OWNER TO mido;
+
+--
+-- Name: availability_exceptions; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.availability_exceptions (
+    user_id bigint NOT NULL,
+    date date NOT NULL,
+    hours smallint[] NOT NULL
+);
+
+
+ALTER TABLE public.availability_exceptions OWNER TO mido;
+
+--
+-- Name: availability_suggestions; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.availability_suggestions (
+    thread bigint NOT NULL,
+    message bigint NOT NULL
+);
+
+
+ALTER TABLE public.availability_suggestions OWNER TO mido;
+
+--
+-- Name: bracket_matches; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.bracket_matches (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    side public.bracket_side NOT NULL,
+    round smallint NOT NULL,
+    "position" smallint NOT NULL,
+    team1 bigint,
+    team2 bigint,
+    ready boolean DEFAULT false NOT NULL,
+    team1_wins smallint DEFAULT 0 NOT NULL,
+    team2_wins smallint DEFAULT 0 NOT NULL,
+    decided boolean DEFAULT false NOT NULL,
+    winner bigint,
+    CONSTRAINT bracket_matches_position_check CHECK (("position" >= 0)),
+    CONSTRAINT bracket_matches_round_check CHECK ((round > 0))
+);
+
+
+ALTER TABLE public.bracket_matches OWNER TO mido;
+
+--
+-- Name: bracket_races; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.bracket_races (
+    race bigint NOT NULL,
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    side public.bracket_side NOT NULL,
+    round smallint NOT NULL,
+    "position" smallint NOT NULL,
+    reported boolean DEFAULT false NOT NULL,
+    winner bigint
+);
+
+
+ALTER TABLE public.bracket_races OWNER TO mido;
+
+--
+-- Name: brackets; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.brackets (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    kind public.bracket_kind NOT NULL,
+    size smallint NOT NULL,
+    game_count smallint NOT NULL,
+    CONSTRAINT brackets_game_count_check CHECK ((game_count > 0)),
+    CONSTRAINT brackets_size_check CHECK ((size >= 2))
+);
+
+
+ALTER TABLE public.brackets OWNER TO mido;
+
 --
 -- Name: discord_roles; Type: TABLE; Schema: public; Owner: mido
 --
@@ -354,6 +538,57 @@ CREATE TABLE public.discord_roles (
 
 ALTER TABLE public.discord_roles OWNER TO mido;
 
+--
+-- Name: draft_actions; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.draft_actions (
+    race bigint NOT NULL,
+    idx integer NOT NULL,
+    "timestamp" timestamp with time zone NOT NULL,
+    source public.draft_action_source NOT NULL,
+    actor_discord bigint,
+    actor_racetime text,
+    action jsonb NOT NULL,
+    state_before jsonb NOT NULL,
+    undone_at timestamp with time zone,
+    undone_by bigint,
+    actor_user bigint
+);
+
+
+ALTER TABLE public.draft_actions OWNER TO mido;
+
+--
+-- Name: draft_timer_state; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.draft_timer_state (
+    race bigint NOT NULL,
+    draft_state jsonb NOT NULL,
+    step_started_at timestamp with time zone NOT NULL,
+    reminders_sent integer DEFAULT 0 NOT NULL
+);
+
+
+ALTER TABLE public.draft_timer_state OWNER TO mido;
+
+--
+-- Name: draft_timers; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.draft_timers (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    step_kind public.draft_step_kind NOT NULL,
+    time_limit interval NOT NULL,
+    reminders interval[] DEFAULT '{}'::interval[] NOT NULL,
+    default_action jsonb
+);
+
+
+ALTER TABLE public.draft_timers OWNER TO mido;
+
 --
 -- Name: events; Type: TABLE; Schema: public; Owner: mido
 --
@@ -395,12 +630,74 @@ CREATE TABLE public.events (
     single_settings jsonb,
     manual_reporting_with_breaks boolean DEFAULT false NOT NULL,
     emulator_settings_reminder boolean DEFAULT false NOT NULL,
-    prevent_late_joins boolean DEFAULT false NOT NULL
+    prevent_late_joins boolean DEFAULT false NOT NULL,
+    draft_spec jsonb,
+    pending_approval boolean DEFAULT false NOT NULL,
+    reschedule_confirmation boolean DEFAULT false NOT NULL,
+    rejected boolean DEFAULT false NOT NULL
 );
 
 
 ALTER TABLE public.events OWNER TO mido;
 
+--
+-- Name: group_matches; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.group_matches (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    group_number smallint NOT NULL,
+    round smallint NOT NULL,
+    team1 bigint NOT NULL,
+    team2 bigint NOT NULL,
+    race bigint,
+    reported_at timestamp with time zone,
+    winner bigint,
+    team1_time interval,
+    team2_time interval,
+    CONSTRAINT group_matches_group_number_check CHECK ((group_number > 0)),
+    CONSTRAINT group_matches_round_check CHECK ((round > 0))
+);
+
+
+ALTER TABLE public.group_matches OWNER TO mido;
+
+--
+-- Name: group_members; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.group_members (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    team bigint NOT NULL,
+    group_number smallint NOT NULL,
+    CONSTRAINT group_members_group_number_check CHECK ((group_number > 0))
+);
+
+
+ALTER TABLE public.group_members OWNER TO mido;
+
+--
+-- Name: group_phases; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.group_phases (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    num_groups smallint NOT NULL,
+    advancing smallint NOT NULL,
+    tiebreakers jsonb NOT NULL,
+    CONSTRAINT group_phases_advancing_check CHECK ((advancing > 0)),
+    CONSTRAINT group_phases_num_groups_check CHECK ((num_groups > 0))
+);
+
+
+ALTER TABLE public.group_phases OWNER TO mido;
+
 --
 -- Name: looking_for_team; Type: TABLE; Schema: public; Owner: mido
 --
@@ -529,7 +826,9 @@ CREATE TABLE public.phase_round_options (
     event character varying(8) NOT NULL,
     phase text NOT NULL,
     round text NOT NULL,
-    display_fr text
+    display_fr text,
+    deadline timestamp with time zone,
+    deadline_reminder interval DEFAULT '3 days'::interval NOT NULL
 );
 
 
@@ -571,6 +870,41 @@ CREATE TABLE public.race_player_videos (
 
 ALTER TABLE public.race_player_videos OWNER TO mido;
 
+--
+-- Name: race_result_log; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.race_result_log (
+    race bigint NOT NULL,
+    editor bigint NOT NULL,
+    edited_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+
+ALTER TABLE public.race_result_log OWNER TO mido;
+
+--
+-- Name: race_results; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.race_results (
+    race bigint NOT NULL,
+    room text,
+    team bigint,
+    discord_id bigint,
+    racetime_id text,
+    name text,
+    twitch_username text,
+    finish_time interval,
+    placement smallint,
+    retimed boolean DEFAULT false NOT NULL,
+    CONSTRAINT race_results_check CHECK (((team IS NOT NULL) OR (discord_id IS NOT NULL) OR (name IS NOT NULL))),
+    CONSTRAINT race_results_placement_check CHECK ((placement > 0))
+);
+
+
+ALTER TABLE public.race_results OWNER TO mido;
+
 --
 -- Name: races; Type: TABLE; Schema: public; Owner: mido
 --
@@ -667,6 +1001,106 @@ CREATE TABLE public.racetime_maintenance (
 
 ALTER TABLE public.racetime_maintenance OWNER TO mido;
 
+--
+-- Name: rated_asyncs; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.rated_asyncs (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    kind public.async_kind NOT NULL,
+    skipped boolean DEFAULT false NOT NULL,
+    period bigint NOT NULL,
+    category public.rating_category,
+    CONSTRAINT rated_asyncs_category_check CHECK ((skipped OR (category IS NOT NULL)))
+);
+
+
+ALTER TABLE public.rated_asyncs OWNER TO mido;
+
+--
+-- Name: rated_races; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.rated_races (
+    race bigint NOT NULL,
+    skipped boolean DEFAULT false NOT NULL,
+    period bigint NOT NULL,
+    category public.rating_category,
+    CONSTRAINT rated_races_category_check CHECK ((skipped OR (category IS NOT NULL)))
+);
+
+
+ALTER TABLE public.rated_races OWNER TO mido;
+
+--
+-- Name: rating_history; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.rating_history (
+    period bigint NOT NULL,
+    category public.rating_category NOT NULL,
+    user_id bigint NOT NULL,
+    rating double precision NOT NULL,
+    deviation double precision NOT NULL,
+    volatility double precision NOT NULL,
+    races integer NOT NULL
+);
+
+
+ALTER TABLE public.rating_history OWNER TO mido;
+
+--
+-- Name: rating_periods; Type: SEQUENCE; Schema: public; Owner: mido
+--
+
+CREATE SEQUENCE public.rating_periods
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.rating_periods OWNER TO mido;
+
+--
+-- Name: ratings; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.ratings (
+    user_id bigint NOT NULL,
+    category public.rating_category NOT NULL,
+    rating double precision NOT NULL,
+    deviation double precision NOT NULL,
+    volatility double precision NOT NULL,
+    races integer NOT NULL
+);
+
+
+ALTER TABLE public.ratings OWNER TO mido;
+
+--
+-- Name: reschedule_requests; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.reschedule_requests (
+    id bigint NOT NULL,
+    race bigint NOT NULL,
+    requester bigint NOT NULL,
+    requested_by bigint NOT NULL,
+    channel bigint NOT NULL,
+    start timestamp with time zone NOT NULL,
+    requested_at timestamp with time zone NOT NULL,
+    expires_at timestamp with time zone NOT NULL,
+    state public.reschedule_request_state DEFAULT 'pending'::public.reschedule_request_state NOT NULL,
+    resolved_by bigint,
+    resolved_at timestamp with time zone
+);
+
+
+ALTER TABLE public.reschedule_requests OWNER TO mido;
+
 --
 -- Name: restreamers; Type: TABLE; Schema: public; Owner: mido
 --
@@ -702,38 +1136,106 @@ CREATE TABLE public.rsl_seeds (
 ALTER TABLE public.rsl_seeds OWNER TO mido;
 
 --
--- Name: speedgaming_disambiguation_messages; Type: TABLE; Schema: public; Owner: mido
+-- Name: scheduling_deadline_state; Type: TABLE; Schema: public; Owner: mido
 --
 
-CREATE TABLE public.speedgaming_disambiguation_messages (
-    message_id bigint NOT NULL,
-    speedgaming_id bigint NOT NULL
+CREATE TABLE public.scheduling_deadline_state (
+    race bigint NOT NULL,
+    deadline timestamp with time zone NOT NULL,
+    reminded boolean DEFAULT false NOT NULL,
+    flagged boolean DEFAULT false NOT NULL
 );
 
 
-ALTER TABLE public.speedgaming_disambiguation_messages OWNER TO mido;
+ALTER TABLE public.scheduling_deadline_state OWNER TO mido;
 
 --
--- Name: team_members; Type: TABLE; Schema: public; Owner: mido
+-- Name: seeds; Type: TABLE; Schema: public; Owner: mido
 --
 
-CREATE TABLE public.team_members (
+CREATE TABLE public.seeds (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
     team bigint NOT NULL,
-    member bigint NOT NULL,
-    status public.signup_status NOT NULL,
-    role public.team_role NOT NULL,
-    startgg_id character varying(8)
+    seed integer NOT NULL,
+    CONSTRAINT seeds_seed_check CHECK ((seed > 0))
 );
 
 
-ALTER TABLE public.team_members OWNER TO mido;
+ALTER TABLE public.seeds OWNER TO mido;
 
 --
--- Name: teams; Type: TABLE; Schema: public; Owner: mido
+-- Name: speedgaming_disambiguation_messages; Type: TABLE; Schema: public; Owner: mido
 --
 
-CREATE TABLE public.teams (
-    id bigint NOT NULL,
+CREATE TABLE public.speedgaming_disambiguation_messages (
+    message_id bigint NOT NULL,
+    speedgaming_id bigint NOT NULL
+);
+
+
+ALTER TABLE public.speedgaming_disambiguation_messages OWNER TO mido;
+
+--
+-- Name: swiss_matches; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.swiss_matches (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    round smallint NOT NULL,
+    team1 bigint NOT NULL,
+    team2 bigint,
+    race bigint,
+    reported_at timestamp with time zone,
+    winner bigint,
+    double_dnf boolean DEFAULT false NOT NULL,
+    CONSTRAINT swiss_matches_check CHECK (((team2 IS NOT NULL) OR ((race IS NULL) AND (winner = team1)))),
+    CONSTRAINT swiss_matches_double_dnf_check CHECK (((NOT double_dnf) OR (winner IS NULL))),
+    CONSTRAINT swiss_matches_round_check CHECK ((round > 0))
+);
+
+
+ALTER TABLE public.swiss_matches OWNER TO mido;
+
+--
+-- Name: swiss_phases; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.swiss_phases (
+    series character varying(8) NOT NULL,
+    event character varying(8) NOT NULL,
+    phase text NOT NULL,
+    rounds smallint NOT NULL,
+    CONSTRAINT swiss_phases_rounds_check CHECK ((rounds > 0))
+);
+
+
+ALTER TABLE public.swiss_phases OWNER TO mido;
+
+--
+-- Name: team_members; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.team_members (
+    team bigint NOT NULL,
+    member bigint NOT NULL,
+    status public.signup_status NOT NULL,
+    role public.team_role NOT NULL,
+    startgg_id character varying(8)
+);
+
+
+ALTER TABLE public.team_members OWNER TO mido;
+
+--
+-- Name: teams; Type: TABLE; Schema: public; Owner: mido
+--
+
+CREATE TABLE public.teams (
+    id bigint NOT NULL,
     series character varying(8) NOT NULL,
     event character varying(8) NOT NULL,
     name character varying(64),
@@ -774,6 +1276,8 @@ CREATE TABLE public.users (
     discord_username character varying(32),
     challonge_id text,
     startgg_id character varying(8),
+    calendar_token character(32),
+    timezone text,
     CONSTRAINT users_check CHECK (((discord_id IS NULL) OR ((discord_discriminator IS NULL) <> (discord_username IS NULL)))),
     CONSTRAINT users_check1 CHECK (((racetime_id IS NULL) = (racetime_display_name IS NULL))),
     CONSTRAINT users_check2 CHECK (((discord_id IS NULL) = (discord_display_name IS NULL)))
@@ -810,6 +1314,54 @@ ALTER TABLE ONLY public.async_teams
     ADD CONSTRAINT async_teams_pkey PRIMARY KEY (team, kind);
 
 
+--
+-- Name: availability_exceptions availability_exceptions_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.availability_exceptions
+    ADD CONSTRAINT availability_exceptions_pkey PRIMARY KEY (user_id, date);
+
+
+--
+-- Name: availability availability_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.availability
+    ADD CONSTRAINT availability_pkey PRIMARY KEY (user_id, weekday, hour);
+
+
+--
+-- Name: availability_suggestions availability_suggestions_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.availability_suggestions
+    ADD CONSTRAINT availability_suggestions_pkey PRIMARY KEY (thread);
+
+
+--
+-- Name: bracket_matches bracket_matches_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_matches
+    ADD CONSTRAINT bracket_matches_pkey PRIMARY KEY (series, event, phase, side, round, "position");
+
+
+--
+-- Name: bracket_races bracket_races_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_races
+    ADD CONSTRAINT bracket_races_pkey PRIMARY KEY (race);
+
+
+--
+-- Name: brackets brackets_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.brackets
+    ADD CONSTRAINT brackets_pkey PRIMARY KEY (series, event, phase);
+
+
 --
 -- Name: discord_roles discord_roles_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -818,6 +1370,30 @@ ALTER TABLE ONLY public.discord_roles
     ADD CONSTRAINT discord_roles_pkey PRIMARY KEY (id);
 
 
+--
+-- Name: draft_actions draft_actions_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_actions
+    ADD CONSTRAINT draft_actions_pkey PRIMARY KEY (race, idx);
+
+
+--
+-- Name: draft_timer_state draft_timer_state_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_timer_state
+    ADD CONSTRAINT draft_timer_state_pkey PRIMARY KEY (race);
+
+
+--
+-- Name: draft_timers draft_timers_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_timers
+    ADD CONSTRAINT draft_timers_pkey PRIMARY KEY (series, event, step_kind);
+
+
 --
 -- Name: events events_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -826,6 +1402,38 @@ ALTER TABLE ONLY public.events
     ADD CONSTRAINT events_pkey PRIMARY KEY (series, event);
 
 
+--
+-- Name: group_matches group_matches_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_pkey PRIMARY KEY (series, event, phase, team1, team2);
+
+
+--
+-- Name: group_matches group_matches_race_key; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_race_key UNIQUE (race);
+
+
+--
+-- Name: group_members group_members_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_members
+    ADD CONSTRAINT group_members_pkey PRIMARY KEY (series, event, phase, team);
+
+
+--
+-- Name: group_phases group_phases_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_phases
+    ADD CONSTRAINT group_phases_pkey PRIMARY KEY (series, event, phase);
+
+
 --
 -- Name: mw_rooms mw_rooms_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -898,6 +1506,46 @@ ALTER TABLE ONLY public.racetime_maintenance
     ADD CONSTRAINT racetime_maintenance_start_key UNIQUE (start);
 
 
+--
+-- Name: rated_asyncs rated_asyncs_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rated_asyncs
+    ADD CONSTRAINT rated_asyncs_pkey PRIMARY KEY (series, event, kind);
+
+
+--
+-- Name: rated_races rated_races_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rated_races
+    ADD CONSTRAINT rated_races_pkey PRIMARY KEY (race);
+
+
+--
+-- Name: rating_history rating_history_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rating_history
+    ADD CONSTRAINT rating_history_pkey PRIMARY KEY (period, user_id);
+
+
+--
+-- Name: ratings ratings_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.ratings
+    ADD CONSTRAINT ratings_pkey PRIMARY KEY (user_id, category);
+
+
+--
+-- Name: reschedule_requests reschedule_requests_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.reschedule_requests
+    ADD CONSTRAINT reschedule_requests_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: rsl_seeds rsl_seeds_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -906,6 +1554,30 @@ ALTER TABLE ONLY public.rsl_seeds
     ADD CONSTRAINT rsl_seeds_pkey PRIMARY KEY (room);
 
 
+--
+-- Name: scheduling_deadline_state scheduling_deadline_state_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.scheduling_deadline_state
+    ADD CONSTRAINT scheduling_deadline_state_pkey PRIMARY KEY (race);
+
+
+--
+-- Name: seeds seeds_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.seeds
+    ADD CONSTRAINT seeds_pkey PRIMARY KEY (series, event, phase, team);
+
+
+--
+-- Name: seeds seeds_series_event_phase_seed_key; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.seeds
+    ADD CONSTRAINT seeds_series_event_phase_seed_key UNIQUE (series, event, phase, seed);
+
+
 --
 -- Name: speedgaming_disambiguation_messages speedgaming_disambiguation_messages_message_id_key; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -922,6 +1594,30 @@ ALTER TABLE ONLY public.speedgaming_disambiguation_messages
     ADD CONSTRAINT speedgaming_disambiguation_messages_pkey PRIMARY KEY (speedgaming_id);
 
 
+--
+-- Name: swiss_matches swiss_matches_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_pkey PRIMARY KEY (series, event, phase, round, team1);
+
+
+--
+-- Name: swiss_matches swiss_matches_race_key; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_race_key UNIQUE (race);
+
+
+--
+-- Name: swiss_phases swiss_phases_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_phases
+    ADD CONSTRAINT swiss_phases_pkey PRIMARY KEY (series, event, phase);
+
+
 --
 -- Name: teams teams_pkey; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -930,6 +1626,14 @@ ALTER TABLE ONLY public.teams
     ADD CONSTRAINT teams_pkey PRIMARY KEY (id);
 
 
+--
+-- Name: users users_calendar_token_key; Type: CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.users
+    ADD CONSTRAINT users_calendar_token_key UNIQUE (calendar_token);
+
+
 --
 -- Name: users users_discord_id_key; Type: CONSTRAINT; Schema: public; Owner: mido
 --
@@ -962,6 +1666,27 @@ ALTER TABLE ONLY public.view_as
     ADD CONSTRAINT view_as_pkey PRIMARY KEY (viewer);
 
 
+--
+-- Name: race_result_log_race_idx; Type: INDEX; Schema: public; Owner: mido
+--
+
+CREATE INDEX race_result_log_race_idx ON public.race_result_log USING btree (race);
+
+
+--
+-- Name: race_results_race_idx; Type: INDEX; Schema: public; Owner: mido
+--
+
+CREATE INDEX race_results_race_idx ON public.race_results USING btree (race);
+
+
+--
+-- Name: rating_history_category_period_idx; Type: INDEX; Schema: public; Owner: mido
+--
+
+CREATE INDEX rating_history_category_period_idx ON public.rating_history USING btree (category, period);
+
+
 --
 -- Name: api_keys api_keys_user_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -1002,6 +1727,78 @@ ALTER TABLE ONLY public.asyncs
     ADD CONSTRAINT asyncs_series_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
 
 
+--
+-- Name: availability_exceptions availability_exceptions_user_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.availability_exceptions
+    ADD CONSTRAINT availability_exceptions_user_id_fkey FOREIGN KEY (user_id) REFERENCES public.users(id) ON DELETE CASCADE;
+
+
+--
+-- Name: availability availability_user_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.availability
+    ADD CONSTRAINT availability_user_id_fkey FOREIGN KEY (user_id) REFERENCES public.users(id) ON DELETE CASCADE;
+
+
+--
+-- Name: bracket_matches bracket_matches_series_event_phase_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_matches
+    ADD CONSTRAINT bracket_matches_series_event_phase_fkey FOREIGN KEY (series, event, phase) REFERENCES public.brackets(series, event, phase) ON DELETE CASCADE;
+
+
+--
+-- Name: bracket_matches bracket_matches_team1_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_matches
+    ADD CONSTRAINT bracket_matches_team1_fkey FOREIGN KEY (team1) REFERENCES public.teams(id) ON DELETE SET NULL;
+
+
+--
+-- Name: bracket_matches bracket_matches_team2_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_matches
+    ADD CONSTRAINT bracket_matches_team2_fkey FOREIGN KEY (team2) REFERENCES public.teams(id) ON DELETE SET NULL;
+
+
+--
+-- Name: bracket_matches bracket_matches_winner_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_matches
+    ADD CONSTRAINT bracket_matches_winner_fkey FOREIGN KEY (winner) REFERENCES public.teams(id) ON DELETE SET NULL;
+
+
+--
+-- Name: bracket_races bracket_races_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_races
+    ADD CONSTRAINT bracket_races_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: bracket_races bracket_races_series_event_phase_side_round_position_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.bracket_races
+    ADD CONSTRAINT bracket_races_series_event_phase_side_round_position_fkey FOREIGN KEY (series, event, phase, side, round, "position") REFERENCES public.bracket_matches(series, event, phase, side, round, "position") ON DELETE CASCADE;
+
+
+--
+-- Name: brackets brackets_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.brackets
+    ADD CONSTRAINT brackets_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
+
+
 --
 -- Name: discord_roles discord_roles_series_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -1010,6 +1807,110 @@ ALTER TABLE ONLY public.discord_roles
     ADD CONSTRAINT discord_roles_series_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
 
 
+--
+-- Name: draft_actions draft_actions_actor_user_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_actions
+    ADD CONSTRAINT draft_actions_actor_user_fkey FOREIGN KEY (actor_user) REFERENCES public.users(id);
+
+
+--
+-- Name: draft_actions draft_actions_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_actions
+    ADD CONSTRAINT draft_actions_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: draft_actions draft_actions_undone_by_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_actions
+    ADD CONSTRAINT draft_actions_undone_by_fkey FOREIGN KEY (undone_by) REFERENCES public.users(id);
+
+
+--
+-- Name: draft_timer_state draft_timer_state_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_timer_state
+    ADD CONSTRAINT draft_timer_state_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: draft_timers draft_timers_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.draft_timers
+    ADD CONSTRAINT draft_timers_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
+
+
+--
+-- Name: group_matches group_matches_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE SET NULL;
+
+
+--
+-- Name: group_matches group_matches_series_event_phase_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_series_event_phase_fkey FOREIGN KEY (series, event, phase) REFERENCES public.group_phases(series, event, phase) ON DELETE CASCADE;
+
+
+--
+-- Name: group_matches group_matches_team1_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_team1_fkey FOREIGN KEY (team1) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: group_matches group_matches_team2_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_team2_fkey FOREIGN KEY (team2) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: group_matches group_matches_winner_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_matches
+    ADD CONSTRAINT group_matches_winner_fkey FOREIGN KEY (winner) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: group_members group_members_series_event_phase_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_members
+    ADD CONSTRAINT group_members_series_event_phase_fkey FOREIGN KEY (series, event, phase) REFERENCES public.group_phases(series, event, phase) ON DELETE CASCADE;
+
+
+--
+-- Name: group_members group_members_team_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_members
+    ADD CONSTRAINT group_members_team_fkey FOREIGN KEY (team) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: group_phases group_phases_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.group_phases
+    ADD CONSTRAINT group_phases_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
+
+
 --
 -- Name: looking_for_team looking_for_team_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -1082,6 +1983,38 @@ ALTER TABLE ONLY public.phase_round_options
     ADD CONSTRAINT phase_round_options_series_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
 
 
+--
+-- Name: race_result_log race_result_log_editor_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.race_result_log
+    ADD CONSTRAINT race_result_log_editor_fkey FOREIGN KEY (editor) REFERENCES public.users(id);
+
+
+--
+-- Name: race_result_log race_result_log_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.race_result_log
+    ADD CONSTRAINT race_result_log_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: race_results race_results_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.race_results
+    ADD CONSTRAINT race_results_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: race_results race_results_team_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.race_results
+    ADD CONSTRAINT race_results_team_fkey FOREIGN KEY (team) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
 --
 -- Name: race_player_videos race_videos_player_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -1130,6 +2063,70 @@ ALTER TABLE ONLY public.races
     ADD CONSTRAINT races_team2_fkey FOREIGN KEY (team2) REFERENCES public.teams(id);
 
 
+--
+-- Name: rated_asyncs rated_asyncs_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rated_asyncs
+    ADD CONSTRAINT rated_asyncs_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event) ON DELETE CASCADE;
+
+
+--
+-- Name: rated_races rated_races_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rated_races
+    ADD CONSTRAINT rated_races_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: rating_history rating_history_user_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.rating_history
+    ADD CONSTRAINT rating_history_user_id_fkey FOREIGN KEY (user_id) REFERENCES public.users(id) ON DELETE CASCADE;
+
+
+--
+-- Name: ratings ratings_user_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.ratings
+    ADD CONSTRAINT ratings_user_id_fkey FOREIGN KEY (user_id) REFERENCES public.users(id) ON DELETE CASCADE;
+
+
+--
+-- Name: reschedule_requests reschedule_requests_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.reschedule_requests
+    ADD CONSTRAINT reschedule_requests_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: reschedule_requests reschedule_requests_requested_by_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.reschedule_requests
+    ADD CONSTRAINT reschedule_requests_requested_by_fkey FOREIGN KEY (requested_by) REFERENCES public.users(id);
+
+
+--
+-- Name: reschedule_requests reschedule_requests_requester_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.reschedule_requests
+    ADD CONSTRAINT reschedule_requests_requester_fkey FOREIGN KEY (requester) REFERENCES public.teams(id);
+
+
+--
+-- Name: reschedule_requests reschedule_requests_resolved_by_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.reschedule_requests
+    ADD CONSTRAINT reschedule_requests_resolved_by_fkey FOREIGN KEY (resolved_by) REFERENCES public.users(id);
+
+
 --
 -- Name: restreamers restreamers_restreamer_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -1146,6 +2143,78 @@ ALTER TABLE ONLY public.restreamers
     ADD CONSTRAINT restreamers_series_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
 
 
+--
+-- Name: scheduling_deadline_state scheduling_deadline_state_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.scheduling_deadline_state
+    ADD CONSTRAINT scheduling_deadline_state_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE CASCADE;
+
+
+--
+-- Name: seeds seeds_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.seeds
+    ADD CONSTRAINT seeds_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
+
+
+--
+-- Name: seeds seeds_team_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.seeds
+    ADD CONSTRAINT seeds_team_fkey FOREIGN KEY (team) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: swiss_matches swiss_matches_race_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_race_fkey FOREIGN KEY (race) REFERENCES public.races(id) ON DELETE SET NULL;
+
+
+--
+-- Name: swiss_matches swiss_matches_series_event_phase_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_series_event_phase_fkey FOREIGN KEY (series, event, phase) REFERENCES public.swiss_phases(series, event, phase) ON DELETE CASCADE;
+
+
+--
+-- Name: swiss_matches swiss_matches_team1_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_team1_fkey FOREIGN KEY (team1) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: swiss_matches swiss_matches_team2_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_team2_fkey FOREIGN KEY (team2) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: swiss_matches swiss_matches_winner_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_matches
+    ADD CONSTRAINT swiss_matches_winner_fkey FOREIGN KEY (winner) REFERENCES public.teams(id) ON DELETE CASCADE;
+
+
+--
+-- Name: swiss_phases swiss_phases_series_event_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
+--
+
+ALTER TABLE ONLY public.swiss_phases
+    ADD CONSTRAINT swiss_phases_series_event_fkey FOREIGN KEY (series, event) REFERENCES public.events(series, event);
+
+
 --
 -- Name: team_members team_members_member_fkey; Type: FK CONSTRAINT; Schema: public; Owner: mido
 --
@@ -0,0 +1,37 @@
+-- Top 8 seeding of past events, which was hardcoded in src/draft/mod.rs before seeds became configurable per phase.
+-- Apply to the production database when deploying this change: psql midos_house < assets/seeds-top8.sql
+
+INSERT INTO seeds (series, event, phase, team, seed) VALUES
+    ('mw', '3', 'Top 8', 5834711445123920517, 1), -- DAD
+    ('mw', '3', 'Top 8', 167966029947875858, 2), -- Snack Pack
+    ('mw', '3', 'Top 8', 541730158313016345, 3), -- The Highest Gorons
+    ('mw', '3', 'Top 8', 8470597845703477673, 4), -- Pirate Ship
+    ('mw', '3', 'Top 8', 3240373008633749917, 5), -- The Good, The Bad and The Shopskipper
+    ('mw', '3', 'Top 8', 4729976791199641222, 6), -- SariasObjects
+    ('mw', '3', 'Top 8', 4543618089366873966, 7), -- Anju's Secret
+    ('mw', '3', 'Top 8', 3547322866530836817, 8), -- Raid: Shadow Temple
+    ('mw', '4', 'Top 8', 8429274534302278572, 1), -- Anju's Secret
+    ('mw', '4', 'Top 8', -6303796146230218195, 2), -- ADD
+    ('mw', '4', 'Top 8', 5548902498821246494, 3), -- Donutdog!!! Wuff! Wuff!
+    ('mw', '4', 'Top 8', 7622448514297787774, 4), -- Snack Pack
+    ('mw', '4', 'Top 8', -4802128691264682325, 5), -- The Highest Gorons
+    ('mw', '4', 'Top 8', 4984265622447250649, 6), -- Bongo Akimbo
+    ('mw', '4', 'Top 8', 592664405695569367, 7), -- The Jhegsons
+    ('mw', '4', 'Top 8', -4041599556675804181, 8), -- Pandora's Brot
+    ('mw', '5', 'Top 8', -2702740142279070889, 1), -- Schulzer, Jay and Bobby are here
+    ('mw', '5', 'Top 8', 1129367405239208119, 2), -- Pandora's Brot
+    ('mw', '5', 'Top 8', -1341987307709454914, 3), -- Moeko Appreciation Society
+    ('mw', '5', 'Top 8', 32215927528820085, 4), -- Captain Levi's Fish and Chimp Shop
+    ('mw', '5', 'Top 8', 4924230698541822293, 5), -- Trois clampins et une chaussette
+    ('mw', '5', 'Top 8', -1271720418177736047, 6), -- SariasObjects
+    ('mw', '5', 'Top 8', 5779097592179749366, 7), -- Clube Athletico Paranaense
+    ('mw', '5', 'Top 8', 3033012543915648585, 8), -- DAChuck
+    ('rsl', '7', 'Top 8', -5807741851131707605, 1), -- Alexis
+    ('rsl', '7', 'Top 8', -2073154410149390909, 2), -- Schulzer
+    ('rsl', '7', 'Top 8', -195333196313634303, 3), -- Chimpanreeve
+    ('rsl', '7', 'Top 8', 7664769147612623797, 4), -- Aranaut
+    ('rsl', '7', 'Top 8', 2933860891811564156, 5), -- Flouche
+    ('rsl', '7', 'Top 8', 6739931103991450481, 6), -- Nopons
+    ('rsl', '7', 'Top 8', 5480028145623953067, 7), -- Rafa
+    ('rsl', '7', 'Top 8', -1071081645130646683, 8) -- Cola
+ON CONFLICT DO NOTHING;
@@ -19,3 +19,17 @@ document.querySelectorAll('.timezone').forEach(function(timezone) {
 document.querySelectorAll('.timezone-wrapper').forEach(function(timezoneWrapper) {
     timezoneWrapper.classList.remove('timezone-wrapper');
 });
+
+document.querySelectorAll('.live-update').forEach(function(liveUpdate) {
+    setInterval(function() {
+        fetch(liveUpdate.dataset.url).then(function(response) {
+            if (response.ok) {
+                return response.text();
+            } else {
+                throw new Error(response.statusText);
+            }
+        }).then(function(content) {
+            liveUpdate.innerHTML = content;
+        }).catch(function() {});
+    }, 10000);
+});
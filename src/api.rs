@@ -367,6 +367,68 @@ struct Race(cal::Race);
     async fn restream_consent(&self) -> Option<bool> {
         self.0.teams_opt().map(|mut teams| teams.all(|team| team.restream_consent))
     }
+
+    /// The results of this race, sorted by placement with entrants who did not finish listed last.
+    /// Empty if the race has not finished yet or if its results were not recorded by Mido's House.
+    async fn results(&self, ctx: &Context<'_>) -> Result<Vec<RaceResult>, cal::Error> {
+        let event = db!(db = ctx; self.0.event(&mut *db).await?);
+        let results = db!(db = ctx; cal::RaceResult::for_race(&mut *db, self.0.id).await?);
+        Ok(results.into_iter().map(|inner| RaceResult { inner, event: event.clone() }).collect())
+    }
+}
+
+struct RaceResult {
+    inner: cal::RaceResult,
+    event: event::Data<'static>,
+}
+
+#[Object] impl RaceResult {
+    /// The entrant if it is a Mido's House team. For solo events, this will be a single-member team.
+    async fn team(&self) -> Option<Team> {
+        if let Entrant::MidosHouseTeam(ref team) = self.inner.entrant {
+            Some(Team { inner: team.clone(), event: self.event.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// The entrant's display name. Null for Mido's House teams and Discord users.
+    async fn name(&self) -> Option<&str> {
+        if let Entrant::Named { ref name, .. } = self.inner.entrant {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// The entrant's racetime.gg user ID, if known. Null for Mido's House teams, use the team's members instead.
+    async fn racetime_id(&self) -> Option<GqlId> {
+        match self.inner.entrant {
+            Entrant::MidosHouseTeam(_) => None,
+            Entrant::Discord { ref racetime_id, .. } | Entrant::Named { ref racetime_id, .. } => racetime_id.as_ref().map(GqlId::from),
+        }
+    }
+
+    /// The entrant's Discord user snowflake ID. Null unless the entrant is a Discord user.
+    async fn discord_id(&self) -> Option<GqlId> {
+        if let Entrant::Discord { id, .. } = self.inner.entrant {
+            Some(id.into())
+        } else {
+            None
+        }
+    }
+
+    /// The racetime.gg room in which the entrant played. Null if the result was entered manually.
+    async fn room(&self) -> Option<&str> { self.inner.room.as_ref().map(|url| url.as_str()) }
+
+    /// The entrant's finish time in seconds. Null if the entrant did not finish or if the event is not scored by finish time.
+    async fn finish_time(&self) -> Option<f64> { self.inner.finish_time.map(|time| time.as_secs_f64()) }
+
+    /// The entrant's placement within the race, counting from 1. Tied entrants have the same placement. Null if the entrant did not finish.
+    async fn placement(&self) -> Option<i16> { self.inner.placement }
+
+    /// Whether this result was entered or corrected by an organizer rather than taken from the race room.
+    async fn retimed(&self) -> bool { self.inner.retimed }
 }
 
 struct Team {
@@ -585,3 +647,47 @@ pub(crate) async fn entrants_csv(db_pool: &State<PgPool>, http_client: &State<re
     }
     Ok((ContentType::CSV, csv.into_inner()?))
 }
+
+#[rocket::get("/api/v1/event/<series>/<event>/results.csv?<api_key>")]
+pub(crate) async fn results_csv(db_pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: crate::series::Series, event: &str, api_key: &str) -> Result<(ContentType, Vec<u8>), StatusOrError<CsvError>> {
+    let mut transaction = db_pool.begin().await?;
+    Scopes::default().validate(&mut transaction, api_key).await?.ok_or(StatusOrError::Status(Status::Forbidden))?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut csv = csv::Writer::from_writer(Vec::default());
+    for race in cal::Race::for_event(&mut transaction, http_client, &event).await? {
+        for result in cal::RaceResult::for_race(&mut transaction, race.id).await? {
+            #[derive(Serialize)]
+            struct Row<'a> {
+                race_id: Id<Races>,
+                phase: Option<&'a str>,
+                round: Option<&'a str>,
+                game: Option<i16>,
+                placement: Option<i16>,
+                team_id: Option<Id<Teams>>,
+                name: Option<String>,
+                racetime_id: Option<&'a str>,
+                finish_time_seconds: Option<f64>,
+                retimed: bool,
+                room: Option<&'a str>,
+            }
+
+            let (team_id, name, racetime_id) = match result.entrant {
+                Entrant::MidosHouseTeam(ref team) => (Some(team.id), team.name(&mut transaction).await?.map(|name| name.into_owned()), None),
+                Entrant::Discord { ref racetime_id, .. } => (None, None, racetime_id.as_deref()),
+                Entrant::Named { ref name, ref racetime_id, .. } => (None, Some(name.clone()), racetime_id.as_deref()),
+            };
+            csv.serialize(Row {
+                race_id: race.id,
+                phase: race.phase.as_deref(),
+                round: race.round.as_deref(),
+                game: race.game,
+                placement: result.placement,
+                finish_time_seconds: result.finish_time.map(|time| time.as_secs_f64()),
+                retimed: result.retimed,
+                room: result.room.as_ref().map(|room| room.as_str()),
+                team_id, name, racetime_id,
+            })?;
+        }
+    }
+    Ok((ContentType::CSV, csv.into_inner()?))
+}
@@ -501,12 +501,12 @@ async fn register_racetime_inner(pool: &State<PgPool>, me: Option<User>, racetim
         } else if let Some(me) = me {
             sqlx::query!("UPDATE users SET racetime_id = $1, racetime_display_name = $2, racetime_discriminator = $3, racetime_pronouns = $4 WHERE id = $5", racetime_user.id, racetime_user.name, racetime_user.discriminator as _, racetime_user.pronouns as _, me.id as _).execute(&mut *transaction).await?;
             transaction.commit().await?;
-            Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(me.id))))
+            Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(me.id, _, _))))
         } else {
             let id = Id::<Users>::new(&mut transaction).await?;
             sqlx::query!("INSERT INTO users (id, display_source, racetime_id, racetime_display_name, racetime_discriminator, racetime_pronouns) VALUES ($1, 'racetime', $2, $3, $4, $5)", id as _, racetime_user.id, racetime_user.name, racetime_user.discriminator as _, racetime_user.pronouns as _).execute(&mut *transaction).await?;
             transaction.commit().await?;
-            Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(id))))
+            Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(id, _, _))))
         }
     } else {
         Redirect::to(uri!(racetime_login(_)))
@@ -527,12 +527,12 @@ async fn register_discord_inner(pool: &State<PgPool>, me: Option<User>, discord_
             if let Some(me) = me {
                 sqlx::query!("UPDATE users SET discord_id = $1, discord_display_name = $2, discord_discriminator = $3, discord_username = $4 WHERE id = $5", PgSnowflake(discord_user.id) as _, display_name, discord_user.discriminator as _, username, me.id as _).execute(&mut *transaction).await?;
                 transaction.commit().await?;
-                Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(me.id))))
+                Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(me.id, _, _))))
             } else {
                 let id = Id::<Users>::new(&mut transaction).await?;
                 sqlx::query!("INSERT INTO users (id, display_source, discord_id, discord_display_name, discord_discriminator, discord_username) VALUES ($1, 'discord', $2, $3, $4, $5)", id as _, PgSnowflake(discord_user.id) as _, display_name, discord_user.discriminator as _, username).execute(&mut *transaction).await?;
                 transaction.commit().await?;
-                Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(id))))
+                Redirect::to(redirect_uri.unwrap_or_else(|| uri!(crate::user::profile(id, _, _))))
             }
         }
     } else {
@@ -575,7 +575,7 @@ pub(crate) async fn merge_accounts(pool: &State<PgPool>, me: User, racetime_user
                 if sqlx::query!("UPDATE users SET discord_id = $1, discord_display_name = $2, discord_discriminator = $3, discord_username = $4 WHERE id = $5", PgSnowflake(discord.id) as _, discord.display_name, discord.username_or_discriminator.as_ref().right() as _, discord.username_or_discriminator.as_ref().left(), me.id as _).execute(&mut *transaction).await.is_ok();
                 then {
                     transaction.commit().await?;
-                    return Ok(Redirect::to(uri!(crate::user::profile(me.id))))
+                    return Ok(Redirect::to(uri!(crate::user::profile(me.id, _, _))))
                 } else {
                     transaction.rollback().await?;
                     transaction = pool.begin().await?;
@@ -587,7 +587,7 @@ pub(crate) async fn merge_accounts(pool: &State<PgPool>, me: User, racetime_user
                                     sqlx::query!("DELETE FROM users WHERE id = $1", racetime_user.id as _).execute(&mut *transaction).await?;
                                     sqlx::query!("UPDATE users SET racetime_id = $1, racetime_display_name = $2, racetime_discriminator = $3, racetime_pronouns = $4 WHERE id = $5", racetime.id, racetime.display_name, racetime.discriminator as _, racetime.pronouns as _, me.id as _).execute(&mut *transaction).await?;
                                     transaction.commit().await?;
-                                    return Ok(Redirect::to(uri!(crate::user::profile(me.id))))
+                                    return Ok(Redirect::to(uri!(crate::user::profile(me.id, _, _))))
                                 }
                             }
                         }
@@ -0,0 +1,495 @@
+//! Players' availability for races, used to suggest match times in scheduling threads.
+//!
+//! Availability is stored in hourly slots in the user's time zone: a recurring weekly schedule, plus exceptions which replace the weekly schedule on specific dates.
+
+use {
+    std::collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    serenity::all::EditMessage,
+    crate::{
+        form::{
+            button_form_ext,
+            form_field,
+            full_form,
+        },
+        prelude::*,
+    },
+};
+
+const WEEKDAYS: [Weekday; 7] = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun];
+/// How many suggested times are posted in a scheduling thread.
+const SUGGESTION_COUNT: usize = 5;
+/// How far ahead times are suggested, even if the event ends later or has no end date.
+const SUGGESTION_HORIZON_DAYS: i64 = 28;
+
+#[derive(Debug, thiserror::Error, rocket_util::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Data(#[from] event::DataError),
+    #[error(transparent)] Page(#[from] PageError),
+    #[error(transparent)] Serenity(#[from] serenity::Error),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+}
+
+impl<E: Into<Error>> From<E> for StatusOrError<Error> {
+    fn from(e: E) -> Self {
+        Self::Err(e.into())
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Parses a list of hours like `18-22, 8` (meaning 18:00–22:00 and 8:00–9:00), or `all` or `none`.
+pub(crate) fn parse_hours(s: &str) -> Option<BTreeSet<i16>> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("none") { return Some(BTreeSet::default()) }
+    if s.eq_ignore_ascii_case("all") { return Some((0..24).collect()) }
+    let mut hours = BTreeSet::default();
+    for part in s.split(',') {
+        let (_, start, end) = regex_captures!("^ *([0-9]{1,2})(?::00)? *(?:[-–] *([0-9]{1,2})(?::00)?)? *$", part)?;
+        let start = start.parse::<i16>().ok()?;
+        let end = if end.is_empty() { start + 1 } else { end.parse::<i16>().ok()? };
+        if start >= 24 || end > 24 || end <= start { return None }
+        hours.extend(start..end);
+    }
+    Some(hours)
+}
+
+/// Formats a list of hours in the format accepted by [`parse_hours`].
+pub(crate) fn format_hours(hours: &BTreeSet<i16>) -> String {
+    if hours.is_empty() { return format!("none") }
+    let mut ranges = Vec::<(i16, i16)>::default();
+    for &hour in hours {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == hour => *end = hour + 1,
+            _ => ranges.push((hour, hour + 1)),
+        }
+    }
+    ranges.into_iter().map(|(start, end)| format!("{start}–{end}")).join(", ")
+}
+
+pub(crate) struct Availability {
+    timezone: Tz,
+    weekly: HashMap<Weekday, BTreeSet<i16>>,
+    exceptions: BTreeMap<NaiveDate, BTreeSet<i16>>,
+}
+
+impl Availability {
+    pub(crate) async fn for_user(transaction: &mut Transaction<'_, Postgres>, user: &User) -> sqlx::Result<Self> {
+        let mut weekly = HashMap::<_, BTreeSet<_>>::default();
+        for row in sqlx::query!("SELECT weekday, hour FROM availability WHERE user_id = $1", user.id as _).fetch_all(&mut **transaction).await? {
+            weekly.entry(WEEKDAYS[row.weekday as usize]).or_default().insert(row.hour);
+        }
+        let exceptions = sqlx::query!("SELECT date, hours FROM availability_exceptions WHERE user_id = $1 AND date >= CURRENT_DATE - 1", user.id as _).fetch_all(&mut **transaction).await?
+            .into_iter()
+            .map(|row| (row.date, row.hours.into_iter().collect()))
+            .collect();
+        Ok(Self {
+            timezone: user.timezone(&mut **transaction).await?.unwrap_or(Tz::UTC),
+            weekly, exceptions,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.weekly.values().all(|hours| hours.is_empty()) && self.exceptions.is_empty()
+    }
+
+    fn is_available(&self, time: DateTime<Utc>) -> bool {
+        let local = time.with_timezone(&self.timezone);
+        let hour = local.hour() as i16;
+        if let Some(hours) = self.exceptions.get(&local.date_naive()) {
+            hours.contains(&hour)
+        } else {
+            self.weekly.get(&local.weekday()).is_some_and(|hours| hours.contains(&hour))
+        }
+    }
+
+    /// Whether the user is available for every hour that a race starting at `start` would overlap.
+    fn is_available_for(&self, start: DateTime<Utc>, duration: TimeDelta) -> bool {
+        let mut time = start;
+        while time < start + duration {
+            if !self.is_available(time) { return false }
+            time += TimeDelta::hours(1);
+        }
+        true
+    }
+}
+
+/// Replaces the user's recurring availability on the given weekday.
+pub(crate) async fn set_weekly(transaction: &mut Transaction<'_, Postgres>, user: Id<Users>, weekday: Weekday, hours: &BTreeSet<i16>) -> sqlx::Result<()> {
+    let weekday = weekday.num_days_from_monday() as i16;
+    sqlx::query!("DELETE FROM availability WHERE user_id = $1 AND weekday = $2", user as _, weekday).execute(&mut **transaction).await?;
+    for &hour in hours {
+        sqlx::query!("INSERT INTO availability (user_id, weekday, hour) VALUES ($1, $2, $3)", user as _, weekday, hour).execute(&mut **transaction).await?;
+    }
+    Ok(())
+}
+
+/// Replaces the user's availability on the given date, overriding their recurring availability for that day.
+pub(crate) async fn set_exception(transaction: &mut Transaction<'_, Postgres>, user: Id<Users>, date: NaiveDate, hours: &BTreeSet<i16>) -> sqlx::Result<()> {
+    sqlx::query!("INSERT INTO availability_exceptions (user_id, date, hours) VALUES ($1, $2, $3) ON CONFLICT (user_id, date) DO UPDATE SET hours = EXCLUDED.hours", user as _, date, hours.iter().copied().collect_vec()).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+/// A range of start times at which every player in a race who has entered their availability is available for the entire race.
+pub(crate) struct Suggestion {
+    pub(crate) earliest_start: DateTime<Utc>,
+    pub(crate) latest_start: DateTime<Utc>,
+}
+
+/// Returns `None` if availability is missing for one of the teams, so there is nothing to compare.
+pub(crate) async fn suggestions(transaction: &mut Transaction<'_, Postgres>, event: &event::Data<'_>, race: &Race) -> sqlx::Result<Option<Vec<Suggestion>>> {
+    let mut players = Vec::default();
+    for team in race.teams() {
+        let mut team_has_availability = false;
+        for member in team.members(&mut *transaction).await? {
+            let availability = Availability::for_user(&mut *transaction, &member).await?;
+            if !availability.is_empty() {
+                team_has_availability = true;
+                players.push(availability);
+            }
+        }
+        if !team_has_availability { return Ok(None) }
+    }
+    if players.is_empty() { return Ok(None) }
+    let now = Utc::now();
+    let duration = event.series.default_race_duration();
+    let Some(earliest) = TimeDelta::from_std(event.min_schedule_notice).ok().and_then(|notice| now.checked_add_signed(notice)) else { return Ok(Some(Vec::default())) };
+    // round up to the next full hour
+    let mut start = earliest.with_minute(0).and_then(|start| start.with_second(0)).and_then(|start| start.with_nanosecond(0)).expect("every hour has a minute 0");
+    if start < earliest { start += TimeDelta::hours(1) }
+    let mut latest = now + TimeDelta::days(SUGGESTION_HORIZON_DAYS);
+    if let Some(end) = event.end {
+        latest = latest.min(end - duration);
+    }
+    let mut suggestions = Vec::<Suggestion>::default();
+    let mut prev_available = false;
+    while start <= latest {
+        let available = players.iter().all(|player| player.is_available_for(start, duration));
+        if available {
+            if prev_available {
+                suggestions.last_mut().expect("previous slot was available").latest_start = start;
+            } else if suggestions.len() < SUGGESTION_COUNT {
+                suggestions.push(Suggestion { earliest_start: start, latest_start: start });
+            } else {
+                break
+            }
+        }
+        prev_available = available;
+        start += TimeDelta::hours(1);
+    }
+    Ok(Some(suggestions))
+}
+
+/// Formats the suggested times for a race as a Discord message, or returns `None` if there is no availability to compare.
+pub(crate) async fn suggestions_message(transaction: &mut Transaction<'_, Postgres>, event: &event::Data<'_>, race: &Race) -> sqlx::Result<Option<String>> {
+    let Some(suggestions) = suggestions(&mut *transaction, event, race).await? else { return Ok(None) };
+    let mut content = MessageBuilder::default();
+    if suggestions.is_empty() {
+        content.push("There are no times in the coming weeks when everyone who entered their availability can race.");
+    } else {
+        content.push_line("Based on the availability you entered, everyone can race at these times:");
+        for suggestion in suggestions {
+            content.push("• ");
+            content.push_timestamp(suggestion.earliest_start, serenity_utils::message::TimestampStyle::LongDateTime);
+            if suggestion.latest_start > suggestion.earliest_start {
+                content.push(" (or start as late as ");
+                content.push_timestamp(suggestion.latest_start, serenity_utils::message::TimestampStyle::ShortTime);
+                content.push(')');
+            }
+            content.push_line("");
+        }
+    }
+    content.push("You can edit your availability at <");
+    content.push(uri!(base_uri(), get).to_string());
+    content.push(">.");
+    Ok(Some(content.build()))
+}
+
+/// Formats the suggested times for the first unscheduled race in a scheduling thread that has availability to compare.
+pub(crate) async fn thread_suggestions_message(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, thread: ChannelId) -> Result<Option<String>, Error> {
+    for race in Race::for_scheduling_channel(&mut *transaction, http_client, thread, None, false).await? {
+        if let RaceSchedule::Unscheduled = race.schedule {
+            let event = race.event(&mut *transaction).await?;
+            if event.speedgaming_slug.is_some() { continue }
+            if let Some(message) = suggestions_message(&mut *transaction, &event, &race).await? {
+                return Ok(Some(message))
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Remembers the message with the suggested times in a scheduling thread so it can be updated when availability changes.
+pub(crate) async fn record_suggestions_message(transaction: &mut Transaction<'_, Postgres>, thread: ChannelId, message: MessageId) -> sqlx::Result<()> {
+    sqlx::query!("INSERT INTO availability_suggestions (thread, message) VALUES ($1, $2) ON CONFLICT (thread) DO UPDATE SET message = EXCLUDED.message", PgSnowflake(thread) as _, PgSnowflake(message) as _).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+async fn update_thread_suggestions(pool: &PgPool, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, thread: ChannelId) -> Result<(), Error> {
+    let mut transaction = pool.begin().await?;
+    let Some(content) = thread_suggestions_message(&mut transaction, http_client, thread).await? else {
+        transaction.rollback().await?;
+        return Ok(())
+    };
+    let previous = sqlx::query_scalar!(r#"SELECT message AS "message: PgSnowflake<MessageId>" FROM availability_suggestions WHERE thread = $1"#, PgSnowflake(thread) as _).fetch_optional(&mut *transaction).await?;
+    let edited = if let Some(PgSnowflake(message)) = previous {
+        // if the previous message can't be edited (e.g. because it was deleted), post a new one instead
+        thread.edit_message(discord_ctx, message, EditMessage::new().content(&content)).await.is_ok()
+    } else {
+        false
+    };
+    if !edited {
+        let message = thread.say(discord_ctx, content).await?;
+        record_suggestions_message(&mut transaction, thread, message.id).await?;
+    }
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Posts the suggested times in the scheduling threads of the user's unscheduled races, or updates the suggestions previously posted there.
+///
+/// Called after the user's availability has been saved, so errors are logged rather than reported to the user.
+pub(crate) async fn update_suggestions(pool: &PgPool, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, user: Id<Users>) {
+    let threads = match sqlx::query_scalar!(r#"SELECT DISTINCT scheduling_thread AS "scheduling_thread!: PgSnowflake<ChannelId>" FROM races JOIN team_members ON team_members.team IN (races.team1, races.team2, races.team3) WHERE member = $1 AND NOT ignored AND scheduling_thread IS NOT NULL AND start IS NULL"#, user as _).fetch_all(pool).await {
+        Ok(threads) => threads,
+        Err(e) => {
+            eprintln!("failed to look up scheduling threads to update availability suggestions for user {user}: {e} ({e:?})");
+            return
+        }
+    };
+    for PgSnowflake(thread) in threads {
+        if let Err(e) = update_thread_suggestions(pool, discord_ctx, http_client, thread).await {
+            eprintln!("failed to update availability suggestions in {thread}: {e} ({e:?})");
+        }
+    }
+}
+
+async fn availability_page(mut transaction: Transaction<'_, Postgres>, me: User, uri: Origin<'_>, csrf: Option<&CsrfToken>, ctx: Context<'_>) -> Result<RawHtml<String>, Error> {
+    let availability = Availability::for_user(&mut transaction, &me).await?;
+    let mut errors = ctx.errors().collect_vec();
+    let weekly_form = full_form(uri!(post), csrf, html! {
+        table(class = "availability") {
+            thead {
+                tr {
+                    th;
+                    @for weekday in WEEKDAYS {
+                        th : weekday_name(weekday);
+                    }
+                }
+            }
+            tbody {
+                @for hour in 0..24 {
+                    tr {
+                        th : format!("{hour:02}:00");
+                        @for weekday in WEEKDAYS {
+                            td {
+                                input(type = "checkbox", name = "slots", value = format!("{}_{hour}", weekday.num_days_from_monday()), checked? = availability.weekly.get(&weekday).is_some_and(|hours| hours.contains(&hour)));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }, Vec::default(), "Save");
+    let exception_form = full_form(uri!(add_exception), csrf, html! {
+        : form_field("date", &mut errors, html! {
+            label(for = "date") : "Date:";
+            input(type = "date", name = "date", value? = ctx.field_value("date"));
+        });
+        : form_field("hours", &mut errors, html! {
+            label(for = "hours") : "Available hours:";
+            input(type = "text", name = "hours", value? = ctx.field_value("hours"), placeholder = "e.g. 18-22");
+            label(class = "help") : "(For example “18-22, 8” for 18:00–22:00 and 8:00–9:00, or “none” if you're not available at all on this date.)";
+        });
+    }, errors, "Add Exception");
+    let mut exceptions = Vec::with_capacity(availability.exceptions.len());
+    for (date, hours) in &availability.exceptions {
+        let (errors, button) = button_form_ext(uri!(remove_exception), csrf, Vec::default(), html! {
+            input(type = "hidden", name = "date", value = date.format("%Y-%m-%d").to_string());
+        }, "Remove");
+        exceptions.push(html! {
+            tr {
+                td : date.format("%A, %B %-d, %Y").to_string();
+                td : format_hours(hours);
+                td {
+                    : errors;
+                    : button;
+                }
+            }
+        });
+    }
+    let timezone = availability.timezone;
+    Ok(page(transaction, &Some(me.clone()), &uri, PageStyle::default(), "Availability — Mido's House", html! {
+        h1 : "Availability";
+        p {
+            : "When you're in a race that needs to be scheduled, the times when all players are available are posted in the scheduling thread and updated whenever someone changes their availability. All times on this page are in ";
+            : timezone.name();
+            : ", which you can ";
+            a(href = uri!(user::profile(me.id, _, _))) : "change on your profile";
+            : ". You can also use the ";
+            code : "/availability";
+            : " command on Discord.";
+        }
+        h2 : "Weekly";
+        p : "Check the hours when you're usually available to race.";
+        : weekly_form;
+        h2 : "Exceptions";
+        p : "An exception replaces your weekly availability on a specific date.";
+        @if !exceptions.is_empty() {
+            table {
+                thead {
+                    tr {
+                        th : "Date";
+                        th : "Available hours";
+                        th;
+                    }
+                }
+                tbody {
+                    @for exception in exceptions {
+                        : exception;
+                    }
+                }
+            }
+        }
+        : exception_form;
+    }).await?)
+}
+
+#[rocket::get("/availability")]
+pub(crate) async fn get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>) -> Result<RedirectOrContent, StatusOrError<Error>> {
+    let Some(me) = me else { return Ok(RedirectOrContent::Redirect(Redirect::to(uri!(auth::login(Some(uri!(get))))))) };
+    let transaction = pool.begin().await?;
+    Ok(RedirectOrContent::Content(availability_page(transaction, me, uri, csrf.as_ref(), Context::default()).await?))
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct WeeklyForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = Vec::new())]
+    slots: Vec<String>,
+}
+
+#[rocket::post("/availability", data = "<form>")]
+pub(crate) async fn post(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, form: Form<Contextual<'_, WeeklyForm>>) -> Result<RedirectOrContent, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        let mut weekly = HashMap::<_, BTreeSet<_>>::default();
+        for slot in &value.slots {
+            let Some((_, weekday, hour)) = regex_captures!("^([0-6])_([0-9]{1,2})$", slot) else { return Err(StatusOrError::Status(Status::BadRequest)) };
+            let hour = hour.parse::<i16>().map_err(|_| StatusOrError::Status(Status::BadRequest))?;
+            if hour >= 24 { return Err(StatusOrError::Status(Status::BadRequest)) }
+            weekly.entry(WEEKDAYS[weekday.parse::<usize>().expect("matched regex")]).or_default().insert(hour);
+        }
+        for weekday in WEEKDAYS {
+            set_weekly(&mut transaction, me.id, weekday, weekly.get(&weekday).unwrap_or(&BTreeSet::default())).await?;
+        }
+        transaction.commit().await?;
+        update_suggestions(pool, &*discord_ctx.read().await, http_client, me.id).await;
+        RedirectOrContent::Redirect(Redirect::to(uri!(get)))
+    } else {
+        RedirectOrContent::Content(availability_page(transaction, me, uri, csrf.as_ref(), form.context).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct ExceptionForm {
+    #[field(default = String::new())]
+    csrf: String,
+    date: String,
+    #[field(default = String::new())]
+    hours: String,
+}
+
+#[rocket::post("/availability/exceptions", data = "<form>")]
+pub(crate) async fn add_exception(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, form: Form<Contextual<'_, ExceptionForm>>) -> Result<RedirectOrContent, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        let date = NaiveDate::parse_from_str(&value.date, "%Y-%m-%d");
+        if date.is_err() {
+            form.context.push_error(form::Error::validation("Please enter a valid date.").with_name("date"));
+        }
+        let hours = parse_hours(&value.hours);
+        if hours.is_none() {
+            form.context.push_error(form::Error::validation("Couldn't understand these hours. Enter them like “18-22, 8”, or enter “none”.").with_name("hours"));
+        }
+        if let (Ok(date), Some(hours), None) = (date, hours, form.context.errors().next()) {
+            set_exception(&mut transaction, me.id, date, &hours).await?;
+            transaction.commit().await?;
+            update_suggestions(pool, &*discord_ctx.read().await, http_client, me.id).await;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get)))
+        } else {
+            RedirectOrContent::Content(availability_page(transaction, me, uri, csrf.as_ref(), form.context).await?)
+        }
+    } else {
+        RedirectOrContent::Content(availability_page(transaction, me, uri, csrf.as_ref(), form.context).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct RemoveExceptionForm {
+    #[field(default = String::new())]
+    csrf: String,
+    date: String,
+}
+
+#[rocket::post("/availability/exceptions/remove", data = "<form>")]
+pub(crate) async fn remove_exception(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, csrf: Option<CsrfToken>, form: Form<Contextual<'_, RemoveExceptionForm>>) -> Result<Redirect, StatusOrError<Error>> {
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    let Some(ref value) = form.value else { return Err(StatusOrError::Status(Status::Forbidden)) };
+    let date = NaiveDate::parse_from_str(&value.date, "%Y-%m-%d").map_err(|_| StatusOrError::Status(Status::BadRequest))?;
+    sqlx::query!("DELETE FROM availability_exceptions WHERE user_id = $1 AND date = $2", me.id as _, date).execute(&**pool).await?;
+    update_suggestions(pool, &*discord_ctx.read().await, http_client, me.id).await;
+    Ok(Redirect::to(uri!(get)))
+}
+
+/// Parses the `day` argument of the `/availability` Discord command, which is either a weekday name or a date.
+pub(crate) fn parse_day(s: &str) -> Option<Either<Weekday, NaiveDate>> {
+    let s = s.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        Some(Either::Right(date))
+    } else {
+        s.get(..3)?.parse().ok().filter(|weekday: &Weekday| weekday_name(*weekday).to_ascii_lowercase().starts_with(&s.to_ascii_lowercase())).map(Either::Left)
+    }
+}
+
+/// Summarizes a user's availability for the reply to the `/availability` Discord command.
+pub(crate) async fn summary(transaction: &mut Transaction<'_, Postgres>, user: &User) -> sqlx::Result<String> {
+    let availability = Availability::for_user(transaction, user).await?;
+    let mut content = MessageBuilder::default();
+    content.push("Your availability (");
+    content.push(availability.timezone.name());
+    content.push_line("):");
+    for weekday in WEEKDAYS {
+        content.push_bold(weekday_name(weekday));
+        content.push(": ");
+        content.push_line(availability.weekly.get(&weekday).map(format_hours).unwrap_or_else(|| format!("none")));
+    }
+    for (date, hours) in &availability.exceptions {
+        content.push_bold(date.format("%Y-%m-%d").to_string());
+        content.push(": ");
+        content.push_line(format_hours(hours));
+    }
+    content.push("You can also edit your availability at <");
+    content.push(uri!(base_uri(), get).to_string());
+    content.push(">.");
+    Ok(content.build())
+}
@@ -1,4 +1,8 @@
 use {
+    std::{
+        cmp::Reverse,
+        collections::BTreeMap,
+    },
     ics::{
         ICalendar,
         parameters::TzIDParam,
@@ -12,6 +16,10 @@ use {
         },
     },
     reqwest::StatusCode,
+    rocket::{
+        http::ContentType,
+        response::content::RawJson,
+    },
     rocket_util::Response,
     serenity::all::{
         CreateMessage,
@@ -21,9 +29,11 @@ use {
     },
     sqlx::types::Json,
     crate::{
+        conflict,
         discord_bot,
         event::Tab,
         prelude::*,
+        reschedule,
         sheets,
     },
 };
@@ -151,6 +161,15 @@ pub(crate) enum Entrants {
 }
 
 impl Entrants {
+    /// Returns the entrants of an invitational race, or `None` if the race doesn't have individually known entrants.
+    pub(crate) fn as_slice(&self) -> Option<&[Entrant]> {
+        match self {
+            Self::Open | Self::Count { .. } | Self::Named(_) => None,
+            Self::Two(entrants) => Some(entrants),
+            Self::Three(entrants) => Some(entrants),
+        }
+    }
+
     fn to_db(&self) -> ([Option<Id<Teams>>; 3], [Option<&String>; 3], [Option<UserId>; 2], [Option<&String>; 2], [Option<&String>; 2], [Option<u32>; 2]) {
         match *self {
             Entrants::Open => ([None; 3], [None; 3], [None; 2], [None; 2], [None; 2], [None; 2]),
@@ -371,6 +390,38 @@ pub(crate) struct Race {
 }
 
 impl Race {
+    /// A new unscheduled race between two Mido's House teams, for phases where Mido's House pairs the teams itself (brackets, Swiss, and groups).
+    ///
+    /// The race isn't saved yet, so the caller can create a scheduling thread for it first.
+    pub(crate) async fn new_for_pairing(transaction: &mut Transaction<'_, Postgres>, event: &event::Data<'_>, phase: &str, round: String, game: Option<i16>, [team1, team2]: [&Team; 2], draft: Option<Draft>) -> sqlx::Result<Self> {
+        Ok(Self {
+            id: Id::<Races>::new(transaction).await?,
+            series: event.series,
+            event: event.event.to_string(),
+            source: Source::Manual,
+            entrants: Entrants::Two([
+                Entrant::MidosHouseTeam(team1.clone()),
+                Entrant::MidosHouseTeam(team2.clone()),
+            ]),
+            phase: Some(phase.to_owned()),
+            round: Some(round),
+            schedule: RaceSchedule::Unscheduled,
+            schedule_updated_at: None,
+            fpa_invoked: false,
+            breaks_used: false,
+            seed: seed::Data::default(),
+            video_urls: HashMap::default(),
+            restreamers: HashMap::default(),
+            last_edited_by: None,
+            last_edited_at: None,
+            ignored: false,
+            schedule_locked: false,
+            notified: false,
+            scheduling_thread: None,
+            game, draft,
+        })
+    }
+
     pub(crate) async fn from_id(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, id: Id<Races>) -> Result<Self, Error> {
         let row = sqlx::query!(r#"SELECT
             series AS "series: Series",
@@ -1196,6 +1247,88 @@ impl Ord for Race {
     }
 }
 
+/// The result of one entrant in a race, recorded when the race ends or entered by an organizer.
+#[derive(Clone)]
+pub(crate) struct RaceResult {
+    pub(crate) entrant: Entrant,
+    /// The racetime.gg room in which this entrant played, if any.
+    pub(crate) room: Option<Url>,
+    /// `None` if the entrant did not finish or if the event isn't scored by finish time.
+    pub(crate) finish_time: Option<Duration>,
+    /// `None` if the entrant did not finish.
+    pub(crate) placement: Option<i16>,
+    /// Whether this result was entered or corrected by an organizer rather than taken from the race room.
+    pub(crate) retimed: bool,
+}
+
+impl RaceResult {
+    /// Returns the results of the given race, sorted by placement with entrants who did not finish last.
+    pub(crate) async fn for_race(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>) -> Result<Vec<Self>, Error> {
+        let rows = sqlx::query!(r#"SELECT
+            room,
+            team AS "team: Id<Teams>",
+            discord_id AS "discord_id: PgSnowflake<UserId>",
+            racetime_id,
+            name,
+            twitch_username,
+            finish_time,
+            placement,
+            retimed
+        FROM race_results WHERE race = $1 ORDER BY placement ASC NULLS LAST, finish_time ASC NULLS LAST"#, race as _).fetch_all(&mut **transaction).await?;
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            results.push(Self {
+                entrant: if let Some(team) = row.team {
+                    Entrant::MidosHouseTeam(Team::from_id(&mut *transaction, team).await?.ok_or(Error::UnknownTeam)?)
+                } else if let Some(PgSnowflake(id)) = row.discord_id {
+                    Entrant::Discord {
+                        racetime_id: row.racetime_id,
+                        twitch_username: row.twitch_username,
+                        id,
+                    }
+                } else {
+                    Entrant::Named {
+                        name: row.name.unwrap_or_default(),
+                        racetime_id: row.racetime_id,
+                        twitch_username: row.twitch_username,
+                    }
+                },
+                room: row.room.map(|room| Url::parse(&room)).transpose()?,
+                finish_time: row.finish_time.map(decode_pginterval).transpose()?,
+                placement: row.placement,
+                retimed: row.retimed,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Replaces any previously recorded results of the given race.
+    pub(crate) async fn save(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, results: &[Self]) -> sqlx::Result<()> {
+        sqlx::query!("DELETE FROM race_results WHERE race = $1", race as _).execute(&mut **transaction).await?;
+        for result in results {
+            let (team, discord_id, racetime_id, name, twitch_username) = match result.entrant {
+                Entrant::MidosHouseTeam(ref team) => (Some(team.id), None, None, None, None),
+                Entrant::Discord { id, ref racetime_id, ref twitch_username } => (None, Some(PgSnowflake(id)), racetime_id.as_deref(), None, twitch_username.as_deref()),
+                Entrant::Named { ref name, ref racetime_id, ref twitch_username } => (None, None, racetime_id.as_deref(), Some(&**name), twitch_username.as_deref()),
+            };
+            sqlx::query!(
+                "INSERT INTO race_results (race, room, team, discord_id, racetime_id, name, twitch_username, finish_time, placement, retimed) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+                race as _,
+                result.room.as_ref().map(|room| room.as_str()),
+                team as _,
+                discord_id as _,
+                racetime_id,
+                name,
+                twitch_username,
+                result.finish_time as _,
+                result.placement,
+                result.retimed,
+            ).execute(&mut **transaction).await?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Event {
     pub(crate) race: Race,
@@ -1436,7 +1569,9 @@ pub(crate) enum Error {
     #[error(transparent)] Discord(#[from] discord_bot::Error),
     #[error(transparent)] Draft(#[from] draft::Error),
     #[error(transparent)] Event(#[from] event::DataError),
+    #[error(transparent)] Json(#[from] serde_json::Error),
     #[error(transparent)] OotrWeb(#[from] ootr_web::Error),
+    #[error(transparent)] PgInterval(#[from] PgIntervalDecodeError),
     #[error(transparent)] ParseInt(#[from] std::num::ParseIntError),
     #[error(transparent)] Reqwest(#[from] reqwest::Error),
     #[error(transparent)] Roll(#[from] racetime_bot::RollError),
@@ -1472,7 +1607,9 @@ impl IsNetworkError for Error {
             Self::Discord(_) => false,
             Self::Draft(e) => e.is_network_error(),
             Self::Event(_) => false,
+            Self::Json(_) => false,
             Self::OotrWeb(e) => e.is_network_error(),
+            Self::PgInterval(_) => false,
             Self::ParseInt(_) => false,
             Self::Reqwest(e) => e.is_network_error(),
             Self::Roll(_) => false, //TODO
@@ -1522,10 +1659,77 @@ fn dtend<Z: TimeZone + IntoIcsTzid>(datetime: DateTime<Z>) -> DtEnd<'static> {
     dtend
 }
 
-async fn add_event_races(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, cal: &mut ICalendar<'_>, event: &event::Data<'_>) -> Result<(), Error> {
+/// The title of a race, or of one part of an async race, as shown in calendars and feeds.
+async fn race_summary(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, event: &event::Data<'_>, race: &Race, kind: EventKind) -> Result<String, Error> {
+    let summary_prefix = match (&race.phase, &race.round) {
+        (Some(phase), Some(round)) => format!("{} {phase} {round}", event.short_name()),
+        (Some(phase), None) => format!("{} {phase}", event.short_name()),
+        (None, Some(round)) => format!("{} {round}", event.short_name()),
+        (None, None) => event.display_name.clone(),
+    };
+    let summary_prefix = match race.entrants {
+        Entrants::Open | Entrants::Count { .. } => summary_prefix,
+        Entrants::Named(ref entrants) => match kind {
+            EventKind::Normal => format!("{summary_prefix}: {entrants}"),
+            EventKind::Async1 | EventKind::Async2 | EventKind::Async3 => format!("{summary_prefix} (async): {entrants}"),
+        },
+        Entrants::Two([ref team1, ref team2]) => match kind {
+            EventKind::Normal => format!(
+                "{summary_prefix}: {} vs {}",
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async1 => format!(
+                "{summary_prefix} (async): {} vs {}",
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async2 => format!(
+                "{summary_prefix} (async): {} vs {}",
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async3 => unreachable!(),
+        },
+        Entrants::Three([ref team1, ref team2, ref team3]) => match kind {
+            EventKind::Normal => format!(
+                "{summary_prefix}: {} vs {} vs {}",
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async1 => format!(
+                "{summary_prefix} (async): {} vs {} vs {}",
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async2 => format!(
+                "{summary_prefix} (async): {} vs {} vs {}",
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+            EventKind::Async3 => format!(
+                "{summary_prefix} (async): {} vs {} vs {}",
+                team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+                team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
+            ),
+        },
+    };
+    Ok(if let Some(game) = race.game {
+        format!("{summary_prefix}, game {game}")
+    } else {
+        summary_prefix
+    })
+}
+
+/// Adds the races of the given event for which `include` returns `true` to the calendar.
+async fn add_event_races(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, cal: &mut ICalendar<'_>, event: &event::Data<'_>, include: impl Fn(&Race) -> bool) -> Result<(), Error> {
     let now = Utc::now();
     let mut latest_instantiated_weeklies = HashMap::new();
-    for race in Race::for_event(transaction, http_client, event).await?.into_iter() {
+    for race in Race::for_event(transaction, http_client, event).await?.into_iter().filter(|race| include(race)) {
         for race_event in race.cal_events() {
             if let Some(start) = race_event.start() {
                 let mut cal_event = ics::Event::new(format!("{}{}@midos.house",
@@ -1537,68 +1741,7 @@ async fn add_event_races(transaction: &mut Transaction<'_, Postgres>, discord_ct
                         EventKind::Async3 => "-3",
                     },
                 ), dtstamp(now));
-                let summary_prefix = match (&race.phase, &race.round) {
-                    (Some(phase), Some(round)) => format!("{} {phase} {round}", event.short_name()),
-                    (Some(phase), None) => format!("{} {phase}", event.short_name()),
-                    (None, Some(round)) => format!("{} {round}", event.short_name()),
-                    (None, None) => event.display_name.clone(),
-                };
-                let summary_prefix = match race.entrants {
-                    Entrants::Open | Entrants::Count { .. } => summary_prefix,
-                    Entrants::Named(ref entrants) => match race_event.kind {
-                        EventKind::Normal => format!("{summary_prefix}: {entrants}"),
-                        EventKind::Async1 | EventKind::Async2 | EventKind::Async3 => format!("{summary_prefix} (async): {entrants}"),
-                    },
-                    Entrants::Two([ref team1, ref team2]) => match race_event.kind {
-                        EventKind::Normal => format!(
-                            "{summary_prefix}: {} vs {}",
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async1 => format!(
-                            "{summary_prefix} (async): {} vs {}",
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async2 => format!(
-                            "{summary_prefix} (async): {} vs {}",
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async3 => unreachable!(),
-                    },
-                    Entrants::Three([ref team1, ref team2, ref team3]) => match race_event.kind {
-                        EventKind::Normal => format!(
-                            "{summary_prefix}: {} vs {} vs {}",
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async1 => format!(
-                            "{summary_prefix} (async): {} vs {} vs {}",
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async2 => format!(
-                            "{summary_prefix} (async): {} vs {} vs {}",
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                        EventKind::Async3 => format!(
-                            "{summary_prefix} (async): {} vs {} vs {}",
-                            team3.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team1.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                            team2.name(&mut *transaction, discord_ctx).await?.unwrap_or(Cow::Borrowed("(unnamed)")),
-                        ),
-                    },
-                };
-                cal_event.push(Summary::new(ics::escape_text(if let Some(game) = race.game {
-                    format!("{summary_prefix}, game {game}")
-                } else {
-                    summary_prefix
-                })));
+                cal_event.push(Summary::new(ics::escape_text(race_summary(&mut *transaction, discord_ctx, event, &race, race_event.kind).await?)));
                 cal_event.push(dtstart(start));
                 cal_event.push(dtend(race_event.end().filter(|_| !race_event.is_private_async_part() || race.cal_events().all(|event| event.end().is_some())).unwrap_or_else(|| start + event.series.default_race_duration()))); //TODO better fallback duration estimates depending on participants
                 let mut urls = Vec::default();
@@ -1666,6 +1809,20 @@ pub(crate) async fn index_help(pool: &State<PgPool>, me: Option<User>, uri: Orig
             li : "In Mozilla Thunderbird, select New Calendar → On the Network. Paste the link into the “Location” field and click “Find Calendars”, then “Properties”. Enable “Read Only” and click “OK”, then “Subscribe”.";
         }
         //p : "You can also find calendar links for individual events on their pages."; //TODO
+        p : "If you're signed in, you can also find a link to a personal calendar on your profile, containing only the races of your teams and the races you're restreaming.";
+        p {
+            : "For tools and bots, the same races are also available as ";
+            a(href = uri!(index_json)) : "JSON";
+            : ", and the most recently scheduled races as an ";
+            a(href = uri!(index_atom)) : "Atom feed";
+            : ". Like the iCalendar link, both are also available for individual series and events by replacing ";
+            code : "/calendar.ics";
+            : " with ";
+            code : "/calendar.json";
+            : " or ";
+            code : "/calendar.atom";
+            : " in the link.";
+        }
     }).await
 }
 
@@ -1675,7 +1832,7 @@ pub(crate) async fn index(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &Stat
     let mut cal = ICalendar::new("2.0", concat!("midos.house/", env!("CARGO_PKG_VERSION")));
     for row in sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE listed"#).fetch_all(&mut *transaction).await? {
         let event = event::Data::new(&mut transaction, row.series, row.event).await?.expect("event deleted during calendar load");
-        add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event).await?;
+        add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event, |_| true).await?;
     }
     transaction.commit().await?;
     Ok(Response(cal))
@@ -1687,7 +1844,7 @@ pub(crate) async fn for_series(discord_ctx: &State<RwFuture<DiscordCtx>>, pool:
     let mut cal = ICalendar::new("2.0", concat!("midos.house/", env!("CARGO_PKG_VERSION")));
     for event in sqlx::query_scalar!(r#"SELECT event FROM events WHERE listed AND series = $1"#, series as _).fetch_all(&mut *transaction).await? {
         let event = event::Data::new(&mut transaction, series, event).await?.expect("event deleted during calendar load");
-        add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event).await?;
+        add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event, |_| true).await?;
     }
     transaction.commit().await?;
     Ok(Response(cal))
@@ -1698,11 +1855,256 @@ pub(crate) async fn for_event(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &
     let mut transaction = pool.begin().await?;
     let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
     let mut cal = ICalendar::new("2.0", concat!("midos.house/", env!("CARGO_PKG_VERSION")));
-    add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event).await?;
+    add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event, |_| true).await?;
     transaction.commit().await?;
     Ok(Response(cal))
 }
 
+/// A personal calendar containing only the races that involve one of the user's teams or that the user is restreaming.
+///
+/// Since this reveals which events the user is participating in even if their team isn't public yet, it requires the token shown on the user's own profile.
+#[rocket::get("/user/<id>/calendar.ics?<token>")]
+pub(crate) async fn for_user(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, id: Id<Users>, token: &str) -> Result<Response<ICalendar<'static>>, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let user = User::from_id(&mut *transaction, id).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    if sqlx::query_scalar!("SELECT calendar_token FROM users WHERE id = $1", id as _).fetch_one(&mut *transaction).await?.is_none_or(|calendar_token| calendar_token != token) {
+        return Err(StatusOrError::Status(Status::NotFound))
+    }
+    let teams = sqlx::query_scalar!(r#"SELECT team AS "team: Id<Teams>" FROM team_members WHERE member = $1"#, id as _).fetch_all(&mut *transaction).await?.into_iter().collect::<HashSet<_>>();
+    let racetime_id = user.racetime.as_ref().map(|racetime| &racetime.id);
+    let mut cal = ICalendar::new("2.0", concat!("midos.house/", env!("CARGO_PKG_VERSION")));
+    for row in sqlx::query!(r#"SELECT DISTINCT series AS "series: Series", event FROM races WHERE NOT ignored AND (
+        EXISTS (SELECT 1 FROM team_members WHERE member = $1 AND team IN (team1, team2, team3))
+        OR p1_discord = $2 OR p2_discord = $2
+        OR p1_racetime = $3 OR p2_racetime = $3
+        OR restreamer = $3 OR restreamer_fr = $3 OR restreamer_pt = $3 OR restreamer_de = $3 OR restreamer_es = $3
+    )"#, id as _, user.discord.as_ref().map(|discord| PgSnowflake(discord.id)) as _, racetime_id).fetch_all(&mut *transaction).await? {
+        let event = event::Data::new(&mut transaction, row.series, row.event).await?.expect("event deleted during calendar load");
+        add_event_races(&mut transaction, &*discord_ctx.read().await, http_client, &mut cal, &event, |race| {
+            race.entrants.as_slice().is_some_and(|entrants| entrants.iter().any(|entrant| user::is_user_entrant(&user, &teams, entrant)))
+            || racetime_id.is_some_and(|racetime_id| race.restreamers.values().any(|restreamer| restreamer == racetime_id))
+        }).await?;
+    }
+    transaction.commit().await?;
+    Ok(Response(cal))
+}
+
+/// The listed events of the given series, or of all series, for the JSON and Atom feeds.
+async fn listed_events(transaction: &mut Transaction<'_, Postgres>, series: Option<Series>) -> Result<Vec<event::Data<'static>>, Error> {
+    let rows = sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE listed AND ($1::text IS NULL OR series = $1)"#, series.map(|series| series.slug())).fetch_all(&mut **transaction).await?;
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        events.push(event::Data::new(&mut *transaction, row.series, row.event).await?.expect("event deleted during calendar load"));
+    }
+    Ok(events)
+}
+
+#[derive(Serialize)]
+struct FeedRace {
+    id: Id<Races>,
+    url: String,
+    series: &'static str,
+    event: String,
+    event_name: String,
+    phase: Option<String>,
+    round: Option<String>,
+    game: Option<i16>,
+    /// `None` if the race doesn't have individually known entrants, e.g. an open qualifier.
+    entrants: Option<Vec<FeedEntrant>>,
+    /// One part for a live race, or one per team for an async race. Empty if the race hasn't been scheduled yet.
+    parts: Vec<FeedRacePart>,
+    /// Restream URLs by language code.
+    restreams: BTreeMap<&'static str, Url>,
+}
+
+/// An entrant as listed in the public calendar feeds. Discord user IDs are not included since entrants haven't agreed to publish them.
+#[derive(Serialize)]
+struct FeedEntrant {
+    name: Option<String>,
+    team_id: Option<Id<Teams>>,
+    racetime_team: Option<String>,
+    racetime_id: Option<String>,
+    twitch_username: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FeedRacePart {
+    /// `live`, `async1`, `async2`, or `async3`.
+    kind: &'static str,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    room: Option<Url>,
+}
+
+async fn feed_races(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, events: &[event::Data<'_>]) -> Result<Vec<FeedRace>, Error> {
+    let mut feed_races = Vec::default();
+    for event in events {
+        for race in Race::for_event(&mut *transaction, http_client, event).await? {
+            let entrants = if let Some(entrants) = race.entrants.as_slice() {
+                let mut feed_entrants = Vec::with_capacity(entrants.len());
+                for entrant in entrants {
+                    feed_entrants.push(FeedEntrant {
+                        name: entrant.name(&mut *transaction, discord_ctx).await?.map(|name| name.into_owned()),
+                        team_id: if let Entrant::MidosHouseTeam(team) = entrant { Some(team.id) } else { None },
+                        racetime_team: if let Entrant::MidosHouseTeam(team) = entrant { team.racetime_slug.clone() } else { None },
+                        racetime_id: match entrant {
+                            Entrant::MidosHouseTeam(_) => None,
+                            Entrant::Discord { racetime_id, .. } | Entrant::Named { racetime_id, .. } => racetime_id.clone(),
+                        },
+                        twitch_username: match entrant {
+                            Entrant::MidosHouseTeam(_) => None,
+                            Entrant::Discord { twitch_username, .. } | Entrant::Named { twitch_username, .. } => twitch_username.clone(),
+                        },
+                    });
+                }
+                Some(feed_entrants)
+            } else {
+                None
+            };
+            // hide room of private async parts until public part finished, same as on the race page
+            let all_ended = race.cal_events().all(|race_event| race_event.end().is_some());
+            feed_races.push(FeedRace {
+                id: race.id,
+                url: uri!(base_uri(), race_info(race.series, &*race.event, race.id)).to_string(),
+                series: race.series.slug(),
+                event_name: event.display_name.clone(),
+                parts: race.cal_events().map(|race_event| FeedRacePart {
+                    kind: match race_event.kind {
+                        EventKind::Normal => "live",
+                        EventKind::Async1 => "async1",
+                        EventKind::Async2 => "async2",
+                        EventKind::Async3 => "async3",
+                    },
+                    start: race_event.start(),
+                    end: race_event.end(),
+                    room: race_event.room().filter(|_| all_ended || !race_event.is_private_async_part()).cloned(),
+                }).collect(),
+                restreams: race.video_urls.iter().map(|(language, video_url)| (language.short_code(), video_url.clone())).collect(),
+                event: race.event,
+                phase: race.phase,
+                round: race.round,
+                game: race.game,
+                entrants,
+            });
+        }
+    }
+    Ok(feed_races)
+}
+
+#[rocket::get("/calendar.json")]
+pub(crate) async fn index_json(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>) -> Result<RawJson<Vec<u8>>, Error> {
+    let mut transaction = pool.begin().await?;
+    let events = listed_events(&mut transaction, None).await?;
+    let races = feed_races(&mut transaction, &*discord_ctx.read().await, http_client, &events).await?;
+    transaction.commit().await?;
+    Ok(RawJson(serde_json::to_vec_pretty(&races)?))
+}
+
+#[rocket::get("/series/<series>/calendar.json")]
+pub(crate) async fn for_series_json(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series) -> Result<RawJson<Vec<u8>>, Error> {
+    let mut transaction = pool.begin().await?;
+    let events = listed_events(&mut transaction, Some(series)).await?;
+    let races = feed_races(&mut transaction, &*discord_ctx.read().await, http_client, &events).await?;
+    transaction.commit().await?;
+    Ok(RawJson(serde_json::to_vec_pretty(&races)?))
+}
+
+#[rocket::get("/event/<series>/<event>/calendar.json")]
+pub(crate) async fn for_event_json(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series, event: &str) -> Result<RawJson<Vec<u8>>, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let races = feed_races(&mut transaction, &*discord_ctx.read().await, http_client, &[event]).await?;
+    transaction.commit().await?;
+    Ok(RawJson(serde_json::to_vec_pretty(&races)?))
+}
+
+/// The maximum number of entries in an Atom feed.
+const ATOM_FEED_LENGTH: usize = 50;
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+/// An Atom feed of the most recently scheduled or rescheduled races of the given events.
+async fn atom_feed(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, title: &str, self_uri: rocket::http::uri::Absolute<'_>, alternate_uri: rocket::http::uri::Absolute<'_>, events: &[event::Data<'_>]) -> Result<(ContentType, String), Error> {
+    let mut races = Vec::default();
+    for event in events {
+        for race in Race::for_event(&mut *transaction, http_client, event).await? {
+            if let Some(schedule_updated_at) = race.schedule_updated_at {
+                if race.cal_events().any(|race_event| race_event.start().is_some()) {
+                    races.push((schedule_updated_at, event, race));
+                }
+            }
+        }
+    }
+    races.sort_by_key(|&(schedule_updated_at, _, ref race)| (Reverse(schedule_updated_at), race.id));
+    races.truncate(ATOM_FEED_LENGTH);
+    let mut feed = String::default();
+    writeln!(&mut feed, r#"<?xml version="1.0" encoding="utf-8"?>"#).unwrap();
+    writeln!(&mut feed, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#).unwrap();
+    writeln!(&mut feed, "<title>{}</title>", xml_escape(title)).unwrap();
+    writeln!(&mut feed, "<id>{}</id>", xml_escape(&self_uri.to_string())).unwrap();
+    writeln!(&mut feed, r#"<link rel="self" href="{}"/>"#, xml_escape(&self_uri.to_string())).unwrap();
+    writeln!(&mut feed, r#"<link rel="alternate" href="{}"/>"#, xml_escape(&alternate_uri.to_string())).unwrap();
+    writeln!(&mut feed, "<updated>{}</updated>", races.first().map_or_else(Utc::now, |&(schedule_updated_at, _, _)| schedule_updated_at).to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap();
+    writeln!(&mut feed, "<author><name>Mido's House</name></author>").unwrap();
+    for (schedule_updated_at, event, race) in races {
+        let race_uri = uri!(base_uri(), race_info(race.series, &*race.event, race.id)).to_string();
+        let mut content = Vec::default();
+        for race_event in race.cal_events() {
+            if let Some(start) = race_event.start() {
+                content.push(match race_event.kind {
+                    EventKind::Normal => format!("Start: {}", start.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                    EventKind::Async1 | EventKind::Async2 | EventKind::Async3 => format!("Async part {}: {}", match race_event.kind {
+                        EventKind::Normal | EventKind::Async1 => 1,
+                        EventKind::Async2 => 2,
+                        EventKind::Async3 => 3,
+                    }, start.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                });
+            }
+        }
+        for (language, video_url) in &race.video_urls {
+            content.push(format!("{language} restream: {video_url}"));
+        }
+        writeln!(&mut feed, "<entry>").unwrap();
+        writeln!(&mut feed, "<title>{}</title>", xml_escape(&race_summary(&mut *transaction, discord_ctx, event, &race, EventKind::Normal).await?)).unwrap();
+        writeln!(&mut feed, "<id>{}</id>", xml_escape(&race_uri)).unwrap();
+        writeln!(&mut feed, r#"<link rel="alternate" href="{}"/>"#, xml_escape(&race_uri)).unwrap();
+        writeln!(&mut feed, "<updated>{}</updated>", schedule_updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap();
+        writeln!(&mut feed, "<content type=\"text\">{}</content>", xml_escape(&content.join("\n"))).unwrap();
+        writeln!(&mut feed, "</entry>").unwrap();
+    }
+    writeln!(&mut feed, "</feed>").unwrap();
+    Ok((ContentType::new("application", "atom+xml"), feed))
+}
+
+#[rocket::get("/calendar.atom")]
+pub(crate) async fn index_atom(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>) -> Result<(ContentType, String), Error> {
+    let mut transaction = pool.begin().await?;
+    let events = listed_events(&mut transaction, None).await?;
+    let feed = atom_feed(&mut transaction, &*discord_ctx.read().await, http_client, "Mido's House races", uri!(base_uri(), index_atom), uri!(base_uri(), index_help), &events).await?;
+    transaction.commit().await?;
+    Ok(feed)
+}
+
+#[rocket::get("/series/<series>/calendar.atom")]
+pub(crate) async fn for_series_atom(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series) -> Result<(ContentType, String), Error> {
+    let mut transaction = pool.begin().await?;
+    let events = listed_events(&mut transaction, Some(series)).await?;
+    let feed = atom_feed(&mut transaction, &*discord_ctx.read().await, http_client, &format!("{} races", series.display_name()), uri!(base_uri(), for_series_atom(series)), uri!(base_uri(), index_help), &events).await?;
+    transaction.commit().await?;
+    Ok(feed)
+}
+
+#[rocket::get("/event/<series>/<event>/calendar.atom")]
+pub(crate) async fn for_event_atom(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series, event: &str) -> Result<(ContentType, String), StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let feed = atom_feed(&mut transaction, &*discord_ctx.read().await, http_client, &format!("{} races", event.display_name), uri!(base_uri(), for_event_atom(series, &*event.event)), uri!(base_uri(), event::races(series, &*event.event)), std::slice::from_ref(&event)).await?;
+    transaction.commit().await?;
+    Ok(feed)
+}
+
 pub(crate) async fn create_race_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: event::Data<'_>, ctx: Context<'_>, is_3p: bool) -> Result<RawHtml<String>, event::Error> {
     let header = event.header(&mut transaction, me.as_ref(), Tab::Races, true).await?;
     let form = if me.is_some() {
@@ -2043,7 +2445,7 @@ pub(crate) async fn race_table(
                             @if options.can_create {
                                 @if let Some(event) = event {
                                     @match event.match_source() {
-                                        MatchSource::Manual | MatchSource::Challonge { .. } => a(class = "button", href = uri!(create_race(races[0].series, &*races[0].event, _))) : "New Race";
+                                        MatchSource::Manual | MatchSource::Challonge { .. } | MatchSource::MidosHouse => a(class = "button", href = uri!(create_race(races[0].series, &*races[0].event, _))) : "New Race";
                                         //MatchSource::Challonge { .. } => a(class = "button", href = uri!(import_races(races[0].series, &*races[0].event))) : "Import"; // disabled due to Challonge pagination bug
                                         MatchSource::League => {}
                                         MatchSource::StartGG(_) => @if !event.auto_import {
@@ -2103,6 +2505,12 @@ pub(crate) async fn race_table(
                                 : race.phase;
                                 : " ";
                                 : race.round;
+                                @if race.draft.is_some() {
+                                    br;
+                                    small {
+                                        a(href = uri!(crate::cal::race_info(race.series, &*race.event, race.id))) : "settings draft";
+                                    }
+                                }
                             }
                         }
                         @if has_games {
@@ -2300,6 +2708,15 @@ pub(crate) async fn import_races_form(mut transaction: Transaction<'_, Postgres>
                 }
             }
         },
+        MatchSource::MidosHouse => html! {
+            article {
+                p {
+                    : "Races for this event are created automatically as the ";
+                    a(href = uri!(event::bracket::get(event.series, &*event.event))) : "bracket";
+                    : " advances.";
+                }
+            }
+        },
         MatchSource::League => html! {
             article {
                 p {
@@ -2432,6 +2849,10 @@ pub(crate) async fn import_races_post(discord_ctx: &State<RwFuture<DiscordCtx>>,
                 }
                 races
             }
+            MatchSource::MidosHouse => {
+                form.context.push_error(form::Error::validation("Races for this event are created automatically as the bracket advances."));
+                Vec::default()
+            }
             MatchSource::League => {
                 form.context.push_error(form::Error::validation("Races for this event are automatically imported from league.ootrandomizer.com."));
                 Vec::default()
@@ -2486,12 +2907,14 @@ async fn auto_import_races_inner(db_pool: PgPool, http_client: reqwest::Client,
     loop {
         lock!(new_room_lock = new_room_lock; {
             let mut transaction = db_pool.begin().await?;
+            let mut conflict_notifications = Vec::default();
             for row in sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE end_time IS NULL OR end_time > NOW()"#).fetch_all(&mut *transaction).await? {
                 let event = event::Data::new(&mut transaction, row.series, row.event).await?.expect("event deleted during transaction");
                 if event.auto_import && event.is_started(&mut transaction).await? {
                     match event.match_source() {
                         MatchSource::Manual => {}
                         MatchSource::Challonge { .. } => {} // Challonge's API doesn't provide enough data to automate race imports
+                        MatchSource::MidosHouse => {} // races are created as the bracket advances
                         MatchSource::League => {
                             let mut races = Vec::default();
                             for id in sqlx::query_scalar!(r#"SELECT id AS "id: Id<Races>" FROM races WHERE series = $1 AND event = $2"#, event.series as _, &event.event).fetch_all(&mut *transaction).await? {
@@ -2549,9 +2972,15 @@ async fn auto_import_races_inner(db_pool: PgPool, http_client: reqwest::Client,
                                     schedule_locked: false,
                                     notified: false,
                                 };
-                                if let Some(race) = races.iter_mut().find(|race| if let Source::League { id } = race.source { id == match_data.id } else { false }) {
+                                let mut check_conflicts = !new_race.ignored;
+                                let race = if let Some(race) = races.iter_mut().find(|race| if let Source::League { id } = race.source { id == match_data.id } else { false }) {
                                     if !race.schedule_locked {
                                         let is_upcoming = !race.has_any_room(); // stop automatically updating certain fields once a room is open
+                                        let start_changed = is_upcoming && !race.schedule.start_matches(&new_race.schedule);
+                                        check_conflicts &= start_changed || is_upcoming && race.restreamers != new_race.restreamers;
+                                        if start_changed {
+                                            reschedule::supersede(&mut transaction, race.id).await?;
+                                        }
                                         *race = Race {
                                             id: race.id,
                                             schedule: if is_upcoming { new_race.schedule } else { mem::take(&mut race.schedule) },
@@ -2564,13 +2993,19 @@ async fn auto_import_races_inner(db_pool: PgPool, http_client: reqwest::Client,
                                             notified: race.notified,
                                             ..new_race
                                         };
+                                    } else {
+                                        check_conflicts = false;
                                     }
                                     race
                                 } else {
                                     new_race.id = Id::<Races>::new(&mut transaction).await?;
                                     races.push(new_race);
                                     races.last_mut().expect("just pushed")
-                                }.save(&mut transaction).await?;
+                                };
+                                race.save(&mut transaction).await?;
+                                if check_conflicts {
+                                    conflict_notifications.extend(conflict::organizer_notification(&mut transaction, &event, race).await?);
+                                }
                             }
                         }
                         MatchSource::StartGG(event_slug) => {
@@ -2692,6 +3127,12 @@ async fn auto_import_races_inner(db_pool: PgPool, http_client: reqwest::Client,
                 }
             }
             transaction.commit().await?;
+            let discord_ctx = discord_ctx.read().await;
+            for (channel, msg) in conflict_notifications {
+                if let Err(e) = channel.say(&*discord_ctx, msg).await {
+                    eprintln!("failed to notify organizers about scheduling conflicts in {channel}: {e} ({e:?})");
+                }
+            }
         });
         select! {
             () = &mut shutdown => break,
@@ -2752,12 +3193,163 @@ pub(crate) async fn practice_seed(pool: &State<PgPool>, http_client: &State<reqw
     Ok(Redirect::to(format!("https://ootrandomizer.com/seed/get?id={id}")))
 }
 
-pub(crate) async fn edit_race_form(mut transaction: Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: event::Data<'_>, race: Race, redirect_to: Option<Origin<'_>>, ctx: Option<Context<'_>>) -> Result<RawHtml<String>, event::Error> {
+pub(crate) async fn race_page(mut transaction: Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: event::Data<'_>, race: Race, ctx: Context<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Races, true).await?;
+    let entrants = match race.entrants {
+        Entrants::Open | Entrants::Count { .. } | Entrants::Named(_) => Vec::default(),
+        Entrants::Two([ref team1, ref team2]) => vec![
+            team1.to_html(&mut transaction, discord_ctx, true).await?,
+            team2.to_html(&mut transaction, discord_ctx, true).await?,
+        ],
+        Entrants::Three([ref team1, ref team2, ref team3]) => vec![
+            team1.to_html(&mut transaction, discord_ctx, true).await?,
+            team2.to_html(&mut transaction, discord_ctx, true).await?,
+            team3.to_html(&mut transaction, discord_ctx, true).await?,
+        ],
+    };
+    let draft = draft::web::section(&mut transaction, me.as_ref(), csrf, &event, &race, &ctx).await?;
+    let mut results = Vec::default();
+    for result in RaceResult::for_race(&mut transaction, race.id).await? {
+        results.push((result.entrant.to_html(&mut transaction, discord_ctx, false).await?, result));
+    }
+    let show_rooms = results.iter().filter_map(|(_, result)| result.room.as_ref()).unique().count() > 1;
+    let content = html! {
+        : header;
+        h2 {
+            : race.phase.as_deref().unwrap_or("Race");
+            @if let Some(ref round) = race.round {
+                : " ";
+                : round;
+            }
+            @if let Some(game) = race.game {
+                : ", game ";
+                : game;
+            }
+        }
+        @if !entrants.is_empty() {
+            p {
+                @for (idx, entrant) in entrants.into_iter().enumerate() {
+                    @if idx > 0 {
+                        : " vs. ";
+                    }
+                    : entrant;
+                }
+            }
+        }
+        p {
+            @match race.schedule {
+                RaceSchedule::Unscheduled => : "Not yet scheduled.";
+                RaceSchedule::Live { start, .. } => {
+                    : "Scheduled for ";
+                    : format_datetime(start, DateTimeFormat { long: true, running_text: true });
+                    : ".";
+                }
+                RaceSchedule::Async { .. } => : "This race is an async.";
+            }
+        }
+        @for room in race.rooms() {
+            p {
+                a(href = room.to_string()) : "race room";
+            }
+        }
+        @if !results.is_empty() {
+            h3 : "Results";
+            table {
+                thead {
+                    tr {
+                        th : "Place";
+                        th : "Entrant";
+                        th : "Finish time";
+                        @if show_rooms {
+                            th : "Room";
+                        }
+                    }
+                }
+                tbody {
+                    @for (entrant, result) in &results {
+                        tr {
+                            td {
+                                @if let Some(placement) = result.placement {
+                                    : placement;
+                                } else {
+                                    : "—";
+                                }
+                            }
+                            td : entrant;
+                            td {
+                                @if let Some(finish_time) = result.finish_time {
+                                    : English.format_duration(finish_time, false);
+                                } else if result.placement.is_none() {
+                                    : "DNF";
+                                } else {
+                                    : "—";
+                                }
+                                @if result.retimed {
+                                    : " (retimed)";
+                                }
+                            }
+                            @if show_rooms {
+                                td {
+                                    @if let Some(ref room) = result.room {
+                                        a(href = room.to_string()) : "race room";
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        : draft;
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Race — {}", event.display_name), content).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/races/<id>")]
+pub(crate) async fn race_info(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, id: Id<Races>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let race = Race::from_id(&mut transaction, http_client, id).await?;
+    if race.series != event.series || race.event != event.event {
+        return Ok(RedirectOrContent::Redirect(Redirect::permanent(uri!(race_info(race.series, race.event, id)))))
+    }
+    Ok(RedirectOrContent::Content(race_page(transaction, &*discord_ctx.read().await, me, uri, csrf.as_ref(), event, race, Context::default()).await?))
+}
+
+pub(crate) async fn edit_race_form(mut transaction: Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: event::Data<'_>, race: Race, redirect_to: Option<Origin<'_>>, ctx: Option<Context<'_>>, result_ctx: Option<Context<'_>>) -> Result<RawHtml<String>, event::Error> {
     let header = event.header(&mut transaction, me.as_ref(), Tab::Races, true).await?;
     let fenhl = User::from_id(&mut *transaction, crate::id::FENHL).await?.ok_or(PageError::FenhlUserData)?;
+    let is_organizer = if let Some(ref me) = me { event.organizers(&mut transaction).await?.contains(me) } else { false };
+    let timezone = if let Some(ref me) = me { me.timezone(&mut *transaction).await? } else { None };
     let form = if me.is_some() {
         let mut errors = ctx.as_ref().map(|ctx| ctx.errors().collect()).unwrap_or_default();
+        let start_fields = match race.schedule {
+            RaceSchedule::Unscheduled | RaceSchedule::Live { .. } => vec![("start", "New start time:")],
+            RaceSchedule::Async { .. } => if let Entrants::Three(_) = race.entrants {
+                vec![("async_start1", "New start time (team A):"), ("async_start2", "New start time (team B):"), ("async_start3", "New start time (team C):")]
+            } else {
+                vec![("async_start1", "New start time (team A):"), ("async_start2", "New start time (team B):")]
+            },
+        };
         full_form(uri!(edit_race_post(event.series, &*event.event, race.id, redirect_to)), csrf, html! {
+            @if let (true, Some(me)) = (is_organizer, &me) {
+                @for (field_name, label_text) in start_fields {
+                    : form_field(field_name, &mut errors, html! {
+                        label(for = field_name) : label_text;
+                        input(type = "text", name = field_name, placeholder = "e.g. tomorrow 8pm", value? = ctx.as_ref().and_then(|ctx| ctx.field_value(field_name)));
+                        @if let Some(confirmed) = ctx.as_ref().and_then(|ctx| ctx.field_value(field_name)) {
+                            input(type = "hidden", name = format!("confirmed_{field_name}"), value = confirmed);
+                        }
+                        label(class = "help") {
+                            : "(Leave blank to keep the current schedule. Times without a time zone are interpreted in ";
+                            : timezone.map_or("UTC", |timezone| timezone.name());
+                            : ", which you can ";
+                            a(href = uri!(user::profile(me.id, _, _))) : "change on your profile";
+                            : ".)";
+                        }
+                    });
+                }
+            }
             @match race.schedule {
                 RaceSchedule::Unscheduled => {}
                 RaceSchedule::Live { ref room, .. } => : form_field("room", &mut errors, html! {
@@ -2864,6 +3456,76 @@ pub(crate) async fn edit_race_form(mut transaction: Transaction<'_, Postgres>, d
             }
         }
     };
+    let result_form = if_chain! {
+        if is_organizer;
+        if let Some(entrants) = race.entrants.as_slice();
+        then {
+            let existing_results = RaceResult::for_race(&mut transaction, race.id).await?;
+            let mut entrant_names = Vec::with_capacity(entrants.len());
+            for entrant in entrants {
+                entrant_names.push(entrant.to_html(&mut transaction, discord_ctx, false).await?);
+            }
+            let mut log = Vec::default();
+            for row in sqlx::query!(r#"SELECT editor AS "editor: Id<Users>", edited_at FROM race_result_log WHERE race = $1 ORDER BY edited_at DESC"#, race.id as _).fetch_all(&mut *transaction).await? {
+                log.push((User::from_id(&mut *transaction, row.editor).await?.ok_or(event::Error::OrganizerUserData)?, row.edited_at));
+            }
+            let mut errors = result_ctx.as_ref().map(|ctx| ctx.errors().collect()).unwrap_or_default();
+            Some(html! {
+                h3 : "Result";
+                p : "If the race room was broken or the race was played outside of racetime.gg, you can enter the result here. The first result entered for a race is announced and reported the same way as an automatically reported race.";
+                p : "If a result has already been recorded, it is replaced. Corrections update Swiss standings, brackets, groups, and ratings, but are not announced again or reported to external sites such as start.gg.";
+                @if !log.is_empty() {
+                    ul {
+                        @for (editor, edited_at) in log {
+                            li {
+                                : "Result entered by ";
+                                : editor;
+                                : " ";
+                                : format_datetime(edited_at, DateTimeFormat { long: true, running_text: true });
+                            }
+                        }
+                    }
+                }
+                : full_form(uri!(edit_race_result(event.series, &*event.event, race.id, redirect_to)), csrf, html! {
+                    table {
+                        thead {
+                            tr {
+                                th : "Entrant";
+                                th : "Finish time";
+                                th : "DNF";
+                            }
+                        }
+                        tbody {
+                            @for (idx, (entrant, entrant_name)) in entrants.iter().zip_eq(entrant_names).enumerate() {
+                                @let existing_result = existing_results.iter().find(|result| result.entrant == *entrant);
+                                tr {
+                                    td : entrant_name;
+                                    @let field_name = format!("finish_times.{idx}");
+                                    : form_table_cell(&field_name, &mut errors, html! {
+                                        input(type = "text", name = &field_name, value? = if let Some(ref ctx) = result_ctx {
+                                            ctx.field_value(&*field_name).map(|time| time.to_owned())
+                                        } else {
+                                            existing_result.and_then(|result| result.finish_time).map(unparse_duration)
+                                        });
+                                    });
+                                    @let field_name = format!("dnf.{idx}");
+                                    : form_table_cell(&field_name, &mut errors, html! {
+                                        input(type = "checkbox", name = &field_name, checked? = if let Some(ref ctx) = result_ctx {
+                                            ctx.field_value(&*field_name) == Some("on")
+                                        } else {
+                                            existing_result.is_some_and(|result| result.placement.is_none())
+                                        });
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }, errors, "Save result")
+            })
+        } else {
+            None
+        }
+    };
     let content = html! {
         : header;
         h2 : "Edit race";
@@ -3019,7 +3681,13 @@ pub(crate) async fn edit_race_form(mut transaction: Transaction<'_, Postgres>, d
             : fenhl;
             : " if you've spotted an error in it.";
         }
+        @if race.draft.is_some() {
+            p {
+                a(href = uri!(draft::history::get(event.series, &*event.event, race.id))) : "View settings draft history";
+            }
+        }
         : form;
+        : result_form;
     };
     Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Edit Race — {}", event.display_name), content).await?)
 }
@@ -3032,7 +3700,25 @@ pub(crate) async fn edit_race(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &
     if race.series != event.series || race.event != event.event {
         return Ok(RedirectOrContent::Redirect(Redirect::permanent(uri!(edit_race(race.series, race.event, id, redirect_to)))))
     }
-    Ok(RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, me, uri, csrf.as_ref(), event, race, redirect_to, None).await?))
+    Ok(RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, me, uri, csrf.as_ref(), event, race, redirect_to, None, None).await?))
+}
+
+/// Interprets a start time entered in the race edit form.
+///
+/// The interpreted time is echoed back as a form error until the same input is submitted again, so organizers can catch misinterpretations.
+fn parse_form_start<'v>(context: &mut Context<'v>, field_name: &'static str, input: &str, confirmed: &str, timezone: Option<Tz>) -> Option<DateTime<Utc>> {
+    if input.is_empty() { return None }
+    if let Some(start) = parse_user_time(input, Utc::now(), timezone.unwrap_or(Tz::UTC)) {
+        if input == confirmed {
+            Some(start.time())
+        } else {
+            context.push_error(form::Error::validation(format!("This will be interpreted as {}. Submit the form again to confirm.", describe_time(start.time(), timezone))).with_name(field_name));
+            None
+        }
+    } else {
+        context.push_error(form::Error::validation("Couldn't understand this time. You can use a date and time like “2026-11-03 19:30 Europe/Berlin”, a day and time like “tomorrow 8pm”, or an offset like “in 2 hours”.").with_name(field_name));
+        None
+    }
 }
 
 #[derive(FromForm, CsrfForm)]
@@ -3047,6 +3733,22 @@ pub(crate) struct EditRaceForm {
     async_room2: String,
     #[field(default = String::new())]
     async_room3: String,
+    #[field(default = String::new())]
+    start: String,
+    #[field(default = String::new())]
+    async_start1: String,
+    #[field(default = String::new())]
+    async_start2: String,
+    #[field(default = String::new())]
+    async_start3: String,
+    #[field(default = String::new())]
+    confirmed_start: String,
+    #[field(default = String::new())]
+    confirmed_async_start1: String,
+    #[field(default = String::new())]
+    confirmed_async_start2: String,
+    #[field(default = String::new())]
+    confirmed_async_start3: String,
     #[field(default = HashMap::new())]
     video_urls: HashMap<Language, String>,
     #[field(default = HashMap::new())]
@@ -3067,6 +3769,23 @@ pub(crate) async fn edit_race_post(discord_ctx: &State<RwFuture<DiscordCtx>>, po
         form.context.push_error(form::Error::validation("You must be an organizer, restream coordinator, or archivist to edit this race. If you would like to be a restream coordinator for this event, please contact the organizers. If you would like to become an archivist, please contact Fenhl on Discord."));
     }
     Ok(if let Some(ref value) = form.value {
+        let mut new_start = None;
+        let mut new_async_starts = [None; 3];
+        if !value.start.is_empty() || !value.async_start1.is_empty() || !value.async_start2.is_empty() || !value.async_start3.is_empty() {
+            if event.organizers(&mut transaction).await?.contains(&me) {
+                let timezone = me.timezone(&mut *transaction).await?;
+                match race.schedule {
+                    RaceSchedule::Unscheduled | RaceSchedule::Live { .. } => new_start = parse_form_start(&mut form.context, "start", &value.start, &value.confirmed_start, timezone),
+                    RaceSchedule::Async { .. } => new_async_starts = [
+                        parse_form_start(&mut form.context, "async_start1", &value.async_start1, &value.confirmed_async_start1, timezone),
+                        parse_form_start(&mut form.context, "async_start2", &value.async_start2, &value.confirmed_async_start2, timezone),
+                        parse_form_start(&mut form.context, "async_start3", &value.async_start3, &value.confirmed_async_start3, timezone),
+                    ],
+                }
+            } else {
+                form.context.push_error(form::Error::validation("Only organizers can change the schedule of a race."));
+            }
+        }
         let mut valid_room_urls = HashMap::new();
         match race.schedule {
             RaceSchedule::Unscheduled => {
@@ -3307,7 +4026,7 @@ pub(crate) async fn edit_race_post(discord_ctx: &State<RwFuture<DiscordCtx>>, po
             }
         }
         if form.context.errors().next().is_some() {
-            RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, Some(form.context)).await?)
+            RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, Some(form.context), None).await?)
         } else {
             match &mut race.schedule {
                 RaceSchedule::Unscheduled => {}
@@ -3318,10 +4037,29 @@ pub(crate) async fn edit_race_post(discord_ctx: &State<RwFuture<DiscordCtx>>, po
                     *room3 = (!value.async_room3.is_empty()).then(|| Url::parse(&value.async_room3).expect("validated"));
                 }
             }
+            if let Some(start) = new_start {
+                race.schedule.set_live_start(start);
+                race.schedule_updated_at = Some(Utc::now());
+            }
+            let [start1, start2, start3] = new_async_starts;
+            if let Some(start1) = start1 {
+                race.schedule.set_async_start1(start1);
+                race.schedule_updated_at = Some(Utc::now());
+            }
+            if let Some(start2) = start2 {
+                race.schedule.set_async_start2(start2);
+                race.schedule_updated_at = Some(Utc::now());
+            }
+            if let Some(start3) = start3 {
+                race.schedule.set_async_start3(start3);
+                race.schedule_updated_at = Some(Utc::now());
+            }
             race.last_edited_by = Some(me.id);
             race.last_edited_at = Some(Utc::now());
+            let mut check_conflicts = new_start.is_some();
             if race.series != Series::League || race.has_any_room() {
                 race.video_urls = value.video_urls.iter().filter(|(_, video_url)| !video_url.is_empty()).map(|(language, video_url)| (*language, Url::parse(video_url).expect("validated"))).collect();
+                check_conflicts |= race.restreamers != restreamers;
                 race.restreamers = restreamers;
             }
             if let Some(file_hash) = file_hash {
@@ -3331,11 +4069,108 @@ pub(crate) async fn edit_race_post(discord_ctx: &State<RwFuture<DiscordCtx>>, po
                 race.seed.files = Some(seed::Files::OotrWeb { id, gen_time, file_stem: Cow::Owned(file_stem) });
             }
             race.save(&mut transaction).await?;
+            if new_start.is_some() || new_async_starts.iter().any(Option::is_some) {
+                reschedule::supersede(&mut transaction, race.id).await?;
+            }
+            let conflicts = if check_conflicts { conflict::for_race(&mut transaction, &race).await? } else { Vec::default() };
+            let redirect_to = redirect_to.map(|Origin(uri)| uri.into_owned()).unwrap_or_else(|| uri!(event::races(event.series, &*event.event)));
+            if conflicts.is_empty() {
+                transaction.commit().await?;
+                RedirectOrContent::Redirect(Redirect::to(redirect_to))
+            } else {
+                // the changes are saved anyway, the organizers may be aware of the conflict or resolve it on the other race
+                transaction.commit().await?;
+                let transaction = pool.begin().await?;
+                RedirectOrContent::Content(page(transaction, &Some(me), &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Scheduling conflicts — {}", event.display_name), html! {
+                    h1 : "Scheduling conflicts";
+                    p : "The race has been saved, but it overlaps with the following races:";
+                    ul {
+                        @for conflict in conflicts {
+                            li : conflict;
+                        }
+                    }
+                    p {
+                        a(href = redirect_to.to_string()) : "Continue";
+                    }
+                }).await?)
+            }
+        }
+    } else {
+        RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, Some(form.context), None).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct EditRaceResultForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = HashMap::new())]
+    finish_times: HashMap<usize, String>,
+    #[field(default = HashMap::new())]
+    dnf: HashMap<usize, bool>,
+}
+
+#[rocket::post("/event/<series>/<event>/races/<id>/result?<redirect_to>", data = "<form>")]
+pub(crate) async fn edit_race_result(discord_ctx: &State<RwFuture<DiscordCtx>>, config: &State<Config>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, id: Id<Races>, redirect_to: Option<Origin<'_>>, form: Form<Contextual<'_, EditRaceResultForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let race = Race::from_id(&mut transaction, http_client, id).await?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    if race.series != event.series || race.event != event.event {
+        form.context.push_error(form::Error::validation("This race is not part of this event."));
+    }
+    if !event.organizers(&mut transaction).await?.contains(&me) {
+        form.context.push_error(form::Error::validation("You must be an organizer to enter race results."));
+    }
+    let entrants = race.entrants.as_slice().map(|entrants| entrants.to_vec()).unwrap_or_default();
+    if entrants.is_empty() {
+        form.context.push_error(form::Error::validation("Results can only be entered for races with known entrants."));
+    }
+    Ok(if let Some(ref value) = form.value {
+        let mut finish_times = Vec::with_capacity(entrants.len());
+        for idx in 0..entrants.len() {
+            if value.dnf.get(&idx).copied().unwrap_or(false) {
+                finish_times.push(None);
+            } else if let Some(finish_time) = value.finish_times.get(&idx).and_then(|finish_time| parse_duration(finish_time, None)) {
+                finish_times.push(Some(finish_time));
+            } else {
+                form.context.push_error(form::Error::validation("Please enter a finish time (e.g. 1:23:45) or mark this entrant as DNF.").with_name(format!("finish_times.{idx}")));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, None, Some(form.context)).await?)
+        } else {
+            let rooms = match race.schedule {
+                RaceSchedule::Unscheduled => vec![None; entrants.len()],
+                RaceSchedule::Live { ref room, .. } => vec![room.clone(); entrants.len()],
+                RaceSchedule::Async { ref room1, ref room2, ref room3, .. } => [room1, room2, room3].into_iter().take(entrants.len()).cloned().collect(),
+            };
+            // results which have already been reported are only corrected, without announcing them again
+            let is_correction = race.is_ended() || !RaceResult::for_race(&mut transaction, race.id).await?.is_empty();
+            sqlx::query!("INSERT INTO race_result_log (race, editor) VALUES ($1, $2)", race.id as _, me.id as _).execute(&mut *transaction).await?;
+            sqlx::query!("UPDATE races SET last_edited_by = $1, last_edited_at = NOW() WHERE id = $2", me.id as _, race.id as _).execute(&mut *transaction).await?;
+            let results = entrants.into_iter().zip_eq(finish_times).zip_eq(rooms).map(|((entrant, finish_time), room)| (entrant, finish_time, room)).collect_vec();
+            let mut bracket_notification = None;
+            if is_correction {
+                (transaction, bracket_notification) = racetime_bot::report::correct_results(transaction, &*discord_ctx.read().await, http_client, &race, &event, results).await?;
+            } else {
+                match <[_; 2]>::try_from(results) {
+                    Ok(results) => transaction = racetime_bot::report::report_1v1(transaction, &*discord_ctx.read().await, http_client, &config.league_api_key, &config.startgg, &race, &event, results, true).await?,
+                    Err(results) => racetime_bot::report::report_multi(&mut transaction, &*discord_ctx.read().await, &race, &event, results).await?,
+                }
+            }
             transaction.commit().await?;
-            RedirectOrContent::Redirect(Redirect::to(redirect_to.map(|Origin(uri)| uri.into_owned()).unwrap_or_else(|| uri!(event::races(event.series, &*event.event)))))
+            if let Some((channel, msg)) = bracket_notification {
+                // the correction has already been saved at this point, so a failure to notify shouldn't be reported to the organizer
+                if let Err(e) = channel.say(&*discord_ctx.read().await, msg).await {
+                    eprintln!("failed to notify organizers of changed bracket match outcome in {}/{}: {e} ({e:?})", event.series, event.event);
+                }
+            }
+            RedirectOrContent::Redirect(Redirect::to(uri!(race_info(event.series, &*event.event, race.id))))
         }
     } else {
-        RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, Some(form.context)).await?)
+        RedirectOrContent::Content(edit_race_form(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, redirect_to, None, Some(form.context)).await?)
     })
 }
 
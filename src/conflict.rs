@@ -0,0 +1,216 @@
+//! Detection of scheduling conflicts between races that share a player or a restreamer, including races from different events.
+//!
+//! Only live races are checked. Races without a recorded end time are assumed to take their series' [default duration](Series::default_race_duration).
+
+use crate::prelude::*;
+
+/// No series has a default race duration this long, so races starting earlier than this before a given race can't overlap with it unless they have already ended.
+const MAX_RACE_DURATION_HOURS: i64 = 6;
+
+enum Reason {
+    /// A member of one of the race's teams is also playing in the other race.
+    Player(User),
+    /// Someone is assigned to restream both races. Restreamers are stored as racetime.gg user IDs, which is also used for display if they don't have a Mido's House account.
+    Restreamer(Either<User, String>),
+}
+
+pub(crate) struct Conflict {
+    reason: Reason,
+    race: Id<Races>,
+    series: Series,
+    event: String,
+    event_name: String,
+    phase: Option<String>,
+    round: Option<String>,
+    game: Option<i16>,
+    start: DateTime<Utc>,
+}
+
+impl Conflict {
+    fn other_race(&self) -> String {
+        let mut description = self.event_name.clone();
+        if let Some(round) = describe_round(self.phase.as_deref(), self.round.as_deref(), self.game) {
+            write!(&mut description, " ({round})").unwrap();
+        }
+        description
+    }
+
+    fn who(&self) -> &str {
+        match &self.reason {
+            Reason::Player(user) | Reason::Restreamer(Either::Left(user)) => user.display_name(),
+            Reason::Restreamer(Either::Right(racetime_id)) => racetime_id,
+        }
+    }
+
+    /// Appends a warning about this conflict on a new line.
+    pub(crate) fn push_warning(&self, msg: &mut MessageBuilder, language: Language) {
+        msg.push_line("");
+        if let French = language {
+            msg.push_bold("Attention :");
+            msg.push(' ');
+            msg.push_safe(self.who());
+            msg.push(match self.reason {
+                Reason::Player(_) => " joue aussi dans ",
+                Reason::Restreamer(_) => " restream aussi ",
+            });
+            msg.push_safe(self.other_race());
+            msg.push(", planifiée pour le ");
+        } else {
+            msg.push_bold("Warning:");
+            msg.push(' ');
+            msg.push_safe(self.who());
+            msg.push(match self.reason {
+                Reason::Player(_) => " is also playing in ",
+                Reason::Restreamer(_) => " is also restreaming ",
+            });
+            msg.push_safe(self.other_race());
+            msg.push(", scheduled for ");
+        }
+        msg.push_timestamp(self.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+        msg.push('.');
+    }
+}
+
+impl ToHtml for Conflict {
+    fn to_html(&self) -> RawHtml<String> {
+        html! {
+            @match &self.reason {
+                Reason::Player(user) | Reason::Restreamer(Either::Left(user)) => { : user; }
+                Reason::Restreamer(Either::Right(racetime_id)) => { : racetime_id; }
+            }
+            @match self.reason {
+                Reason::Player(_) => { : " is also playing in "; }
+                Reason::Restreamer(_) => { : " is also restreaming "; }
+            }
+            a(href = uri!(cal::race_info(self.series, &*self.event, self.race))) : self.other_race();
+            : ", scheduled for ";
+            : format_datetime(self.start, DateTimeFormat { long: false, running_text: true });
+        }
+    }
+}
+
+/// A short description of a race's position within its event, e.g. “Top 8 Round 1, game 2”.
+pub(crate) fn describe_round(phase: Option<&str>, round: Option<&str>, game: Option<i16>) -> Option<String> {
+    let mut description = match (phase, round) {
+        (Some(phase), Some(round)) => format!("{phase} {round}"),
+        (Some(phase_or_round), None) | (None, Some(phase_or_round)) => phase_or_round.to_owned(),
+        (None, None) => String::default(),
+    };
+    if let Some(game) = game {
+        if !description.is_empty() {
+            description.push_str(", ");
+        }
+        write!(&mut description, "game {game}").unwrap();
+    }
+    (!description.is_empty()).then_some(description)
+}
+
+fn overlaps(start: DateTime<Utc>, end: DateTime<Utc>, series: Series, other_start: DateTime<Utc>, other_end: Option<DateTime<Utc>>) -> bool {
+    other_start < end && other_end.unwrap_or_else(|| other_start + series.default_race_duration()) > start
+}
+
+/// Returns the races which share a player or a restreamer with the given race and overlap with it.
+pub(crate) async fn for_race(transaction: &mut Transaction<'_, Postgres>, race: &Race) -> sqlx::Result<Vec<Conflict>> {
+    let RaceSchedule::Live { start, end, .. } = race.schedule else { return Ok(Vec::default()) };
+    let end = end.unwrap_or_else(|| start + race.series.default_race_duration());
+    let earliest_start = start - TimeDelta::hours(MAX_RACE_DURATION_HOURS);
+    let teams = race.teams().map(|team| i64::from(team.id)).collect_vec();
+    let mut conflicts = Vec::default();
+    for row in sqlx::query!(r#"SELECT DISTINCT
+        races.id AS "id: Id<Races>",
+        races.series AS "series: Series",
+        races.event,
+        events.display_name,
+        races.phase,
+        races.round,
+        races.game,
+        races.start AS "start!",
+        races.end_time,
+        other_member.member AS "member: Id<Users>"
+    FROM races
+    JOIN events ON events.series = races.series AND events.event = races.event
+    JOIN team_members AS other_member ON other_member.team IN (races.team1, races.team2, races.team3)
+    JOIN team_members AS this_member ON this_member.member = other_member.member
+    WHERE this_member.team = ANY($1) AND races.id <> $2 AND NOT races.ignored AND races.start < $3 AND (races.start > $4 OR races.end_time > $5)"#, &teams, race.id as _, end, earliest_start, start).fetch_all(&mut **transaction).await? {
+        if !overlaps(start, end, row.series, row.start, row.end_time) { continue }
+        let Some(user) = User::from_id(&mut **transaction, row.member).await? else { continue };
+        conflicts.push(Conflict {
+            reason: Reason::Player(user),
+            race: row.id,
+            series: row.series,
+            event: row.event,
+            event_name: row.display_name,
+            phase: row.phase,
+            round: row.round,
+            game: row.game,
+            start: row.start,
+        });
+    }
+    if !race.restreamers.is_empty() {
+        for row in sqlx::query!(r#"SELECT
+            races.id AS "id: Id<Races>",
+            races.series AS "series: Series",
+            races.event,
+            events.display_name,
+            races.phase,
+            races.round,
+            races.game,
+            races.start AS "start!",
+            races.end_time,
+            races.restreamer,
+            races.restreamer_fr,
+            races.restreamer_de,
+            races.restreamer_pt,
+            races.restreamer_es
+        FROM races
+        JOIN events ON events.series = races.series AND events.event = races.event
+        WHERE races.id <> $1 AND NOT races.ignored AND races.start < $2 AND (races.start > $3 OR races.end_time > $4)
+        AND (races.restreamer IS NOT NULL OR races.restreamer_fr IS NOT NULL OR races.restreamer_de IS NOT NULL OR races.restreamer_pt IS NOT NULL OR races.restreamer_es IS NOT NULL)"#, race.id as _, end, earliest_start, start).fetch_all(&mut **transaction).await? {
+            if !overlaps(start, end, row.series, row.start, row.end_time) { continue }
+            let other_restreamers = [row.restreamer, row.restreamer_fr, row.restreamer_de, row.restreamer_pt, row.restreamer_es].into_iter().flatten().collect::<HashSet<_>>();
+            for racetime_id in other_restreamers {
+                if race.restreamers.values().any(|restreamer| *restreamer == racetime_id) {
+                    conflicts.push(Conflict {
+                        reason: Reason::Restreamer(if let Some(user) = User::from_racetime(&mut **transaction, &racetime_id).await? { Either::Left(user) } else { Either::Right(racetime_id) }),
+                        race: row.id,
+                        series: row.series,
+                        event: row.event.clone(),
+                        event_name: row.display_name.clone(),
+                        phase: row.phase.clone(),
+                        round: row.round.clone(),
+                        game: row.game,
+                        start: row.start,
+                    });
+                }
+            }
+        }
+    }
+    conflicts.sort_unstable_by_key(|conflict| conflict.start);
+    Ok(conflicts)
+}
+
+/// Builds warnings about the race's conflicts for the event's organizer channel, if any. Used when a race is scheduled by an import, where there's no one to respond to.
+///
+/// The message should only be sent once the import has been committed, so a rollback doesn't leave a stale notification.
+pub(crate) async fn organizer_notification(transaction: &mut Transaction<'_, Postgres>, event: &event::Data<'_>, race: &Race) -> sqlx::Result<Option<(ChannelId, String)>> {
+    let Some(channel) = event.discord_organizer_channel else { return Ok(None) };
+    let conflicts = for_race(transaction, race).await?;
+    if conflicts.is_empty() { return Ok(None) }
+    let mut msg = MessageBuilder::default();
+    msg.push("A race imported for ");
+    msg.push_safe(&event.display_name);
+    if let Some(round) = describe_round(race.phase.as_deref(), race.round.as_deref(), race.game) {
+        msg.push(" (");
+        msg.push_safe(round);
+        msg.push(')');
+    }
+    if let RaceSchedule::Live { start, .. } = race.schedule {
+        msg.push(" and scheduled for ");
+        msg.push_timestamp(start, serenity_utils::message::TimestampStyle::ShortDateTime);
+    }
+    msg.push(" has scheduling conflicts:");
+    for conflict in &conflicts {
+        conflict.push_warning(&mut msg, English);
+    }
+    Ok(Some((channel, msg.build())))
+}
@@ -0,0 +1,173 @@
+//! Optional per-round scheduling deadlines, configured using the `deadline` and `deadline_reminder` columns of the `phase_round_options` table.
+//!
+//! A background task posts a reminder to the scheduling threads of unscheduled races once the deadline is near,
+//! and flags races which are still unscheduled in the event's organizer channel once it has passed.
+//! Races which only get created after their round's deadline has passed (e.g. by bracket advancement) are flagged right away.
+//! Since there was no chance to remind their entrants, the reminder is posted in the same check, noting that the deadline has passed.
+//!
+//! The notification state is committed before the messages are sent, so a failure to send is logged rather than retried,
+//! which avoids duplicate pings.
+
+use crate::{
+    conflict,
+    prelude::*,
+};
+
+async fn reminder(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, event: &event::Data<'_>, race: &Race, deadline: DateTime<Utc>, passed: bool) -> Result<String, event::Error> {
+    let command_ids = if let Some(guild_id) = event.discord_guild {
+        discord_ctx.data.read().await.get::<CommandIds>().and_then(|command_ids| command_ids.get(&guild_id).copied().flatten())
+    } else {
+        None
+    };
+    let mut msg = MessageBuilder::default();
+    let mut any_teams = false;
+    for team in race.teams() {
+        if any_teams {
+            msg.push(' ');
+        }
+        msg.mention_team(&mut *transaction, event.discord_guild, team).await?;
+        any_teams = true;
+    }
+    if any_teams {
+        msg.push(": ");
+    }
+    msg.push(if let Some(game) = race.game { format!("game {game}") } else { format!("this race") });
+    if passed {
+        msg.push(" was supposed to be scheduled by ");
+        msg.push_timestamp(deadline, serenity_utils::message::TimestampStyle::LongDateTime);
+        msg.push(". Please use ");
+    } else {
+        msg.push(" has to be scheduled by ");
+        msg.push_timestamp(deadline, serenity_utils::message::TimestampStyle::LongDateTime);
+        msg.push(" (");
+        msg.push_timestamp(deadline, serenity_utils::message::TimestampStyle::Relative);
+        msg.push("). Please use ");
+    }
+    if let Some(command_ids) = command_ids {
+        msg.mention_command(command_ids.schedule, "schedule");
+    } else {
+        msg.push_mono("/schedule");
+    }
+    msg.push(if passed { " to schedule it as soon as possible." } else { " to schedule it, or contact a tournament organizer if you need more time." });
+    Ok(msg.build())
+}
+
+async fn flag(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, event: &event::Data<'_>, race: &Race, deadline: DateTime<Utc>) -> Result<String, event::Error> {
+    let mut entrants = Vec::default();
+    if let Some(slice) = race.entrants.as_slice() {
+        for entrant in slice {
+            if let Some(name) = entrant.name(&mut *transaction, discord_ctx).await? {
+                entrants.push(name.into_owned());
+            }
+        }
+    }
+    let mut msg = MessageBuilder::default();
+    msg.push("A race in ");
+    msg.push_safe(&event.display_name);
+    if let Some(round) = conflict::describe_round(race.phase.as_deref(), race.round.as_deref(), race.game) {
+        msg.push(" (");
+        msg.push_safe(round);
+        msg.push(')');
+    }
+    if let Some(entrants) = English.join_str_opt(entrants) {
+        msg.push(" between ");
+        msg.push_safe(entrants);
+    }
+    msg.push(" has not been scheduled by its deadline of ");
+    msg.push_timestamp(deadline, serenity_utils::message::TimestampStyle::LongDateTime);
+    msg.push('.');
+    if let Some(thread) = race.scheduling_thread {
+        msg.push(" Scheduling thread: ");
+        msg.mention(&thread);
+    }
+    Ok(msg.build())
+}
+
+async fn check_all(db_pool: &PgPool, http_client: &reqwest::Client, discord_ctx: &DiscordCtx) -> Result<(), event::Error> {
+    let rows = sqlx::query!(r#"SELECT
+        races.id AS "id: Id<Races>",
+        phase_round_options.deadline AS "deadline!",
+        scheduling_deadline_state.deadline AS "notified_deadline?",
+        scheduling_deadline_state.reminded AS "reminded?",
+        scheduling_deadline_state.flagged AS "flagged?"
+    FROM races
+    JOIN phase_round_options ON phase_round_options.series = races.series AND phase_round_options.event = races.event AND phase_round_options.phase = races.phase AND phase_round_options.round = races.round
+    JOIN events ON events.series = races.series AND events.event = races.event
+    LEFT JOIN scheduling_deadline_state ON scheduling_deadline_state.race = races.id
+    WHERE phase_round_options.deadline IS NOT NULL
+    AND phase_round_options.deadline - phase_round_options.deadline_reminder <= NOW()
+    AND NOT races.ignored
+    AND races.start IS NULL AND races.async_start1 IS NULL AND races.async_start2 IS NULL AND races.async_start3 IS NULL
+    AND (events.end_time IS NULL OR events.end_time > NOW())"#).fetch_all(db_pool).await?;
+    for row in rows {
+        // if the deadline has been changed since the last notification, start over
+        let (reminded, flagged) = if row.notified_deadline == Some(row.deadline) {
+            (row.reminded.unwrap_or_default(), row.flagged.unwrap_or_default())
+        } else {
+            (false, false)
+        };
+        let passed = row.deadline <= Utc::now();
+        if flagged || (reminded && !passed) { continue }
+        let mut transaction = db_pool.begin().await?;
+        let race = Race::from_id(&mut transaction, http_client, row.id).await?;
+        let event = race.event(&mut transaction).await?;
+        let mut messages = Vec::default();
+        if !reminded {
+            if let Some(thread) = race.scheduling_thread {
+                messages.push((thread, reminder(&mut transaction, discord_ctx, &event, &race, row.deadline, passed).await?));
+            }
+        }
+        if passed {
+            if let Some(channel) = event.discord_organizer_channel {
+                messages.push((channel, flag(&mut transaction, discord_ctx, &event, &race, row.deadline).await?));
+            }
+        }
+        sqlx::query!("INSERT INTO scheduling_deadline_state (race, deadline, reminded, flagged) VALUES ($1, $2, TRUE, $3) ON CONFLICT (race) DO UPDATE SET deadline = EXCLUDED.deadline, reminded = EXCLUDED.reminded, flagged = EXCLUDED.flagged", row.id as _, row.deadline, passed).execute(&mut *transaction).await?;
+        transaction.commit().await?;
+        for (channel, msg) in messages {
+            if let Err(e) = channel.say(discord_ctx, msg).await {
+                eprintln!("failed to post scheduling deadline notification for race {} in {channel}: {e} ({e:?})", row.id);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_inner(db_pool: &PgPool, http_client: &reqwest::Client, discord_ctx: &RwFuture<DiscordCtx>, shutdown: &mut rocket::Shutdown) -> Result<(), event::Error> {
+    loop {
+        check_all(db_pool, http_client, &*discord_ctx.read().await).await?;
+        select! {
+            () = &mut *shutdown => break,
+            () = sleep(Duration::from_secs(60)) => {}
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run(db_pool: PgPool, http_client: reqwest::Client, discord_ctx: RwFuture<DiscordCtx>, mut shutdown: rocket::Shutdown) -> Result<(), event::Error> {
+    let mut last_crash = Instant::now();
+    let mut wait_time = Duration::from_secs(1);
+    loop {
+        match run_inner(&db_pool, &http_client, &discord_ctx, &mut shutdown).await {
+            Ok(()) => break Ok(()),
+            Err(e) if e.is_network_error() => {
+                if last_crash.elapsed() >= Duration::from_secs(60 * 60 * 24) {
+                    wait_time = Duration::from_secs(1); // reset wait time after no crash for a day
+                } else {
+                    wait_time *= 2; // exponential backoff
+                }
+                if wait_time >= Duration::from_secs(2 * 60) {
+                    eprintln!("failed to check scheduling deadlines (retrying in {}): {e} ({e:?})", English.format_duration(wait_time, true));
+                }
+                sleep(wait_time).await;
+                last_crash = Instant::now();
+            }
+            Err(e) => {
+                if let Environment::Production = Environment::default() {
+                    wheel::night_report(&format!("{}/error", night_path()), Some(&format!("failed to check scheduling deadlines: {e} ({e:?})"))).await?;
+                }
+                break Err(e)
+            }
+        }
+    }
+}
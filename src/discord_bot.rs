@@ -23,7 +23,12 @@ use {
         Encode,
         types::Json,
     },
-    crate::prelude::*,
+    crate::{
+        availability,
+        conflict,
+        prelude::*,
+        reschedule,
+    },
 };
 
 pub(crate) const FENHL: UserId = UserId::new(86841168427495424);
@@ -180,9 +185,11 @@ impl TypeMapKey for ExtraRoomTx {
 
 #[derive(Clone, Copy)]
 pub(crate) struct CommandIds {
+    pub(crate) availability: CommandId,
     pub(crate) ban: Option<CommandId>,
     delete_after: CommandId,
     draft: Option<CommandId>,
+    draft_undo: Option<CommandId>,
     pub(crate) first: Option<CommandId>,
     pub(crate) no: Option<CommandId>,
     pub(crate) pick: Option<CommandId>,
@@ -355,7 +362,9 @@ async fn check_scheduling_thread_permissions<'a>(ctx: &'a DiscordCtx, interactio
 }
 
 async fn check_draft_permissions<'a>(ctx: &'a DiscordCtx, interaction: &impl GenericInteraction) -> Result<Option<(event::Data<'static>, Race, draft::Kind, draft::MessageContext<'a>)>, Box<dyn std::error::Error + Send + Sync>> {
-    let Some((mut transaction, race, team)) = check_scheduling_thread_permissions(ctx, interaction, None, false, Some("You can continue the draft in the race room")).await? else { return Ok(None) };
+    let Some((mut transaction, mut race, team)) = check_scheduling_thread_permissions(ctx, interaction, None, false, Some("You can continue the draft in the race room")).await? else { return Ok(None) };
+    // reload the draft under lock, since it may have changed since the race was loaded
+    race.draft = Draft::lock(&mut transaction, race.id).await?;
     let guild_id = interaction.guild_id().expect("Received interaction from outside of a guild");
     let event = race.event(&mut transaction).await?;
     Ok(if let Some(team) = team {
@@ -503,6 +512,8 @@ async fn send_draft_settings_page(ctx: &DiscordCtx, interaction: &impl GenericIn
 
 async fn draft_action(ctx: &DiscordCtx, interaction: &impl GenericInteraction, action: draft::Action) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let Some((event, mut race, draft_kind, mut msg_ctx)) = check_draft_permissions(ctx, interaction).await? else { return Ok(()) };
+    let state_before = race.draft.clone().unwrap();
+    let logged_action = action.clone();
     match race.draft.as_mut().unwrap().apply(draft_kind, race.game, &mut msg_ctx, action).await? {
         Ok(apply_response) => {
             interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
@@ -515,6 +526,7 @@ async fn draft_action(ctx: &DiscordCtx, interaction: &impl GenericInteraction, a
             }
             let mut transaction = msg_ctx.into_transaction();
             sqlx::query!("UPDATE races SET draft_state = $1 WHERE id = $2", Json(race.draft.as_ref().unwrap()) as _, race.id as _).execute(&mut *transaction).await?;
+            draft::history::record(&mut transaction, race.id, draft::history::Source::Discord, Some(draft::history::Actor::Discord(interaction.user_id())), &state_before, &logged_action).await?;
             transaction.commit().await?;
         }
         Err(error_msg) => {
@@ -528,10 +540,551 @@ async fn draft_action(ctx: &DiscordCtx, interaction: &impl GenericInteraction, a
     Ok(())
 }
 
-fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
-    regex_captures!("^<t:(-?[0-9]+)(?::[tTdDfFR])?>$", timestamp)
-        .and_then(|(_, timestamp)| timestamp.parse().ok())
-        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+/// Echoes back how a human-readable start time was interpreted and asks the user to confirm it.
+async fn confirm_schedule(ctx: &DiscordCtx, interaction: &impl GenericInteraction, language: Language, timezone: Option<Tz>, start: DateTime<Utc>, custom_id: String) -> serenity::Result<()> {
+    let mut content = MessageBuilder::default();
+    if let French = language {
+        content.push("La race sera planifiée pour le ");
+        content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+        content.push(" (");
+        content.push_safe(describe_time(start, timezone));
+        content.push("). Cliquez sur Confirmer si c'est correct.");
+        if timezone.is_none() {
+            content.push_line("");
+            content.push("Les heures sans fuseau horaire sont interprétées en UTC. Vous pouvez choisir votre fuseau horaire sur votre profil Mido's House.");
+        }
+    } else {
+        content.push("This will schedule the race for ");
+        content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+        content.push(" (");
+        content.push_safe(describe_time(start, timezone));
+        content.push("). Click Confirm if this is correct.");
+        if timezone.is_none() {
+            content.push_line("");
+            content.push("Times without a time zone are interpreted as UTC. You can set your time zone on your Mido's House profile.");
+        }
+    }
+    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+        .ephemeral(true)
+        .content(content.build())
+        .button(CreateButton::new(custom_id).label(if let French = language { "Confirmer" } else { "Confirm" }))
+    )).await
+}
+
+/// Sets the race's start time and announces it in the scheduling thread, opening the race room right away if the race starts soon.
+async fn apply_live_start(ctx: &DiscordCtx, interaction: &impl GenericInteraction, mut transaction: Transaction<'_, Postgres>, event: &event::Data<'_>, mut race: Race, start: DateTime<Utc>, was_scheduled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    reschedule::supersede(&mut transaction, race.id).await?;
+    race.schedule.set_live_start(start);
+    race.schedule_updated_at = Some(Utc::now());
+    let mut cal_event = cal::Event { kind: cal::EventKind::Normal, race };
+    if start - Utc::now() < TimeDelta::minutes(30) {
+        let (http_client, new_room_lock, racetime_host, racetime_config, extra_room_tx, clean_shutdown) = {
+            let data = ctx.data.read().await;
+            (
+                data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
+                data.get::<NewRoomLock>().expect("new room lock missing from Discord context").clone(),
+                data.get::<RacetimeHost>().expect("racetime.gg host missing from Discord context").clone(),
+                data.get::<ConfigRaceTime>().expect("racetime.gg config missing from Discord context").clone(),
+                data.get::<ExtraRoomTx>().expect("extra room sender missing from Discord context").clone(),
+                data.get::<CleanShutdown>().expect("clean shutdown state missing from Discord context").clone(),
+            )
+        };
+        lock!(new_room_lock = new_room_lock; {
+            if let Some((_, msg)) = racetime_bot::create_room(&mut transaction, ctx, &racetime_host, &racetime_config.client_id, &racetime_config.client_secret, &extra_room_tx, &http_client, clean_shutdown, &mut cal_event, event).await? {
+                if let Some(channel) = event.discord_race_room_channel {
+                    channel.say(ctx, &msg).await?;
+                }
+                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                    .ephemeral(false)
+                    .content(msg)
+                )).await?;
+            } else {
+                let mut response_content = MessageBuilder::default();
+                response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
+                response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
+                response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+                let response_content = response_content
+                    .push(". The race room will be opened momentarily.")
+                    .build();
+                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                    .ephemeral(false)
+                    .content(response_content)
+                )).await?;
+            }
+            cal_event.race.save(&mut transaction).await?;
+            transaction.commit().await?;
+        })
+    } else {
+        cal_event.race.save(&mut transaction).await?;
+        let overlapping_maintenance_windows = if let RaceHandleMode::RaceTime = cal_event.should_create_room(&mut transaction, event).await? {
+            sqlx::query_as!(Range::<DateTime<Utc>>, r#"SELECT start, end_time AS "end" FROM racetime_maintenance WHERE start < $1 AND end_time > $2"#, start + event.series.default_race_duration(), start - TimeDelta::minutes(30)).fetch_all(&mut *transaction).await?
+        } else {
+            Vec::default()
+        };
+        let conflicts = conflict::for_race(&mut transaction, &cal_event.race).await?;
+        transaction.commit().await?;
+        let french = matches!(event.language, French) && cal_event.race.game.is_none();
+        let mut response_content = MessageBuilder::default();
+        if french {
+            response_content.push("Votre race a été planifiée pour le ");
+        } else {
+            response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
+            response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
+        }
+        response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+        response_content.push('.');
+        for window in overlapping_maintenance_windows {
+            response_content.push_line("");
+            if french {
+                response_content.push_bold("Attention :");
+                response_content.push(" cette race pourrait coïncider avec une maintenance de racetime.gg prévue du ");
+                response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+                response_content.push(" au ");
+            } else {
+                response_content.push_bold("Warning:");
+                response_content.push(" this race may overlap with racetime.gg maintenance planned for ");
+                response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+                response_content.push(" until ");
+            }
+            response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
+            response_content.push('.');
+        }
+        for conflict in &conflicts {
+            conflict.push_warning(&mut response_content, if french { French } else { English });
+        }
+        let response_content = response_content.build();
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(false)
+            .content(response_content)
+        )).await?;
+    }
+    Ok(())
+}
+
+/// Records a request to move an already scheduled race to a new start time and asks the opposing teams to confirm it.
+async fn request_reschedule(ctx: &DiscordCtx, interaction: &impl GenericInteraction, mut transaction: Transaction<'_, Postgres>, event: &event::Data<'_>, race: Race, team: &Team, start: DateTime<Utc>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let now = Utc::now();
+    let current_start = if let RaceSchedule::Live { start, .. } = race.schedule { Some(start) } else { None };
+    let expires_at = reschedule::expiry(now, current_start, start, event.min_schedule_notice);
+    if race.schedule_locked {
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content("Sorry, the schedule for this race has been locked. Please contact a tournament organizer to reschedule it.")
+        )).await?;
+        transaction.rollback().await?;
+    } else if expires_at <= now {
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content("Sorry, this race starts too soon for your opponents to confirm a reschedule. Please contact a tournament organizer to reschedule it.")
+        )).await?;
+        transaction.rollback().await?;
+    } else {
+        let requested_by = User::from_discord(&mut *transaction, interaction.user_id()).await?.expect("team member without Mido's House account");
+        let id = reschedule::create(&mut transaction, race.id, team.id, requested_by.id, interaction.channel_id(), start, expires_at).await?;
+        let mut content = MessageBuilder::default();
+        for opponent in race.teams().filter(|opponent| opponent.id != team.id) {
+            content.mention_team(&mut transaction, event.discord_guild, opponent).await?;
+            content.push(' ');
+        }
+        content.push(": ");
+        content.mention_team(&mut transaction, event.discord_guild, team).await?;
+        content.push(" would like to reschedule ");
+        content.push(if let Some(game) = race.game { format!("game {game}") } else { format!("this race") });
+        if let Some(current_start) = current_start {
+            content.push(" from ");
+            content.push_timestamp(current_start, serenity_utils::message::TimestampStyle::LongDateTime);
+        }
+        content.push(" to ");
+        content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+        content.push(". This request expires ");
+        content.push_timestamp(expires_at, serenity_utils::message::TimestampStyle::Relative);
+        content.push(" and can also be accepted or declined by a tournament organizer.");
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(false)
+            .content(content.build())
+            .button(CreateButton::new(format!("rescheduleaccept_{id}")).label("Accept").style(ButtonStyle::Success))
+            .button(CreateButton::new(format!("rescheduledecline_{id}")).label("Decline").style(ButtonStyle::Danger))
+            .button(CreateButton::new(format!("reschedulewithdraw_{id}")).label("Withdraw").style(ButtonStyle::Secondary))
+        )).await?;
+        transaction.commit().await?;
+    }
+    Ok(())
+}
+
+/// Handles a click on one of the buttons attached to a reschedule request. `action` is the state the request should be moved to.
+async fn resolve_reschedule_request(ctx: &DiscordCtx, interaction: &ComponentInteraction, id: Id<RescheduleRequests>, action: reschedule::State) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (mut transaction, http_client) = {
+        let data = ctx.data.read().await;
+        (
+            data.get::<DbPool>().expect("database connection pool missing from Discord context").begin().await?,
+            data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
+        )
+    };
+    let Some(request) = reschedule::Request::for_update(&mut transaction, id).await? else {
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content("Sorry, this reschedule request no longer exists.")
+        )).await?;
+        transaction.rollback().await?;
+        return Ok(())
+    };
+    let race = Race::from_id(&mut transaction, &http_client, request.race).await?;
+    let event = race.event(&mut transaction).await?;
+    let user = User::from_discord(&mut *transaction, interaction.user.id).await?;
+    let is_organizer = event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user.id));
+    let mut team = None;
+    for iter_team in race.teams() {
+        if iter_team.members(&mut transaction).await?.into_iter().any(|member| member.discord.is_some_and(|discord| discord.id == interaction.user.id)) {
+            team = Some(iter_team.id);
+            break
+        }
+    }
+    let error = if !request.is_open() {
+        Some("Sorry, this reschedule request is no longer open.")
+    } else if race.schedule_updated_at.is_some_and(|updated_at| updated_at > request.requested_at) {
+        request.resolve(&mut transaction, reschedule::State::Superseded, None).await?;
+        Some("Sorry, this race has been rescheduled since this request was made, so it is no longer valid.")
+    } else if race.has_any_room() {
+        Some("Sorry, this race can no longer be rescheduled since a race room is already open. Please contact a tournament organizer if necessary.")
+    } else if matches!(action, reschedule::State::Accepted) && race.schedule_locked && !is_organizer {
+        Some("Sorry, the schedule for this race has been locked since this request was made. Please contact a tournament organizer to reschedule it.")
+    } else {
+        match action {
+            reschedule::State::Withdrawn => (team != Some(request.requester)).then_some("Sorry, only the team which made this request can withdraw it."),
+            reschedule::State::Accepted | reschedule::State::Declined => (!is_organizer && team.is_none_or(|team| team == request.requester)).then_some("Sorry, only the opposing team or a tournament organizer can respond to this request."),
+            reschedule::State::Pending | reschedule::State::Superseded | reschedule::State::Expired => unreachable!("invalid reschedule request action"),
+        }
+    };
+    if let Some(error) = error {
+        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(error)
+        )).await?;
+        transaction.commit().await?;
+    } else {
+        request.resolve(&mut transaction, action, user.as_ref().map(|user| user.id)).await?;
+        if let reschedule::State::Accepted = action {
+            apply_live_start(ctx, interaction, transaction, &event, race, request.start, true).await?;
+        } else {
+            let mut content = MessageBuilder::default();
+            content.mention(&interaction.user);
+            content.push(if let reschedule::State::Withdrawn = action { " withdrew the request to reschedule " } else { " declined the request to reschedule " });
+            content.push(if let Some(game) = race.game { format!("game {game}") } else { format!("this race") });
+            content.push(" for ");
+            content.push_timestamp(request.start, serenity_utils::message::TimestampStyle::LongDateTime);
+            content.push(". The race remains scheduled as before.");
+            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .ephemeral(false)
+                .content(content.build())
+            )).await?;
+            transaction.commit().await?;
+        }
+    }
+    Ok(())
+}
+
+async fn schedule_race(ctx: &DiscordCtx, interaction: &impl GenericInteraction, game: Option<i16>, start: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some((mut transaction, race, team)) = check_scheduling_thread_permissions(ctx, interaction, game, false, None).await? {
+        let event = race.event(&mut transaction).await?;
+        let is_organizer = event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user_id()));
+        let was_scheduled = !matches!(race.schedule, RaceSchedule::Unscheduled);
+        if let Some(speedgaming_slug) = &event.speedgaming_slug {
+            let response_content = if was_scheduled {
+                format!("Please contact a tournament organizer to reschedule this race.")
+            } else {
+                MessageBuilder::default()
+                    .push("Please use <https://speedgaming.org/")
+                    .push(speedgaming_slug)
+                    .push("/submit> to schedule races for this event.")
+                    .build()
+            };
+            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(response_content)
+            )).await?;
+            transaction.rollback().await?;
+        } else if team.is_some() || is_organizer {
+            let timezone = if let Some(user) = User::from_discord(&mut *transaction, interaction.user_id()).await? { user.timezone(&mut *transaction).await? } else { None };
+            if let Some(parsed_start) = parse_user_time(start, Utc::now(), timezone.unwrap_or(Tz::UTC)) {
+                let start = parsed_start.time();
+                if (start - Utc::now()).to_std().map_or(true, |schedule_notice| schedule_notice < event.min_schedule_notice) {
+                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                        .ephemeral(true)
+                        .content(if event.min_schedule_notice <= Duration::default() {
+                            if let French = event.language {
+                                format!("Désolé mais cette date est dans le passé.")
+                            } else {
+                                format!("Sorry, that timestamp is in the past.")
+                            }
+                        } else {
+                            if let French = event.language {
+                                format!("Désolé, les races doivent être planifiées au moins {} en avance.", French.format_duration(event.min_schedule_notice, true))
+                            } else {
+                                format!("Sorry, races must be scheduled at least {} in advance.", English.format_duration(event.min_schedule_notice, true))
+                            }
+                        })
+                    )).await?;
+                    transaction.rollback().await?;
+                } else if let ParsedTime::Interpreted(_) = parsed_start {
+                    confirm_schedule(ctx, interaction, event.language, timezone, start, format!("scheduleconfirm_{}_{}", start.timestamp(), game.map(|game| game.to_string()).unwrap_or_default())).await?;
+                    transaction.rollback().await?;
+                } else {
+                    if_chain! {
+                        if was_scheduled && event.reschedule_confirmation && !is_organizer;
+                        if let Some(ref team) = team;
+                        then {
+                            request_reschedule(ctx, interaction, transaction, &event, race, team, start).await?;
+                        } else {
+                            apply_live_start(ctx, interaction, transaction, &event, race, start, was_scheduled).await?;
+                        }
+                    }
+                }
+            } else {
+                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content(if let French = event.language {
+                        "Désolé, je n'ai pas compris cette date. Vous pouvez utiliser un timestamp de Discord (<https://hammertime.cyou/> permet d'en générer un), une date et une heure comme `2026-11-03 19:30 Europe/Paris`, un jour et une heure comme `tomorrow 8pm` ou un décalage comme `in 2 hours`."
+                    } else {
+                        "Sorry, I couldn't understand that time. You can use a Discord timestamp (<https://hammertime.cyou/> can generate one), a date and time like `2026-11-03 19:30 Europe/Berlin`, a day and time like `tomorrow 8pm`, or an offset like `in 2 hours`."
+                    })
+                )).await?;
+                transaction.rollback().await?;
+            }
+        } else {
+            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(if let French = event.language {
+                    "Désolé, seuls les participants de cette race et les organisateurs peuvent utiliser cette commande."
+                } else {
+                    "Sorry, only participants in this race and organizers can use this command."
+                })
+            )).await?;
+            transaction.rollback().await?;
+        }
+    }
+    Ok(())
+}
+
+async fn schedule_async_race(ctx: &DiscordCtx, interaction: &impl GenericInteraction, game: Option<i16>, start: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some((mut transaction, mut race, team)) = check_scheduling_thread_permissions(ctx, interaction, game, true, None).await? {
+        let event = race.event(&mut transaction).await?;
+        let is_organizer = event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user_id()));
+        let was_scheduled = !matches!(race.schedule, RaceSchedule::Unscheduled);
+        if let Some(speedgaming_slug) = &event.speedgaming_slug {
+            let response_content = if was_scheduled {
+                format!("Please contact a tournament organizer to reschedule this race.")
+            } else {
+                MessageBuilder::default()
+                    .push("Please use <https://speedgaming.org/")
+                    .push(speedgaming_slug)
+                    .push("/submit> to schedule races for this event.")
+                    .build()
+            };
+            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(response_content)
+            )).await?;
+            transaction.rollback().await?;
+        } else if team.is_some() && event.asyncs_allowed() || is_organizer {
+            let timezone = if let Some(user) = User::from_discord(&mut *transaction, interaction.user_id()).await? { user.timezone(&mut *transaction).await? } else { None };
+            if let Some(parsed_start) = parse_user_time(start, Utc::now(), timezone.unwrap_or(Tz::UTC)) {
+                let start = parsed_start.time();
+                if (start - Utc::now()).to_std().map_or(true, |schedule_notice| schedule_notice < event.min_schedule_notice) {
+                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                        .ephemeral(true)
+                        .content(if event.min_schedule_notice <= Duration::default() {
+                            if let French = event.language {
+                                format!("Désolé mais cette date est dans le passé.")
+                            } else {
+                                format!("Sorry, that timestamp is in the past.")
+                            }
+                        } else {
+                            if let French = event.language {
+                                format!("Désolé, les races doivent être planifiées au moins {} en avance.", French.format_duration(event.min_schedule_notice, true))
+                            } else {
+                                format!("Sorry, races must be scheduled at least {} in advance.", English.format_duration(event.min_schedule_notice, true))
+                            }
+                        })
+                    )).await?;
+                    transaction.rollback().await?;
+                } else if let ParsedTime::Interpreted(_) = parsed_start {
+                    confirm_schedule(ctx, interaction, event.language, timezone, start, format!("scheduleasyncconfirm_{}_{}", start.timestamp(), game.map(|game| game.to_string()).unwrap_or_default())).await?;
+                    transaction.rollback().await?;
+                } else {
+                    let (kind, was_scheduled) = match race.entrants {
+                        Entrants::Two([Entrant::MidosHouseTeam(ref team1), Entrant::MidosHouseTeam(ref team2)]) => {
+                            if team.as_ref().is_some_and(|team| team1 == team) {
+                                let was_scheduled = race.schedule.set_async_start1(start).is_some();
+                                race.schedule_updated_at = Some(Utc::now());
+                                (cal::EventKind::Async1, was_scheduled)
+                            } else if team.as_ref().is_some_and(|team| team2 == team) {
+                                let was_scheduled = race.schedule.set_async_start2(start).is_some();
+                                race.schedule_updated_at = Some(Utc::now());
+                                (cal::EventKind::Async2, was_scheduled)
+                            } else {
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(true)
+                                    .content("Sorry, only participants in this race can use this command for now. Please contact Fenhl to edit the schedule.") //TODO allow TOs to schedule as async (with team parameter)
+                                )).await?;
+                                transaction.rollback().await?;
+                                return Ok(())
+                            }
+                        }
+                        Entrants::Three([Entrant::MidosHouseTeam(ref team1), Entrant::MidosHouseTeam(ref team2), Entrant::MidosHouseTeam(ref team3)]) => {
+                            if team.as_ref().is_some_and(|team| team1 == team) {
+                                let was_scheduled = race.schedule.set_async_start1(start).is_some();
+                                race.schedule_updated_at = Some(Utc::now());
+                                (cal::EventKind::Async1, was_scheduled)
+                            } else if team.as_ref().is_some_and(|team| team2 == team) {
+                                let was_scheduled = race.schedule.set_async_start2(start).is_some();
+                                race.schedule_updated_at = Some(Utc::now());
+                                (cal::EventKind::Async2, was_scheduled)
+                            } else if team.as_ref().is_some_and(|team| team3 == team) {
+                                let was_scheduled = race.schedule.set_async_start3(start).is_some();
+                                race.schedule_updated_at = Some(Utc::now());
+                                (cal::EventKind::Async3, was_scheduled)
+                            } else {
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(true)
+                                    .content("Sorry, only participants in this race can use this command for now. Please contact Fenhl to edit the schedule.") //TODO allow TOs to schedule as async (with team parameter)
+                                )).await?;
+                                transaction.rollback().await?;
+                                return Ok(())
+                            }
+                        }
+                        _ => panic!("tried to schedule race with not 2 or 3 MH teams as async"),
+                    };
+                    let mut cal_event = cal::Event { race, kind };
+                    if start - Utc::now() < TimeDelta::minutes(30) {
+                        let (http_client, new_room_lock, racetime_host, racetime_config, extra_room_tx, clean_shutdown) = {
+                            let data = ctx.data.read().await;
+                            (
+                                data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
+                                data.get::<NewRoomLock>().expect("new room lock missing from Discord context").clone(),
+                                data.get::<RacetimeHost>().expect("racetime.gg host missing from Discord context").clone(),
+                                data.get::<ConfigRaceTime>().expect("racetime.gg config missing from Discord context").clone(),
+                                data.get::<ExtraRoomTx>().expect("extra room sender missing from Discord context").clone(),
+                                data.get::<CleanShutdown>().expect("clean shutdown state missing from Discord context").clone(),
+                            )
+                        };
+                        lock!(new_room_lock = new_room_lock; {
+                            let should_post_regular_response = if let Some((is_room_url, mut msg)) = racetime_bot::create_room(&mut transaction, ctx, &racetime_host, &racetime_config.client_id, &racetime_config.client_secret, &extra_room_tx, &http_client, clean_shutdown, &mut cal_event, &event).await? {
+                                if is_room_url && cal_event.is_private_async_part() {
+                                    msg = match cal_event.race.entrants {
+                                        Entrants::Two(_) => format!("unlisted room for first async half: {msg}"),
+                                        Entrants::Three(_) => format!("unlisted room for first/second async part: {msg}"),
+                                        _ => format!("unlisted room for async part: {msg}"),
+                                    };
+                                    if let Some(channel) = event.discord_organizer_channel {
+                                        channel.say(ctx, &msg).await?;
+                                    } else {
+                                        FENHL.create_dm_channel(ctx).await?.say(ctx, &msg).await?;
+                                    }
+                                } else {
+                                    if let Some(channel) = event.discord_race_room_channel {
+                                        channel.send_message(ctx, CreateMessage::default().content(&msg).allowed_mentions(CreateAllowedMentions::default())).await?;
+                                    }
+                                }
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(cal_event.is_private_async_part()) //TODO create public response without room link
+                                    .content(msg)
+                                )).await?;
+                                cal_event.is_private_async_part()
+                            } else {
+                                true
+                            };
+                            if should_post_regular_response {
+                                let mut response_content = MessageBuilder::default();
+                                response_content.push(if let Entrants::Two(_) = cal_event.race.entrants { "Your half of " } else { "Your part of " });
+                                response_content.push(if let Some(game) = cal_event.race.game { format!("game {game}") } else { format!("this race") });
+                                response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
+                                response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+                                let response_content = response_content
+                                    .push(". The race room will be opened momentarily.")
+                                    .build();
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(false)
+                                    .content(response_content)
+                                )).await?;
+                            }
+                            cal_event.race.save(&mut transaction).await?;
+                            transaction.commit().await?;
+                        });
+                    } else {
+                        cal_event.race.save(&mut transaction).await?;
+                        let overlapping_maintenance_windows = if let RaceHandleMode::RaceTime = cal_event.should_create_room(&mut transaction, &event).await? {
+                            sqlx::query_as!(Range::<DateTime<Utc>>, r#"SELECT start, end_time AS "end" FROM racetime_maintenance WHERE start < $1 AND end_time > $2"#, start + event.series.default_race_duration(), start - TimeDelta::minutes(30)).fetch_all(&mut *transaction).await?
+                        } else {
+                            Vec::default()
+                        };
+                        transaction.commit().await?;
+                        let response_content = if_chain! {
+                            if let French = event.language;
+                            if cal_event.race.game.is_none();
+                            if overlapping_maintenance_windows.is_empty();
+                            then {
+                                MessageBuilder::default()
+                                    .push("La partie de votre async a été planifiée pour le ")
+                                    .push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime)
+                                    .push('.')
+                                    .build()
+                            } else {
+                                let mut response_content = MessageBuilder::default();
+                                response_content.push(if let Entrants::Two(_) = cal_event.race.entrants { "Your half of " } else { "Your part of " });
+                                response_content.push(if let Some(game) = cal_event.race.game { format!("game {game}") } else { format!("this race") });
+                                response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
+                                response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
+                                response_content.push('.');
+                                for window in overlapping_maintenance_windows {
+                                    response_content.push_line("");
+                                    response_content.push_bold("Warning:");
+                                    if let Entrants::Two(_) = cal_event.race.entrants {
+                                        response_content.push(" this async half may overlap with racetime.gg maintenance planned for ");
+                                    } else {
+                                        response_content.push(" this async part may overlap with racetime.gg maintenance planned for ");
+                                    }
+                                    response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+                                    response_content.push(" until ");
+                                    response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
+                                    response_content.push('.');
+                                }
+                                response_content.build()
+                            }
+                        };
+                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                            .ephemeral(false)
+                            .content(response_content)
+                        )).await?;
+                    }
+                }
+            } else {
+                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                    .ephemeral(true)
+                    .content(if let French = event.language {
+                        "Désolé, je n'ai pas compris cette date. Vous pouvez utiliser un timestamp de Discord (<https://hammertime.cyou/> permet d'en générer un), une date et une heure comme `2026-11-03 19:30 Europe/Paris`, un jour et une heure comme `tomorrow 8pm` ou un décalage comme `in 2 hours`."
+                    } else {
+                        "Sorry, I couldn't understand that time. You can use a Discord timestamp (<https://hammertime.cyou/> can generate one), a date and time like `2026-11-03 19:30 Europe/Berlin`, a day and time like `tomorrow 8pm`, or an offset like `in 2 hours`."
+                    })
+                )).await?;
+                transaction.rollback().await?;
+            }
+        } else {
+            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(if event.asyncs_allowed() {
+                    if let French = event.language {
+                        "Désolé, seuls les participants de cette race et les organisateurs peuvent utiliser cette commande."
+                    } else {
+                        "Sorry, only participants in this race and organizers can use this command."
+                    }
+                } else {
+                    "Sorry, asyncing races is not allowed for this event."
+                })
+            )).await?;
+            transaction.rollback().await?;
+        }
+    }
+    Ok(())
 }
 
 pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_pool: PgPool, http_client: reqwest::Client, config: Config, new_room_lock: Arc<Mutex<()>>, extra_room_tx: Arc<RwLock<mpsc::Sender<String>>>, clean_shutdown: Arc<Mutex<CleanShutdown>>, shutdown: rocket::Shutdown) -> serenity_utils::Builder {
@@ -570,10 +1123,36 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                     draft_kind = Some(new_kind);
                 }
             }
+            let availability = {
+                let idx = commands.len();
+                commands.push(CreateCommand::new("availability")
+                    .kind(CommandType::ChatInput)
+                    .add_context(InteractionContext::Guild)
+                    .description("Shows or edits the times when you're available to race.")
+                    .description_localized("fr", "Affiche ou modifie les heures où vous êtes disponible pour des races.")
+                    .add_option(CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "day",
+                        "A weekday to edit your weekly availability, or a date like 2026-11-03 for an exception",
+                    )
+                        .description_localized("fr", "Un jour de la semaine (en anglais) ou une date comme 2026-11-03 pour une exception")
+                        .required(false)
+                    )
+                    .add_option(CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "hours",
+                        "The hours when you're available on that day in your time zone, e.g. 18-22, 8 or none",
+                    )
+                        .description_localized("fr", "Les heures où vous êtes disponible ce jour-là, par ex. 18-22, 8 ou none")
+                        .required(false)
+                    )
+                );
+                idx
+            };
             let ban = draft_kind.map(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("ban")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("ban")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Locks a setting for this race to its default value."),
@@ -611,7 +1190,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let draft = draft_kind.and_then(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("draft")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("draft")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Chooses a setting for this race (same as /pick)."),
@@ -625,10 +1204,28 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                 });
                 Some(idx)
             });
+            let draft_undo = draft_kind.map(|_| {
+                let idx = commands.len();
+                commands.push(CreateCommand::new("draft-undo")
+                    .kind(CommandType::ChatInput)
+                    .add_context(InteractionContext::Guild)
+                    .description("Reverts the most recent action in this race's settings draft.")
+                    .add_option(CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "game",
+                        "The game number within the match.",
+                    )
+                        .min_int_value(1)
+                        .max_int_value(255)
+                        .required(false)
+                    )
+                );
+                idx
+            });
             let first = draft_kind.map(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("first")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("first")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Go first in the settings draft."),
@@ -666,7 +1263,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let no = draft_kind.and_then(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::RslS7 => return None,
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::RslS7 | draft::Kind::Custom(_) => return None,
                     draft::Kind::TournoiFrancoS3 | draft::Kind::TournoiFrancoS4 | draft::Kind::TournoiFrancoS5 => CreateCommand::new("no")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
@@ -679,7 +1276,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let pick = draft_kind.map(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("pick")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("pick")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Chooses a setting for this race."),
@@ -777,9 +1374,9 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                     .add_option(CreateCommandOption::new(
                         CommandOptionType::String,
                         "start",
-                        "The starting time as a Discord timestamp",
+                        "The starting time, e.g. a Discord timestamp, 2026-11-03 19:30 Europe/Berlin, or tomorrow 8pm",
                     )
-                        .description_localized("fr", "La date de début comme timestamp de Discord")
+                        .description_localized("fr", "La date de début, par ex. un timestamp de Discord, 2026-11-03 19:30 Europe/Paris ou tomorrow 8pm")
                         .required(true)
                     )
                     .add_option(CreateCommandOption::new(
@@ -804,9 +1401,9 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                     .add_option(CreateCommandOption::new(
                         CommandOptionType::String,
                         "start",
-                        "The starting time as a Discord timestamp",
+                        "The starting time, e.g. a Discord timestamp, 2026-11-03 19:30 Europe/Berlin, or tomorrow 8pm",
                     )
-                        .description_localized("fr", "La date de début comme timestamp de Discord")
+                        .description_localized("fr", "La date de début, par ex. un timestamp de Discord, 2026-11-03 19:30 Europe/Paris ou tomorrow 8pm")
                         .required(true)
                     )
                     .add_option(CreateCommandOption::new(
@@ -843,7 +1440,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let second = draft_kind.map(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("second")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("second")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Go second in the settings draft."),
@@ -881,7 +1478,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let skip = draft_kind.map(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => CreateCommand::new("skip")
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => CreateCommand::new("skip")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
                         .description("Skips your current turn of the settings draft."),
@@ -937,7 +1534,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             let yes = draft_kind.and_then(|draft_kind| {
                 let idx = commands.len();
                 commands.push(match draft_kind {
-                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::RslS7 => return None,
+                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::RslS7 | draft::Kind::Custom(_) => return None,
                     draft::Kind::TournoiFrancoS3 | draft::Kind::TournoiFrancoS4 | draft::Kind::TournoiFrancoS5 => CreateCommand::new("yes")
                         .kind(CommandType::ChatInput)
                         .add_context(InteractionContext::Guild)
@@ -949,9 +1546,11 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
             });
             let commands = guild.set_commands(ctx, commands).await?;
             ctx.data.write().await.entry::<CommandIds>().or_default().insert(guild.id, Some(CommandIds {
+                availability: commands[availability].id,
                 ban: ban.map(|idx| commands[idx].id),
                 delete_after: commands[delete_after].id,
                 draft: draft.map(|idx| commands[idx].id),
+                draft_undo: draft_undo.map(|idx| commands[idx].id),
                 first: first.map(|idx| commands[idx].id),
                 no: no.map(|idx| commands[idx].id),
                 pick: pick.map(|idx| commands[idx].id),
@@ -976,7 +1575,70 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                 Interaction::Command(interaction) => {
                     let guild_id = interaction.guild_id.expect("Discord slash command called outside of a guild");
                     if let Some(&Some(command_ids)) = ctx.data.read().await.get::<CommandIds>().and_then(|command_ids| command_ids.get(&guild_id)) {
-                        if Some(interaction.data.id) == command_ids.ban {
+                        if interaction.data.id == command_ids.availability {
+                            let option = |name: &str| interaction.data.options.iter().find(|option| option.name == name).map(|option| match option.value {
+                                CommandDataOptionValue::String(ref value) => value.clone(),
+                                _ => panic!("unexpected slash command option type"),
+                            });
+                            let (db_pool, http_client) = {
+                                let data = ctx.data.read().await;
+                                (
+                                    data.get::<DbPool>().expect("database connection pool missing from Discord context").clone(),
+                                    data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
+                                )
+                            };
+                            let mut transaction = db_pool.begin().await?;
+                            let Some(user) = User::from_discord(&mut *transaction, interaction.user.id).await? else {
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(true)
+                                    .content("Sorry, you need a Mido's House account connected to your Discord account to enter your availability. You can create one at <https://midos.house/login>.")
+                                )).await?;
+                                transaction.rollback().await?;
+                                return Ok(())
+                            };
+                            match (option("day"), option("hours")) {
+                                (Some(day), Some(hours)) => if let (Some(day), Some(hours)) = (availability::parse_day(&day), availability::parse_hours(&hours)) {
+                                    match day {
+                                        Either::Left(weekday) => availability::set_weekly(&mut transaction, user.id, weekday, &hours).await?,
+                                        Either::Right(date) => availability::set_exception(&mut transaction, user.id, date, &hours).await?,
+                                    }
+                                    let content = availability::summary(&mut transaction, &user).await?;
+                                    transaction.commit().await?;
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(true)
+                                        .content(content)
+                                    )).await?;
+                                    availability::update_suggestions(&db_pool, ctx, &http_client, user.id).await;
+                                } else {
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(true)
+                                        .content("Sorry, I couldn't understand that. Please enter a weekday like `monday` or a date like `2026-11-03`, and hours like `18-22, 8` or `none`.")
+                                    )).await?;
+                                    transaction.rollback().await?;
+                                },
+                                (None, None) => {
+                                    // in a scheduling thread, compare the availability of everyone in the race
+                                    let suggestions = availability::thread_suggestions_message(&mut transaction, &http_client, interaction.channel_id).await?;
+                                    let (ephemeral, content) = if let Some(suggestions) = suggestions {
+                                        (false, suggestions)
+                                    } else {
+                                        (true, availability::summary(&mut transaction, &user).await?)
+                                    };
+                                    transaction.commit().await?;
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(ephemeral)
+                                        .content(content)
+                                    )).await?;
+                                }
+                                (Some(_), None) | (None, Some(_)) => {
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(true)
+                                        .content("Please specify both the day and the hours to edit your availability, or neither to view it.")
+                                    )).await?;
+                                    transaction.rollback().await?;
+                                }
+                            }
+                        } else if Some(interaction.data.id) == command_ids.ban {
                             send_draft_settings_page(ctx, interaction, "ban", 0).await?;
                         } else if interaction.data.id == command_ids.delete_after {
                             let Some(parent_channel) = interaction.channel.as_ref().and_then(|thread| thread.parent_id) else {
@@ -990,7 +1652,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                             if let Some(event_row) = sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE discord_scheduling_channel = $1 AND end_time IS NULL"#, PgSnowflake(parent_channel) as _).fetch_optional(&mut *transaction).await? {
                                 let event = event::Data::new(&mut transaction, event_row.series, event_row.event).await?.expect("just received from database");
                                 match event.match_source() {
-                                    MatchSource::Manual | MatchSource::Challonge { .. } => {}
+                                    MatchSource::Manual | MatchSource::Challonge { .. } | MatchSource::MidosHouse => {}
                                     MatchSource::StartGG(_) => {} //TODO automate
                                     MatchSource::League => {
                                         interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
@@ -1030,6 +1692,104 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                             }
                         } else if Some(interaction.data.id) == command_ids.draft || Some(interaction.data.id) == command_ids.pick {
                             send_draft_settings_page(ctx, interaction, "draft", 0).await?;
+                        } else if Some(interaction.data.id) == command_ids.draft_undo {
+                            let Some(parent_channel) = interaction.channel.as_ref().and_then(|thread| thread.parent_id) else {
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(true)
+                                    .content("Sorry, this command can only be used inside threads and forum posts.")
+                                )).await?;
+                                return Ok(())
+                            };
+                            let mut transaction = ctx.data.read().await.get::<DbPool>().as_ref().expect("database connection pool missing from Discord context").begin().await?;
+                            if let Some(event_row) = sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE discord_scheduling_channel = $1 AND end_time IS NULL"#, PgSnowflake(parent_channel) as _).fetch_optional(&mut *transaction).await? {
+                                let event = event::Data::new(&mut transaction, event_row.series, event_row.event).await?.expect("just received from database");
+                                let Some(organizer) = event.organizers(&mut transaction).await?.into_iter().find(|organizer| organizer.discord.as_ref().is_some_and(|discord| discord.id == interaction.user.id)) else {
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(true)
+                                        .content("Sorry, only event organizers can use this command.")
+                                    )).await?;
+                                    return Ok(())
+                                };
+                                let Some(draft_kind) = event.draft_kind() else {
+                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                        .ephemeral(true)
+                                        .content("Sorry, there is no settings draft for this event.")
+                                    )).await?;
+                                    return Ok(())
+                                };
+                                let mut game = None;
+                                for option in &interaction.data.options {
+                                    match &*option.name {
+                                        "game" => match option.value {
+                                            CommandDataOptionValue::Integer(value) => game = Some(i16::try_from(value).expect("game number out of range")),
+                                            _ => panic!("unexpected slash command option type"),
+                                        },
+                                        name => panic!("unexpected option for /draft-undo: {name}"),
+                                    }
+                                }
+                                let http_client = {
+                                    let data = ctx.data.read().await;
+                                    data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone()
+                                };
+                                match Race::for_scheduling_channel(&mut transaction, &http_client, interaction.channel_id(), game, true).await?.into_iter().at_most_one() {
+                                    Ok(None) => {
+                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                            .ephemeral(true)
+                                            .content(if game.is_some() {
+                                                "Sorry, there don't seem to be any races with that game number associated with this thread."
+                                            } else {
+                                                "Sorry, this thread is not associated with any races."
+                                            })
+                                        )).await?;
+                                        transaction.rollback().await?;
+                                    }
+                                    Ok(Some(mut race)) => if race.has_any_room() {
+                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                            .ephemeral(true)
+                                            .content("Sorry, this race's settings draft can't be changed since a race room is already open.")
+                                        )).await?;
+                                        transaction.rollback().await?;
+                                    } else if let Some(entry) = draft::history::undo_last(&mut transaction, race.id, organizer.id).await? {
+                                        race.draft = Some(entry.state_before);
+                                        sqlx::query!("UPDATE races SET draft_state = $1 WHERE id = $2", Json(race.draft.as_ref().unwrap()) as _, race.id as _).execute(&mut *transaction).await?;
+                                        let mut msg_ctx = draft::MessageContext::Discord {
+                                            teams: race.teams().cloned().collect(),
+                                            team: Team::dummy(),
+                                            transaction, guild_id, command_ids,
+                                        };
+                                        let next_step = race.draft.as_ref().unwrap().next_step(draft_kind, race.game, &mut msg_ctx).await?.message;
+                                        msg_ctx.into_transaction().commit().await?;
+                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                            .ephemeral(false)
+                                            .content(MessageBuilder::default()
+                                                .push("The most recent settings draft action (")
+                                                .push_safe(entry.action.to_string())
+                                                .push(") has been undone.")
+                                                .build()
+                                            )
+                                        )).await?;
+                                        interaction.channel_id.say(ctx, next_step).await?;
+                                    } else {
+                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                            .ephemeral(true)
+                                            .content("Sorry, there are no settings draft actions to undo for this race.")
+                                        )).await?;
+                                        transaction.rollback().await?;
+                                    },
+                                    Err(_) => {
+                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                            .ephemeral(true)
+                                            .content("Sorry, this thread is associated with multiple races. Please specify the game number.")
+                                        )).await?;
+                                        transaction.rollback().await?;
+                                    }
+                                }
+                            } else {
+                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
+                                    .ephemeral(true)
+                                    .content("Sorry, this channel is not configured as the scheduling channel for any ongoing Mido's House events.")
+                                )).await?;
+                            }
                         } else if Some(interaction.data.id) == command_ids.first {
                             if let Some((_, mut race, draft_kind, msg_ctx)) = check_draft_permissions(ctx, interaction).await? {
                                 match draft_kind {
@@ -1095,7 +1855,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                                             }
                                         }
                                     }
-                                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => {}
+                                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => {}
                                 }
                                 draft_action(ctx, interaction, draft::Action::GoFirst(true)).await?;
                             }
@@ -1215,13 +1975,13 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                             let mut transaction = ctx.data.read().await.get::<DbPool>().as_ref().expect("database connection pool missing from Discord context").begin().await?;
                             if let Some(event_row) = sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE discord_scheduling_channel = $1 AND end_time IS NULL"#, PgSnowflake(parent_channel) as _).fetch_optional(&mut *transaction).await? {
                                 let event = event::Data::new(&mut transaction, event_row.series, event_row.event).await?.expect("just received from database");
-                                if !event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user.id)) {
+                                let Some(organizer) = event.organizers(&mut transaction).await?.into_iter().find(|organizer| organizer.discord.as_ref().is_some_and(|discord| discord.id == interaction.user.id)) else {
                                     interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
                                         .ephemeral(true)
                                         .content("Sorry, only event organizers can use this command.")
                                     )).await?;
                                     return Ok(())
-                                }
+                                };
                                 let mut game = None;
                                 let mut reset_draft = false;
                                 let mut reset_schedule = false;
@@ -1313,6 +2073,9 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                                             schedule_locked: race.schedule_locked,
                                         };
                                         race.save(&mut transaction).await?;
+                                        if reset_draft {
+                                            draft::history::undo_all(&mut transaction, race.id, organizer.id).await?;
+                                        }
                                         transaction.commit().await?;
                                         let verb = if aspects_reset.len() == NonZero::<usize>::MIN { " has" } else { " have" };
                                         let response_content = MessageBuilder::default()
@@ -1344,377 +2107,21 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                                 CommandDataOptionValue::Integer(game) => i16::try_from(game).expect("game number out of range"),
                                 _ => panic!("unexpected slash command option type"),
                             });
-                            if let Some((mut transaction, mut race, team)) = check_scheduling_thread_permissions(ctx, interaction, game, false, None).await? {
-                                let event = race.event(&mut transaction).await?;
-                                let is_organizer = event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user.id));
-                                let was_scheduled = !matches!(race.schedule, RaceSchedule::Unscheduled);
-                                if let Some(speedgaming_slug) = &event.speedgaming_slug {
-                                    let response_content = if was_scheduled {
-                                        format!("Please contact a tournament organizer to reschedule this race.")
-                                    } else {
-                                        MessageBuilder::default()
-                                            .push("Please use <https://speedgaming.org/")
-                                            .push(speedgaming_slug)
-                                            .push("/submit> to schedule races for this event.")
-                                            .build()
-                                    };
-                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                        .ephemeral(true)
-                                        .content(response_content)
-                                    )).await?;
-                                    transaction.rollback().await?;
-                                } else if team.is_some() || is_organizer {
-                                    let start = match interaction.data.options[0].value {
-                                        CommandDataOptionValue::String(ref start) => start,
-                                        _ => panic!("unexpected slash command option type"),
-                                    };
-                                    if let Some(start) = parse_timestamp(start) {
-                                        if (start - Utc::now()).to_std().map_or(true, |schedule_notice| schedule_notice < event.min_schedule_notice) {
-                                            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                .ephemeral(true)
-                                                .content(if event.min_schedule_notice <= Duration::default() {
-                                                    if let French = event.language {
-                                                        format!("Désolé mais cette date est dans le passé.")
-                                                    } else {
-                                                        format!("Sorry, that timestamp is in the past.")
-                                                    }
-                                                } else {
-                                                    if let French = event.language {
-                                                        format!("Désolé, les races doivent être planifiées au moins {} en avance.", French.format_duration(event.min_schedule_notice, true))
-                                                    } else {
-                                                        format!("Sorry, races must be scheduled at least {} in advance.", English.format_duration(event.min_schedule_notice, true))
-                                                    }
-                                                })
-                                            )).await?;
-                                            transaction.rollback().await?;
-                                        } else {
-                                            race.schedule.set_live_start(start);
-                                            race.schedule_updated_at = Some(Utc::now());
-                                            let mut cal_event = cal::Event { kind: cal::EventKind::Normal, race };
-                                            if start - Utc::now() < TimeDelta::minutes(30) {
-                                                let (http_client, new_room_lock, racetime_host, racetime_config, extra_room_tx, clean_shutdown) = {
-                                                    let data = ctx.data.read().await;
-                                                    (
-                                                        data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
-                                                        data.get::<NewRoomLock>().expect("new room lock missing from Discord context").clone(),
-                                                        data.get::<RacetimeHost>().expect("racetime.gg host missing from Discord context").clone(),
-                                                        data.get::<ConfigRaceTime>().expect("racetime.gg config missing from Discord context").clone(),
-                                                        data.get::<ExtraRoomTx>().expect("extra room sender missing from Discord context").clone(),
-                                                        data.get::<CleanShutdown>().expect("clean shutdown state missing from Discord context").clone(),
-                                                    )
-                                                };
-                                                lock!(new_room_lock = new_room_lock; {
-                                                    if let Some((_, msg)) = racetime_bot::create_room(&mut transaction, ctx, &racetime_host, &racetime_config.client_id, &racetime_config.client_secret, &extra_room_tx, &http_client, clean_shutdown, &mut cal_event, &event).await? {
-                                                        if let Some(channel) = event.discord_race_room_channel {
-                                                            channel.say(ctx, &msg).await?;
-                                                        }
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(false)
-                                                            .content(msg)
-                                                        )).await?;
-                                                    } else {
-                                                        let mut response_content = MessageBuilder::default();
-                                                        response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
-                                                        response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
-                                                        response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
-                                                        let response_content = response_content
-                                                            .push(". The race room will be opened momentarily.")
-                                                            .build();
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(false)
-                                                            .content(response_content)
-                                                        )).await?;
-                                                    }
-                                                    cal_event.race.save(&mut transaction).await?;
-                                                    transaction.commit().await?;
-                                                })
-                                            } else {
-                                                cal_event.race.save(&mut transaction).await?;
-                                                let overlapping_maintenance_windows = if let RaceHandleMode::RaceTime = cal_event.should_create_room(&mut transaction, &event).await? {
-                                                    sqlx::query_as!(Range::<DateTime<Utc>>, r#"SELECT start, end_time AS "end" FROM racetime_maintenance WHERE start < $1 AND end_time > $2"#, start + event.series.default_race_duration(), start - TimeDelta::minutes(30)).fetch_all(&mut *transaction).await?
-                                                } else {
-                                                    Vec::default()
-                                                };
-                                                transaction.commit().await?;
-                                                let response_content = if_chain! {
-                                                    if let French = event.language;
-                                                    if cal_event.race.game.is_none();
-                                                    if overlapping_maintenance_windows.is_empty();
-                                                    then {
-                                                        MessageBuilder::default()
-                                                            .push("Votre race a été planifiée pour le ")
-                                                            .push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime)
-                                                            .push('.')
-                                                            .build()
-                                                    } else {
-                                                        let mut response_content = MessageBuilder::default();
-                                                        response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
-                                                        response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
-                                                        response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
-                                                        response_content.push('.');
-                                                        for window in overlapping_maintenance_windows {
-                                                            response_content.push_line("");
-                                                            response_content.push_bold("Warning:");
-                                                            response_content.push(" this race may overlap with racetime.gg maintenance planned for ");
-                                                            response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                                            response_content.push(" until ");
-                                                            response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                                            response_content.push('.');
-                                                        }
-                                                        response_content.build()
-                                                    }
-                                                };
-                                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                    .ephemeral(false)
-                                                    .content(response_content)
-                                                )).await?;
-                                            }
-                                        }
-                                    } else {
-                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                            .ephemeral(true)
-                                            .content(if let French = event.language {
-                                                "Désolé, cela n'est pas un timestamp au format de Discord. Vous pouvez utiliser <https://hammertime.cyou/> pour en générer un."
-                                            } else {
-                                                "Sorry, that doesn't look like a Discord timestamp. You can use <https://hammertime.cyou/> to generate one."
-                                            })
-                                        )).await?;
-                                        transaction.rollback().await?;
-                                    }
-                                } else {
-                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                        .ephemeral(true)
-                                        .content(if let French = event.language {
-                                            "Désolé, seuls les participants de cette race et les organisateurs peuvent utiliser cette commande."
-                                        } else {
-                                            "Sorry, only participants in this race and organizers can use this command."
-                                        })
-                                    )).await?;
-                                    transaction.rollback().await?;
-                                }
-                            }
+                            let start = match interaction.data.options[0].value {
+                                CommandDataOptionValue::String(ref start) => start,
+                                _ => panic!("unexpected slash command option type"),
+                            };
+                            schedule_race(ctx, interaction, game, start).await?;
                         } else if interaction.data.id == command_ids.schedule_async {
                             let game = interaction.data.options.get(1).map(|option| match option.value {
                                 CommandDataOptionValue::Integer(game) => i16::try_from(game).expect("game number out of range"),
                                 _ => panic!("unexpected slash command option type"),
                             });
-                            if let Some((mut transaction, mut race, team)) = check_scheduling_thread_permissions(ctx, interaction, game, true, None).await? {
-                                let event = race.event(&mut transaction).await?;
-                                let is_organizer = event.organizers(&mut transaction).await?.into_iter().any(|organizer| organizer.discord.is_some_and(|discord| discord.id == interaction.user.id));
-                                let was_scheduled = !matches!(race.schedule, RaceSchedule::Unscheduled);
-                                if let Some(speedgaming_slug) = &event.speedgaming_slug {
-                                    let response_content = if was_scheduled {
-                                        format!("Please contact a tournament organizer to reschedule this race.")
-                                    } else {
-                                        MessageBuilder::default()
-                                            .push("Please use <https://speedgaming.org/")
-                                            .push(speedgaming_slug)
-                                            .push("/submit> to schedule races for this event.")
-                                            .build()
-                                    };
-                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                        .ephemeral(true)
-                                        .content(response_content)
-                                    )).await?;
-                                    transaction.rollback().await?;
-                                } else if team.is_some() && event.asyncs_allowed() || is_organizer {
-                                    let start = match interaction.data.options[0].value {
-                                        CommandDataOptionValue::String(ref start) => start,
-                                        _ => panic!("unexpected slash command option type"),
-                                    };
-                                    if let Some(start) = parse_timestamp(start) {
-                                        if (start - Utc::now()).to_std().map_or(true, |schedule_notice| schedule_notice < event.min_schedule_notice) {
-                                            interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                .ephemeral(true)
-                                                .content(if event.min_schedule_notice <= Duration::default() {
-                                                    if let French = event.language {
-                                                        format!("Désolé mais cette date est dans le passé.")
-                                                    } else {
-                                                        format!("Sorry, that timestamp is in the past.")
-                                                    }
-                                                } else {
-                                                    if let French = event.language {
-                                                        format!("Désolé, les races doivent être planifiées au moins {} en avance.", French.format_duration(event.min_schedule_notice, true))
-                                                    } else {
-                                                        format!("Sorry, races must be scheduled at least {} in advance.", English.format_duration(event.min_schedule_notice, true))
-                                                    }
-                                                })
-                                            )).await?;
-                                            transaction.rollback().await?;
-                                        } else {
-                                            let (kind, was_scheduled) = match race.entrants {
-                                                Entrants::Two([Entrant::MidosHouseTeam(ref team1), Entrant::MidosHouseTeam(ref team2)]) => {
-                                                    if team.as_ref().is_some_and(|team| team1 == team) {
-                                                        let was_scheduled = race.schedule.set_async_start1(start).is_some();
-                                                        race.schedule_updated_at = Some(Utc::now());
-                                                        (cal::EventKind::Async1, was_scheduled)
-                                                    } else if team.as_ref().is_some_and(|team| team2 == team) {
-                                                        let was_scheduled = race.schedule.set_async_start2(start).is_some();
-                                                        race.schedule_updated_at = Some(Utc::now());
-                                                        (cal::EventKind::Async2, was_scheduled)
-                                                    } else {
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(true)
-                                                            .content("Sorry, only participants in this race can use this command for now. Please contact Fenhl to edit the schedule.") //TODO allow TOs to schedule as async (with team parameter)
-                                                        )).await?;
-                                                        transaction.rollback().await?;
-                                                        return Ok(())
-                                                    }
-                                                }
-                                                Entrants::Three([Entrant::MidosHouseTeam(ref team1), Entrant::MidosHouseTeam(ref team2), Entrant::MidosHouseTeam(ref team3)]) => {
-                                                    if team.as_ref().is_some_and(|team| team1 == team) {
-                                                        let was_scheduled = race.schedule.set_async_start1(start).is_some();
-                                                        race.schedule_updated_at = Some(Utc::now());
-                                                        (cal::EventKind::Async1, was_scheduled)
-                                                    } else if team.as_ref().is_some_and(|team| team2 == team) {
-                                                        let was_scheduled = race.schedule.set_async_start2(start).is_some();
-                                                        race.schedule_updated_at = Some(Utc::now());
-                                                        (cal::EventKind::Async2, was_scheduled)
-                                                    } else if team.as_ref().is_some_and(|team| team3 == team) {
-                                                        let was_scheduled = race.schedule.set_async_start3(start).is_some();
-                                                        race.schedule_updated_at = Some(Utc::now());
-                                                        (cal::EventKind::Async3, was_scheduled)
-                                                    } else {
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(true)
-                                                            .content("Sorry, only participants in this race can use this command for now. Please contact Fenhl to edit the schedule.") //TODO allow TOs to schedule as async (with team parameter)
-                                                        )).await?;
-                                                        transaction.rollback().await?;
-                                                        return Ok(())
-                                                    }
-                                                }
-                                                _ => panic!("tried to schedule race with not 2 or 3 MH teams as async"),
-                                            };
-                                            let mut cal_event = cal::Event { race, kind };
-                                            if start - Utc::now() < TimeDelta::minutes(30) {
-                                                let (http_client, new_room_lock, racetime_host, racetime_config, extra_room_tx, clean_shutdown) = {
-                                                    let data = ctx.data.read().await;
-                                                    (
-                                                        data.get::<HttpClient>().expect("HTTP client missing from Discord context").clone(),
-                                                        data.get::<NewRoomLock>().expect("new room lock missing from Discord context").clone(),
-                                                        data.get::<RacetimeHost>().expect("racetime.gg host missing from Discord context").clone(),
-                                                        data.get::<ConfigRaceTime>().expect("racetime.gg config missing from Discord context").clone(),
-                                                        data.get::<ExtraRoomTx>().expect("extra room sender missing from Discord context").clone(),
-                                                        data.get::<CleanShutdown>().expect("clean shutdown state missing from Discord context").clone(),
-                                                    )
-                                                };
-                                                lock!(new_room_lock = new_room_lock; {
-                                                    let should_post_regular_response = if let Some((is_room_url, mut msg)) = racetime_bot::create_room(&mut transaction, ctx, &racetime_host, &racetime_config.client_id, &racetime_config.client_secret, &extra_room_tx, &http_client, clean_shutdown, &mut cal_event, &event).await? {
-                                                        if is_room_url && cal_event.is_private_async_part() {
-                                                            msg = match cal_event.race.entrants {
-                                                                Entrants::Two(_) => format!("unlisted room for first async half: {msg}"),
-                                                                Entrants::Three(_) => format!("unlisted room for first/second async part: {msg}"),
-                                                                _ => format!("unlisted room for async part: {msg}"),
-                                                            };
-                                                            if let Some(channel) = event.discord_organizer_channel {
-                                                                channel.say(ctx, &msg).await?;
-                                                            } else {
-                                                                FENHL.create_dm_channel(ctx).await?.say(ctx, &msg).await?;
-                                                            }
-                                                        } else {
-                                                            if let Some(channel) = event.discord_race_room_channel {
-                                                                channel.send_message(ctx, CreateMessage::default().content(&msg).allowed_mentions(CreateAllowedMentions::default())).await?;
-                                                            }
-                                                        }
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(cal_event.is_private_async_part()) //TODO create public response without room link
-                                                            .content(msg)
-                                                        )).await?;
-                                                        cal_event.is_private_async_part()
-                                                    } else {
-                                                        true
-                                                    };
-                                                    if should_post_regular_response {
-                                                        let mut response_content = MessageBuilder::default();
-                                                        response_content.push(if let Entrants::Two(_) = cal_event.race.entrants { "Your half of " } else { "Your part of " });
-                                                        response_content.push(if let Some(game) = cal_event.race.game { format!("game {game}") } else { format!("this race") });
-                                                        response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
-                                                        response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
-                                                        let response_content = response_content
-                                                            .push(". The race room will be opened momentarily.")
-                                                            .build();
-                                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                            .ephemeral(false)
-                                                            .content(response_content)
-                                                        )).await?;
-                                                    }
-                                                    cal_event.race.save(&mut transaction).await?;
-                                                    transaction.commit().await?;
-                                                });
-                                            } else {
-                                                cal_event.race.save(&mut transaction).await?;
-                                                let overlapping_maintenance_windows = if let RaceHandleMode::RaceTime = cal_event.should_create_room(&mut transaction, &event).await? {
-                                                    sqlx::query_as!(Range::<DateTime<Utc>>, r#"SELECT start, end_time AS "end" FROM racetime_maintenance WHERE start < $1 AND end_time > $2"#, start + event.series.default_race_duration(), start - TimeDelta::minutes(30)).fetch_all(&mut *transaction).await?
-                                                } else {
-                                                    Vec::default()
-                                                };
-                                                transaction.commit().await?;
-                                                let response_content = if_chain! {
-                                                    if let French = event.language;
-                                                    if cal_event.race.game.is_none();
-                                                    if overlapping_maintenance_windows.is_empty();
-                                                    then {
-                                                        MessageBuilder::default()
-                                                            .push("La partie de votre async a été planifiée pour le ")
-                                                            .push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime)
-                                                            .push('.')
-                                                            .build()
-                                                    } else {
-                                                        let mut response_content = MessageBuilder::default();
-                                                        response_content.push(if let Entrants::Two(_) = cal_event.race.entrants { "Your half of " } else { "Your part of " });
-                                                        response_content.push(if let Some(game) = cal_event.race.game { format!("game {game}") } else { format!("this race") });
-                                                        response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
-                                                        response_content.push_timestamp(start, serenity_utils::message::TimestampStyle::LongDateTime);
-                                                        response_content.push('.');
-                                                        for window in overlapping_maintenance_windows {
-                                                            response_content.push_line("");
-                                                            response_content.push_bold("Warning:");
-                                                            if let Entrants::Two(_) = cal_event.race.entrants {
-                                                                response_content.push(" this async half may overlap with racetime.gg maintenance planned for ");
-                                                            } else {
-                                                                response_content.push(" this async part may overlap with racetime.gg maintenance planned for ");
-                                                            }
-                                                            response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                                            response_content.push(" until ");
-                                                            response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                                            response_content.push('.');
-                                                        }
-                                                        response_content.build()
-                                                    }
-                                                };
-                                                interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                                    .ephemeral(false)
-                                                    .content(response_content)
-                                                )).await?;
-                                            }
-                                        }
-                                    } else {
-                                        interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                            .ephemeral(true)
-                                            .content(if let French = event.language {
-                                                "Désolé, cela n'est pas un timestamp au format de Discord. Vous pouvez utiliser <https://hammertime.cyou/> pour en générer un."
-                                            } else {
-                                                "Sorry, that doesn't look like a Discord timestamp. You can use <https://hammertime.cyou/> to generate one."
-                                            })
-                                        )).await?;
-                                        transaction.rollback().await?;
-                                    }
-                                } else {
-                                    interaction.create_response(ctx, CreateInteractionResponse::Message(CreateInteractionResponseMessage::new()
-                                        .ephemeral(true)
-                                        .content(if event.asyncs_allowed() {
-                                            if let French = event.language {
-                                                "Désolé, seuls les participants de cette race et les organisateurs peuvent utiliser cette commande."
-                                            } else {
-                                                "Sorry, only participants in this race and organizers can use this command."
-                                            }
-                                        } else {
-                                            "Sorry, asyncing races is not allowed for this event."
-                                        })
-                                    )).await?;
-                                    transaction.rollback().await?;
-                                }
-                            }
+                            let start = match interaction.data.options[0].value {
+                                CommandDataOptionValue::String(ref start) => start,
+                                _ => panic!("unexpected slash command option type"),
+                            };
+                            schedule_async_race(ctx, interaction, game, start).await?;
                         } else if interaction.data.id == command_ids.schedule_remove {
                             let game = interaction.data.options.get(0).map(|option| match option.value {
                                 CommandDataOptionValue::Integer(game) => i16::try_from(game).expect("game number out of range"),
@@ -1887,7 +2294,7 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                                             }
                                         }
                                     }
-                                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => {}
+                                    draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => {}
                                 }
                                 draft_action(ctx, interaction, draft::Action::GoFirst(false)).await?;
                             }
@@ -2152,6 +2559,18 @@ pub(crate) fn configure_builder(discord_builder: serenity_utils::Builder, db_poo
                         }
                     } else if let Some((setting, value)) = custom_id.strip_prefix("draft_option_").and_then(|setting_value| setting_value.split_once("__")) {
                         draft_action(ctx, interaction, draft::Action::Pick { setting: setting.to_owned(), value: value.to_owned() }).await?;
+                    } else if let Some((timestamp, game)) = custom_id.strip_prefix("scheduleconfirm_").and_then(|args| args.split_once('_')) {
+                        let game = if game.is_empty() { None } else { Some(game.parse()?) };
+                        schedule_race(ctx, interaction, game, &format!("<t:{timestamp}>")).await?;
+                    } else if let Some(id) = custom_id.strip_prefix("rescheduleaccept_") {
+                        resolve_reschedule_request(ctx, interaction, id.parse()?, reschedule::State::Accepted).await?;
+                    } else if let Some(id) = custom_id.strip_prefix("rescheduledecline_") {
+                        resolve_reschedule_request(ctx, interaction, id.parse()?, reschedule::State::Declined).await?;
+                    } else if let Some(id) = custom_id.strip_prefix("reschedulewithdraw_") {
+                        resolve_reschedule_request(ctx, interaction, id.parse()?, reschedule::State::Withdrawn).await?;
+                    } else if let Some((timestamp, game)) = custom_id.strip_prefix("scheduleasyncconfirm_").and_then(|args| args.split_once('_')) {
+                        let game = if game.is_empty() { None } else { Some(game.parse()?) };
+                        schedule_async_race(ctx, interaction, game, &format!("<t:{timestamp}>")).await?;
                     } else if let Some(speedgaming_id) = custom_id.strip_prefix("sgdisambig_") {
                         let (db_pool, http_client) = {
                             let data = ctx.data.read().await;
@@ -2281,7 +2700,7 @@ pub(crate) async fn create_scheduling_thread<'a>(ctx: &DiscordCtx, mut transacti
             content.mention_command(command_ids.schedule, "schedule");
             content.push(" pour schedule votre race en live ou ");
             content.mention_command(command_ids.schedule_async, "schedule-async");
-            content.push(" pour schedule votre async. Vous devez insérer un timestamp Discord que vous pouvez créer sur <https://hammertime.cyou/>.");
+            content.push(" pour schedule votre async. Vous pouvez insérer un timestamp Discord (que vous pouvez créer sur <https://hammertime.cyou/>) ou une heure comme `tomorrow 8pm`.");
         } else {
             for team in race.teams() {
                 content.mention_team(&mut transaction, Some(guild_id), team).await?;
@@ -2310,15 +2729,18 @@ pub(crate) async fn create_scheduling_thread<'a>(ctx: &DiscordCtx, mut transacti
                 if event.asyncs_allowed() {
                     content.push(" to schedule as a live race or ");
                     content.mention_command(command_ids.schedule_async, "schedule-async");
-                    content.push(" to schedule as an async. These commands take a Discord timestamp, which you can generate at <https://hammertime.cyou/>.");
+                    content.push(" to schedule as an async. These commands take a Discord timestamp (which you can generate at <https://hammertime.cyou/>) or a time like `tomorrow 8pm`.");
                 } else {
-                    content.push(" to schedule your race. This command takes a Discord timestamp, which you can generate at <https://hammertime.cyou/>.");
+                    content.push(" to schedule your race. This command takes a Discord timestamp (which you can generate at <https://hammertime.cyou/>) or a time like `tomorrow 8pm`.");
                 }
                 if game_count > 1 {
                     content.push(" You can use the ");
                     content.push_mono("game:");
                     content.push(" parameter with these commands to schedule subsequent games ahead of time.");
                 }
+                content.push(" To find a time that works for everyone, enter your availability using ");
+                content.mention_command(command_ids.availability, "availability");
+                content.push('.');
             }
         }
     };
@@ -2354,6 +2776,13 @@ pub(crate) async fn create_scheduling_thread<'a>(ctx: &DiscordCtx, mut transacti
         thread.say(ctx, content.build()).await?;
         thread.id
     });
+    if event.speedgaming_slug.is_none() {
+        if let Some(suggestions) = availability::suggestions_message(&mut transaction, &event, race).await? {
+            let thread = race.scheduling_thread.expect("just created");
+            let message = thread.say(ctx, suggestions).await?;
+            availability::record_suggestions_message(&mut transaction, thread, message.id).await?;
+        }
+    }
     Ok(transaction)
 }
 
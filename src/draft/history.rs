@@ -0,0 +1,288 @@
+//! Audit trail of settings draft actions, stored in the `draft_actions` table.
+
+use {
+    sqlx::types::Json,
+    crate::{
+        draft::{
+            Action,
+            MessageContext,
+        },
+        event::Tab,
+        prelude::*,
+    },
+};
+
+/// Where a draft action was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "draft_action_source", rename_all = "snake_case")]
+pub(crate) enum Source {
+    Discord,
+    #[sqlx(rename = "racetime")]
+    RaceTime,
+    Web,
+    /// Applied automatically because the step's time limit ran out.
+    Timer,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Discord => write!(f, "Discord"),
+            Self::RaceTime => write!(f, "racetime.gg"),
+            Self::Web => write!(f, "Mido's House"),
+            Self::Timer => write!(f, "time limit"),
+        }
+    }
+}
+
+/// Who took a draft action, identified by the account they used to take it.
+pub(crate) enum Actor {
+    Discord(UserId),
+    RaceTime(String),
+    MidosHouse(Id<Users>),
+}
+
+impl Actor {
+    async fn to_html(&self, transaction: &mut Transaction<'_, Postgres>) -> sqlx::Result<RawHtml<String>> {
+        Ok(match self {
+            Self::Discord(discord_id) => if let Some(user) = User::from_discord(&mut **transaction, *discord_id).await? {
+                user.to_html()
+            } else {
+                html! {
+                    : "Discord user ";
+                    code : discord_id.to_string();
+                }
+            },
+            Self::RaceTime(racetime_id) => if let Some(user) = User::from_racetime(&mut **transaction, racetime_id).await? {
+                user.to_html()
+            } else {
+                html! {
+                    a(href = format!("https://{}/user/{racetime_id}", racetime_host())) : racetime_id;
+                }
+            },
+            Self::MidosHouse(id) => if let Some(user) = User::from_id(&mut **transaction, *id).await? {
+                user.to_html()
+            } else {
+                html! {
+                    : "deleted user";
+                }
+            },
+        })
+    }
+}
+
+pub(crate) struct Entry {
+    pub(crate) idx: i32,
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) source: Source,
+    pub(crate) actor: Option<Actor>,
+    pub(crate) action: Action,
+    /// The draft state the action was applied to. Undoing the action restores this state.
+    pub(crate) state_before: Draft,
+    pub(crate) undone_at: Option<DateTime<Utc>>,
+    pub(crate) undone_by: Option<Id<Users>>,
+}
+
+/// Records an action that was successfully applied to `state_before`.
+///
+/// Locks the race row first so concurrent actions on the same race can't be assigned the same index.
+pub(crate) async fn record(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, source: Source, actor: Option<Actor>, state_before: &Draft, action: &Action) -> sqlx::Result<()> {
+    let (actor_discord, actor_racetime, actor_user) = match actor {
+        Some(Actor::Discord(discord_id)) => (Some(discord_id), None, None),
+        Some(Actor::RaceTime(racetime_id)) => (None, Some(racetime_id), None),
+        Some(Actor::MidosHouse(id)) => (None, None, Some(id)),
+        None => (None, None, None),
+    };
+    Draft::lock(transaction, race).await?;
+    sqlx::query!("INSERT INTO draft_actions (race, idx, timestamp, source, actor_discord, actor_racetime, actor_user, action, state_before) VALUES ($1, (SELECT COALESCE(MAX(idx) + 1, 0) FROM draft_actions WHERE race = $1), NOW(), $2, $3, $4, $5, $6, $7)",
+        race as _,
+        source as _,
+        actor_discord.map(PgSnowflake) as _,
+        actor_racetime,
+        actor_user as _,
+        Json(action) as _,
+        Json(state_before) as _,
+    ).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+/// Returns all recorded actions for the given race in the order they were taken, including ones that have been undone.
+pub(crate) async fn for_race(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>) -> sqlx::Result<Vec<Entry>> {
+    Ok(sqlx::query!(r#"SELECT
+        idx,
+        timestamp,
+        source AS "source: Source",
+        actor_discord AS "actor_discord: PgSnowflake<UserId>",
+        actor_racetime,
+        actor_user AS "actor_user: Id<Users>",
+        action AS "action: Json<Action>",
+        state_before AS "state_before: Json<Draft>",
+        undone_at,
+        undone_by AS "undone_by: Id<Users>"
+    FROM draft_actions WHERE race = $1 ORDER BY idx ASC"#, race as _).fetch_all(&mut **transaction).await?
+        .into_iter()
+        .map(|row| Entry {
+            idx: row.idx,
+            timestamp: row.timestamp,
+            source: row.source,
+            actor: if let Some(PgSnowflake(discord_id)) = row.actor_discord {
+                Some(Actor::Discord(discord_id))
+            } else if let Some(racetime_id) = row.actor_racetime {
+                Some(Actor::RaceTime(racetime_id))
+            } else {
+                row.actor_user.map(Actor::MidosHouse)
+            },
+            action: row.action.0,
+            state_before: row.state_before.0,
+            undone_at: row.undone_at,
+            undone_by: row.undone_by,
+        })
+        .collect())
+}
+
+/// Marks the most recent action that hasn't been undone yet as undone and returns it.
+///
+/// Locks the race (see [`Draft::lock`]) for the rest of the transaction. The caller is responsible for restoring `races.draft_state` from [`Entry::state_before`].
+pub(crate) async fn undo_last(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, undone_by: Id<Users>) -> sqlx::Result<Option<Entry>> {
+    Draft::lock(transaction, race).await?;
+    let Some(mut entry) = for_race(transaction, race).await?.into_iter().rev().find(|entry| entry.undone_at.is_none()) else { return Ok(None) };
+    entry.undone_at = Some(sqlx::query_scalar!(r#"UPDATE draft_actions SET undone_at = NOW(), undone_by = $1 WHERE race = $2 AND idx = $3 RETURNING undone_at AS "undone_at!""#, undone_by as _, race as _, entry.idx).fetch_one(&mut **transaction).await?);
+    entry.undone_by = Some(undone_by);
+    Ok(Some(entry))
+}
+
+/// Marks all actions for the given race as undone, e.g. because the draft has been reset.
+pub(crate) async fn undo_all(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, undone_by: Id<Users>) -> sqlx::Result<()> {
+    sqlx::query!("UPDATE draft_actions SET undone_at = NOW(), undone_by = $1 WHERE race = $2 AND undone_at IS NULL", undone_by as _, race as _).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+/// Lists the picks that differ between two draft states.
+fn changes(before: &Draft, after: &Draft) -> Vec<String> {
+    let mut changes = Vec::default();
+    if before.went_first != after.went_first {
+        if let Some(went_first) = after.went_first {
+            changes.push(format!("high seed goes {}", if went_first { "first" } else { "second" }));
+        }
+    }
+    if before.skipped_bans != after.skipped_bans {
+        changes.push(format!("skipped bans: {}", after.skipped_bans));
+    }
+    for (setting, value) in after.settings.iter().sorted() {
+        if before.settings.get(setting) != Some(value) {
+            changes.push(format!("{setting}: {value}"));
+        }
+    }
+    changes
+}
+
+#[rocket::get("/event/<series>/<event>/races/<id>/draft")]
+pub(crate) async fn get(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, series: Series, event: &str, id: Id<Races>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let race = Race::from_id(&mut transaction, http_client, id).await?;
+    if race.series != event.series || race.event != event.event {
+        return Ok(RedirectOrContent::Redirect(Redirect::permanent(uri!(get(race.series, race.event, id)))))
+    }
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Races, true).await?;
+    let content = if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let entries = for_race(&mut transaction, race.id).await?;
+            let mut rows = Vec::with_capacity(entries.len());
+            for entry in &entries {
+                let result = if let Some(draft_kind) = event.draft_kind() {
+                    // replay the action on top of the recorded state to show its effect
+                    let mut state = entry.state_before.clone();
+                    match state.apply(draft_kind, race.game, &mut MessageContext::None, entry.action.clone()).await? {
+                        Ok(_) => {
+                            let picks = changes(&entry.state_before, &state);
+                            if picks.is_empty() { format!("no change") } else { picks.join(", ") }
+                        }
+                        Err(_) => format!("could not be replayed"),
+                    }
+                } else {
+                    format!("no settings draft configured")
+                };
+                let undone_by = if let Some(undone_by) = entry.undone_by {
+                    User::from_id(&mut *transaction, undone_by).await?
+                } else {
+                    None
+                };
+                let actor = if let Some(ref actor) = entry.actor {
+                    actor.to_html(&mut transaction).await?
+                } else {
+                    html! {
+                        : "unknown";
+                    }
+                };
+                rows.push((entry, actor, result, undone_by));
+            }
+            html! {
+                @if rows.is_empty() {
+                    p : "No settings draft actions have been recorded for this race.";
+                } else {
+                    table {
+                        thead {
+                            tr {
+                                th : "#";
+                                th : "Time";
+                                th : "Source";
+                                th : "Actor";
+                                th : "Action";
+                                th : "Result";
+                                th : "Undone";
+                            }
+                        }
+                        tbody {
+                            @for (entry, actor, result, undone_by) in rows {
+                                tr {
+                                    td : (entry.idx + 1).to_string();
+                                    td : format_datetime(entry.timestamp, DateTimeFormat { long: false, running_text: false });
+                                    td : entry.source.to_string();
+                                    td : actor;
+                                    td {
+                                        @if entry.undone_at.is_some() {
+                                            del : entry.action.to_string();
+                                        } else {
+                                            : entry.action.to_string();
+                                        }
+                                    }
+                                    td : result;
+                                    td {
+                                        @if let Some(undone_at) = entry.undone_at {
+                                            : format_datetime(undone_at, DateTimeFormat { long: false, running_text: false });
+                                            @if let Some(undone_by) = undone_by {
+                                                : " by ";
+                                                : undone_by;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(get(event.series, &*event.event, id)))))) : "Sign in or create a Mido's House account";
+                    : " to view this race's settings draft history.";
+                }
+            }
+        }
+    };
+    Ok(RedirectOrContent::Content(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Draft History — {}", event.display_name), html! {
+        : header;
+        h2 : "Settings draft history";
+        : content;
+    }).await?))
+}
@@ -9,9 +9,17 @@ use {
             SignupsTeam,
         },
         prelude::*,
+        rating,
     },
 };
 
+pub(crate) mod history;
+pub(crate) mod simulate;
+pub(crate) mod spec;
+pub(crate) mod stats;
+pub(crate) mod timer;
+pub(crate) mod web;
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
     #[error(transparent)] RslScriptPath(#[from] rsl::ScriptPathError),
@@ -72,6 +80,8 @@ pub(crate) enum Kind {
     TournoiFrancoS3,
     TournoiFrancoS4,
     TournoiFrancoS5,
+    /// A draft format defined in the database, see [`spec::Spec`].
+    Custom(&'static spec::Spec),
 }
 
 impl Kind {
@@ -84,6 +94,7 @@ impl Kind {
             | Self::RslS7
             | Self::TournoiFrancoS4
             | Self::TournoiFrancoS5
+            | Self::Custom(_)
                 => English,
             | Self::TournoiFrancoS3
                 => French,
@@ -91,6 +102,76 @@ impl Kind {
     }
 }
 
+/// A setting that can be banned or picked in a draft format.
+pub(crate) struct PoolSetting {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) display: Cow<'static, str>,
+    /// The value a setting is locked to when it's banned.
+    pub(crate) default: Cow<'static, str>,
+    /// Display names for the values it can be picked to.
+    pub(crate) options: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl PoolSetting {
+    pub(crate) fn value_display<'a>(&'a self, value: &'a str) -> &'a str {
+        self.options.iter().find(|(name, _)| name == value).map_or(value, |(_, display)| &**display)
+    }
+}
+
+impl Kind {
+    /// The settings that can be banned or picked in this draft format.
+    pub(crate) fn settings_pool(&self) -> Vec<PoolSetting> {
+        match *self {
+            Kind::S7 => s::S7_SETTINGS.into_iter().map(|setting| PoolSetting {
+                name: Cow::Borrowed(setting.name),
+                display: Cow::Borrowed(setting.display),
+                default: Cow::Borrowed("default"),
+                options: setting.other.iter().map(|(name, display, _)| (Cow::Borrowed(*name), Cow::Borrowed(*display))).collect(),
+            }).collect(),
+            Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 => match *self {
+                Kind::MultiworldS3 => mw::S3_SETTINGS,
+                Kind::MultiworldS4 => mw::S4_SETTINGS,
+                Kind::MultiworldS5 => mw::S5_SETTINGS,
+                _ => unreachable!(),
+            }.iter().map(|setting| PoolSetting {
+                name: Cow::Borrowed(setting.name),
+                display: Cow::Borrowed(setting.display),
+                default: Cow::Borrowed(setting.default),
+                options: setting.other.iter().map(|(name, display)| (Cow::Borrowed(*name), Cow::Borrowed(*display))).collect(),
+            }).collect(),
+            Kind::TournoiFrancoS3 | Kind::TournoiFrancoS4 | Kind::TournoiFrancoS5 => match *self {
+                Kind::TournoiFrancoS3 => &fr::S3_SETTINGS[..],
+                Kind::TournoiFrancoS4 => &fr::S4_SETTINGS[..],
+                Kind::TournoiFrancoS5 => &fr::S5_SETTINGS[..],
+                _ => unreachable!(),
+            }.iter().map(|setting| PoolSetting {
+                name: Cow::Borrowed(setting.name),
+                display: Cow::Borrowed(setting.display),
+                default: Cow::Borrowed(setting.default),
+                options: setting.other.iter().map(|(name, _, display)| (Cow::Borrowed(*name), Cow::Borrowed(*display))).collect(),
+            }).collect(),
+            // In RSL, bans are called blocks, and picks are called bans.
+            Kind::RslS7 => rsl::FORCE_OFF_SETTINGS.into_iter().map(|setting| PoolSetting {
+                name: Cow::Borrowed(setting.name),
+                display: Cow::Borrowed(setting.display),
+                default: Cow::Borrowed("blocked"),
+                options: Vec::default(),
+            }).chain(rsl::FIFTY_FIFTY_SETTINGS.into_iter().chain(rsl::MULTI_OPTION_SETTINGS).map(|setting| PoolSetting {
+                name: Cow::Borrowed(setting.name),
+                display: Cow::Borrowed(setting.display),
+                default: Cow::Borrowed("blocked"),
+                options: setting.options.iter().map(|(name, display, _, _)| (Cow::Borrowed(*name), Cow::Borrowed(*display))).collect(),
+            })).collect(),
+            Kind::Custom(spec) => spec.all_settings().map(|setting| PoolSetting {
+                name: Cow::Borrowed(&*setting.name),
+                display: Cow::Borrowed(&*setting.display),
+                default: Cow::Borrowed(&*setting.default),
+                options: setting.options.iter().map(|option| (Cow::Borrowed(&*option.name), Cow::Borrowed(&*option.display))).collect(),
+            }).collect(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct BanSetting {
     pub(crate) name: &'static str,
@@ -203,6 +284,10 @@ pub(crate) enum MessageContext<'a> {
         low_seed_name: &'a str,
         reply_to: &'a str,
     },
+    /// The draft section of a race page on the website.
+    ///
+    /// The page renders the current step from its [`StepKind`], so only messages that don't refer to slash commands or chat commands are generated.
+    Web,
 }
 
 impl<'a> MessageContext<'a> {
@@ -213,6 +298,8 @@ impl<'a> MessageContext<'a> {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub(crate) enum Action {
     GoFirst(bool),
     Ban {
@@ -226,6 +313,20 @@ pub(crate) enum Action {
     BooleanChoice(bool),
 }
 
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GoFirst(true) => write!(f, "go first"),
+            Self::GoFirst(false) => write!(f, "go second"),
+            Self::Ban { setting } => write!(f, "ban {setting}"),
+            Self::Pick { setting, value } => write!(f, "pick {setting}: {value}"),
+            Self::Skip => write!(f, "skip"),
+            Self::BooleanChoice(true) => write!(f, "yes"),
+            Self::BooleanChoice(false) => write!(f, "no"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct Draft {
     pub(crate) high_seed: Id<Teams>,
@@ -237,96 +338,73 @@ pub(crate) struct Draft {
 }
 
 impl Draft {
+    /// Locks the given race's row until the end of the transaction and returns its current draft state.
+    ///
+    /// Every code path that changes `races.draft_state` must call this before reading the draft it's going to modify,
+    /// so that actions from Discord, the website, racetime.gg, and the draft timer can't overwrite each other.
+    pub(crate) async fn lock(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>) -> sqlx::Result<Option<Self>> {
+        Ok(sqlx::query_scalar!(r#"SELECT draft_state AS "draft_state: Json<Self>" FROM races WHERE id = $1 FOR UPDATE"#, race as _).fetch_one(&mut **transaction).await?.map(|Json(draft)| draft))
+    }
+
+    /// The high and low seed according to the seeding configured by the event's organizers for the given phase, if both teams are seeded.
+    async fn configured_seeding(transaction: &mut Transaction<'_, Postgres>, event: &event::Data<'_>, phase: Option<&str>, [team1, team2]: [&team::Team; 2]) -> sqlx::Result<Option<[Id<Teams>; 2]>> {
+        let Some(phase) = phase else { return Ok(None) };
+        let team_ids = sqlx::query_scalar!(r#"SELECT team AS "team: Id<Teams>" FROM seeds WHERE series = $1 AND event = $2 AND phase = $3 AND (team = $4 OR team = $5) ORDER BY seed"#, event.series as _, &event.event, phase, team1.id as _, team2.id as _).fetch_all(&mut **transaction).await?;
+        Ok(<[_; 2]>::try_from(team_ids).ok())
+    }
+
     pub(crate) async fn for_game1(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, kind: Kind, event: &event::Data<'_>, phase: Option<&str>, [team1, team2]: [&team::Team; 2]) -> Result<Self, cal::Error> {
-        let [high_seed, low_seed] = match kind {
-            Kind::S7 => [
-                min_by_key(team1, team2, |team| team.qualifier_rank).id,
-                max_by_key(team1, team2, |team| team.qualifier_rank).id,
-            ],
-            Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 => if phase.is_some_and(|phase| phase == "Top 8") {
-                let seeding = match kind {
-                    Kind::MultiworldS3 => [
-                        Id::from(5834711445123920517_u64), // DAD
-                        Id::from(167966029947875858_u64), // Snack Pack
-                        Id::from(541730158313016345_u64), // The Highest Gorons
-                        Id::from(8470597845703477673_u64), // Pirate Ship
-                        Id::from(3240373008633749917_u64), // The Good, The Bad and The Shopskipper
-                        Id::from(4729976791199641222_u64), // SariasObjects
-                        Id::from(4543618089366873966_u64), // Anju's Secret
-                        Id::from(3547322866530836817_u64), // Raid: Shadow Temple
-                    ],
-                    Kind::MultiworldS4 => [
-                        Id::from(8429274534302278572_u64), // Anju's Secret
-                        Id::from(12142947927479333421_u64), // ADD
-                        Id::from(5548902498821246494_u64), // Donutdog!!! Wuff! Wuff!
-                        Id::from(7622448514297787774_u64), // Snack Pack
-                        Id::from(13644615382444869291_u64), // The Highest Gorons
-                        Id::from(4984265622447250649_u64), // Bongo Akimbo
-                        Id::from(592664405695569367_u64), // The Jhegsons
-                        Id::from(14405144517033747435_u64), // Pandora's Brot
-                    ],
-                    Kind::MultiworldS5 => [
-                        Id::from(15744003931430480727_u64), // Schulzer, Jay and Bobby are here
-                        Id::from(1129367405239208119_u64), // Pandora's Brot
-                        Id::from(17104756766000096702_u64), // Moeko Appreciation Society
-                        Id::from(32215927528820085_u64), // Captain Levi's Fish and Chimp Shop
-                        Id::from(4924230698541822293_u64), // Trois clampins et une chaussette
-                        Id::from(17175023655531815569_u64), // SariasObjects
-                        Id::from(5779097592179749366_u64), // Clube Athletico Paranaense
-                        Id::from(3033012543915648585_u64), // DAChuck
-                    ],
-                    _ => unreachable!("checked by outer match"),
-                };
-                let mut team_ids = [team1.id, team2.id];
-                team_ids.sort_unstable_by_key(|team| seeding.iter().position(|iter_team| iter_team == team));
-                team_ids
-            } else {
-                let qualifier_kind = event.qualifier_kind(&mut *transaction, None).await?;
-                let signups = teams::signups_sorted(&mut *transaction, &mut teams::Cache::new(http_client.clone()), None, event, false, qualifier_kind, None).await?;
-                let SignupsTeam { members: members1, .. } = signups.iter().find(|SignupsTeam { team, .. }| team.as_ref().is_some_and(|team| team == team1)).expect("match with team that didn't sign up");
-                let SignupsTeam { members: members2, .. } = signups.iter().find(|SignupsTeam { team, .. }| team.as_ref().is_some_and(|team| team == team2)).expect("match with team that didn't sign up");
-                let avg1 = members1.iter().try_fold(Duration::default(), |acc, member| Some(acc + member.qualifier_time?)).map(|total| total / u32::try_from(members1.len()).expect("too many team members"));
-                let avg2 = members2.iter().try_fold(Duration::default(), |acc, member| Some(acc + member.qualifier_time?)).map(|total| total / u32::try_from(members2.len()).expect("too many team members"));
-                match [avg1, avg2] {
-                    [Some(_), None] => [team1.id, team2.id],
-                    [None, Some(_)] => [team2.id, team1.id],
-                    [Some(avg1), Some(avg2)] if avg1 < avg2 => [team1.id, team2.id],
-                    [Some(avg1), Some(avg2)] if avg1 > avg2 => [team2.id, team1.id],
-                    _ => {
-                        // tie broken by coin flip
-                        let mut team_ids = [team1.id, team2.id];
-                        team_ids.shuffle(&mut rng());
-                        team_ids
+        let [high_seed, low_seed] = if let Some(seeding) = Self::configured_seeding(transaction, event, phase, [team1, team2]).await? {
+            seeding
+        } else {
+            match kind {
+                Kind::S7 | Kind::RslS7 => [
+                    min_by_key(team1, team2, |team| team.qualifier_rank).id,
+                    max_by_key(team1, team2, |team| team.qualifier_rank).id,
+                ],
+                Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 => {
+                    let qualifier_kind = event.qualifier_kind(&mut *transaction, None).await?;
+                    let signups = teams::signups_sorted(&mut *transaction, &mut teams::Cache::new(http_client.clone()), None, event, false, qualifier_kind, None).await?;
+                    let SignupsTeam { members: members1, .. } = signups.iter().find(|SignupsTeam { team, .. }| team.as_ref().is_some_and(|team| team == team1)).expect("match with team that didn't sign up");
+                    let SignupsTeam { members: members2, .. } = signups.iter().find(|SignupsTeam { team, .. }| team.as_ref().is_some_and(|team| team == team2)).expect("match with team that didn't sign up");
+                    let avg1 = members1.iter().try_fold(Duration::default(), |acc, member| Some(acc + member.qualifier_time?)).map(|total| total / u32::try_from(members1.len()).expect("too many team members"));
+                    let avg2 = members2.iter().try_fold(Duration::default(), |acc, member| Some(acc + member.qualifier_time?)).map(|total| total / u32::try_from(members2.len()).expect("too many team members"));
+                    match [avg1, avg2] {
+                        [Some(_), None] => [team1.id, team2.id],
+                        [None, Some(_)] => [team2.id, team1.id],
+                        [Some(avg1), Some(avg2)] if avg1 < avg2 => [team1.id, team2.id],
+                        [Some(avg1), Some(avg2)] if avg1 > avg2 => [team2.id, team1.id],
+                        _ => {
+                            // tie broken by coin flip
+                            let mut team_ids = [team1.id, team2.id];
+                            team_ids.shuffle(&mut rng());
+                            team_ids
+                        }
                     }
+                },
+                Kind::TournoiFrancoS3 | Kind::TournoiFrancoS4 | Kind::TournoiFrancoS5 | Kind::Custom(spec::Spec { seeding: spec::Seeding::CoinFlip, .. }) => {
+                    let mut team_ids = [team1.id, team2.id];
+                    team_ids.shuffle(&mut rng());
+                    team_ids
                 }
-            },
-            Kind::RslS7 => if phase.is_some_and(|phase| phase == "Top 8") {
-                let seeding = match kind {
-                    Kind::RslS7 => [
-                        Id::from(12639002222577844011_u64), // Alexis
-                        Id::from(16373589663560160707_u64), // Schulzer
-                        Id::from(18251410877395917313_u64), // Chimpanreeve
-                        Id::from(7664769147612623797_u64), // Aranaut
-                        Id::from(2933860891811564156_u64), // Flouche
-                        Id::from(6739931103991450481_u64), // Nopons
-                        Id::from(5480028145623953067_u64), // Rafa
-                        Id::from(17375662428578904933_u64), // Cola
-                    ],
-                    _ => unreachable!("checked by outer match"),
-                };
-                let mut team_ids = [team1.id, team2.id];
-                team_ids.sort_unstable_by_key(|team| seeding.iter().position(|iter_team| iter_team == team));
-                team_ids
-            } else {
-                [
+                Kind::Custom(spec::Spec { seeding: spec::Seeding::QualifierRank, .. }) => [
                     min_by_key(team1, team2, |team| team.qualifier_rank).id,
                     max_by_key(team1, team2, |team| team.qualifier_rank).id,
-                ]
-            },
-            Kind::TournoiFrancoS3 | Kind::TournoiFrancoS4 | Kind::TournoiFrancoS5 => {
-                let mut team_ids = [team1.id, team2.id];
-                team_ids.shuffle(&mut rng());
-                team_ids
+                ],
+                Kind::Custom(spec::Spec { seeding: spec::Seeding::Rating, .. }) => {
+                    let rating1 = rating::team_rating(&mut *transaction, event.team_config, team1).await?;
+                    let rating2 = rating::team_rating(&mut *transaction, event.team_config, team2).await?;
+                    match rating1.total_cmp(&rating2) {
+                        Greater => [team1.id, team2.id],
+                        Less => [team2.id, team1.id],
+                        Equal => {
+                            // tie broken by coin flip
+                            let mut team_ids = [team1.id, team2.id];
+                            team_ids.shuffle(&mut rng());
+                            team_ids
+                        }
+                    }
+                }
             }
         };
         Ok(Self::for_next_game(transaction, kind, high_seed, low_seed).await?)
@@ -338,7 +416,7 @@ impl Draft {
             went_first: None,
             skipped_bans: 0,
             settings: match kind {
-                Kind::S7 | Kind::MultiworldS3 | Kind::MultiworldS5 => HashMap::default(),
+                Kind::S7 | Kind::MultiworldS3 | Kind::MultiworldS5 | Kind::Custom(_) => HashMap::default(),
                 // accessibility accommodation for The Aussie Boiiz in mw/4 to default to CSMC
                 Kind::MultiworldS4 => HashMap::from_iter(
                     (loser == Id::from(17814073240662869290_u64) || winner == Id::from(17814073240662869290_u64))
@@ -376,11 +454,13 @@ impl Draft {
             Kind::TournoiFrancoS3 => self.skipped_bans + u8::try_from(fr::S3_SETTINGS.into_iter().filter(|&fr::Setting { name, .. }| self.settings.contains_key(name)).count()).unwrap(),
             Kind::TournoiFrancoS4 => self.skipped_bans + u8::try_from(fr::S4_SETTINGS.into_iter().filter(|&fr::Setting { name, .. }| self.settings.contains_key(name)).count()).unwrap(),
             Kind::TournoiFrancoS5 => self.skipped_bans + u8::try_from(fr::S5_SETTINGS.into_iter().filter(|&fr::Setting { name, .. }| self.settings.contains_key(name)).count()).unwrap(),
+            Kind::Custom(spec) => spec.pick_count(self),
         }
     }
 
     pub(crate) async fn next_step(&self, kind: Kind, game: Option<i16>, msg_ctx: &mut MessageContext<'_>) -> Result<Step, Error> {
         Ok(match kind {
+            Kind::Custom(spec) => spec.next_step(self, game, msg_ctx).await?,
             Kind::S7 => {
                 if let Some(went_first) = self.went_first {
                     match self.pick_count(kind) {
@@ -416,7 +496,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -461,7 +541,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -495,7 +575,7 @@ impl Draft {
                             kind: StepKind::Done(s::resolve_s7_draft_settings(&self.settings)),
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => format!("Settings draft completed. You will be playing with {}.", s::display_s7_draft_picks(&self.settings)),
+                                MessageContext::Discord { .. } | MessageContext::Web => format!("Settings draft completed. You will be playing with {}.", s::display_s7_draft_picks(&self.settings)),
                                 MessageContext::RaceTime { .. } => s::display_s7_draft_picks(&self.settings),
                             },
                         },
@@ -504,7 +584,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -606,7 +686,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -676,7 +756,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -710,7 +790,7 @@ impl Draft {
                             },
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => format!("Weights draft completed. You will be playing with {}.", rsl::display_s7_draft_picks(&self.settings)),
+                                MessageContext::Discord { .. } | MessageContext::Web => format!("Weights draft completed. You will be playing with {}.", rsl::display_s7_draft_picks(&self.settings)),
                                 MessageContext::RaceTime { .. } => rsl::display_s7_draft_picks(&self.settings),
                             },
                         },
@@ -719,7 +799,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -777,7 +857,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -824,7 +904,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -873,7 +953,7 @@ impl Draft {
                             kind: StepKind::Done(mw::resolve_s3_draft_settings(&self.settings)),
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => format!("Settings draft completed. You will be playing with {}.", mw::display_s3_draft_picks(&self.settings)),
+                                MessageContext::Discord { .. } | MessageContext::Web => format!("Settings draft completed. You will be playing with {}.", mw::display_s3_draft_picks(&self.settings)),
                                 MessageContext::RaceTime { .. } => mw::display_s3_draft_picks(&self.settings),
                             },
                         },
@@ -882,7 +962,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -946,7 +1026,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -1006,7 +1086,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -1056,7 +1136,7 @@ impl Draft {
                             kind: StepKind::Done(mw::resolve_s4_draft_settings(&self.settings)),
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => format!("Settings draft completed. You will be playing with {}.", mw::display_s4_draft_picks(&self.settings)),
+                                MessageContext::Discord { .. } | MessageContext::Web => format!("Settings draft completed. You will be playing with {}.", mw::display_s4_draft_picks(&self.settings)),
                                 MessageContext::RaceTime { .. } => mw::display_s4_draft_picks(&self.settings),
                             },
                         },
@@ -1065,7 +1145,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -1122,7 +1202,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -1169,7 +1249,7 @@ impl Draft {
                                     team,
                                 },
                                 message: match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                         let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                         let high_seed = high_seed.remove(0);
@@ -1219,7 +1299,7 @@ impl Draft {
                             kind: StepKind::Done(mw::resolve_s5_draft_settings(&self.settings)),
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => format!("Settings draft completed. You will be playing with {}.", mw::display_s5_draft_picks(&self.settings)),
+                                MessageContext::Discord { .. } | MessageContext::Web => format!("Settings draft completed. You will be playing with {}.", mw::display_s5_draft_picks(&self.settings)),
                                 MessageContext::RaceTime { .. } => mw::display_s5_draft_picks(&self.settings),
                             },
                         },
@@ -1228,7 +1308,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -1261,7 +1341,7 @@ impl Draft {
                     Kind::TournoiFrancoS3 => &fr::S3_SETTINGS[..],
                     Kind::TournoiFrancoS4 => &fr::S4_SETTINGS[..],
                     Kind::TournoiFrancoS5 => &fr::S5_SETTINGS[..],
-                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 => unreachable!(),
+                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_) => unreachable!(),
                 };
                 if let Some(went_first) = self.went_first {
                     let mut pick_count = self.pick_count(kind);
@@ -1278,11 +1358,11 @@ impl Draft {
                                 Kind::TournoiFrancoS3 => fr::resolve_s3_draft_settings(&self.settings),
                                 Kind::TournoiFrancoS4 => fr::resolve_s4_draft_settings(&self.settings),
                                 Kind::TournoiFrancoS5 => fr::resolve_s5_draft_settings(&self.settings),
-                                Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 => unreachable!(),
+                                Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_) => unreachable!(),
                             }),
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
-                                MessageContext::Discord { .. } => if let French = kind.language() {
+                                MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                     format!("Fin du draft ! Voici un récapitulatif : {}.", fr::display_draft_picks(kind.language(), all_settings, &self.settings))
                                 } else {
                                     format!("Settings draft completed. You will be playing with {}.", fr::display_draft_picks(kind.language(), all_settings, &self.settings))
@@ -1290,13 +1370,18 @@ impl Draft {
                                 MessageContext::RaceTime { .. } => fr::display_draft_picks(kind.language(), all_settings, &self.settings),
                             },
                         }),
-                        (Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7, _, _) => unreachable!(),
+                        (Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_), _, _) => unreachable!(),
                     };
                     if select_mixed_dungeons {
                         Step {
                             kind: StepKind::BooleanChoice { team },
                             message: match msg_ctx {
                                 MessageContext::None => String::default(),
+                                MessageContext::Web => if let French = kind.language() {
+                                    format!("Est-ce que les donjons seront mixés avec les intérieurs et les grottos ?")
+                                } else {
+                                    format!("Should dungeon entrances be mixed with interiors and grottos?")
+                                },
                                 MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                     let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                     let high_seed = high_seed.remove(0);
@@ -1367,7 +1452,7 @@ impl Draft {
                                         team,
                                     },
                                     message: match msg_ctx {
-                                        MessageContext::None => String::default(),
+                                        MessageContext::None | MessageContext::Web => String::default(),
                                         MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                             let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                             let high_seed = high_seed.remove(0);
@@ -1404,13 +1489,13 @@ impl Draft {
                                     Kind::TournoiFrancoS3 => 10,
                                     Kind::TournoiFrancoS4 => 8,
                                     Kind::TournoiFrancoS5 => 8,
-                                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 => unreachable!(),
+                                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_) => unreachable!(),
                                 };
                                 let hard_settings_ok = self.settings.get("hard_settings_ok").map(|hard_settings_ok| &**hard_settings_ok).unwrap_or("no") == "ok";
                                 let can_ban = match kind {
                                     Kind::TournoiFrancoS3 | Kind::TournoiFrancoS4 => n < round_count - 2 || self.settings.get(team.choose("high_seed_has_picked", "low_seed_has_picked")).map(|has_picked| &**has_picked).unwrap_or("no") == "yes",
                                     Kind::TournoiFrancoS5 => n == 4 || n == 5,
-                                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 => unreachable!(),
+                                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_) => unreachable!(),
                                 };
                                 let skippable = n == round_count - 1 && can_ban;
                                 let (hard_settings, classic_settings) = all_settings.iter()
@@ -1444,7 +1529,7 @@ impl Draft {
                                         team, skippable,
                                     },
                                     message: match msg_ctx {
-                                        MessageContext::None => String::default(),
+                                        MessageContext::None | MessageContext::Web => String::default(),
                                         MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
                                             let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                             let high_seed = high_seed.remove(0);
@@ -1566,7 +1651,7 @@ impl Draft {
                     Step {
                         kind: StepKind::GoFirst,
                         message: match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => if let French = kind.language() {
                                 let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == self.high_seed);
                                 let high_seed = high_seed.remove(0);
@@ -1627,6 +1712,7 @@ impl Draft {
 
     pub(crate) async fn apply(&mut self, kind: Kind, game: Option<i16>, msg_ctx: &mut MessageContext<'_>, action: Action) -> Result<Result<String, String>, Error> {
         Ok(match kind {
+            Kind::Custom(spec) => spec.apply(self, game, msg_ctx, action).await?,
             Kind::S7 => {
                 let resolved_action = match action {
                     Action::Ban { setting } => if let Some(setting) = s::S7_SETTINGS.into_iter().find(|&s::Setting { name, .. }| *name == setting) {
@@ -1634,7 +1720,7 @@ impl Draft {
                     } else {
                         return Ok(Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => {
+                            MessageContext::Discord { .. } | MessageContext::Web => {
                                 let mut content = MessageBuilder::default();
                                 content.push("Sorry, I don't recognize that setting. Use one of the following: ");
                                 for (i, setting) in s::S7_SETTINGS.into_iter().enumerate() {
@@ -1659,7 +1745,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have" } else { " has" })
@@ -1671,20 +1757,20 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, first pick has already been chosen."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -1697,7 +1783,7 @@ impl Draft {
                             if value == setting.default {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have locked in " } else { " has locked in " })
@@ -1707,7 +1793,7 @@ impl Draft {
                                 })
                             } else {
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, bans haven't been chosen yet, use ")
                                         .mention_command(command_ids.ban.unwrap(), "ban")
@@ -1718,7 +1804,7 @@ impl Draft {
                         } else {
                             let exists = s::S7_SETTINGS.into_iter().any(|s::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -1751,7 +1837,7 @@ impl Draft {
                             if let Some(option) = setting.options.iter().find(|option| option.name == value) {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have picked " } else { " has picked " })
@@ -1762,7 +1848,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -1782,7 +1868,7 @@ impl Draft {
                         } else {
                             let exists = s::S7_SETTINGS.into_iter().any(|s::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -1814,13 +1900,13 @@ impl Draft {
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -1836,7 +1922,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -1849,13 +1935,13 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
@@ -1864,12 +1950,12 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, the current step is not a yes/no question."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
                         }),
                     },
@@ -1882,7 +1968,7 @@ impl Draft {
                     } else {
                         return Ok(Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => {
+                            MessageContext::Discord { .. } | MessageContext::Web => {
                                 let mut content = MessageBuilder::default();
                                 content.push("Sorry, I don't recognize that setting. Use one of the following: ");
                                 for (i, setting) in mw::S3_SETTINGS.iter().copied().enumerate() {
@@ -1907,7 +1993,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have" } else { " has" })
@@ -1919,20 +2005,20 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, first pick has already been chosen."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -1945,7 +2031,7 @@ impl Draft {
                             if value == setting.default {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have locked in " } else { " has locked in " })
@@ -1955,7 +2041,7 @@ impl Draft {
                                 })
                             } else {
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, bans haven't been chosen yet, use ")
                                         .mention_command(command_ids.ban.unwrap(), "ban")
@@ -1966,7 +2052,7 @@ impl Draft {
                         } else {
                             let exists = mw::S3_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -1999,7 +2085,7 @@ impl Draft {
                             if let Some(option) = setting.options.iter().find(|option| option.name == value) {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have picked " } else { " has picked " })
@@ -2010,7 +2096,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -2030,7 +2116,7 @@ impl Draft {
                         } else {
                             let exists = mw::S3_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -2062,13 +2148,13 @@ impl Draft {
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2084,7 +2170,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -2097,13 +2183,13 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
@@ -2112,12 +2198,12 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, the current step is not a yes/no question."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
                         }),
                     },
@@ -2137,7 +2223,7 @@ impl Draft {
                     } else {
                         return Ok(Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => {
+                            MessageContext::Discord { .. } | MessageContext::Web => {
                                 let mut content = MessageBuilder::default();
                                 content.push("Sorry, I don't recognize that setting. Use one of the following: ");
                                 for (i, setting) in mw::S4_SETTINGS.iter().copied().enumerate() {
@@ -2162,7 +2248,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have" } else { " has" })
@@ -2174,20 +2260,20 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, first pick has already been chosen."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2200,7 +2286,7 @@ impl Draft {
                             if value == setting.default {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have locked in " } else { " has locked in " })
@@ -2210,7 +2296,7 @@ impl Draft {
                                 })
                             } else {
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, bans haven't been chosen yet, use ")
                                         .mention_command(command_ids.ban.unwrap(), "ban")
@@ -2221,7 +2307,7 @@ impl Draft {
                         } else {
                             let exists = mw::S4_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -2254,7 +2340,7 @@ impl Draft {
                             if let Some(option) = setting.options.iter().find(|option| option.name == value) {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have picked " } else { " has picked " })
@@ -2265,7 +2351,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -2285,7 +2371,7 @@ impl Draft {
                         } else {
                             let exists = mw::S4_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -2317,13 +2403,13 @@ impl Draft {
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2340,7 +2426,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -2353,13 +2439,13 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
@@ -2368,12 +2454,12 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, the current step is not a yes/no question."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
                         }),
                     },
@@ -2389,7 +2475,7 @@ impl Draft {
                     } else {
                         return Ok(Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => {
+                            MessageContext::Discord { .. } | MessageContext::Web => {
                                 let mut content = MessageBuilder::default();
                                 content.push("Sorry, I don't recognize that setting. Use one of the following: ");
                                 for (i, setting) in mw::S5_SETTINGS.iter().copied().enumerate() {
@@ -2414,7 +2500,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have" } else { " has" })
@@ -2426,20 +2512,20 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, first pick has already been chosen."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2452,7 +2538,7 @@ impl Draft {
                             if value == setting.default {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have locked in " } else { " has locked in " })
@@ -2462,7 +2548,7 @@ impl Draft {
                                 })
                             } else {
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, bans haven't been chosen yet, use ")
                                         .mention_command(command_ids.ban.unwrap(), "ban")
@@ -2473,7 +2559,7 @@ impl Draft {
                         } else {
                             let exists = mw::S5_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -2506,7 +2592,7 @@ impl Draft {
                             if let Some(option) = setting.options.iter().find(|option| option.name == value) {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have picked " } else { " has picked " })
@@ -2517,7 +2603,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -2537,7 +2623,7 @@ impl Draft {
                         } else {
                             let exists = mw::S5_SETTINGS.iter().copied().any(|mw::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -2569,13 +2655,13 @@ impl Draft {
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2592,7 +2678,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -2605,13 +2691,13 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                     },
@@ -2620,12 +2706,12 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this settings draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, the current step is not a yes/no question."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
                         }),
                     },
@@ -2645,7 +2731,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => {
                                     let mut content = MessageBuilder::default();
                                     content.mention_team(transaction, Some(*guild_id), team).await?;
@@ -2665,20 +2751,20 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, first pick has already been chosen."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::Done(_) => unreachable!(),
                         StepKind::DoneRsl { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this weights draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this weights draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this weights draft is already completed."),
                         }),
                     },
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2691,7 +2777,7 @@ impl Draft {
                             if value == setting.default {
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have blocked " } else { " has blocked " })
@@ -2701,7 +2787,7 @@ impl Draft {
                                 })
                             } else {
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, the current step is a block, not a ban, use ")
                                         .mention_command(command_ids.ban.unwrap(), "block")
@@ -2711,7 +2797,7 @@ impl Draft {
                             }
                         } else {
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     content.push("Sorry, that setting doesn't exist or can no longer be blocked. Use one of the following: ");
@@ -2752,7 +2838,7 @@ impl Draft {
                                     hash_map::Entry::Vacant(entry) => { entry.insert(Cow::Owned(team.to_string())); }
                                 }
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                         .mention_team(transaction, Some(*guild_id), team).await?
                                         .push(if team.name_is_plural() { " have banned " } else { " has banned " })
@@ -2763,7 +2849,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -2782,7 +2868,7 @@ impl Draft {
                             }
                         } else {
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     content.push("Sorry, that setting doesn't exist or can no longer be banned. Use one of the following: ");
@@ -2809,13 +2895,13 @@ impl Draft {
                         StepKind::BooleanChoice { .. } | StepKind::Done(_) => unreachable!(),
                         StepKind::DoneRsl { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this weights draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this weights draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this weights draft is already completed."),
                         }),
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -2832,7 +2918,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -2845,13 +2931,13 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } | StepKind::Done(_) => unreachable!(),
                         StepKind::DoneRsl { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this weights draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this weights draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this weights draft is already completed."),
                         }),
                     },
@@ -2860,12 +2946,12 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this weights draft is already completed."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this weights draft is already completed."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this weights draft is already completed."),
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, the current step is not a yes/no question."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
                         }),
                     },
@@ -2876,7 +2962,7 @@ impl Draft {
                     Kind::TournoiFrancoS3 => &fr::S3_SETTINGS[..],
                     Kind::TournoiFrancoS4 => &fr::S4_SETTINGS[..],
                     Kind::TournoiFrancoS5 => &fr::S5_SETTINGS[..],
-                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 => unreachable!(),
+                    Kind::MultiworldS3 | Kind::MultiworldS4 | Kind::MultiworldS5 | Kind::RslS7 | Kind::S7 | Kind::Custom(_) => unreachable!(),
                 };
                 let resolved_action = match action {
                     Action::Ban { setting } => if let Some(setting) = all_settings.iter().find(|&&fr::Setting { name, .. }| *name == setting) {
@@ -2884,7 +2970,7 @@ impl Draft {
                     } else {
                         return Ok(Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => {
+                            MessageContext::Discord { .. } | MessageContext::Web => {
                                 let mut content = MessageBuilder::default();
                                 content.push("Sorry, I don't recognize that setting. Use one of the following: ");
                                 for (i, setting) in all_settings.iter().enumerate() {
@@ -2908,7 +2994,7 @@ impl Draft {
                         StepKind::GoFirst => {
                             self.went_first = Some(first);
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => if let French = kind.language() {
                                     let mut content = MessageBuilder::default();
                                     content.mention_team(transaction, Some(*guild_id), team).await?;
@@ -2951,7 +3037,7 @@ impl Draft {
                         }
                         StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, le premier pick a déjà été sélectionné.")
                             } else {
                                 format!("Sorry, first pick has already been chosen.")
@@ -2963,7 +3049,7 @@ impl Draft {
                             },
                         }),
                         StepKind::BooleanChoice { .. } => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => if let French = kind.language() {
                                 MessageBuilder::default()
                                     .push("Désolé, avant que le draft ne puisse continuer, vous devez d'abord choisir si les donjons seront mixés ou non avec le reste. Utilisez ")
@@ -2990,7 +3076,7 @@ impl Draft {
                         StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, ce draft est terminé.")
                             } else {
                                 format!("Sorry, this settings draft is already completed.")
@@ -3005,7 +3091,7 @@ impl Draft {
                     Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
                     Action::Pick { setting, value } => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -3019,7 +3105,7 @@ impl Draft {
                                 let hard_settings_ok = self.settings.get("hard_settings_ok").map(|hard_settings_ok| &**hard_settings_ok).unwrap_or("no") == "ok";
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => if let French = kind.language() {
                                         MessageBuilder::default()
                                             .mention_team(transaction, Some(*guild_id), team).await?
@@ -3039,7 +3125,7 @@ impl Draft {
                             } else {
                                 //TODO check if this setting is disabled because it is hard
                                 Err(match msg_ctx {
-                                    MessageContext::None => String::default(),
+                                    MessageContext::None | MessageContext::Web => String::default(),
                                     MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                         .push("Sorry, bans haven't been chosen yet, use ")
                                         .mention_command(command_ids.ban.unwrap(), "ban")
@@ -3050,7 +3136,7 @@ impl Draft {
                         } else {
                             let exists = all_settings.iter().any(|&fr::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -3089,7 +3175,7 @@ impl Draft {
                                 }
                                 self.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
                                 Ok(match msg_ctx {
-                                    MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                    MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                     MessageContext::Discord { transaction, guild_id, team, .. } => if let French = kind.language() {
                                         MessageBuilder::default()
                                             .mention_team(transaction, Some(*guild_id), team).await?
@@ -3109,7 +3195,7 @@ impl Draft {
                             } else {
                                 Err(match msg_ctx {
                                     MessageContext::None => String::default(),
-                                    MessageContext::Discord { .. } => {
+                                    MessageContext::Discord { .. } | MessageContext::Web => {
                                         let mut content = MessageBuilder::default();
                                         content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
                                         for (i, value) in setting.options.into_iter().enumerate() {
@@ -3129,7 +3215,7 @@ impl Draft {
                         } else {
                             let exists = all_settings.iter().any(|&fr::Setting { name, .. }| setting == name);
                             Err(match msg_ctx {
-                                MessageContext::None => String::default(),
+                                MessageContext::None | MessageContext::Web => String::default(),
                                 MessageContext::Discord { command_ids, .. } => {
                                     let mut content = MessageBuilder::default();
                                     if exists {
@@ -3159,7 +3245,7 @@ impl Draft {
                             })
                         },
                         StepKind::BooleanChoice { .. } => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => if let French = kind.language() {
                                 MessageBuilder::default()
                                     .push("Désolé, avant que le draft ne puisse continuer, vous devez d'abord choisir si les donjons seront mixés ou non avec le reste. Utilisez ")
@@ -3186,7 +3272,7 @@ impl Draft {
                         StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, ce draft est terminé.")
                             } else {
                                 format!("Sorry, this settings draft is already completed.")
@@ -3200,7 +3286,7 @@ impl Draft {
                     },
                     Action::Skip => match self.next_step(kind, game, &mut MessageContext::None).await?.kind {
                         StepKind::GoFirst => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
                                 .push("Sorry, first pick hasn't been chosen yet, use ")
                                 .mention_command(command_ids.first.unwrap(), "first")
@@ -3216,7 +3302,7 @@ impl Draft {
                             };
                             self.skipped_bans += 1;
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
                                     .mention_team(&mut *transaction, Some(*guild_id), team).await?
                                     .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
@@ -3229,11 +3315,11 @@ impl Draft {
                         }
                         StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => format!("Sorry, this part of the draft can't be skipped."),
+                            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
                             MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
                         }),
                         StepKind::BooleanChoice { .. } => Err(match msg_ctx {
-                            MessageContext::None => String::default(),
+                            MessageContext::None | MessageContext::Web => String::default(),
                             MessageContext::Discord { command_ids, .. } => if let French = kind.language() {
                                 MessageBuilder::default()
                                     .push("Désolé, avant que le draft ne puisse continuer, vous devez d'abord choisir si les donjons seront mixés ou non avec le reste. Utilisez ")
@@ -3260,7 +3346,7 @@ impl Draft {
                         StepKind::DoneRsl { .. } => unreachable!(),
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, ce draft est terminé.")
                             } else {
                                 format!("Sorry, this settings draft is already completed.")
@@ -3276,7 +3362,7 @@ impl Draft {
                         StepKind::BooleanChoice { .. } => {
                             self.settings.insert(Cow::Borrowed("mixed-dungeons"), Cow::Borrowed(if value { "mixed" } else { "separate" }));
                             Ok(match msg_ctx {
-                                MessageContext::None | MessageContext::RaceTime { .. } => String::default(),
+                                MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
                                 MessageContext::Discord { transaction, guild_id, team, .. } => if let French = kind.language() {
                                     MessageBuilder::default()
                                         .mention_team(&mut *transaction, Some(*guild_id), team).await?
@@ -3300,7 +3386,7 @@ impl Draft {
                         }
                         StepKind::Done(_) => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, ce draft est terminé.")
                             } else {
                                 format!("Sorry, this settings draft is already completed.")
@@ -3313,7 +3399,7 @@ impl Draft {
                         }),
                         _ => Err(match msg_ctx {
                             MessageContext::None => String::default(),
-                            MessageContext::Discord { .. } => if let French = kind.language() {
+                            MessageContext::Discord { .. } | MessageContext::Web => if let French = kind.language() {
                                 format!("Désolé, vous n'avez pas à répondre oui ou non.")
                             } else {
                                 format!("Sorry, the current step is not a yes/no question.")
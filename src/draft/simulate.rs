@@ -0,0 +1,275 @@
+//! Offline simulation of settings drafts with random choices, for checking draft formats without a database, Discord, or racetime.gg.
+
+use {
+    std::panic::AssertUnwindSafe,
+    crate::{
+        draft::{
+            Action,
+            Kind,
+            MessageContext,
+            PoolSetting,
+            StepKind,
+            spec::{
+                Spec,
+                SpecError,
+            },
+        },
+        prelude::*,
+    },
+};
+
+/// Upper bound on the number of actions in a simulated draft. All current draft formats finish in far fewer steps.
+const MAX_STEPS: usize = 1000;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SimulationError {
+    #[error(transparent)] Draft(#[from] draft::Error),
+    #[error(transparent)] Spec(#[from] SpecError),
+    #[error(transparent)] Wheel(#[from] wheel::Error),
+    #[error("draft did not finish within {MAX_STEPS} steps")]
+    Unterminated,
+    #[error("draft step offered no settings or values to choose from")]
+    NoChoices,
+    #[error("draft rejected the offered action {action}: {msg}")]
+    Rejected {
+        action: Action,
+        msg: String,
+    },
+    #[error("draft set {setting} to {value:?} which is not one of its options")]
+    InvalidValue {
+        setting: String,
+        value: String,
+    },
+    #[error("draft finished with empty settings")]
+    EmptySettings,
+    #[error("draft finished with settings missing the base setting {0:?}")]
+    MissingBaseSetting(String),
+    #[error("draft finished with {0} worlds")]
+    WorldCount(u8),
+}
+
+pub(crate) enum Outcome {
+    Settings(seed::Settings),
+    Rsl {
+        preset: rsl::VersionedPreset,
+        world_count: u8,
+    },
+}
+
+pub(crate) struct Simulation {
+    pub(crate) actions: Vec<Action>,
+    pub(crate) draft: Draft,
+    pub(crate) outcome: Outcome,
+}
+
+/// The state of a draft before any actions, with random answers to the questions asked during signup, see [`Draft::for_next_game`].
+fn initial_state(kind: Kind, rng: &mut impl Rng) -> Draft {
+    Draft {
+        high_seed: Id::dummy(),
+        went_first: None,
+        skipped_bans: 0,
+        settings: match kind {
+            Kind::S7 | Kind::MultiworldS3 | Kind::MultiworldS5 | Kind::Custom(_) => HashMap::default(),
+            Kind::MultiworldS4 => HashMap::from_iter(rng.random_bool(0.1).then_some((Cow::Borrowed("special_csmc"), Cow::Borrowed("yes")))),
+            Kind::RslS7 => collect![as HashMap<_, _>:
+                Cow::Borrowed("lite_ok") => Cow::Borrowed(if rng.random() { "ok" } else { "no" }),
+            ],
+            Kind::TournoiFrancoS3 | Kind::TournoiFrancoS4 | Kind::TournoiFrancoS5 => collect![as HashMap<_, _>:
+                Cow::Borrowed("hard_settings_ok") => Cow::Borrowed(if rng.random() { "ok" } else { "no" }),
+                Cow::Borrowed("mq_ok") => Cow::Borrowed(if rng.random() { "ok" } else { "no" }),
+            ],
+        },
+    }
+}
+
+/// Checks that every drafted setting in the pool has one of its known values.
+fn validate_picks(kind: Kind, pool: &[PoolSetting], draft: &Draft) -> Result<(), SimulationError> {
+    for setting in pool {
+        // settings without options, like RSL's “force off” settings, can only be banned or blocked
+        if setting.options.is_empty() { continue }
+        let Some(value) = draft.settings.get(&*setting.name) else { continue };
+        let is_valid = |value: &str| value == setting.default || setting.options.iter().any(|(name, _)| name == value);
+        let valid = if let Kind::RslS7 = kind {
+            // RSL stores multiple banned weights of the same setting as a comma-separated list
+            value.split(',').all(is_valid)
+        } else {
+            is_valid(value)
+        };
+        if !valid {
+            return Err(SimulationError::InvalidValue { setting: setting.name.to_string(), value: value.to_string() })
+        }
+    }
+    Ok(())
+}
+
+/// Runs a draft of the given format to completion, making a uniformly random choice among the offered actions at every step.
+pub(crate) async fn simulate(kind: Kind, rng: &mut impl Rng) -> Result<Simulation, SimulationError> {
+    let pool = kind.settings_pool();
+    let mut draft = initial_state(kind, rng);
+    let mut actions = Vec::default();
+    let outcome = loop {
+        if actions.len() >= MAX_STEPS { return Err(SimulationError::Unterminated) }
+        let action = match draft.next_step(kind, None, &mut MessageContext::None).await?.kind {
+            StepKind::GoFirst => Action::GoFirst(rng.random()),
+            StepKind::Ban { available_settings, skippable, .. } => {
+                let mut settings = available_settings.all().map(Some).collect_vec();
+                if skippable {
+                    settings.push(None);
+                }
+                match settings.into_iter().choose(rng).ok_or(SimulationError::NoChoices)? {
+                    Some(setting) => Action::Ban { setting: setting.name.to_owned() },
+                    None => Action::Skip,
+                }
+            }
+            StepKind::Pick { available_choices, skippable, .. } => {
+                let mut settings = available_choices.all().map(Some).collect_vec();
+                if skippable {
+                    settings.push(None);
+                }
+                match settings.into_iter().choose(rng).ok_or(SimulationError::NoChoices)? {
+                    Some(setting) => Action::Pick {
+                        value: setting.options.choose(rng).ok_or(SimulationError::NoChoices)?.name.to_owned(),
+                        setting: setting.name.to_owned(),
+                    },
+                    None => Action::Skip,
+                }
+            }
+            StepKind::BooleanChoice { .. } => Action::BooleanChoice(rng.random()),
+            StepKind::Done(settings) => break Outcome::Settings(settings),
+            StepKind::DoneRsl { preset, world_count } => break Outcome::Rsl { preset, world_count },
+        };
+        if let Err(msg) = draft.apply(kind, None, &mut MessageContext::None, action.clone()).await? {
+            return Err(SimulationError::Rejected { action, msg })
+        }
+        validate_picks(kind, &pool, &draft)?;
+        actions.push(action);
+    };
+    match outcome {
+        Outcome::Settings(ref settings) => {
+            if settings.is_empty() { return Err(SimulationError::EmptySettings) }
+            if let Kind::Custom(spec) = kind {
+                if let Some(missing) = spec.base.keys().find(|setting| !settings.contains_key(*setting)) {
+                    return Err(SimulationError::MissingBaseSetting(missing.clone()))
+                }
+            }
+        }
+        Outcome::Rsl { world_count, .. } => if world_count == 0 { return Err(SimulationError::WorldCount(world_count)) },
+    }
+    Ok(Simulation { actions, draft, outcome })
+}
+
+/// Parses a draft format for the `simulate-draft` subcommand: either the short name of a hardcoded format or the path to a [`Spec`] JSON file.
+async fn parse_format(format: &str) -> Result<Kind, SimulationError> {
+    Ok(match format {
+        "s7" => Kind::S7,
+        "mw3" => Kind::MultiworldS3,
+        "mw4" => Kind::MultiworldS4,
+        "mw5" => Kind::MultiworldS5,
+        "rsl7" => Kind::RslS7,
+        "fr3" => Kind::TournoiFrancoS3,
+        "fr4" => Kind::TournoiFrancoS4,
+        "fr5" => Kind::TournoiFrancoS5,
+        path => Kind::Custom(fs::read_json::<Spec>(path).await?.leak()?),
+    })
+}
+
+/// Entry point for the `simulate-draft` subcommand. Returns whether all simulated drafts succeeded.
+pub(crate) async fn cli(format: &str, runs: u32, seed: Option<u64>, verbose: bool) -> Result<bool, SimulationError> {
+    let kind = parse_format(format).await?;
+    let base_seed = seed.unwrap_or_else(|| rng().random());
+    let mut failures = 0;
+    for run in 0..runs {
+        let seed = base_seed.wrapping_add(run.into());
+        let mut rng = StdRng::seed_from_u64(seed);
+        match AssertUnwindSafe(simulate(kind, &mut rng)).catch_unwind().await {
+            Ok(Ok(Simulation { actions, draft, outcome })) => if verbose {
+                println!("seed {seed}: finished after {} actions", actions.len());
+                for action in &actions {
+                    println!("  {action}");
+                }
+                for (setting, value) in draft.settings.iter().sorted() {
+                    println!("  {setting}: {value}");
+                }
+                match outcome {
+                    Outcome::Settings(settings) => println!("  settings: {}", serde_json::Value::Object(settings)),
+                    Outcome::Rsl { preset, world_count } => println!("  RSL preset: {} ({world_count} worlds)", preset.name_or_weights().left_or("custom weights")),
+                }
+            },
+            Ok(Err(e)) => {
+                failures += 1;
+                println!("seed {seed}: {e}");
+            }
+            Err(_) => {
+                failures += 1;
+                println!("seed {seed}: panicked");
+            }
+        }
+    }
+    println!("{} of {runs} simulated drafts succeeded (base seed {base_seed})", runs - failures);
+    Ok(failures == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(kind: Kind) {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            if let Err(e) = runtime.block_on(simulate(kind, &mut rng)) {
+                panic!("simulated draft with seed {seed} failed: {e}");
+            }
+        }
+    }
+
+    #[test] fn s7() { check(Kind::S7) }
+    #[test] fn multiworld_s3() { check(Kind::MultiworldS3) }
+    #[test] fn multiworld_s4() { check(Kind::MultiworldS4) }
+    #[test] fn multiworld_s5() { check(Kind::MultiworldS5) }
+    #[test] #[ignore = "requires a local checkout of the RSL script"] fn rsl_s7() { check(Kind::RslS7) }
+    #[test] fn tournoi_franco_s3() { check(Kind::TournoiFrancoS3) }
+    #[test] fn tournoi_franco_s4() { check(Kind::TournoiFrancoS4) }
+    #[test] fn tournoi_franco_s5() { check(Kind::TournoiFrancoS5) }
+
+    #[test]
+    fn custom() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "settings": [
+                {
+                    "name": "Major Settings",
+                    "settings": [
+                        {"name": "keysanity", "display": "keysanity", "default": "off", "defaultDisplay": "own dungeon keys", "description": "keysanity: off or on", "options": [{"name": "on", "display": "keysanity"}]},
+                        {"name": "bridge", "display": "bridge", "default": "meds", "defaultDisplay": "medallions bridge", "description": "bridge: meds or stones", "options": [{"name": "stones", "display": "stones bridge"}, {"name": "open", "display": "open bridge"}]},
+                        {"name": "ganon_bk", "display": "Ganon boss key", "default": "remove", "defaultDisplay": "boss key removed", "description": "ganon_bk: remove or tokens", "options": [{"name": "tokens", "display": "boss key on tokens"}]},
+                    ],
+                },
+                {
+                    "name": "Minor Settings",
+                    "settings": [
+                        {"name": "deku", "display": "Kokiri Forest", "default": "closed", "defaultDisplay": "closed forest", "description": "deku: closed or open", "options": [{"name": "open", "display": "open forest"}]},
+                        {"name": "cows", "display": "cowsanity", "default": "off", "defaultDisplay": "no cowsanity", "description": "cows: off or on", "options": [{"name": "on", "display": "cowsanity"}]},
+                        {"name": "scrubs", "display": "scrubsanity", "default": "off", "defaultDisplay": "no scrubsanity", "description": "scrubs: off or affordable", "options": [{"name": "affordable", "display": "affordable scrubsanity"}]},
+                    ],
+                },
+            ],
+            "steps": [
+                {"type": "ban", "team": "highSeed"},
+                {"type": "ban", "team": "lowSeed", "skippable": true},
+                {"type": "pick", "team": "first", "pages": ["Major Settings"]},
+                {"type": "pick", "team": "second", "includeDefault": true},
+                {"type": "pick", "team": "second", "skippable": true},
+                {"type": "pick", "team": "first"},
+            ],
+            "conflicts": [["bridge", "ganon_bk"]],
+            "base": {"world_count": 1},
+            "resolve": {
+                "bridge": {
+                    "stones": {"bridge": "stones", "bridge_stones": 3},
+                    "open": {"bridge": "open"},
+                },
+            },
+        })).unwrap().leak().unwrap();
+        check(Kind::Custom(spec));
+    }
+}
@@ -0,0 +1,633 @@
+//! Declarative draft formats, stored as JSON in `events.draft_spec` and executed by a generic engine instead of a hardcoded [`super::Kind`].
+
+use {
+    std::sync::Mutex as SyncMutex,
+    crate::{
+        draft::{
+            Action,
+            BanSetting,
+            BanSettings,
+            DraftSetting,
+            DraftSettingChoice,
+            DraftSettings,
+            Error,
+            MessageContext,
+            Picks,
+            Step,
+            StepKind,
+            Team,
+        },
+        prelude::*,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SpecError {
+    #[error("draft spec has no steps")]
+    NoSteps,
+    #[error("draft spec has no settings")]
+    NoSettings,
+    #[error("draft spec lists the setting {0:?} more than once")]
+    DuplicateSetting(String),
+    #[error("draft spec lists the option {option:?} more than once for the setting {setting:?}")]
+    DuplicateOption {
+        setting: String,
+        option: String,
+    },
+    #[error("draft spec refers to the setting {0:?} which is not in the settings pool")]
+    UnknownSetting(String),
+    #[error("draft spec refers to the option {option:?} which is not available for the setting {setting:?}")]
+    UnknownOption {
+        setting: String,
+        option: String,
+    },
+    #[error("draft spec refers to the settings page {0:?} which does not exist")]
+    UnknownPage(String),
+}
+
+/// How the high seed of game 1 of a match is determined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Seeding {
+    /// The team with the better `teams.qualifier_rank` is the high seed.
+    QualifierRank,
+    /// The team with the better average player rating across Mido's House events is the high seed, with ties broken by coin flip.
+    Rating,
+    #[default]
+    CoinFlip,
+}
+
+/// Which team acts in a given step, relative to the seeding or to the choice made in the GoFirst step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SpecTeam {
+    HighSeed,
+    LowSeed,
+    /// The team that chose to go first, or was made to go first by the other team.
+    First,
+    Second,
+}
+
+impl SpecTeam {
+    fn resolve(&self, went_first: bool) -> Team {
+        match (self, went_first) {
+            (Self::HighSeed, _) | (Self::First, true) | (Self::Second, false) => Team::HighSeed,
+            (Self::LowSeed, _) | (Self::First, false) | (Self::Second, true) => Team::LowSeed,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpecChoice {
+    pub(crate) name: String,
+    pub(crate) display: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpecSetting {
+    pub(crate) name: String,
+    pub(crate) display: String,
+    pub(crate) default: String,
+    pub(crate) default_display: String,
+    pub(crate) description: String,
+    /// The non-default values this setting can be picked to.
+    pub(crate) options: Vec<SpecChoice>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpecPage {
+    pub(crate) name: String,
+    pub(crate) settings: Vec<SpecSetting>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum SpecStep {
+    /// The given team locks one of the available settings to its default value.
+    #[serde(rename_all = "camelCase")]
+    Ban {
+        team: SpecTeam,
+        #[serde(default)]
+        skippable: bool,
+        /// Restricts this step to the settings on the given pages. All pages are available if omitted.
+        #[serde(default)]
+        pages: Option<Vec<String>>,
+    },
+    /// The given team sets one of the available settings to a value of their choice.
+    #[serde(rename_all = "camelCase")]
+    Pick {
+        team: SpecTeam,
+        #[serde(default)]
+        skippable: bool,
+        /// Whether the setting's default value is offered alongside its other options.
+        #[serde(default)]
+        include_default: bool,
+        /// Restricts this step to the settings on the given pages. All pages are available if omitted.
+        #[serde(default)]
+        pages: Option<Vec<String>>,
+    },
+}
+
+/// A draft format. The high seed always starts by choosing whether to go first or second, followed by `steps` in order.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Spec {
+    #[serde(default)]
+    pub(crate) seeding: Seeding,
+    /// The settings pool, grouped into named pages in case they exceed the button limit for Discord message components.
+    pub(crate) settings: Vec<SpecPage>,
+    pub(crate) steps: Vec<SpecStep>,
+    /// Within each group, at most one setting may be picked to a non-default value. A setting that conflicts with an earlier pick is no longer available.
+    #[serde(default)]
+    pub(crate) conflicts: Vec<Vec<String>>,
+    /// Randomizer settings used regardless of the draft.
+    pub(crate) base: seed::Settings,
+    /// Randomizer settings applied on top of `base` for each draftable setting, keyed by setting name and then by its value (including the default).
+    #[serde(default)]
+    pub(crate) resolve: HashMap<String, HashMap<String, seed::Settings>>,
+}
+
+impl Spec {
+    /// Validates the given event's spec and returns a reference to a cached copy which can be used in the `Copy` type [`super::Kind`].
+    ///
+    /// The cache has one entry per event, so loading an event whose spec hasn't changed reuses the existing copy.
+    /// An edited spec replaces the event's entry, but the previous copy stays allocated since a [`super::Kind`] may still refer to it.
+    pub(crate) fn intern(self, series: Series, event: &str) -> Result<&'static Self, SpecError> {
+        static SPECS: LazyLock<SyncMutex<HashMap<(Series, String), &'static Spec>>> = LazyLock::new(SyncMutex::default);
+
+        self.validate()?;
+        let mut specs = SPECS.lock().expect("draft spec cache poisoned");
+        let key = (series, event.to_owned());
+        if let Some(&spec) = specs.get(&key) {
+            if *spec == self { return Ok(spec) }
+        }
+        let spec = &*Box::leak(Box::new(self));
+        specs.insert(key, spec);
+        Ok(spec)
+    }
+
+    /// Validates a spec which doesn't belong to an event, e.g. one loaded from a file by the draft simulator, and leaks it for use in [`super::Kind`].
+    pub(crate) fn leak(self) -> Result<&'static Self, SpecError> {
+        self.validate()?;
+        Ok(Box::leak(Box::new(self)))
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SpecError> {
+        if self.steps.is_empty() { return Err(SpecError::NoSteps) }
+        if self.all_settings().next().is_none() { return Err(SpecError::NoSettings) }
+        let mut names = HashSet::new();
+        for setting in self.all_settings() {
+            if !names.insert(&*setting.name) { return Err(SpecError::DuplicateSetting(setting.name.clone())) }
+            let mut options = HashSet::new();
+            for option in iter::once(&*setting.default).chain(setting.options.iter().map(|option| &*option.name)) {
+                if !options.insert(option) {
+                    return Err(SpecError::DuplicateOption { setting: setting.name.clone(), option: option.to_owned() })
+                }
+            }
+        }
+        for step in &self.steps {
+            let (SpecStep::Ban { pages, .. } | SpecStep::Pick { pages, .. }) = step;
+            for page in pages.iter().flatten() {
+                if !self.settings.iter().any(|iter_page| iter_page.name == *page) { return Err(SpecError::UnknownPage(page.clone())) }
+            }
+        }
+        for name in self.conflicts.iter().flatten() {
+            if self.setting(name).is_none() { return Err(SpecError::UnknownSetting(name.clone())) }
+        }
+        for (name, values) in &self.resolve {
+            let Some(setting) = self.setting(name) else { return Err(SpecError::UnknownSetting(name.clone())) };
+            for value in values.keys() {
+                if *value != setting.default && !setting.options.iter().any(|option| option.name == *value) {
+                    return Err(SpecError::UnknownOption { setting: name.clone(), option: value.clone() })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn all_settings(&self) -> impl Iterator<Item = &SpecSetting> {
+        self.settings.iter().flat_map(|page| &page.settings)
+    }
+
+    fn setting(&self, name: &str) -> Option<&SpecSetting> {
+        self.all_settings().find(|setting| setting.name == name)
+    }
+
+    fn pages(&self, pages: Option<&[String]>) -> impl Iterator<Item = &SpecPage> {
+        self.settings.iter().filter(move |page| pages.is_none_or(|pages| pages.contains(&page.name)))
+    }
+
+    /// Whether picking this setting would violate one of the conflict rules.
+    fn is_blocked(&self, picks: &Picks, setting: &SpecSetting) -> bool {
+        self.conflicts.iter()
+            .filter(|group| group.contains(&setting.name))
+            .flatten()
+            .filter(|&other| *other != setting.name)
+            .any(|other| picks.get(&**other).is_some_and(|value| self.setting(other).is_some_and(|other| **value != other.default)))
+    }
+
+    pub(crate) fn pick_count(&self, draft: &Draft) -> u8 {
+        draft.skipped_bans + u8::try_from(self.all_settings().filter(|setting| draft.settings.contains_key(&*setting.name)).count()).unwrap()
+    }
+
+    pub(crate) fn resolve_settings(&self, picks: &Picks) -> seed::Settings {
+        let mut settings = self.base.clone();
+        for setting in self.all_settings() {
+            let value = picks.get(&*setting.name).map(|value| &**value).unwrap_or(&*setting.default);
+            if let Some(patch) = self.resolve.get(&setting.name).and_then(|values| values.get(value)) {
+                settings.extend(patch.clone());
+            }
+        }
+        settings
+    }
+
+    pub(crate) fn display_picks(&self, picks: &Picks) -> String {
+        English.join_str_opt(
+            self.all_settings()
+                .filter_map(|setting| picks.get(&*setting.name).and_then(|pick| setting.options.iter().find(|option| **pick == option.name)).map(|option| &option.display)),
+        ).unwrap_or_else(|| format!("base settings"))
+    }
+
+    pub(crate) async fn next_step(&'static self, draft: &Draft, game: Option<i16>, msg_ctx: &mut MessageContext<'_>) -> Result<Step, Error> {
+        let Some(went_first) = draft.went_first else {
+            return Ok(Step {
+                kind: StepKind::GoFirst,
+                message: match msg_ctx {
+                    MessageContext::None | MessageContext::Web => String::default(),
+                    MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
+                        let (mut high_seed, _) = teams.iter().partition::<Vec<_>, _>(|team| team.id == draft.high_seed);
+                        let high_seed = high_seed.remove(0);
+                        let mut builder = MessageBuilder::default();
+                        builder.mention_team(transaction, Some(*guild_id), high_seed).await?;
+                        if game.is_some_and(|game| game > 1) {
+                            builder.push(if high_seed.name_is_plural() { ": as the losers" } else { ": as the loser" });
+                            builder.push(" of the previous race, please choose whether you want to go ");
+                        } else {
+                            builder.push(": you have the higher seed. Choose whether you want to go ");
+                        }
+                        builder.mention_command(command_ids.first.unwrap(), "first");
+                        builder.push(" or ");
+                        builder.mention_command(command_ids.second.unwrap(), "second");
+                        if let Some(game) = game {
+                            builder.push(" in the settings draft for game ");
+                            builder.push(game.to_string());
+                            builder.push('.');
+                        } else {
+                            builder.push(" in the settings draft.");
+                        }
+                        builder.build()
+                    }
+                    MessageContext::RaceTime { high_seed_name, .. } => format!("{high_seed_name}, you have the higher seed. Choose whether you want to go !first or !second"),
+                },
+            })
+        };
+        let n = self.pick_count(draft);
+        Ok(match self.steps.get(usize::from(n)) {
+            Some(SpecStep::Ban { team, skippable, pages }) => {
+                let team = team.resolve(went_first);
+                let available_settings = BanSettings(self.pages(pages.as_deref())
+                    .map(|page| (&*page.name, page.settings.iter()
+                        .filter(|setting| !draft.settings.contains_key(&*setting.name))
+                        .map(|setting| BanSetting {
+                            name: &setting.name,
+                            display: &setting.display,
+                            default: &setting.default,
+                            default_display: &setting.default_display,
+                            description: Cow::Borrowed(&*setting.description),
+                        })
+                        .collect_vec()))
+                    .filter(|(_, settings)| !settings.is_empty())
+                    .collect());
+                // a step with nothing left to ban can always be skipped so the draft can't get stuck
+                let skippable = *skippable || available_settings.num_settings() == 0;
+                Step {
+                    message: match msg_ctx {
+                        MessageContext::None | MessageContext::Web => String::default(),
+                        MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
+                            let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == draft.high_seed);
+                            let high_seed = high_seed.remove(0);
+                            let low_seed = low_seed.remove(0);
+                            let mut builder = MessageBuilder::default();
+                            builder.mention_team(transaction, Some(*guild_id), team.choose(high_seed, low_seed)).await?;
+                            builder.push(": lock a setting to its default using ");
+                            builder.mention_command(command_ids.ban.unwrap(), "ban");
+                            if skippable {
+                                builder.push(", or use ");
+                                builder.mention_command(command_ids.skip.unwrap(), "skip");
+                                builder.push(" if you don't want to ban anything.");
+                            } else {
+                                builder.push('.');
+                            }
+                            builder.build()
+                        }
+                        MessageContext::RaceTime { high_seed_name, low_seed_name, .. } => format!(
+                            "{}, lock a setting to its default using “!ban <setting>”{}{}",
+                            team.choose(high_seed_name, low_seed_name),
+                            if skippable { ", or use “!skip” if you don't want to ban anything." } else { "." },
+                            if n == 0 { " Use “!settings” for a list of available settings." } else { "" },
+                        ),
+                    },
+                    kind: StepKind::Ban {
+                        rsl: false,
+                        available_settings, skippable, team,
+                    },
+                }
+            }
+            Some(SpecStep::Pick { team, skippable, include_default, pages }) => {
+                let team = team.resolve(went_first);
+                let available_choices = DraftSettings(self.pages(pages.as_deref())
+                    .map(|page| (&*page.name, page.settings.iter()
+                        .filter(|setting| !draft.settings.contains_key(&*setting.name) && !self.is_blocked(&draft.settings, setting))
+                        .map(|setting| DraftSetting {
+                            name: &setting.name,
+                            display: &setting.display,
+                            options: include_default.then(|| DraftSettingChoice { name: &setting.default, display: Cow::Borrowed(&*setting.default_display) }).into_iter()
+                                .chain(setting.options.iter().map(|option| DraftSettingChoice { name: &option.name, display: Cow::Borrowed(&*option.display) }))
+                                .collect(),
+                            description: Cow::Borrowed(&*setting.description),
+                        })
+                        .collect_vec()))
+                    .filter(|(_, settings)| !settings.is_empty())
+                    .collect());
+                // a step with nothing left to pick can always be skipped so the draft can't get stuck
+                let skippable = *skippable || available_choices.num_settings() == 0;
+                Step {
+                    message: match msg_ctx {
+                        MessageContext::None | MessageContext::Web => String::default(),
+                        MessageContext::Discord { transaction, guild_id, command_ids, teams, .. } => {
+                            let (mut high_seed, mut low_seed) = teams.iter().partition::<Vec<_>, _>(|team| team.id == draft.high_seed);
+                            let high_seed = high_seed.remove(0);
+                            let low_seed = low_seed.remove(0);
+                            let mut builder = MessageBuilder::default();
+                            builder.mention_team(transaction, Some(*guild_id), team.choose(high_seed, low_seed)).await?;
+                            builder.push(": pick a setting using ");
+                            builder.mention_command(command_ids.pick.unwrap(), "pick");
+                            if skippable {
+                                builder.push(". You can also use ");
+                                builder.mention_command(command_ids.skip.unwrap(), "skip");
+                                builder.push(" if you want to leave the settings as they are.");
+                            } else {
+                                builder.push('.');
+                            }
+                            builder.build()
+                        }
+                        MessageContext::RaceTime { high_seed_name, low_seed_name, .. } => format!(
+                            "{}, pick a setting using “!pick <setting> <value>”{}",
+                            team.choose(high_seed_name, low_seed_name),
+                            if skippable { ". You can also use “!skip” if you want to leave the settings as they are." } else { "" },
+                        ),
+                    },
+                    kind: StepKind::Pick {
+                        rsl: false,
+                        available_choices, skippable, team,
+                    },
+                }
+            }
+            None => Step {
+                kind: StepKind::Done(self.resolve_settings(&draft.settings)),
+                message: match msg_ctx {
+                    MessageContext::None => String::default(),
+                    MessageContext::Discord { .. } | MessageContext::Web => format!("Settings draft completed. You will be playing with {}.", self.display_picks(&draft.settings)),
+                    MessageContext::RaceTime { .. } => self.display_picks(&draft.settings),
+                },
+            },
+        })
+    }
+
+    pub(crate) async fn apply(&'static self, draft: &mut Draft, game: Option<i16>, msg_ctx: &mut MessageContext<'_>, action: Action) -> Result<Result<String, String>, Error> {
+        let resolved_action = match action {
+            Action::Ban { setting } => if let Some(setting) = self.setting(&setting) {
+                Action::Pick { setting: setting.name.clone(), value: setting.default.clone() }
+            } else {
+                return Ok(Err(match msg_ctx {
+                    MessageContext::None => String::default(),
+                    MessageContext::Discord { .. } | MessageContext::Web => {
+                        let mut content = MessageBuilder::default();
+                        content.push("Sorry, I don't recognize that setting. Use one of the following: ");
+                        for (i, setting) in self.all_settings().enumerate() {
+                            if i > 0 {
+                                content.push(" or ");
+                            }
+                            content.push_mono(&setting.name);
+                        }
+                        content.build()
+                    }
+                    MessageContext::RaceTime { reply_to, .. } => format!(
+                        "Sorry {reply_to}, I don't recognize that setting. Use one of the following: {}",
+                        self.all_settings().map(|setting| &setting.name).format(" or "),
+                    ),
+                }))
+            },
+            Action::BooleanChoice(value) if matches!(self.next_step(draft, game, &mut MessageContext::None).await?.kind, StepKind::GoFirst) => Action::GoFirst(value),
+            _ => action,
+        };
+        let already_completed = match msg_ctx {
+            MessageContext::None => String::default(),
+            MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this settings draft is already completed."),
+            MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this settings draft is already completed."),
+        };
+        Ok(match resolved_action {
+            Action::GoFirst(first) => match self.next_step(draft, game, &mut MessageContext::None).await?.kind {
+                StepKind::GoFirst => {
+                    draft.went_first = Some(first);
+                    Ok(match msg_ctx {
+                        MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
+                        MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
+                            .mention_team(transaction, Some(*guild_id), team).await?
+                            .push(if team.name_is_plural() { " have" } else { " has" })
+                            .push(" chosen to go ")
+                            .push(if first { "first" } else { "second" })
+                            .push(" in the settings draft.")
+                            .build(),
+                    })
+                }
+                StepKind::Ban { .. } | StepKind::Pick { .. } => Err(match msg_ctx {
+                    MessageContext::None => String::default(),
+                    MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, first pick has already been chosen."),
+                    MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick has already been chosen."),
+                }),
+                StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!("not produced by draft specs"),
+                StepKind::Done(_) => Err(already_completed),
+            },
+            Action::Ban { .. } => unreachable!("normalized to Action::Pick above"),
+            Action::Pick { setting, value } => match self.next_step(draft, game, &mut MessageContext::None).await?.kind {
+                StepKind::GoFirst => Err(match msg_ctx {
+                    MessageContext::None | MessageContext::Web => String::default(),
+                    MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
+                        .push("Sorry, first pick hasn't been chosen yet, use ")
+                        .mention_command(command_ids.first.unwrap(), "first")
+                        .push(" or ")
+                        .mention_command(command_ids.second.unwrap(), "second")
+                        .build(),
+                    MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick hasn't been chosen yet, use “!first” or “!second”"),
+                }),
+                StepKind::Ban { available_settings, skippable, .. } => if let Some(setting) = available_settings.get(&setting) {
+                    if value == setting.default {
+                        draft.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(setting.default));
+                        Ok(match msg_ctx {
+                            MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
+                            MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
+                                .mention_team(transaction, Some(*guild_id), team).await?
+                                .push(if team.name_is_plural() { " have locked in " } else { " has locked in " })
+                                .push(setting.default_display)
+                                .push('.')
+                                .build(),
+                        })
+                    } else {
+                        Err(match msg_ctx {
+                            MessageContext::None | MessageContext::Web => String::default(),
+                            MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
+                                .push("Sorry, bans haven't been chosen yet, use ")
+                                .mention_command(command_ids.ban.unwrap(), "ban")
+                                .build(),
+                            MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, bans haven't been chosen yet. Use “!ban <setting>”"),
+                        })
+                    }
+                } else {
+                    let exists = self.setting(&setting).is_some();
+                    Err(match msg_ctx {
+                        MessageContext::None | MessageContext::Web => String::default(),
+                        MessageContext::Discord { command_ids, .. } => {
+                            let mut content = MessageBuilder::default();
+                            if exists {
+                                content.push("Sorry, that setting is not available. Use one of the following: ");
+                            } else {
+                                content.push("Sorry, I don't recognize that setting. Use one of the following: ");
+                            }
+                            for (i, setting) in available_settings.all().enumerate() {
+                                if i > 0 {
+                                    content.push(" or ");
+                                }
+                                content.push_mono(setting.name);
+                            }
+                            if exists && skippable {
+                                content.push(". Use ");
+                                content.mention_command(command_ids.skip.unwrap(), "skip");
+                                content.push(" if you don't want to ban anything.");
+                            }
+                            content.build()
+                        }
+                        MessageContext::RaceTime { reply_to, .. } => format!(
+                            "Sorry {reply_to}, {}. Use one of the following: {}{}",
+                            if exists { "that setting is not available" } else { "I don't recognize that setting" },
+                            available_settings.all().map(|setting| setting.name).format(" or "),
+                            if exists && skippable { ". Use “!skip” if you don't want to ban anything." } else { "" },
+                        ),
+                    })
+                },
+                StepKind::Pick { available_choices, skippable, .. } => if let Some(setting) = available_choices.get(&setting) {
+                    if let Some(option) = setting.options.iter().find(|option| option.name == value) {
+                        draft.settings.insert(Cow::Borrowed(setting.name), Cow::Borrowed(option.name));
+                        Ok(match msg_ctx {
+                            MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
+                            MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
+                                .mention_team(transaction, Some(*guild_id), team).await?
+                                .push(if team.name_is_plural() { " have picked " } else { " has picked " })
+                                .push(&*option.display)
+                                .push('.')
+                                .build(),
+                        })
+                    } else {
+                        Err(match msg_ctx {
+                            MessageContext::None => String::default(),
+                            MessageContext::Discord { .. } | MessageContext::Web => {
+                                let mut content = MessageBuilder::default();
+                                content.push("Sorry, that's not a possible value for this setting. Use one of the following: ");
+                                for (i, value) in setting.options.into_iter().enumerate() {
+                                    if i > 0 {
+                                        content.push(" or ");
+                                    }
+                                    content.push_mono(value.name);
+                                }
+                                content.build()
+                            }
+                            MessageContext::RaceTime { reply_to, .. } => format!(
+                                "Sorry {reply_to}, that's not a possible value for this setting. Use one of the following: {}",
+                                setting.options.into_iter().map(|value| value.name).format(" or "),
+                            ),
+                        })
+                    }
+                } else {
+                    let exists = self.setting(&setting).is_some();
+                    Err(match msg_ctx {
+                        MessageContext::None | MessageContext::Web => String::default(),
+                        MessageContext::Discord { command_ids, .. } => {
+                            let mut content = MessageBuilder::default();
+                            if exists {
+                                content.push("Sorry, that setting is not available. Use one of the following: ");
+                            } else {
+                                content.push("Sorry, I don't recognize that setting. Use one of the following: ");
+                            }
+                            for (i, setting) in available_choices.all().enumerate() {
+                                if i > 0 {
+                                    content.push(" or ");
+                                }
+                                content.push_mono(setting.name);
+                            }
+                            if exists && skippable {
+                                content.push(". Use ");
+                                content.mention_command(command_ids.skip.unwrap(), "skip");
+                                content.push(" if you don't want to pick anything.");
+                            }
+                            content.build()
+                        }
+                        MessageContext::RaceTime { reply_to, .. } => format!(
+                            "Sorry {reply_to}, {}. Use one of the following: {}{}",
+                            if exists { "that setting is not available" } else { "I don't recognize that setting" },
+                            available_choices.all().map(|setting| setting.name).format(" or "),
+                            if exists && skippable { ". Use “!skip” if you don't want to pick anything." } else { "" },
+                        ),
+                    })
+                },
+                StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!("not produced by draft specs"),
+                StepKind::Done(_) => Err(already_completed),
+            },
+            Action::Skip => match self.next_step(draft, game, &mut MessageContext::None).await?.kind {
+                StepKind::GoFirst => Err(match msg_ctx {
+                    MessageContext::None | MessageContext::Web => String::default(),
+                    MessageContext::Discord { command_ids, .. } => MessageBuilder::default()
+                        .push("Sorry, first pick hasn't been chosen yet, use ")
+                        .mention_command(command_ids.first.unwrap(), "first")
+                        .push(" or ")
+                        .mention_command(command_ids.second.unwrap(), "second")
+                        .build(),
+                    MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, first pick hasn't been chosen yet, use “!first” or “!second”"),
+                }),
+                step_kind @ (StepKind::Ban { skippable: true, .. } | StepKind::Pick { skippable: true, .. }) => {
+                    let skip_kind = if let StepKind::Ban { .. } = step_kind { "ban" } else { "pick" };
+                    draft.skipped_bans += 1;
+                    Ok(match msg_ctx {
+                        MessageContext::None | MessageContext::RaceTime { .. } | MessageContext::Web => String::default(),
+                        MessageContext::Discord { transaction, guild_id, team, .. } => MessageBuilder::default()
+                            .mention_team(&mut *transaction, Some(*guild_id), team).await?
+                            .push(if team.name_is_plural() { " have skipped " } else { " has skipped " })
+                            .push(team.possessive_determiner(transaction).await?)
+                            .push(' ')
+                            .push(skip_kind)
+                            .push('.')
+                            .build(),
+                    })
+                }
+                StepKind::Ban { skippable: false, .. } | StepKind::Pick { skippable: false, .. } => Err(match msg_ctx {
+                    MessageContext::None => String::default(),
+                    MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, this part of the draft can't be skipped."),
+                    MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, this part of the draft can't be skipped."),
+                }),
+                StepKind::BooleanChoice { .. } | StepKind::DoneRsl { .. } => unreachable!("not produced by draft specs"),
+                StepKind::Done(_) => Err(already_completed),
+            },
+            Action::BooleanChoice(_) => match self.next_step(draft, game, &mut MessageContext::None).await?.kind {
+                StepKind::GoFirst => unreachable!("normalized to Action::GoFirst above"),
+                StepKind::Done(_) => Err(already_completed),
+                _ => Err(match msg_ctx {
+                    MessageContext::None => String::default(),
+                    MessageContext::Discord { .. } | MessageContext::Web => format!("Sorry, the current step is not a yes/no question."),
+                    MessageContext::RaceTime { reply_to, .. } => format!("Sorry {reply_to}, the current step is not a yes/no question."),
+                }),
+            },
+        })
+    }
+}
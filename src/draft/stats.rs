@@ -0,0 +1,241 @@
+//! Aggregate statistics about an event's settings drafts, to help organizers design the settings pool for future events.
+
+use {
+    crate::{
+        draft::{
+            Kind,
+            MessageContext,
+            StepKind,
+            history,
+        },
+        event::Tab,
+        prelude::*,
+    },
+};
+
+#[derive(Default, Clone, Copy)]
+struct Record {
+    wins: usize,
+    losses: usize,
+}
+
+impl Record {
+    fn add(&mut self, won: bool) {
+        if won { self.wins += 1 } else { self.losses += 1 }
+    }
+}
+
+impl ToHtml for Record {
+    fn to_html(&self) -> RawHtml<String> {
+        let total = self.wins + self.losses;
+        if total == 0 {
+            html! {
+                : "—";
+            }
+        } else {
+            html! {
+                : percent(self.wins, total);
+                : format!(" ({}–{})", self.wins, self.losses);
+            }
+        }
+    }
+}
+
+fn percent(count: usize, total: usize) -> String {
+    if total == 0 {
+        format!("—")
+    } else {
+        format!("{:.1}%", 100.0 * count as f64 / total as f64)
+    }
+}
+
+#[derive(Default)]
+struct SettingStats {
+    bans: usize,
+    picks: HashMap<String, usize>,
+    /// Results of the teams that banned this setting.
+    banned_by: Record,
+    /// Results of the teams that picked this setting.
+    picked_by: Record,
+}
+
+/// The team that won a 1v1 race, determined from its recorded results.
+async fn winner(transaction: &mut Transaction<'_, Postgres>, race: &Race) -> Result<Option<Id<Teams>>, event::Error> {
+    let Entrants::Two([Entrant::MidosHouseTeam(ref team1), Entrant::MidosHouseTeam(ref team2)]) = race.entrants else { return Ok(None) };
+    let mut placements = HashMap::new();
+    for result in cal::RaceResult::for_race(&mut *transaction, race.id).await? {
+        if let Entrant::MidosHouseTeam(team) = result.entrant {
+            placements.insert(team.id, result.placement);
+        }
+    }
+    let (Some(&placement1), Some(&placement2)) = (placements.get(&team1.id), placements.get(&team2.id)) else { return Ok(None) };
+    Ok(match (placement1, placement2) {
+        (Some(placement1), Some(placement2)) => match placement1.cmp(&placement2) {
+            Less => Some(team1.id),
+            Greater => Some(team2.id),
+            Equal => None,
+        },
+        (Some(_), None) => Some(team1.id),
+        (None, Some(_)) => Some(team2.id),
+        (None, None) => None,
+    })
+}
+
+/// Which team chose each setting of the race's draft, according to the recorded draft history.
+async fn choosers(transaction: &mut Transaction<'_, Postgres>, kind: Kind, race: &Race, draft: &Draft) -> Result<HashMap<String, Id<Teams>>, event::Error> {
+    let mut choosers = HashMap::default();
+    let Some(low_seed) = race.teams().find(|team| team.id != draft.high_seed).map(|team| team.id) else { return Ok(choosers) };
+    for entry in history::for_race(transaction, race.id).await? {
+        if entry.undone_at.is_some() { continue }
+        let Some(team) = entry.state_before.active_team(kind, race.game).await? else { continue };
+        let mut after = entry.state_before.clone();
+        if after.apply(kind, race.game, &mut MessageContext::None, entry.action).await?.is_err() { continue }
+        for (setting, value) in &after.settings {
+            if entry.state_before.settings.get(setting) != Some(value) {
+                choosers.insert(setting.to_string(), team.choose(draft.high_seed, low_seed));
+            }
+        }
+    }
+    Ok(choosers)
+}
+
+#[rocket::get("/event/<series>/<event>/drafts")]
+pub(crate) async fn get(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, series: Series, event: &str) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let kind = event.draft_kind().ok_or(StatusOrError::Status(Status::NotFound))?;
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Races, true).await?;
+    let pool = kind.settings_pool();
+    let mut completed = 0;
+    let mut went_first = 0;
+    let mut went_second = 0;
+    let mut first_record = Record::default();
+    let mut second_record = Record::default();
+    let mut stats = pool.iter().map(|setting| (setting.name.clone(), SettingStats::default())).collect::<HashMap<_, _>>();
+    for race in Race::for_event(&mut transaction, http_client, &event).await? {
+        if race.ignored { continue }
+        let Some(ref draft) = race.draft else { continue };
+        if !matches!(draft.next_step(kind, race.game, &mut MessageContext::None).await?.kind, StepKind::Done(_) | StepKind::DoneRsl { .. }) { continue }
+        completed += 1;
+        match draft.went_first {
+            Some(true) => went_first += 1,
+            Some(false) => went_second += 1,
+            None => {}
+        }
+        for setting in &pool {
+            let Some(value) = draft.settings.get(&*setting.name) else { continue };
+            let setting_stats = stats.get_mut(&setting.name).expect("initialized above");
+            if *value == setting.default {
+                setting_stats.bans += 1;
+            } else {
+                *setting_stats.picks.entry(value.to_string()).or_default() += 1;
+            }
+        }
+        let Some(winner) = winner(&mut transaction, &race).await? else { continue };
+        if let Some(went_first) = draft.went_first {
+            let high_seed_won = winner == draft.high_seed;
+            if went_first {
+                first_record.add(high_seed_won);
+            } else {
+                second_record.add(high_seed_won);
+            }
+        }
+        for (setting, chooser) in choosers(&mut transaction, kind, &race, draft).await? {
+            let Some(pool_setting) = pool.iter().find(|pool_setting| pool_setting.name == setting) else { continue };
+            let Some(value) = draft.settings.get(&*setting) else { continue };
+            let setting_stats = stats.get_mut(&pool_setting.name).expect("initialized above");
+            if *value == pool_setting.default {
+                setting_stats.banned_by.add(chooser == winner);
+            } else {
+                setting_stats.picked_by.add(chooser == winner);
+            }
+        }
+    }
+    let content = html! {
+        : header;
+        h2 : "Settings draft statistics";
+        @if completed == 0 {
+            p : "No settings drafts have been completed for this event yet.";
+        } else {
+            p {
+                : "Based on ";
+                : completed;
+                @if completed == 1 {
+                    : " completed settings draft.";
+                } else {
+                    : " completed settings drafts.";
+                }
+                : " Win rates only include 1v1 races with recorded results whose draft history was recorded on Mido's House.";
+            }
+            @if went_first + went_second > 0 {
+                h3 : "First pick";
+                table {
+                    thead {
+                        tr {
+                            th : "High seed's choice";
+                            th : "Count";
+                            th : "Rate";
+                            th : "High seed's win rate";
+                        }
+                    }
+                    tbody {
+                        tr {
+                            td : "Go first";
+                            td : went_first;
+                            td : percent(went_first, went_first + went_second);
+                            td : first_record;
+                        }
+                        tr {
+                            td : "Go second";
+                            td : went_second;
+                            td : percent(went_second, went_first + went_second);
+                            td : second_record;
+                        }
+                    }
+                }
+            }
+            h3 : "Settings";
+            table {
+                thead {
+                    tr {
+                        th : "Setting";
+                        th : "Ban rate";
+                        th : "Pick rate";
+                        th : "Picked values";
+                        th : "Win rate when banned";
+                        th : "Win rate when picked";
+                    }
+                }
+                tbody {
+                    @for setting in &pool {
+                        @let setting_stats = &stats[&setting.name];
+                        @let picks = setting_stats.picks.values().sum::<usize>();
+                        tr {
+                            td {
+                                : setting.display;
+                                : " (";
+                                code : setting.name;
+                                : ")";
+                            }
+                            td : format!("{} ({})", percent(setting_stats.bans, completed), setting_stats.bans);
+                            td : format!("{} ({})", percent(picks, completed), picks);
+                            td {
+                                @for (idx, (value, count)) in setting_stats.picks.iter().sorted_by(|(value1, count1), (value2, count2)| count2.cmp(count1).then_with(|| value1.cmp(value2))).enumerate() {
+                                    @if idx > 0 {
+                                        br;
+                                    }
+                                    : setting.value_display(value);
+                                    : ": ";
+                                    : *count;
+                                }
+                            }
+                            td : setting_stats.banned_by;
+                            td : setting_stats.picked_by;
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Draft Statistics — {}", event.display_name), content).await?)
+}
@@ -0,0 +1,308 @@
+//! Optional per-event time limits for settings draft steps, configured by organizers on the event configuration page and stored in the `draft_timers` table.
+//!
+//! A background task checks drafts in scheduling threads, posts reminders to the thread as a step's time limit approaches,
+//! and applies a default action once it runs out.
+
+use {
+    sqlx::types::Json,
+    crate::{
+        draft::{
+            Action,
+            MessageContext,
+            StepKind,
+            history,
+        },
+        prelude::*,
+    },
+};
+
+/// The kinds of draft steps that can have a time limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, FromFormField, Sequence)]
+#[sqlx(type_name = "draft_step_kind", rename_all = "snake_case")]
+pub(crate) enum TimedStep {
+    #[field(value = "go_first")]
+    GoFirst,
+    #[field(value = "ban")]
+    Ban,
+    #[field(value = "pick")]
+    Pick,
+    #[field(value = "boolean_choice")]
+    BooleanChoice,
+}
+
+impl TimedStep {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::GoFirst => "go_first",
+            Self::Ban => "ban",
+            Self::Pick => "pick",
+            Self::BooleanChoice => "boolean_choice",
+        }
+    }
+
+    pub(crate) fn display_name(&self) -> &'static str {
+        match self {
+            Self::GoFirst => "Choosing whether to go first",
+            Self::Ban => "Bans",
+            Self::Pick => "Picks",
+            Self::BooleanChoice => "Yes/no choices",
+        }
+    }
+
+    /// Parses a default action as entered on the event configuration page, e.g. “second” for going first, “setting: value” for picks, or “no” for yes/no choices.
+    ///
+    /// Whether the action is actually possible is only checked when the time limit runs out.
+    pub(crate) fn parse_default_action(&self, input: &str) -> Option<Action> {
+        let input = input.trim();
+        match self {
+            Self::GoFirst => match &*input.to_ascii_lowercase() {
+                "first" => Some(Action::GoFirst(true)),
+                "second" => Some(Action::GoFirst(false)),
+                _ => None,
+            },
+            Self::Ban => (!input.is_empty()).then(|| Action::Ban { setting: input.to_owned() }),
+            Self::Pick => {
+                let (setting, value) = input.split_once(':')?;
+                let (setting, value) = (setting.trim(), value.trim());
+                (!setting.is_empty() && !value.is_empty()).then(|| Action::Pick { setting: setting.to_owned(), value: value.to_owned() })
+            }
+            Self::BooleanChoice => match &*input.to_ascii_lowercase() {
+                "yes" => Some(Action::BooleanChoice(true)),
+                "no" => Some(Action::BooleanChoice(false)),
+                _ => None,
+            },
+        }
+    }
+
+    fn from_step_kind(kind: &StepKind) -> Option<Self> {
+        match kind {
+            StepKind::GoFirst => Some(Self::GoFirst),
+            StepKind::Ban { .. } => Some(Self::Ban),
+            StepKind::Pick { .. } => Some(Self::Pick),
+            StepKind::BooleanChoice { .. } => Some(Self::BooleanChoice),
+            StepKind::Done(_) | StepKind::DoneRsl { .. } => None,
+        }
+    }
+}
+
+pub(crate) struct Timer {
+    pub(crate) time_limit: Duration,
+    /// How long before the time limit runs out reminders should be posted, from earliest to latest.
+    pub(crate) reminders: Vec<Duration>,
+    /// The action to take if the step isn't skippable. If this is [`None`] or not possible at this point in the draft, a random choice is made.
+    pub(crate) default_action: Option<Action>,
+}
+
+impl Timer {
+    pub(crate) async fn for_step(transaction: &mut Transaction<'_, Postgres>, series: Series, event: &str, step: TimedStep) -> Result<Option<Self>, event::Error> {
+        let Some(row) = sqlx::query!(r#"SELECT time_limit, reminders, default_action AS "default_action: Json<Action>" FROM draft_timers WHERE series = $1 AND event = $2 AND step_kind = $3"#, series as _, event, step as _).fetch_optional(&mut **transaction).await? else { return Ok(None) };
+        let mut reminders = row.reminders.into_iter().map(decode_pginterval).collect::<Result<Vec<_>, _>>().map_err(event::DataError::PgInterval)?;
+        reminders.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Some(Self {
+            time_limit: decode_pginterval(row.time_limit).map_err(event::DataError::PgInterval)?,
+            default_action: row.default_action.map(|Json(action)| action),
+            reminders,
+        }))
+    }
+}
+
+/// Returns when the current step of the race's draft times out, if it has a time limit and the timer task has started tracking it.
+pub(crate) async fn deadline(transaction: &mut Transaction<'_, Postgres>, race: &Race, draft_kind: draft::Kind) -> Result<Option<DateTime<Utc>>, event::Error> {
+    let Some(ref draft) = race.draft else { return Ok(None) };
+    let step = draft.next_step(draft_kind, race.game, &mut MessageContext::None).await?;
+    let Some(timed_step) = TimedStep::from_step_kind(&step.kind) else { return Ok(None) };
+    let Some(timer) = Timer::for_step(transaction, race.series, &race.event, timed_step).await? else { return Ok(None) };
+    let Some(step_started_at) = sqlx::query_scalar!("SELECT step_started_at FROM draft_timer_state WHERE race = $1 AND draft_state = $2", race.id as _, Json(draft) as _).fetch_optional(&mut **transaction).await? else { return Ok(None) };
+    Ok(Some(step_started_at + TimeDelta::from_std(timer.time_limit).expect("draft time limit too long")))
+}
+
+/// Picks a random action for the given step. Only returns [`None`] if the draft is already done or there's nothing left to choose.
+fn random_action(kind: &StepKind) -> Option<Action> {
+    let mut rng = rng();
+    match kind {
+        StepKind::GoFirst => Some(Action::GoFirst(rng.random())),
+        StepKind::Ban { available_settings, .. } => available_settings.0.iter()
+            .flat_map(|(_, settings)| settings)
+            .choose(&mut rng)
+            .map(|setting| Action::Ban { setting: setting.name.to_owned() }),
+        StepKind::Pick { available_choices, .. } => available_choices.0.iter()
+            .flat_map(|(_, settings)| settings)
+            .filter(|setting| !setting.options.is_empty())
+            .choose(&mut rng)
+            .and_then(|setting| setting.options.choose(&mut rng).map(|option| Action::Pick { setting: setting.name.to_owned(), value: option.name.to_owned() })),
+        StepKind::BooleanChoice { .. } => Some(Action::BooleanChoice(rng.random())),
+        StepKind::Done(_) | StepKind::DoneRsl { .. } => None,
+    }
+}
+
+/// Prepares the given message for the race's scheduling thread, followed by the next step of the draft.
+///
+/// The messages are only sent by [`check_all`] after the transaction has been committed, so a failure to post them can't roll back the draft.
+async fn messages<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, event: &event::Data<'_>, race: &Race, draft_kind: draft::Kind, thread: ChannelId, msg: String, include_next_step: bool) -> Result<(Transaction<'a, Postgres>, Vec<(ChannelId, String)>), event::Error> {
+    let Some(guild_id) = event.discord_guild else { return Ok((transaction, Vec::default())) };
+    let Some(&Some(command_ids)) = discord_ctx.data.read().await.get::<CommandIds>().and_then(|command_ids| command_ids.get(&guild_id)) else { return Ok((transaction, Vec::default())) };
+    let mut messages = vec![(thread, msg)];
+    if include_next_step {
+        let mut msg_ctx = MessageContext::Discord {
+            teams: race.teams().cloned().collect(),
+            team: Team::dummy(),
+            transaction, guild_id, command_ids,
+        };
+        let next_step_msg = race.draft.as_ref().expect("posted draft timer message for race without draft").next_step(draft_kind, race.game, &mut msg_ctx).await?.message;
+        transaction = msg_ctx.into_transaction();
+        messages.push((thread, next_step_msg));
+    }
+    Ok((transaction, messages))
+}
+
+async fn check_race<'a>(mut transaction: Transaction<'a, Postgres>, http_client: &reqwest::Client, discord_ctx: &DiscordCtx, id: Id<Races>) -> Result<(Transaction<'a, Postgres>, Vec<(ChannelId, String)>), event::Error> {
+    // lock the race to avoid racing with draft actions taken by the teams
+    Draft::lock(&mut transaction, id).await?;
+    let mut race = Race::from_id(&mut transaction, http_client, id).await?;
+    let event = event::Data::new(&mut transaction, race.series, race.event.clone()).await?.expect("race with nonexistent event");
+    let (Some(draft_kind), Some(thread)) = (event.draft_kind(), race.scheduling_thread) else { return Ok((transaction, Vec::default())) };
+    if race.has_any_room() { return Ok((transaction, Vec::default())) } // the draft continues in the race room, where its state is held by the racetime.gg bot
+    let Some(draft) = race.draft.clone() else { return Ok((transaction, Vec::default())) };
+    let step = draft.next_step(draft_kind, race.game, &mut MessageContext::None).await?;
+    let Some(timed_step) = TimedStep::from_step_kind(&step.kind) else {
+        sqlx::query!("DELETE FROM draft_timer_state WHERE race = $1", race.id as _).execute(&mut *transaction).await?;
+        return Ok((transaction, Vec::default()))
+    };
+    let (step_started_at, reminders_sent) = if let Some(row) = sqlx::query!(r#"SELECT step_started_at, reminders_sent FROM draft_timer_state WHERE race = $1 AND draft_state = $2"#, race.id as _, Json(&draft) as _).fetch_optional(&mut *transaction).await? {
+        (row.step_started_at, row.reminders_sent)
+    } else {
+        // the draft has advanced (or was undone or reset) since the last check, so the current step starts now
+        let step_started_at = sqlx::query_scalar!("INSERT INTO draft_timer_state (race, draft_state, step_started_at, reminders_sent) VALUES ($1, $2, NOW(), 0) ON CONFLICT (race) DO UPDATE SET draft_state = EXCLUDED.draft_state, step_started_at = EXCLUDED.step_started_at, reminders_sent = 0 RETURNING step_started_at", race.id as _, Json(&draft) as _).fetch_one(&mut *transaction).await?;
+        (step_started_at, 0)
+    };
+    let Some(timer) = Timer::for_step(&mut transaction, race.series, &race.event, timed_step).await? else { return Ok((transaction, Vec::default())) };
+    let skippable = matches!(step.kind, StepKind::Ban { skippable: true, .. } | StepKind::Pick { skippable: true, .. });
+    let active_team = match step.kind {
+        StepKind::GoFirst => draft::Team::HighSeed,
+        StepKind::Ban { team, .. } | StepKind::Pick { team, .. } | StepKind::BooleanChoice { team } => team,
+        StepKind::Done(_) | StepKind::DoneRsl { .. } => unreachable!("checked by TimedStep::from_step_kind"),
+    };
+    let (high_seed, low_seed) = race.teams().partition::<Vec<_>, _>(|team| team.id == draft.high_seed);
+    let (Ok(high_seed), Ok(low_seed)) = (high_seed.into_iter().exactly_one(), low_seed.into_iter().exactly_one()) else { return Ok((transaction, Vec::default())) };
+    let active_team = active_team.choose(high_seed, low_seed).clone();
+    let deadline = step_started_at + TimeDelta::from_std(timer.time_limit).expect("draft time limit too long");
+    let remaining = (deadline - Utc::now()).to_std().unwrap_or_default();
+    if !remaining.is_zero() {
+        let due = timer.reminders.iter().filter(|&&reminder| reminder >= remaining).count();
+        if due > usize::try_from(reminders_sent).expect("negative reminder count") {
+            let msg = MessageBuilder::default()
+                .mention_team(&mut transaction, event.discord_guild, &active_team).await?
+                .push(if let TeamConfig::Solo = event.team_config { ": you have " } else { ": your team has " })
+                .push(English.format_duration(remaining, true))
+                .push(" left for the current step of the settings draft. After that, ")
+                .push(if skippable {
+                    format!("the step will be skipped.")
+                } else if let Some(ref default_action) = timer.default_action {
+                    format!("`{default_action}` will be chosen if possible, otherwise a random choice will be made.")
+                } else {
+                    format!("a random choice will be made.")
+                })
+                .build();
+            sqlx::query!("UPDATE draft_timer_state SET reminders_sent = $1 WHERE race = $2", i32::try_from(due).expect("too many reminders"), race.id as _).execute(&mut *transaction).await?;
+            return messages(transaction, discord_ctx, &event, &race, draft_kind, thread, msg, false).await
+        }
+        return Ok((transaction, Vec::default()))
+    }
+    let mut candidates = Vec::with_capacity(2);
+    if skippable {
+        candidates.push(Action::Skip);
+    } else {
+        candidates.extend(timer.default_action);
+        candidates.extend(random_action(&step.kind));
+    }
+    let mut applied = None;
+    for action in candidates {
+        let mut new_state = draft.clone();
+        if new_state.apply(draft_kind, race.game, &mut MessageContext::None, action.clone()).await?.is_ok() {
+            applied = Some((action, new_state));
+            break
+        }
+    }
+    let Some((action, new_state)) = applied else {
+        eprintln!("draft step timed out for race {} but no default action could be applied", race.id);
+        return Ok((transaction, Vec::default()))
+    };
+    sqlx::query!("UPDATE races SET draft_state = $1 WHERE id = $2", Json(&new_state) as _, race.id as _).execute(&mut *transaction).await?;
+    history::record(&mut transaction, race.id, history::Source::Timer, None, &draft, &action).await?;
+    sqlx::query!("UPDATE draft_timer_state SET draft_state = $1, step_started_at = NOW(), reminders_sent = 0 WHERE race = $2", Json(&new_state) as _, race.id as _).execute(&mut *transaction).await?;
+    race.draft = Some(new_state);
+    let msg = MessageBuilder::default()
+        .mention_team(&mut transaction, event.discord_guild, &active_team).await?
+        .push(" ran out of time for the current step of the settings draft, so ")
+        .push_mono_safe(action.to_string())
+        .push(" was chosen automatically.")
+        .build();
+    messages(transaction, discord_ctx, &event, &race, draft_kind, thread, msg, true).await
+}
+
+async fn check_all(db_pool: &PgPool, http_client: &reqwest::Client, discord_ctx: &DiscordCtx) -> Result<(), event::Error> {
+    let race_ids = sqlx::query_scalar!(r#"SELECT id AS "id: Id<Races>" FROM races WHERE
+        draft_state IS NOT NULL
+        AND scheduling_thread IS NOT NULL
+        AND NOT ignored
+        AND room IS NULL AND async_room1 IS NULL AND async_room2 IS NULL AND async_room3 IS NULL
+        AND EXISTS (SELECT 1 FROM draft_timers WHERE draft_timers.series = races.series AND draft_timers.event = races.event)
+        AND EXISTS (SELECT 1 FROM events WHERE events.series = races.series AND events.event = races.event AND (events.end_time IS NULL OR events.end_time > NOW()))
+    "#).fetch_all(db_pool).await?;
+    for id in race_ids {
+        let transaction = db_pool.begin().await?;
+        match check_race(transaction, http_client, discord_ctx, id).await {
+            Ok((transaction, messages)) => {
+                transaction.commit().await?;
+                for (thread, msg) in messages {
+                    if let Err(e) = thread.say(discord_ctx, msg).await {
+                        eprintln!("failed to post draft timer message for race {id} in {thread}: {e} ({e:?})");
+                    }
+                }
+            }
+            Err(e) if e.is_network_error() => return Err(e),
+            // don't let a single broken race stop the timers for all other races
+            Err(e) => eprintln!("failed to check draft timer for race {id}: {e} ({e:?})"),
+        }
+    }
+    Ok(())
+}
+
+async fn run_inner(db_pool: &PgPool, http_client: &reqwest::Client, discord_ctx: &RwFuture<DiscordCtx>, shutdown: &mut rocket::Shutdown) -> Result<(), event::Error> {
+    loop {
+        check_all(db_pool, http_client, &*discord_ctx.read().await).await?;
+        select! {
+            () = &mut *shutdown => break,
+            () = sleep(Duration::from_secs(60)) => {}
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run(db_pool: PgPool, http_client: reqwest::Client, discord_ctx: RwFuture<DiscordCtx>, mut shutdown: rocket::Shutdown) -> Result<(), event::Error> {
+    let mut last_crash = Instant::now();
+    let mut wait_time = Duration::from_secs(1);
+    loop {
+        match run_inner(&db_pool, &http_client, &discord_ctx, &mut shutdown).await {
+            Ok(()) => break Ok(()),
+            Err(e) if e.is_network_error() => {
+                if last_crash.elapsed() >= Duration::from_secs(60 * 60 * 24) {
+                    wait_time = Duration::from_secs(1); // reset wait time after no crash for a day
+                } else {
+                    wait_time *= 2; // exponential backoff
+                }
+                if wait_time >= Duration::from_secs(2 * 60) {
+                    eprintln!("failed to check draft timers (retrying in {}): {e} ({e:?})", English.format_duration(wait_time, true));
+                }
+                sleep(wait_time).await;
+                last_crash = Instant::now();
+            }
+            Err(e) => {
+                if let Environment::Production = Environment::default() {
+                    wheel::night_report(&format!("{}/error", night_path()), Some(&format!("failed to check draft timers: {e} ({e:?})"))).await?;
+                }
+                break Err(e)
+            }
+        }
+    }
+}
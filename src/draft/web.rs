@@ -0,0 +1,351 @@
+//! The settings draft section of race pages, which lets team members take draft steps on the website and lets spectators follow along.
+
+use {
+    sqlx::types::Json,
+    crate::{
+        draft::{
+            Action,
+            MessageContext,
+            StepKind,
+            history,
+            timer,
+        },
+        prelude::*,
+    },
+};
+
+#[derive(Clone, Copy, FromFormField)]
+pub(crate) enum ActionKind {
+    #[field(value = "first")]
+    First,
+    #[field(value = "second")]
+    Second,
+    #[field(value = "ban")]
+    Ban,
+    #[field(value = "pick")]
+    Pick,
+    #[field(value = "skip")]
+    Skip,
+    #[field(value = "yes")]
+    Yes,
+    #[field(value = "no")]
+    No,
+}
+
+impl ActionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::First => "first",
+            Self::Second => "second",
+            Self::Ban => "ban",
+            Self::Pick => "pick",
+            Self::Skip => "skip",
+            Self::Yes => "yes",
+            Self::No => "no",
+        }
+    }
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct ActionForm {
+    #[field(default = String::new())]
+    csrf: String,
+    action: ActionKind,
+    #[field(default = String::new())]
+    setting: String,
+    #[field(default = String::new())]
+    value: String,
+}
+
+impl ActionForm {
+    fn to_action(&self) -> Action {
+        match self.action {
+            ActionKind::First => Action::GoFirst(true),
+            ActionKind::Second => Action::GoFirst(false),
+            ActionKind::Ban => Action::Ban { setting: self.setting.clone() },
+            ActionKind::Pick => Action::Pick { setting: self.setting.clone(), value: self.value.clone() },
+            ActionKind::Skip => Action::Skip,
+            ActionKind::Yes => Action::BooleanChoice(true),
+            ActionKind::No => Action::BooleanChoice(false),
+        }
+    }
+}
+
+fn action_button(event: &event::Data<'_>, race: &Race, csrf: Option<&CsrfToken>, action: ActionKind, setting: Option<&str>, value: Option<&str>, label: &str) -> RawHtml<String> {
+    let (_, button) = button_form_ext(uri!(post(event.series, &*event.event, race.id)), csrf, Vec::default(), html! {
+        input(type = "hidden", name = "action", value = action.as_str());
+        @if let Some(setting) = setting {
+            input(type = "hidden", name = "setting", value = setting);
+        }
+        @if let Some(value) = value {
+            input(type = "hidden", name = "value", value = value);
+        }
+    }, label);
+    button
+}
+
+/// The team the given user is a member of, if they're participating in the race.
+async fn my_team(transaction: &mut Transaction<'_, Postgres>, me: &User, race: &Race) -> sqlx::Result<Option<Team>> {
+    for team in race.teams() {
+        if team.members(&mut *transaction).await?.contains(me) {
+            return Ok(Some(team.clone()))
+        }
+    }
+    Ok(None)
+}
+
+/// The current state of the draft, with buttons for the next step if `me` is a member of the active team.
+async fn status(transaction: &mut Transaction<'_, Postgres>, me: Option<&User>, csrf: Option<&CsrfToken>, event: &event::Data<'_>, race: &Race, draft_kind: draft::Kind) -> Result<RawHtml<String>, event::Error> {
+    let Some(ref draft) = race.draft else {
+        return Ok(html! {
+            p : "This race's settings draft has not been initialized.";
+        })
+    };
+    let (high_seed, low_seed) = race.teams().partition::<Vec<_>, _>(|team| team.id == draft.high_seed);
+    let (Ok(high_seed), Ok(low_seed)) = (high_seed.into_iter().exactly_one(), low_seed.into_iter().exactly_one()) else {
+        return Ok(html! {
+            p : "The settings draft for this race can't be displayed since it doesn't have exactly two teams.";
+        })
+    };
+    let step = draft.next_step(draft_kind, race.game, &mut MessageContext::Web).await?;
+    let active_team = match step.kind {
+        StepKind::GoFirst => Some(draft::Team::HighSeed),
+        StepKind::Ban { team, .. } | StepKind::Pick { team, .. } | StepKind::BooleanChoice { team } => Some(team),
+        StepKind::Done(_) | StepKind::DoneRsl { .. } => None,
+    };
+    let active_team = if let Some(active_team) = active_team {
+        Some(active_team.choose(high_seed, low_seed))
+    } else {
+        None
+    };
+    let can_act = if_chain! {
+        if let Some(me) = me;
+        if let Some(active_team) = active_team;
+        if !race.has_any_room();
+        if let Some(my_team) = my_team(transaction, me, race).await?;
+        then {
+            my_team.id == active_team.id
+        } else {
+            false
+        }
+    };
+    let active_team = if let Some(active_team) = active_team {
+        Some(active_team.to_html(transaction, true).await?)
+    } else {
+        None
+    };
+    let deadline = timer::deadline(transaction, race, draft_kind).await?;
+    let picks = draft.settings.iter().sorted().collect_vec();
+    Ok(html! {
+        @if !picks.is_empty() {
+            p : "Picks so far:";
+            ul {
+                @for (setting, value) in picks {
+                    li {
+                        code : setting;
+                        : ": ";
+                        : value;
+                    }
+                }
+            }
+        }
+        @match step.kind {
+            StepKind::GoFirst => {
+                p {
+                    : active_team;
+                    : " will choose whether to go first or second in the settings draft.";
+                }
+                @if can_act {
+                    div(class = "button-row") {
+                        : action_button(event, race, csrf, ActionKind::First, None, None, "Go first");
+                        : action_button(event, race, csrf, ActionKind::Second, None, None, "Go second");
+                    }
+                }
+            }
+            StepKind::Ban { available_settings, skippable, rsl, .. } => {
+                p {
+                    : active_team;
+                    : if rsl { " will block the weights of a setting from being changed." } else { " will lock a setting to its default value." };
+                }
+                @if can_act {
+                    @for (page_name, settings) in &available_settings.0 {
+                        h3 : *page_name;
+                        div(class = "button-row") {
+                            @for setting in settings {
+                                : action_button(event, race, csrf, ActionKind::Ban, Some(setting.name), None, &format!("{} ({})", setting.display, setting.default_display));
+                            }
+                        }
+                    }
+                    @if skippable {
+                        : action_button(event, race, csrf, ActionKind::Skip, None, None, "Skip");
+                    }
+                }
+            }
+            StepKind::Pick { available_choices, skippable, rsl, .. } => {
+                p {
+                    : active_team;
+                    : if rsl { " will ban a setting." } else { " will pick a setting." };
+                }
+                @if can_act {
+                    @for (page_name, settings) in &available_choices.0 {
+                        h3 : *page_name;
+                        @for setting in settings {
+                            p : setting.display;
+                            div(class = "button-row") {
+                                @for option in &setting.options {
+                                    : action_button(event, race, csrf, ActionKind::Pick, Some(setting.name), Some(option.name), &option.display);
+                                }
+                            }
+                        }
+                    }
+                    @if skippable {
+                        : action_button(event, race, csrf, ActionKind::Skip, None, None, "Skip");
+                    }
+                }
+            }
+            StepKind::BooleanChoice { .. } => {
+                p {
+                    : active_team;
+                    : " will answer: ";
+                    : step.message;
+                }
+                @if can_act {
+                    div(class = "button-row") {
+                        : action_button(event, race, csrf, ActionKind::Yes, None, None, "Yes");
+                        : action_button(event, race, csrf, ActionKind::No, None, None, "No");
+                    }
+                }
+            }
+            StepKind::Done(_) | StepKind::DoneRsl { .. } => p : step.message;
+        }
+        @if let Some(deadline) = deadline {
+            p {
+                : "If no choice is made by ";
+                : format_datetime(deadline, DateTimeFormat { long: true, running_text: true });
+                : ", a default choice will be made automatically.";
+            }
+        }
+    })
+}
+
+/// The draft section of a race page. The section's content is reloaded periodically from [`live`] so spectators can follow the draft.
+pub(crate) async fn section(transaction: &mut Transaction<'_, Postgres>, me: Option<&User>, csrf: Option<&CsrfToken>, event: &event::Data<'_>, race: &Race, ctx: &Context<'_>) -> Result<RawHtml<String>, event::Error> {
+    let Some(draft_kind) = event.draft_kind() else { return Ok(html! {}) };
+    let status = status(transaction, me, csrf, event, race, draft_kind).await?;
+    Ok(html! {
+        h2 : "Settings draft";
+        @for error in ctx.errors() {
+            : render_form_error(error);
+        }
+        div(class = "live-update", data_url = uri!(live(event.series, &*event.event, race.id)).to_string()) {
+            : status;
+        }
+    })
+}
+
+#[rocket::get("/event/<series>/<event>/races/<id>/draft/live")]
+pub(crate) async fn live(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, csrf: Option<CsrfToken>, series: Series, event: &str, id: Id<Races>) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let race = Race::from_id(&mut transaction, http_client, id).await?;
+    if race.series != event.series || race.event != event.event {
+        return Err(StatusOrError::Status(Status::NotFound))
+    }
+    let draft_kind = event.draft_kind().ok_or(StatusOrError::Status(Status::NotFound))?;
+    let status = status(&mut transaction, me.as_ref(), csrf.as_ref(), &event, &race, draft_kind).await?;
+    transaction.commit().await?;
+    Ok(status)
+}
+
+#[rocket::post("/event/<series>/<event>/races/<id>/draft/action", data = "<form>")]
+pub(crate) async fn post(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, id: Id<Races>, form: Form<Contextual<'_, ActionForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = event::Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut race = Race::from_id(&mut transaction, http_client, id).await?;
+    race.draft = Draft::lock(&mut transaction, id).await?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    if race.series != event.series || race.event != event.event {
+        form.context.push_error(form::Error::validation("This race is not part of this event."));
+    }
+    let draft_kind = event.draft_kind();
+    if draft_kind.is_none() {
+        form.context.push_error(form::Error::validation("There is no settings draft for this event."));
+    }
+    if race.draft.is_none() {
+        form.context.push_error(form::Error::validation("This race's settings draft has not been initialized. Please contact a tournament organizer to fix this."));
+    }
+    if race.has_any_room() {
+        form.context.push_error(form::Error::validation("This race's settings draft can't be changed here since a race room is already open. You can continue the draft in the race room."));
+    }
+    let team = my_team(&mut transaction, &me, &race).await?;
+    if team.is_none() {
+        form.context.push_error(form::Error::validation("Only participants in this race can take part in its settings draft."));
+    }
+    let mut announcement = Vec::default();
+    Ok(if let Some(ref value) = form.value {
+        if form.context.errors().next().is_none() {
+            let draft_kind = draft_kind.expect("checked above");
+            let team = team.expect("checked above");
+            let draft = race.draft.as_mut().expect("checked above");
+            if draft.is_active_team(draft_kind, race.game, team.id).await? {
+                let state_before = draft.clone();
+                let action = value.to_action();
+                match draft.apply(draft_kind, race.game, &mut MessageContext::Web, action.clone()).await? {
+                    Ok(_) => {
+                        sqlx::query!("UPDATE races SET draft_state = $1 WHERE id = $2", Json(&*draft) as _, race.id as _).execute(&mut *transaction).await?;
+                        history::record(&mut transaction, race.id, history::Source::Web, Some(history::Actor::MidosHouse(me.id)), &state_before, &action).await?;
+                        (transaction, announcement) = announce(transaction, &*discord_ctx.read().await, &event, &race, draft_kind, &team, &action).await?;
+                    }
+                    Err(error_msg) => form.context.push_error(form::Error::validation(if error_msg.is_empty() {
+                        format!("Sorry, that action is not possible at this point in the settings draft.")
+                    } else {
+                        error_msg
+                    })),
+                }
+            } else {
+                form.context.push_error(form::Error::validation(if let TeamConfig::Solo = event.team_config {
+                    "Sorry, it's not your turn in the settings draft."
+                } else {
+                    "Sorry, it's not your team's turn in the settings draft."
+                }));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(cal::race_page(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, form.context).await?)
+        } else {
+            transaction.commit().await?;
+            // the action has already been saved at this point, so a failure to announce it shouldn't be reported to the player
+            let discord_ctx = discord_ctx.read().await;
+            for (thread, msg) in announcement {
+                if let Err(e) = thread.say(&*discord_ctx, msg).await {
+                    eprintln!("failed to announce draft action for race {} in {thread}: {e} ({e:?})", race.id);
+                }
+            }
+            RedirectOrContent::Redirect(Redirect::to(uri!(cal::race_info(event.series, &*event.event, race.id))))
+        }
+    } else {
+        RedirectOrContent::Content(cal::race_page(transaction, &*discord_ctx.read().await, Some(me), uri, csrf.as_ref(), event, race, form.context).await?)
+    })
+}
+
+/// Prepares the announcement of a draft action taken on the website for the race's scheduling thread, along with the next step, so the draft can be followed on Discord as well.
+///
+/// The messages are only sent by [`post`] after the transaction has been committed, so a failure to post them can't roll back the action.
+async fn announce<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, event: &event::Data<'_>, race: &Race, draft_kind: draft::Kind, team: &Team, action: &Action) -> Result<(Transaction<'a, Postgres>, Vec<(ChannelId, String)>), event::Error> {
+    let (Some(thread), Some(guild_id)) = (race.scheduling_thread, event.discord_guild) else { return Ok((transaction, Vec::default())) };
+    let Some(&Some(command_ids)) = discord_ctx.data.read().await.get::<CommandIds>().and_then(|command_ids| command_ids.get(&guild_id)) else { return Ok((transaction, Vec::default())) };
+    let action_msg = MessageBuilder::default()
+        .mention_team(&mut transaction, Some(guild_id), team).await?
+        .push(" chose ")
+        .push_mono_safe(action.to_string())
+        .push(" on Mido's House.")
+        .build();
+    let mut msg_ctx = MessageContext::Discord {
+        teams: race.teams().cloned().collect(),
+        team: team.clone(),
+        transaction, guild_id, command_ids,
+    };
+    let next_step_msg = race.draft.as_ref().expect("announced draft action for race without draft").next_step(draft_kind, race.game, &mut msg_ctx).await?.message;
+    Ok((msg_ctx.into_transaction(), vec![(thread, action_msg), (thread, next_step_msg)]))
+}
@@ -0,0 +1,970 @@
+//! Single- and double-elimination brackets whose matches are generated and advanced by Mido's House rather than an external bracket.
+//!
+//! A bracket is created for a phase of an event from a seeding source. The seeding is saved as the seeds for that phase, so it also applies to drafts. Bracket sizes are rounded up to a power of 2, with the top seeds receiving byes.
+//! Each match is a best-of-N series of races, where N is odd. Winners are advanced automatically once enough races have been reported, and the remaining races of a decided match are ignored.
+//! Drawn games don't count for either team. Instead, another game is added to the match for each draw, so every match is eventually decided.
+//! Double-elimination brackets end with a single grand final between the winners of the winners and losers brackets, without a bracket reset.
+
+use {
+    std::ops::RangeInclusive,
+    sqlx::PgPool,
+    crate::{
+        event::{
+            Data,
+            Tab,
+            groups,
+            swiss,
+        },
+        prelude::*,
+        rating,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Discord(#[from] crate::discord_bot::Error),
+    #[error(transparent)] Groups(#[from] groups::Error),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+    #[error("a bracket match references a team that doesn't exist")]
+    TeamData,
+}
+
+impl IsNetworkError for Error {
+    fn is_network_error(&self) -> bool {
+        match self {
+            Self::Cal(e) => e.is_network_error(),
+            Self::Discord(_) => false,
+            Self::Groups(e) => e.is_network_error(),
+            Self::Sql(_) => false,
+            Self::TeamData => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, FromFormField)]
+#[sqlx(type_name = "bracket_kind", rename_all = "snake_case")]
+pub(crate) enum Kind {
+    #[field(value = "single_elimination")]
+    SingleElimination,
+    #[field(value = "double_elimination")]
+    DoubleElimination,
+}
+
+impl Kind {
+    fn display_name(&self) -> &'static str {
+        match self {
+            Self::SingleElimination => "Single elimination",
+            Self::DoubleElimination => "Double elimination",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type, FromFormField)]
+#[sqlx(type_name = "bracket_side", rename_all = "snake_case")]
+pub(crate) enum Side {
+    #[field(value = "winners")]
+    Winners,
+    #[field(value = "losers")]
+    Losers,
+    #[field(value = "grand_final")]
+    GrandFinal,
+}
+
+impl Side {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Winners => "winners",
+            Self::Losers => "losers",
+            Self::GrandFinal => "grand_final",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct MatchId {
+    side: Side,
+    round: i16,
+    /// 0-based position of the match within its round, from top to bottom.
+    position: i16,
+}
+
+/// Where one of the two teams of a match comes from.
+enum Feeder {
+    Seed(i32),
+    Winner(MatchId),
+    Loser(MatchId),
+}
+
+struct Bracket {
+    phase: String,
+    kind: Kind,
+    size: i16,
+    game_count: i16,
+}
+
+impl Bracket {
+    async fn load(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> sqlx::Result<Option<Self>> {
+        Ok(sqlx::query!(r#"SELECT kind AS "kind: Kind", size, game_count FROM brackets WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, phase).fetch_optional(&mut **transaction).await?
+            .map(|row| Self { phase: phase.to_owned(), kind: row.kind, size: row.size, game_count: row.game_count }))
+    }
+
+    async fn for_event(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>) -> sqlx::Result<Vec<Self>> {
+        Ok(sqlx::query!(r#"SELECT phase, kind AS "kind: Kind", size, game_count FROM brackets WHERE series = $1 AND event = $2 ORDER BY phase"#, event.series as _, &event.event).fetch_all(&mut **transaction).await?
+            .into_iter()
+            .map(|row| Self { phase: row.phase, kind: row.kind, size: row.size, game_count: row.game_count })
+            .collect())
+    }
+
+    /// The number of rounds in the winners bracket.
+    fn winners_rounds(&self) -> i16 {
+        self.size.trailing_zeros() as i16
+    }
+
+    /// The number of rounds in the losers bracket.
+    fn losers_rounds(&self) -> i16 {
+        match self.kind {
+            Kind::SingleElimination => 0,
+            Kind::DoubleElimination => 2 * (self.winners_rounds() - 1),
+        }
+    }
+
+    /// The number of matches in the given round.
+    fn round_size(&self, side: Side, round: i16) -> i16 {
+        match side {
+            Side::Winners => self.size >> round,
+            Side::Losers => self.size >> ((round + 1) / 2 + 1),
+            Side::GrandFinal => 1,
+        }
+    }
+
+    /// All matches of the bracket, ordered such that each match comes after the matches it's fed by.
+    fn match_ids(&self) -> Vec<MatchId> {
+        let mut ids = Vec::default();
+        for round in 1..=self.winners_rounds() {
+            for position in 0..self.round_size(Side::Winners, round) {
+                ids.push(MatchId { side: Side::Winners, round, position });
+            }
+        }
+        if let Kind::DoubleElimination = self.kind {
+            for round in 1..=self.losers_rounds() {
+                for position in 0..self.round_size(Side::Losers, round) {
+                    ids.push(MatchId { side: Side::Losers, round, position });
+                }
+            }
+            ids.push(MatchId { side: Side::GrandFinal, round: 1, position: 0 });
+        }
+        ids
+    }
+
+    fn feeders(&self, MatchId { side, round, position }: MatchId) -> [Feeder; 2] {
+        match side {
+            Side::Winners => if round == 1 {
+                let seeds = seed_positions(self.size);
+                [Feeder::Seed(seeds[2 * position as usize]), Feeder::Seed(seeds[2 * position as usize + 1])]
+            } else {
+                [
+                    Feeder::Winner(MatchId { side: Side::Winners, round: round - 1, position: 2 * position }),
+                    Feeder::Winner(MatchId { side: Side::Winners, round: round - 1, position: 2 * position + 1 }),
+                ]
+            },
+            Side::Losers => if round == 1 {
+                [
+                    Feeder::Loser(MatchId { side: Side::Winners, round: 1, position: 2 * position }),
+                    Feeder::Loser(MatchId { side: Side::Winners, round: 1, position: 2 * position + 1 }),
+                ]
+            } else if round % 2 == 0 {
+                // teams dropping down from the winners bracket are placed in reverse order to postpone rematches
+                [
+                    Feeder::Winner(MatchId { side: Side::Losers, round: round - 1, position }),
+                    Feeder::Loser(MatchId { side: Side::Winners, round: round / 2 + 1, position: self.round_size(Side::Losers, round) - 1 - position }),
+                ]
+            } else {
+                [
+                    Feeder::Winner(MatchId { side: Side::Losers, round: round - 1, position: 2 * position }),
+                    Feeder::Winner(MatchId { side: Side::Losers, round: round - 1, position: 2 * position + 1 }),
+                ]
+            },
+            Side::GrandFinal => [
+                Feeder::Winner(MatchId { side: Side::Winners, round: self.winners_rounds(), position: 0 }),
+                Feeder::Winner(MatchId { side: Side::Losers, round: self.losers_rounds(), position: 0 }),
+            ],
+        }
+    }
+
+    fn round_name(&self, side: Side, round: i16) -> String {
+        match (self.kind, side) {
+            (Kind::SingleElimination, _) => match self.winners_rounds() - round {
+                0 => format!("Final"),
+                1 => format!("Semifinals"),
+                2 => format!("Quarterfinals"),
+                n => format!("Round of {}", 2 << n),
+            },
+            (Kind::DoubleElimination, Side::Winners) => if round == self.winners_rounds() {
+                format!("Winners Final")
+            } else {
+                format!("Winners Round {round}")
+            },
+            (Kind::DoubleElimination, Side::Losers) => if round == self.losers_rounds() {
+                format!("Losers Final")
+            } else {
+                format!("Losers Round {round}")
+            },
+            (Kind::DoubleElimination, Side::GrandFinal) => format!("Grand Final"),
+        }
+    }
+}
+
+/// The seeds in the order in which they appear in the first round of a bracket of the given size, e.g. `[1, 8, 4, 5, 2, 7, 3, 6]` for 8 teams.
+///
+/// This ensures that the top seeds can only meet in the latest possible rounds.
+fn seed_positions(size: i16) -> Vec<i32> {
+    let mut positions = vec![1];
+    while positions.len() < size as usize {
+        let sum = 2 * positions.len() as i32 + 1;
+        positions = positions.into_iter().flat_map(|seed| [seed, sum - seed]).collect();
+    }
+    positions
+}
+
+struct Match {
+    team1: Option<Id<Teams>>,
+    team2: Option<Id<Teams>>,
+    /// Whether both teams of this match have been determined. A missing team in a ready match is a bye.
+    ready: bool,
+    team1_wins: i16,
+    team2_wins: i16,
+    decided: bool,
+    /// `None` for a decided match only if both teams were byes.
+    winner: Option<Id<Teams>>,
+}
+
+impl Match {
+    fn loser(&self) -> Option<Id<Teams>> {
+        if self.winner.is_some() && self.winner == self.team1 {
+            self.team2
+        } else if self.winner.is_some() && self.winner == self.team2 {
+            self.team1
+        } else {
+            None
+        }
+    }
+
+    fn is_playable(&self) -> bool {
+        self.ready && !self.decided && self.team1.is_some() && self.team2.is_some()
+    }
+}
+
+async fn load_matches(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> sqlx::Result<HashMap<MatchId, Match>> {
+    Ok(sqlx::query!(r#"SELECT side AS "side: Side", round, position, team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>", ready, team1_wins, team2_wins, decided, winner AS "winner: Id<Teams>" FROM bracket_matches WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await?
+        .into_iter()
+        .map(|row| (MatchId { side: row.side, round: row.round, position: row.position }, Match {
+            team1: row.team1,
+            team2: row.team2,
+            ready: row.ready,
+            team1_wins: row.team1_wins,
+            team2_wins: row.team2_wins,
+            decided: row.decided,
+            winner: row.winner,
+        }))
+        .collect())
+}
+
+/// Creates the races with the given game numbers for a match, either all of them when it just became playable or replays of drawn games.
+///
+/// The races share a single scheduling thread, and the draft (if any) is initialized for the first game.
+async fn create_races<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, bracket: &Bracket, id: MatchId, [team1, team2]: [Id<Teams>; 2], games: RangeInclusive<i16>) -> Result<Transaction<'a, Postgres>, Error> {
+    let team1 = Team::from_id(&mut transaction, team1).await?.ok_or(Error::TeamData)?;
+    let team2 = Team::from_id(&mut transaction, team2).await?.ok_or(Error::TeamData)?;
+    let draft = if let (Some(draft_kind), true) = (event.draft_kind(), games.contains(&1)) {
+        Some(Draft::for_game1(&mut transaction, http_client, draft_kind, event, Some(&bracket.phase), [&team1, &team2]).await?)
+    } else {
+        None
+    };
+    let mut scheduling_thread = sqlx::query_scalar!(r#"SELECT scheduling_thread AS "scheduling_thread!: PgSnowflake<ChannelId>" FROM races WHERE scheduling_thread IS NOT NULL AND id IN (SELECT race FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6) LIMIT 1"#, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).fetch_optional(&mut *transaction).await?.map(|PgSnowflake(thread)| thread);
+    for game in games {
+        let mut race = Race::new_for_pairing(&mut transaction, event, &bracket.phase, bracket.round_name(id.side, id.round), (bracket.game_count > 1 || game > 1).then_some(game), [&team1, &team2], draft.as_ref().filter(|_| game == 1).cloned()).await?;
+        race.scheduling_thread = scheduling_thread;
+        if scheduling_thread.is_none() {
+            transaction = crate::discord_bot::create_scheduling_thread(discord_ctx, transaction, &mut race, bracket.game_count).await?;
+            scheduling_thread = race.scheduling_thread;
+        }
+        race.save(&mut transaction).await?;
+        sqlx::query!("INSERT INTO bracket_races (race, series, event, phase, side, round, position) VALUES ($1, $2, $3, $4, $5, $6, $7)", race.id as _, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).execute(&mut *transaction).await?;
+    }
+    Ok(transaction)
+}
+
+/// Fills in the teams of matches whose feeders have been decided, advances teams with byes, and creates the races for matches that became playable.
+async fn resolve<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, bracket: &Bracket) -> Result<Transaction<'a, Postgres>, Error> {
+    let seeds = sqlx::query!(r#"SELECT team AS "team: Id<Teams>", seed FROM seeds WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, &bracket.phase).fetch_all(&mut *transaction).await?
+        .into_iter()
+        .map(|row| (row.seed, row.team))
+        .collect::<HashMap<_, _>>();
+    let with_races = sqlx::query!(r#"SELECT side AS "side: Side", round, position FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, &bracket.phase).fetch_all(&mut *transaction).await?
+        .into_iter()
+        .map(|row| MatchId { side: row.side, round: row.round, position: row.position })
+        .collect::<HashSet<_>>();
+    let mut matches = load_matches(&mut transaction, event, &bracket.phase).await?;
+    for id in bracket.match_ids() {
+        // `None` if the feeder hasn't been decided yet, `Some(None)` for a bye
+        let [team1, team2] = bracket.feeders(id).map(|feeder| match feeder {
+            Feeder::Seed(seed) => Some(seeds.get(&seed).copied()),
+            Feeder::Winner(source) => matches.get(&source).filter(|source| source.decided).map(|source| source.winner),
+            Feeder::Loser(source) => matches.get(&source).filter(|source| source.decided).map(|source| source.loser()),
+        });
+        let Some(m) = matches.get_mut(&id) else { continue };
+        let mut changed = false;
+        if !m.ready {
+            if let Some(team1) = team1 {
+                changed |= m.team1 != team1;
+                m.team1 = team1;
+            }
+            if let Some(team2) = team2 {
+                changed |= m.team2 != team2;
+                m.team2 = team2;
+            }
+            if team1.is_some() && team2.is_some() {
+                m.ready = true;
+                changed = true;
+            }
+        }
+        if m.ready && !m.decided {
+            match (m.team1, m.team2) {
+                (Some(_), Some(_)) => {}
+                (winner, None) | (None, winner) => {
+                    m.decided = true;
+                    m.winner = winner;
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            sqlx::query!("UPDATE bracket_matches SET team1 = $1, team2 = $2, ready = $3, decided = $4, winner = $5 WHERE series = $6 AND event = $7 AND phase = $8 AND side = $9 AND round = $10 AND position = $11", m.team1 as _, m.team2 as _, m.ready, m.decided, m.winner as _, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).execute(&mut *transaction).await?;
+        }
+        if let (true, false, Some(team1), Some(team2)) = (m.is_playable(), with_races.contains(&id), m.team1, m.team2) {
+            transaction = create_races(transaction, discord_ctx, http_client, event, bracket, id, [team1, team2], 1..=bracket.game_count).await?;
+        }
+    }
+    Ok(transaction)
+}
+
+/// Marks a match as decided, ignores its races that haven't been opened yet, and advances the bracket.
+async fn decide<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, bracket: &Bracket, id: MatchId, winner: Id<Teams>) -> Result<Transaction<'a, Postgres>, Error> {
+    sqlx::query!("UPDATE bracket_matches SET decided = TRUE, winner = $1 WHERE series = $2 AND event = $3 AND phase = $4 AND side = $5 AND round = $6 AND position = $7", winner as _, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).execute(&mut *transaction).await?;
+    sqlx::query!("UPDATE races SET ignored = TRUE WHERE room IS NULL AND id IN (SELECT race FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6 AND NOT reported)", event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).execute(&mut *transaction).await?;
+    resolve(transaction, discord_ctx, http_client, event, bracket).await
+}
+
+/// Records the result of a race if it's part of a bracket. Once a team has won the majority of the match's games, it advances in the bracket.
+///
+/// `winner` is `None` if the race was a draw. Drawn games don't count for either team, and a replay is added to the match for each of them.
+/// If the race had already been reported, the match score is recomputed with the corrected result. Teams which have already advanced aren't moved back,
+/// so if a correction changes the outcome of a decided match, a notification for the organizers is returned instead.
+/// The notification should only be sent once the transaction has been committed, so a rollback doesn't leave a stale message.
+pub(crate) async fn record_result<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, race: Id<Races>, winner: Option<Id<Teams>>) -> Result<(Transaction<'a, Postgres>, Option<(ChannelId, String)>), Error> {
+    let Some(row) = sqlx::query!(r#"UPDATE bracket_races SET reported = TRUE, winner = $1 WHERE race = $2 RETURNING phase, side AS "side: Side", round, position"#, winner as _, race as _).fetch_optional(&mut *transaction).await? else { return Ok((transaction, None)) };
+    let Some(bracket) = Bracket::load(&mut transaction, event, &row.phase).await? else { return Ok((transaction, None)) };
+    let id = MatchId { side: row.side, round: row.round, position: row.position };
+    let Some(row) = sqlx::query!(r#"UPDATE bracket_matches SET
+        team1_wins = (SELECT COUNT(*) FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6 AND reported AND winner = bracket_matches.team1),
+        team2_wins = (SELECT COUNT(*) FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6 AND reported AND winner = bracket_matches.team2)
+    WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6
+    RETURNING team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>", team1_wins, team2_wins, decided, winner AS "winner: Id<Teams>""#, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).fetch_optional(&mut *transaction).await? else { return Ok((transaction, None)) };
+    let majority_winner = if row.team1_wins > bracket.game_count / 2 {
+        row.team1
+    } else if row.team2_wins > bracket.game_count / 2 {
+        row.team2
+    } else {
+        None
+    };
+    if row.decided {
+        if majority_winner != row.winner {
+            if let Some(channel) = event.discord_organizer_channel {
+                let mut msg = MessageBuilder::default();
+                msg.push("A corrected race result changes the outcome of a ");
+                msg.push_safe(&bracket.phase);
+                msg.push(' ');
+                msg.push_safe(bracket.round_name(id.side, id.round));
+                msg.push(" match in ");
+                msg.push_safe(&event.display_name);
+                msg.push(", which has already been decided. The bracket has not been changed, please adjust it manually if necessary.");
+                return Ok((transaction, Some((channel, msg.build()))))
+            }
+        }
+    } else if let Some(winner) = majority_winner {
+        transaction = decide(transaction, discord_ctx, http_client, event, &bracket, id, winner).await?;
+    } else if let (Some(team1), Some(team2)) = (row.team1, row.team2) {
+        let counts = sqlx::query!(r#"SELECT COUNT(*)::smallint AS "games!", (COUNT(*) FILTER (WHERE reported AND winner IS NULL))::smallint AS "draws!" FROM bracket_races WHERE series = $1 AND event = $2 AND phase = $3 AND side = $4 AND round = $5 AND position = $6"#, event.series as _, &event.event, &bracket.phase, id.side as _, id.round, id.position).fetch_one(&mut *transaction).await?;
+        if counts.games < bracket.game_count + counts.draws {
+            transaction = create_races(transaction, discord_ctx, http_client, event, &bracket, id, [team1, team2], counts.games + 1..=bracket.game_count + counts.draws).await?;
+        }
+    }
+    Ok((transaction, None))
+}
+
+/// Seeding sources offered when creating a bracket, as form values and descriptions.
+async fn seeding_sources(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>) -> sqlx::Result<Vec<(String, String)>> {
+    let mut sources = vec![
+        (format!("qualifier"), format!("Qualifier rank")),
+        (format!("seeds"), format!("Seeds configured for this phase")),
+        (format!("rating"), format!("Player ratings across Mido's House events")),
+    ];
+    for phase in sqlx::query_scalar!("SELECT phase FROM swiss_phases WHERE series = $1 AND event = $2 ORDER BY phase", event.series as _, &event.event).fetch_all(&mut **transaction).await? {
+        sources.push((format!("swiss:{phase}"), format!("Standings of Swiss phase {phase}")));
+    }
+    for phase in sqlx::query_scalar!("SELECT phase FROM group_phases WHERE series = $1 AND event = $2 ORDER BY phase", event.series as _, &event.event).fetch_all(&mut **transaction).await? {
+        sources.push((format!("groups:{phase}"), format!("Advancing teams of group phase {phase}")));
+    }
+    Ok(sources)
+}
+
+/// The active teams to seed into a new bracket from the given seeding source, from 1st seed to last. Returns `None` if the seeding source is invalid.
+async fn seeded_teams(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str, source: &str) -> Result<Option<Vec<Id<Teams>>>, Error> {
+    let mut teams = Team::for_event(transaction, event.series, &event.event).await?;
+    Ok(if source == "qualifier" {
+        teams.retain(|team| team.qualifier_rank.is_some());
+        teams.sort_by_key(|team| (team.qualifier_rank, team.id));
+        Some(teams.into_iter().map(|team| team.id).collect())
+    } else if source == "seeds" {
+        let active = teams.into_iter().map(|team| team.id).collect::<HashSet<_>>();
+        Some(
+            sqlx::query_scalar!(r#"SELECT team AS "team: Id<Teams>" FROM seeds WHERE series = $1 AND event = $2 AND phase = $3 ORDER BY seed"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await?
+                .into_iter()
+                .filter(|team| active.contains(team))
+                .collect()
+        )
+    } else if source == "rating" {
+        let mut ratings = Vec::with_capacity(teams.len());
+        for team in teams {
+            ratings.push((rating::team_rating(&mut *transaction, event.team_config, &team).await?, team.id));
+        }
+        ratings.sort_by(|(rating1, id1), (rating2, id2)| rating2.total_cmp(rating1).then_with(|| id1.cmp(id2)));
+        Some(ratings.into_iter().map(|(_, team)| team).collect())
+    } else if let Some(swiss_phase) = source.strip_prefix("swiss:") {
+        if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM swiss_phases WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, event.series as _, &event.event, swiss_phase).fetch_one(&mut **transaction).await? {
+            Some(swiss::standings(transaction, event, swiss_phase).await?.into_iter().map(|standing| standing.team).collect())
+        } else {
+            None
+        }
+    } else if let Some(group_phase) = source.strip_prefix("groups:") {
+        groups::advancing_teams(transaction, event, group_phase).await?
+    } else {
+        None
+    })
+}
+
+/// Loads the display names of all teams appearing in the given matches, including teams that have since resigned.
+async fn team_names(transaction: &mut Transaction<'_, Postgres>, matches: &HashMap<MatchId, Match>) -> Result<HashMap<Id<Teams>, RawHtml<String>>, Error> {
+    let mut team_names = HashMap::default();
+    for team in matches.values().flat_map(|m| [m.team1, m.team2]).flatten() {
+        if !team_names.contains_key(&team) {
+            let name = Team::from_id(&mut *transaction, team).await?.ok_or(Error::TeamData)?.to_html(&mut *transaction, false).await?;
+            team_names.insert(team, name);
+        }
+    }
+    Ok(team_names)
+}
+
+fn bracket_html(bracket: &Bracket, matches: &HashMap<MatchId, Match>, team_names: &HashMap<Id<Teams>, RawHtml<String>>) -> RawHtml<String> {
+    let sides = match bracket.kind {
+        Kind::SingleElimination => vec![(None, Side::Winners, bracket.winners_rounds())],
+        Kind::DoubleElimination => vec![
+            (Some("Winners bracket"), Side::Winners, bracket.winners_rounds()),
+            (Some("Losers bracket"), Side::Losers, bracket.losers_rounds()),
+            (Some("Grand final"), Side::GrandFinal, 1),
+        ],
+    };
+    html! {
+        @for (heading, side, rounds) in sides {
+            @if let Some(heading) = heading {
+                h4 : heading;
+            }
+            div(class = "bracket") {
+                @for round in 1..=rounds {
+                    div(class = "bracket-round") {
+                        h5 : bracket.round_name(side, round);
+                        @for position in 0..bracket.round_size(side, round) {
+                            @if let Some(m) = matches.get(&MatchId { side, round, position }) {
+                                div(class = "bracket-match") {
+                                    @for (team, wins) in [(m.team1, m.team1_wins), (m.team2, m.team2_wins)] {
+                                        @let class = if m.decided && team.is_some() && m.winner == team { "bracket-team winner" } else { "bracket-team" };
+                                        div(class = class) {
+                                            span {
+                                                @if let Some(team) = team {
+                                                    : team_names.get(&team);
+                                                } else if m.ready {
+                                                    : "bye";
+                                                } else {
+                                                    : "TBD";
+                                                }
+                                            }
+                                            @if bracket.game_count > 1 && m.team1.is_some() && m.team2.is_some() {
+                                                span : wins;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[rocket::get("/event/<series>/<event>/bracket")]
+pub(crate) async fn get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, series: Series, event: &str) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let header = data.header(&mut transaction, me.as_ref(), Tab::Bracket, false).await?;
+    let is_organizer = if let Some(ref me) = me {
+        data.organizers(&mut transaction).await?.contains(me)
+    } else {
+        false
+    };
+    let mut brackets = Vec::default();
+    for bracket in Bracket::for_event(&mut transaction, &data).await? {
+        let matches = load_matches(&mut transaction, &data, &bracket.phase).await?;
+        let team_names = team_names(&mut transaction, &matches).await?;
+        brackets.push((bracket, matches, team_names));
+    }
+    let content = html! {
+        @if brackets.is_empty() {
+            article {
+                p : "This event has no brackets yet.";
+            }
+        }
+        @for (bracket, matches, team_names) in &brackets {
+            h2 : &bracket.phase;
+            @if bracket.game_count > 1 {
+                p : format!("Best of {}", bracket.game_count);
+            }
+            : bracket_html(bracket, matches, team_names);
+        }
+        @if is_organizer {
+            p {
+                a(href = uri!(configure_get(series, event))) : "Manage brackets";
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: data.chests().await?, ..PageStyle::default() }, &format!("Bracket — {}", data.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+enum BracketFormDefaults<'v> {
+    None,
+    CreateContext(Context<'v>),
+    ReportContext(String, MatchId, Context<'v>),
+}
+
+impl<'v> BracketFormDefaults<'v> {
+    fn create_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::CreateContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn create_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::CreateContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+
+    fn report_errors(&self, for_phase: &str, for_match: MatchId) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::ReportContext(phase, id, ctx) if phase == for_phase && *id == for_match => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+}
+
+async fn bracket_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: BracketFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let mut brackets = Vec::default();
+            for bracket in Bracket::for_event(&mut transaction, &event).await? {
+                let matches = load_matches(&mut transaction, &event, &bracket.phase).await?;
+                let team_names = team_names(&mut transaction, &matches).await?;
+                let playable = bracket.match_ids().into_iter().filter(|id| matches.get(id).is_some_and(Match::is_playable)).collect_vec();
+                brackets.push((bracket, matches, team_names, playable));
+            }
+            let seeding_sources = seeding_sources(&mut transaction, &event).await?;
+            html! {
+                h2 : "Brackets";
+                p {
+                    : "Mido's House can generate a single- or double-elimination bracket for a phase of this event and create the races for each match as soon as both teams are known. Results are recorded automatically when a race room finishes, and a team advances once it has won the majority of the match's games. The bracket is shown on the ";
+                    a(href = uri!(get(event.series, &*event.event))) : "Bracket";
+                    : " tab.";
+                }
+                p : "The bracket's seeding is saved as the seeds for its phase, replacing any previously configured seeds for that phase. If the number of teams isn't a power of 2, the top seeds receive byes. Double-elimination brackets end with a single grand final without a bracket reset. Draws and other disputed results can be resolved by setting the winner of a match below.";
+                @for (bracket, matches, team_names, playable) in &brackets {
+                    h3 : &bracket.phase;
+                    p : format!("{}, {} teams, best of {}", bracket.kind.display_name(), bracket.size, bracket.game_count);
+                    @if playable.is_empty() {
+                        p : "No matches are currently in progress.";
+                    } else {
+                        table {
+                            thead {
+                                tr {
+                                    th : "Round";
+                                    th : "Match";
+                                    th : "Score";
+                                    th;
+                                }
+                            }
+                            tbody {
+                                @for &id in playable {
+                                    @let m = &matches[&id];
+                                    @if let (Some(team1), Some(team2)) = (m.team1, m.team2) {
+                                        tr {
+                                            td : bracket.round_name(id.side, id.round);
+                                            td {
+                                                : team_names.get(&team1);
+                                                : " vs ";
+                                                : team_names.get(&team2);
+                                            }
+                                            td : format!("{}–{}", m.team1_wins, m.team2_wins);
+                                            td {
+                                                @let errors = defaults.report_errors(&bracket.phase, id);
+                                                @let (errors, button) = button_form_ext(uri!(report(event.series, &*event.event)), csrf, errors, html! {
+                                                    input(type = "hidden", name = "phase", value = &bracket.phase);
+                                                    input(type = "hidden", name = "side", value = id.side.as_str());
+                                                    input(type = "hidden", name = "round", value = id.round.to_string());
+                                                    input(type = "hidden", name = "position", value = id.position.to_string());
+                                                    select(name = "winner") {
+                                                        option(value = "team1") : team_names.get(&team1);
+                                                        option(value = "team2") : team_names.get(&team2);
+                                                    }
+                                                }, "Set winner");
+                                                : errors;
+                                                div(class = "button-row") : button;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h3 : "Create bracket";
+                @let mut errors = defaults.create_errors();
+                : full_form(uri!(create(event.series, &*event.event)), csrf, html! {
+                    : form_field("phase", &mut errors, html! {
+                        label(for = "phase") : "Phase:";
+                        input(type = "text", name = "phase", value = defaults.create_value("phase").unwrap_or("Top 8"));
+                        label(class = "help") : "(Used as the phase of the created races.)";
+                    });
+                    : form_field("kind", &mut errors, html! {
+                        label(for = "kind") : "Format:";
+                        select(name = "kind") {
+                            @for kind in [Kind::SingleElimination, Kind::DoubleElimination] {
+                                @let value = match kind {
+                                    Kind::SingleElimination => "single_elimination",
+                                    Kind::DoubleElimination => "double_elimination",
+                                };
+                                option(value = value, selected? = defaults.create_value("kind") == Some(value)) : kind.display_name();
+                            }
+                        }
+                    });
+                    : form_field("game_count", &mut errors, html! {
+                        label(for = "game_count") : "Games per match:";
+                        input(type = "number", min = "1", step = "2", name = "game_count", value = defaults.create_value("game_count").map(|game_count| game_count.to_owned()).unwrap_or_else(|| event.default_game_count.to_string()));
+                        label(class = "help") : "(Matches are best of this many games, which must be an odd number. Drawn games are replayed.)";
+                    });
+                    : form_field("seeding", &mut errors, html! {
+                        label(for = "seeding") : "Seeding:";
+                        select(name = "seeding") {
+                            @for (value, description) in &seeding_sources {
+                                option(value = value, selected? = defaults.create_value("seeding") == Some(&**value)) : description;
+                            }
+                        }
+                    });
+                    : form_field("num_teams", &mut errors, html! {
+                        label(for = "num_teams") : "Number of teams:";
+                        input(type = "number", min = "2", name = "num_teams", value? = defaults.create_value("num_teams"));
+                        label(class = "help") : "(Only the top seeds advance to the bracket. Leave blank to include all seeded teams.)";
+                    });
+                }, errors, "Create");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(configure_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Brackets — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/brackets")]
+pub(crate) async fn configure_get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(bracket_form(transaction, me, uri, csrf.as_ref(), data, BracketFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct CreateForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    kind: Kind,
+    game_count: i16,
+    seeding: String,
+    num_teams: Option<i16>,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/brackets", data = "<form>")]
+pub(crate) async fn create(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, CreateForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let phase = value.phase.trim();
+        if phase.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a phase.").with_name("phase"));
+        } else if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM brackets WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, phase).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is already a bracket for this phase.").with_name("phase"));
+        }
+        if value.game_count < 1 {
+            form.context.push_error(form::Error::validation("Matches must consist of at least one game.").with_name("game_count"));
+        } else if value.game_count % 2 == 0 {
+            form.context.push_error(form::Error::validation("Matches must consist of an odd number of games so they can't end in a tie.").with_name("game_count"));
+        }
+        let teams = if let Some(mut teams) = seeded_teams(&mut transaction, &data, phase, &value.seeding).await? {
+            if let Some(num_teams) = value.num_teams {
+                if num_teams < 2 {
+                    form.context.push_error(form::Error::validation("A bracket must have at least 2 teams.").with_name("num_teams"));
+                } else if usize::try_from(num_teams).expect("checked above") > teams.len() {
+                    form.context.push_error(form::Error::validation(format!("This seeding source only has {} teams.", teams.len())).with_name("num_teams"));
+                } else {
+                    teams.truncate(num_teams.try_into().expect("checked above"));
+                }
+            }
+            let min_teams = match value.kind {
+                Kind::SingleElimination => 2,
+                Kind::DoubleElimination => 3,
+            };
+            if teams.len() < min_teams {
+                form.context.push_error(form::Error::validation(format!("At least {min_teams} seeded teams are required for this format.")).with_name("seeding"));
+            } else if i16::try_from(teams.len().next_power_of_two()).is_err() {
+                form.context.push_error(form::Error::validation("Too many teams for a bracket.").with_name("seeding"));
+            }
+            teams
+        } else {
+            form.context.push_error(form::Error::validation("Invalid seeding source.").with_name("seeding"));
+            Vec::default()
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(bracket_form(transaction, Some(me), uri, csrf.as_ref(), data, BracketFormDefaults::CreateContext(form.context)).await?)
+        } else {
+            let bracket = Bracket {
+                phase: phase.to_owned(),
+                kind: value.kind,
+                size: teams.len().next_power_of_two().try_into().expect("checked above"),
+                game_count: value.game_count,
+            };
+            sqlx::query!("DELETE FROM seeds WHERE series = $1 AND event = $2 AND phase = $3", data.series as _, &data.event, phase).execute(&mut *transaction).await?;
+            for (seed, team) in (1..).zip(teams) {
+                sqlx::query!("INSERT INTO seeds (series, event, phase, team, seed) VALUES ($1, $2, $3, $4, $5)", data.series as _, &data.event, phase, team as _, seed).execute(&mut *transaction).await?;
+            }
+            sqlx::query!("INSERT INTO brackets (series, event, phase, kind, size, game_count) VALUES ($1, $2, $3, $4, $5, $6)", data.series as _, &data.event, phase, bracket.kind as _, bracket.size, bracket.game_count).execute(&mut *transaction).await?;
+            for id in bracket.match_ids() {
+                sqlx::query!("INSERT INTO bracket_matches (series, event, phase, side, round, position) VALUES ($1, $2, $3, $4, $5, $6)", data.series as _, &data.event, phase, id.side as _, id.round, id.position).execute(&mut *transaction).await?;
+            }
+            transaction = resolve(transaction, &*discord_ctx.read().await, http_client, &data, &bracket).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(bracket_form(transaction, Some(me), uri, csrf.as_ref(), data, BracketFormDefaults::CreateContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct ReportForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    side: Side,
+    round: i16,
+    position: i16,
+    winner: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/brackets/report", data = "<form>")]
+pub(crate) async fn report(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, ReportForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        let id = MatchId { side: value.side, round: value.round, position: value.position };
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let bracket = Bracket::load(&mut transaction, &data, &value.phase).await?;
+        let winner = if bracket.is_some() {
+            let mut matches = load_matches(&mut transaction, &data, &value.phase).await?;
+            match matches.remove(&id) {
+                Some(m) if m.is_playable() => match &*value.winner {
+                    "team1" => m.team1,
+                    "team2" => m.team2,
+                    _ => {
+                        form.context.push_error(form::Error::validation("Invalid winner.").with_name("winner"));
+                        None
+                    }
+                },
+                Some(_) => {
+                    form.context.push_error(form::Error::validation("This match isn't in progress."));
+                    None
+                }
+                None => {
+                    form.context.push_error(form::Error::validation("There is no such match."));
+                    None
+                }
+            }
+        } else {
+            form.context.push_error(form::Error::validation("There is no bracket for this phase."));
+            None
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(bracket_form(transaction, Some(me), uri, csrf.as_ref(), data, BracketFormDefaults::ReportContext(value.phase.clone(), id, form.context)).await?)
+        } else {
+            let bracket = bracket.expect("checked above");
+            let winner = winner.expect("checked above");
+            transaction = decide(transaction, &*discord_ctx.read().await, http_client, &data, &bracket, id, winner).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(configure_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(bracket_form(transaction, Some(me), uri, csrf.as_ref(), data, BracketFormDefaults::None).await?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bracket(kind: Kind, size: i16) -> Bracket {
+        Bracket { phase: format!("Top {size}"), kind, size, game_count: 1 }
+    }
+
+    #[test]
+    fn seed_positions_small() {
+        assert_eq!(seed_positions(1), [1]);
+        assert_eq!(seed_positions(2), [1, 2]);
+        assert_eq!(seed_positions(4), [1, 4, 2, 3]);
+        assert_eq!(seed_positions(8), [1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn seed_positions_pair_top_and_bottom_seeds() {
+        for size in [2, 4, 8, 16, 32, 64] {
+            let positions = seed_positions(size);
+            assert_eq!(positions.iter().copied().sorted().collect_vec(), (1..=i32::from(size)).collect_vec());
+            for pair in positions.chunks(2) {
+                assert_eq!(pair[0] + pair[1], i32::from(size) + 1, "unbalanced first round match in bracket of size {size}: {pair:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_sizes() {
+        let single = bracket(Kind::SingleElimination, 8);
+        assert_eq!(single.winners_rounds(), 3);
+        assert_eq!(single.losers_rounds(), 0);
+        assert_eq!((1..=3).map(|round| single.round_size(Side::Winners, round)).collect_vec(), [4, 2, 1]);
+        let double = bracket(Kind::DoubleElimination, 8);
+        assert_eq!(double.losers_rounds(), 4);
+        assert_eq!((1..=4).map(|round| double.round_size(Side::Losers, round)).collect_vec(), [2, 2, 1, 1]);
+        assert_eq!(double.round_size(Side::GrandFinal, 1), 1);
+        let double = bracket(Kind::DoubleElimination, 16);
+        assert_eq!((1..=6).map(|round| double.round_size(Side::Losers, round)).collect_vec(), [4, 4, 2, 2, 1, 1]);
+    }
+
+    /// Checks that every match is only fed by earlier matches, every seed enters the bracket exactly once,
+    /// every winner except the champion advances exactly once, and in double elimination every winners bracket loser drops down exactly once.
+    fn check_feeders(bracket: &Bracket) {
+        let ids = bracket.match_ids();
+        let mut seeds = Vec::default();
+        let mut winners_fed = HashMap::<MatchId, usize>::default();
+        let mut losers_fed = HashMap::<MatchId, usize>::default();
+        for (idx, &id) in ids.iter().enumerate() {
+            for feeder in bracket.feeders(id) {
+                match feeder {
+                    Feeder::Seed(seed) => seeds.push(seed),
+                    Feeder::Winner(source) => {
+                        assert!(ids[..idx].contains(&source), "{id:?} is fed by the winner of {source:?}, which doesn't come before it");
+                        *winners_fed.entry(source).or_default() += 1;
+                    }
+                    Feeder::Loser(source) => {
+                        assert!(ids[..idx].contains(&source), "{id:?} is fed by the loser of {source:?}, which doesn't come before it");
+                        assert_eq!(source.side, Side::Winners);
+                        *losers_fed.entry(source).or_default() += 1;
+                    }
+                }
+            }
+        }
+        seeds.sort_unstable();
+        assert_eq!(seeds, (1..=i32::from(bracket.size)).collect_vec());
+        let final_id = *ids.last().expect("bracket has no matches");
+        for &id in &ids {
+            assert_eq!(winners_fed.get(&id).copied().unwrap_or_default(), if id == final_id { 0 } else { 1 }, "winner of {id:?}");
+            let expected_losers = if let (Kind::DoubleElimination, Side::Winners) = (bracket.kind, id.side) { 1 } else { 0 };
+            assert_eq!(losers_fed.get(&id).copied().unwrap_or_default(), expected_losers, "loser of {id:?}");
+        }
+    }
+
+    #[test]
+    fn feeders_single_elimination() {
+        for size in [2, 4, 8, 16, 32] {
+            check_feeders(&bracket(Kind::SingleElimination, size));
+        }
+    }
+
+    #[test]
+    fn feeders_double_elimination() {
+        for size in [4, 8, 16, 32] {
+            check_feeders(&bracket(Kind::DoubleElimination, size));
+        }
+    }
+}
@@ -1,23 +1,46 @@
-use crate::{
-    event::{
-        Data,
-        Tab,
+use {
+    sqlx::types::Json,
+    crate::{
+        conflict,
+        draft::{
+            Action,
+            timer::TimedStep,
+        },
+        event::{
+            Data,
+            Tab,
+            create::{
+                is_valid_slug,
+                parse_datetime,
+            },
+            enter,
+        },
+        prelude::*,
     },
-    prelude::*,
 };
 
 async fn configure_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, ctx: Context<'_>) -> Result<RawHtml<String>, event::Error> {
     let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, false).await?;
     let content = if event.is_ended() {
+        let is_organizer = if let Some(ref me) = me { event.organizers(&mut transaction).await?.contains(me) } else { false };
         html! {
             article {
                 p : "This event has ended and can no longer be configured.";
+                @if is_organizer {
+                    p {
+                        a(href = uri!(clone_get(event.series, &*event.event))) : "Create a new event based on this one";
+                    }
+                }
             }
         }
     } else if let Some(ref me) = me {
         if event.organizers(&mut transaction).await?.contains(me) {
             let mut errors = ctx.errors().collect_vec();
+            let rejected = sqlx::query_scalar!("SELECT rejected FROM events WHERE series = $1 AND event = $2", event.series as _, &event.event).fetch_one(&mut *transaction).await?;
             html! {
+                @if rejected {
+                    p : "This event was not approved by an admin, so it won't be listed on the front page. You can still configure it and share its link with others.";
+                }
                 @if event.series == Series::Standard && event.event == "w" {
                     p {
                         : "Preroll mode: ";
@@ -67,6 +90,13 @@ async fn configure_form(mut transaction: Transaction<'_, Postgres>, me: Option<U
                             input(type = "text", name = "min_schedule_notice", value = ctx.field_value("min_schedule_notice").map(Cow::Borrowed).unwrap_or_else(|| Cow::Owned(unparse_duration(event.min_schedule_notice)))); //TODO h:m:s fields?
                             label(class = "help") : "(Races must be scheduled at least this far in advance. Can be configured to be as low as 0 seconds, but note that if a race is scheduled less than 30 minutes in advance, the room is opened immediately, and if a race is scheduled less than 15 minutes in advance, the seed is posted immediately.)";
                         });
+                        @if event.speedgaming_slug.is_none() {
+                            : form_field("reschedule_confirmation", &mut errors, html! {
+                                input(type = "checkbox", id = "reschedule_confirmation", name = "reschedule_confirmation", checked? = ctx.field_value("reschedule_confirmation").map_or(event.reschedule_confirmation, |value| value == "on"));
+                                label(for = "reschedule_confirmation") : "Require opponent confirmation for rescheduling";
+                                label(class = "help") : "(If this option is turned on, a team changing the start time of an already scheduled race creates a request which the opponent can accept or decline. Organizers can still reschedule races directly.)";
+                            });
+                        }
                         @if matches!(event.match_source(), MatchSource::StartGG(_)) || event.discord_race_results_channel.is_some() {
                             : form_field("retime_window", &mut errors, html! {
                                 label(for = "retime_window") : "Retime window:";
@@ -93,6 +123,35 @@ async fn configure_form(mut transaction: Transaction<'_, Postgres>, me: Option<U
                     li {
                         a(href = uri!(restreamers_get(event.series, &*event.event))) : "Manage restream coordinators";
                     }
+                    li {
+                        a(href = uri!(conflicts(event.series, &*event.event))) : "Check for scheduling conflicts";
+                    }
+                    li {
+                        a(href = uri!(seeding_get(event.series, &*event.event))) : "Manage seeding";
+                    }
+                    li {
+                        a(href = uri!(deadlines_get(event.series, &*event.event))) : "Manage scheduling deadlines";
+                    }
+                    @if event.draft_kind().is_some() {
+                        li {
+                            a(href = uri!(draft_timers_get(event.series, &*event.event))) : "Manage settings draft time limits";
+                        }
+                    }
+                    li {
+                        a(href = uri!(enter_flow_get(event.series, &*event.event))) : "Edit enter flow";
+                    }
+                    li {
+                        a(href = uri!(super::swiss::get(event.series, &*event.event))) : "Manage Swiss phases";
+                    }
+                    li {
+                        a(href = uri!(super::groups::get(event.series, &*event.event))) : "Manage group phases";
+                    }
+                    li {
+                        a(href = uri!(super::bracket::configure_get(event.series, &*event.event))) : "Manage brackets";
+                    }
+                    li {
+                        a(href = uri!(clone_get(event.series, &*event.event))) : "Create a new event based on this one";
+                    }
                 }
             }
         } else {
@@ -134,6 +193,7 @@ pub(crate) struct ConfigureForm {
     min_schedule_notice: String,
     retime_window: Option<String>,
     manual_reporting_with_breaks: bool,
+    reschedule_confirmation: bool,
 }
 
 #[rocket::post("/event/<series>/<event>/configure", data = "<form>")]
@@ -174,6 +234,9 @@ pub(crate) async fn post(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf:
             if let Some(min_schedule_notice) = min_schedule_notice {
                 sqlx::query!("UPDATE events SET min_schedule_notice = $1 WHERE series = $2 AND event = $3", min_schedule_notice as _, data.series as _, &data.event).execute(&mut *transaction).await?;
             }
+            if data.speedgaming_slug.is_none() {
+                sqlx::query!("UPDATE events SET reschedule_confirmation = $1 WHERE series = $2 AND event = $3", value.reschedule_confirmation, data.series as _, &data.event).execute(&mut *transaction).await?;
+            }
             if let Some(retime_window) = retime_window {
                 sqlx::query!("UPDATE events SET retime_window = $1 WHERE series = $2 AND event = $3", retime_window as _, data.series as _, &data.event).execute(&mut *transaction).await?;
             }
@@ -188,6 +251,80 @@ pub(crate) async fn post(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf:
     })
 }
 
+#[rocket::get("/event/<series>/<event>/configure/conflicts")]
+pub(crate) async fn conflicts(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, series: Series, event: &str) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let event = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let now = Utc::now();
+            let mut races_with_conflicts = Vec::default();
+            for race in Race::for_event(&mut transaction, http_client, &event).await? {
+                let RaceSchedule::Live { start, end: None, .. } = race.schedule else { continue };
+                if race.ignored || start + race.series.default_race_duration() < now { continue }
+                let conflicts = conflict::for_race(&mut transaction, &race).await?;
+                if !conflicts.is_empty() {
+                    races_with_conflicts.push((race, start, conflicts));
+                }
+            }
+            html! {
+                h2 : "Scheduling conflicts";
+                p : "Upcoming races of this event which overlap with another race sharing a player or a restreamer, including races from other events:";
+                @if races_with_conflicts.is_empty() {
+                    p : "No conflicts found.";
+                } else {
+                    table {
+                        thead {
+                            tr {
+                                th : "Start";
+                                th : "Race";
+                                th : "Conflicts";
+                            }
+                        }
+                        tbody {
+                            @for (race, start, conflicts) in races_with_conflicts {
+                                tr {
+                                    td : format_datetime(start, DateTimeFormat { long: false, running_text: false });
+                                    td {
+                                        a(href = uri!(cal::race_info(event.series, &*event.event, race.id))) : conflict::describe_round(race.phase.as_deref(), race.round.as_deref(), race.game).unwrap_or_else(|| format!("Race"));
+                                    }
+                                    td {
+                                        ul {
+                                            @for conflict in conflicts {
+                                                li : conflict;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(conflicts(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to view this page.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Scheduling conflicts — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
 enum RestreamersFormDefaults<'v> {
     None,
     AddContext(Context<'v>),
@@ -367,3 +504,1634 @@ pub(crate) async fn remove_restreamer(pool: &State<PgPool>, me: User, uri: Origi
         RedirectOrContent::Content(restreamers_form(transaction, Some(me), uri, csrf.as_ref(), data, RestreamersFormDefaults::RemoveContext(restreamer, form.context)).await?)
     })
 }
+
+enum SeedingFormDefaults<'v> {
+    None,
+    AddContext(Context<'v>),
+    RemoveContext(String, Id<Teams>, Context<'v>),
+}
+
+impl<'v> SeedingFormDefaults<'v> {
+    fn remove_errors(&self, for_phase: &str, for_team: Id<Teams>) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::RemoveContext(phase, team, ctx) if phase == for_phase && *team == for_team => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn add_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::AddContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn field_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::AddContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+}
+
+async fn seeding_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: SeedingFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let seeds = sqlx::query!(r#"SELECT phase, team AS "team: Id<Teams>", seed FROM seeds WHERE series = $1 AND event = $2 ORDER BY phase, seed"#, event.series as _, &event.event).fetch_all(&mut *transaction).await?;
+            let mut seed_data = Vec::with_capacity(seeds.len());
+            for row in seeds {
+                let team = Team::from_id(&mut transaction, row.team).await?.expect("foreign key constraint violated");
+                seed_data.push((row.phase, row.seed, team.to_html(&mut transaction, false).await?, row.team));
+            }
+            let teams = Team::for_event(&mut transaction, event.series, &event.event).await?;
+            let mut team_data = Vec::with_capacity(teams.len());
+            for team in teams {
+                let name = if let Some(name) = team.name(&mut transaction).await? {
+                    name.into_owned()
+                } else {
+                    format!("unnamed team ({})", English.join_str_opt(team.members(&mut transaction).await?).unwrap_or_else(|| format!("no members")))
+                };
+                team_data.push((team.id.to_string(), name));
+            }
+            team_data.sort_unstable_by(|(_, name1), (_, name2)| name1.cmp(name2));
+            html! {
+                h2 : "Manage seeding";
+                p : "When a match in a phase listed here is created or imported, the seeding below is used to determine the high seed for the settings draft in game 1. The lower seed number is the higher seed. If either team has no seed for the match's phase, the high seed is determined as usual for this event.";
+                @if seed_data.is_empty() {
+                    p : "No seeds so far.";
+                } else {
+                    table {
+                        thead {
+                            tr {
+                                th : "Phase";
+                                th : "Seed";
+                                @if let TeamConfig::Solo = event.team_config {
+                                    th : "Player";
+                                } else {
+                                    th : "Team";
+                                }
+                                th;
+                            }
+                        }
+                        tbody {
+                            @for (phase, seed, team, team_id) in seed_data {
+                                tr {
+                                    td : &phase;
+                                    td : seed;
+                                    td : team;
+                                    td {
+                                        @let errors = defaults.remove_errors(&phase, team_id);
+                                        @let (errors, button) = button_form_ext(uri!(remove_seed(event.series, &*event.event, team_id)), csrf, errors, html! {
+                                            input(type = "hidden", name = "phase", value = &phase);
+                                        }, "Remove");
+                                        : errors;
+                                        div(class = "button-row") : button;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h3 : "Add seed";
+                @let mut errors = defaults.add_errors();
+                : full_form(uri!(add_seed(event.series, &*event.event)), csrf, html! {
+                    : form_field("phase", &mut errors, html! {
+                        label(for = "phase") : "Phase:";
+                        input(type = "text", name = "phase", value? = defaults.field_value("phase"));
+                        label(class = "help") : "(Must match the phase of the races exactly, e.g. “Top 8”.)";
+                    });
+                    : form_field("team", &mut errors, html! {
+                        label(for = "team") {
+                            @if let TeamConfig::Solo = event.team_config {
+                                : "Player:";
+                            } else {
+                                : "Team:";
+                            }
+                        }
+                        select(name = "team") {
+                            @for (id, name) in &team_data {
+                                option(value = id, selected? = defaults.field_value("team") == Some(id)) : name;
+                            }
+                        }
+                    });
+                    : form_field("seed", &mut errors, html! {
+                        label(for = "seed") : "Seed:";
+                        input(type = "number", min = "1", name = "seed", value? = defaults.field_value("seed"));
+                        label(class = "help") : "(If this team already has a seed in this phase, it will be replaced.)";
+                    });
+                }, errors, "Save");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(seeding_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Manage seeding — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/seeding")]
+pub(crate) async fn seeding_get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(seeding_form(transaction, me, uri, csrf.as_ref(), data, SeedingFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct AddSeedForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    team: Id<Teams>,
+    seed: i32,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/seeding", data = "<form>")]
+pub(crate) async fn add_seed(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, AddSeedForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let phase = value.phase.trim();
+        if phase.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a phase.").with_name("phase"));
+        }
+        if value.seed < 1 {
+            form.context.push_error(form::Error::validation("Seeds must be positive.").with_name("seed"));
+        }
+        if !Team::for_event(&mut transaction, data.series, &data.event).await?.into_iter().any(|team| team.id == value.team) {
+            form.context.push_error(form::Error::validation("This team is not part of this event.").with_name("team"));
+        }
+        if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM seeds WHERE series = $1 AND event = $2 AND phase = $3 AND seed = $4 AND team <> $5) AS "exists!""#, data.series as _, &data.event, phase, value.seed, value.team as _).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("Another team already has this seed in this phase.").with_name("seed"));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(seeding_form(transaction, Some(me), uri, csrf.as_ref(), data, SeedingFormDefaults::AddContext(form.context)).await?)
+        } else {
+            sqlx::query!("INSERT INTO seeds (series, event, phase, team, seed) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (series, event, phase, team) DO UPDATE SET seed = EXCLUDED.seed", data.series as _, &data.event, phase, value.team as _, value.seed).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(seeding_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(seeding_form(transaction, Some(me), uri, csrf.as_ref(), data, SeedingFormDefaults::AddContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct RemoveSeedForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/seeding/<team>/remove", data = "<form>")]
+pub(crate) async fn remove_seed(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, team: Id<Teams>, form: Form<Contextual<'_, RemoveSeedForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(seeding_form(transaction, Some(me), uri, csrf.as_ref(), data, SeedingFormDefaults::RemoveContext(value.phase.clone(), team, form.context)).await?)
+        } else {
+            sqlx::query!("DELETE FROM seeds WHERE series = $1 AND event = $2 AND phase = $3 AND team = $4", data.series as _, &data.event, value.phase, team as _).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(seeding_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(seeding_form(transaction, Some(me), uri, csrf.as_ref(), data, SeedingFormDefaults::None).await?)
+    })
+}
+
+enum DeadlinesFormDefaults<'v> {
+    None,
+    SetContext(Context<'v>),
+    RemoveContext(String, String, Context<'v>),
+}
+
+impl<'v> DeadlinesFormDefaults<'v> {
+    fn remove_errors(&self, for_phase: &str, for_round: &str) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::RemoveContext(phase, round, ctx) if phase == for_phase && round == for_round => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn set_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::SetContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn field_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::SetContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+}
+
+async fn deadlines_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: DeadlinesFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let deadlines = sqlx::query!(r#"SELECT phase, round, deadline AS "deadline!", deadline_reminder FROM phase_round_options WHERE series = $1 AND event = $2 AND deadline IS NOT NULL ORDER BY deadline, phase, round"#, event.series as _, &event.event).fetch_all(&mut *transaction).await?;
+            let mut deadline_data = Vec::with_capacity(deadlines.len());
+            for row in deadlines {
+                deadline_data.push((row.phase, row.round, row.deadline, decode_pginterval(row.deadline_reminder).map_err(event::DataError::PgInterval)?));
+            }
+            html! {
+                h2 : "Manage scheduling deadlines";
+                p : "Races in a phase and round listed here which are still unscheduled get a reminder in their scheduling thread the specified time before the deadline. Once the deadline has passed, they are listed in the organizer channel.";
+                @if deadline_data.is_empty() {
+                    p : "No deadlines so far.";
+                } else {
+                    table {
+                        thead {
+                            tr {
+                                th : "Phase";
+                                th : "Round";
+                                th : "Deadline";
+                                th : "Reminder";
+                                th;
+                            }
+                        }
+                        tbody {
+                            @for (phase, round, deadline, reminder) in deadline_data {
+                                tr {
+                                    td : &phase;
+                                    td : &round;
+                                    td : format_datetime(deadline, DateTimeFormat { long: false, running_text: false });
+                                    td : English.format_duration(reminder, true);
+                                    td {
+                                        @let errors = defaults.remove_errors(&phase, &round);
+                                        @let (errors, button) = button_form_ext(uri!(remove_deadline(event.series, &*event.event)), csrf, errors, html! {
+                                            input(type = "hidden", name = "phase", value = &phase);
+                                            input(type = "hidden", name = "round", value = &round);
+                                        }, "Remove");
+                                        : errors;
+                                        div(class = "button-row") : button;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h3 : "Set deadline";
+                @let mut errors = defaults.set_errors();
+                : full_form(uri!(set_deadline(event.series, &*event.event)), csrf, html! {
+                    : form_field("phase", &mut errors, html! {
+                        label(for = "phase") : "Phase:";
+                        input(type = "text", name = "phase", value? = defaults.field_value("phase"));
+                        label(class = "help") : "(Must match the phase of the races exactly, e.g. “Swiss”.)";
+                    });
+                    : form_field("round", &mut errors, html! {
+                        label(for = "round") : "Round:";
+                        input(type = "text", name = "round", value? = defaults.field_value("round"));
+                        label(class = "help") : "(Must match the round of the races exactly, e.g. “Round 1”. If this round already has a deadline, it will be replaced.)";
+                    });
+                    : form_field("deadline", &mut errors, html! {
+                        label(for = "deadline") : "Deadline (UTC):";
+                        input(type = "datetime-local", name = "deadline", value? = defaults.field_value("deadline"));
+                    });
+                    : form_field("reminder", &mut errors, html! {
+                        label(for = "reminder") : "Reminder:";
+                        input(type = "text", name = "reminder", value = defaults.field_value("reminder").map(Cow::Borrowed).unwrap_or_else(|| Cow::Owned(unparse_duration(Duration::from_secs(3 * 24 * 60 * 60)))));
+                        label(class = "help") : "(How long before the deadline unscheduled races should be reminded, formatted like “1:23:45” or “1h 23m 45s”.)";
+                    });
+                }, errors, "Save");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(deadlines_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Manage scheduling deadlines — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/deadlines")]
+pub(crate) async fn deadlines_get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(deadlines_form(transaction, me, uri, csrf.as_ref(), data, DeadlinesFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct SetDeadlineForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    round: String,
+    deadline: String,
+    reminder: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/deadlines", data = "<form>")]
+pub(crate) async fn set_deadline(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, SetDeadlineForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let phase = value.phase.trim();
+        if phase.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a phase.").with_name("phase"));
+        }
+        let round = value.round.trim();
+        if round.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a round.").with_name("round"));
+        }
+        let deadline = parse_datetime(&mut form.context, "deadline", &value.deadline);
+        if value.deadline.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a deadline.").with_name("deadline"));
+        }
+        let reminder = if let Some(time) = parse_duration(&value.reminder, None) {
+            Some(time)
+        } else {
+            form.context.push_error(form::Error::validation("Duration must be formatted like “1:23:45” or “1h 23m 45s”.").with_name("reminder"));
+            None
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(deadlines_form(transaction, Some(me), uri, csrf.as_ref(), data, DeadlinesFormDefaults::SetContext(form.context)).await?)
+        } else {
+            let deadline = deadline.expect("validated");
+            let reminder = reminder.expect("validated");
+            if sqlx::query!("UPDATE phase_round_options SET deadline = $1, deadline_reminder = $2 WHERE series = $3 AND event = $4 AND phase = $5 AND round = $6", deadline, reminder as _, data.series as _, &data.event, phase, round).execute(&mut *transaction).await?.rows_affected() == 0 {
+                sqlx::query!("INSERT INTO phase_round_options (series, event, phase, round, deadline, deadline_reminder) VALUES ($1, $2, $3, $4, $5, $6)", data.series as _, &data.event, phase, round, deadline, reminder as _).execute(&mut *transaction).await?;
+            }
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(deadlines_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(deadlines_form(transaction, Some(me), uri, csrf.as_ref(), data, DeadlinesFormDefaults::SetContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct RemoveDeadlineForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    round: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/deadlines/remove", data = "<form>")]
+pub(crate) async fn remove_deadline(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, RemoveDeadlineForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(deadlines_form(transaction, Some(me), uri, csrf.as_ref(), data, DeadlinesFormDefaults::RemoveContext(value.phase.clone(), value.round.clone(), form.context)).await?)
+        } else {
+            sqlx::query!("UPDATE phase_round_options SET deadline = NULL WHERE series = $1 AND event = $2 AND phase = $3 AND round = $4", data.series as _, &data.event, value.phase, value.round).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(deadlines_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(deadlines_form(transaction, Some(me), uri, csrf.as_ref(), data, DeadlinesFormDefaults::None).await?)
+    })
+}
+
+enum DraftTimersFormDefaults<'v> {
+    None,
+    SetContext(Context<'v>),
+    RemoveContext(TimedStep, Context<'v>),
+}
+
+impl<'v> DraftTimersFormDefaults<'v> {
+    fn remove_errors(&self, for_step: TimedStep) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::RemoveContext(step, ctx) if *step == for_step => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn set_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::SetContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn field_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::SetContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+}
+
+async fn draft_timers_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: DraftTimersFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let rows = sqlx::query!(r#"SELECT step_kind AS "step_kind: TimedStep", time_limit, reminders, default_action AS "default_action: Json<Action>" FROM draft_timers WHERE series = $1 AND event = $2 ORDER BY step_kind"#, event.series as _, &event.event).fetch_all(&mut *transaction).await?;
+            let mut timers = Vec::with_capacity(rows.len());
+            for row in rows {
+                let mut reminders = row.reminders.into_iter().map(decode_pginterval).collect::<Result<Vec<_>, _>>().map_err(event::DataError::PgInterval)?;
+                reminders.sort_unstable_by(|a, b| b.cmp(a));
+                timers.push((row.step_kind, decode_pginterval(row.time_limit).map_err(event::DataError::PgInterval)?, reminders, row.default_action.map(|Json(action)| action)));
+            }
+            html! {
+                h2 : "Manage settings draft time limits";
+                p : "If a kind of draft step listed here has a time limit, teams drafting in their scheduling thread are reminded as the time limit approaches. Once it runs out, a skippable step is skipped, otherwise the default action is taken if possible, or a random choice is made. Drafts in race rooms are not affected.";
+                @if timers.is_empty() {
+                    p : "No time limits so far.";
+                } else {
+                    table {
+                        thead {
+                            tr {
+                                th : "Step";
+                                th : "Time limit";
+                                th : "Reminders";
+                                th : "Default action";
+                                th;
+                            }
+                        }
+                        tbody {
+                            @for (step, time_limit, reminders, default_action) in timers {
+                                tr {
+                                    td : step.display_name();
+                                    td : English.format_duration(time_limit, true);
+                                    td : English.join_str_opt(reminders.iter().map(|&reminder| format!("{} before", English.format_duration(reminder, true)))).unwrap_or_else(|| format!("none"));
+                                    td : default_action.map(|action| action.to_string()).unwrap_or_else(|| format!("random"));
+                                    td {
+                                        @let errors = defaults.remove_errors(step);
+                                        @let (errors, button) = button_form_ext(uri!(remove_draft_timer(event.series, &*event.event)), csrf, errors, html! {
+                                            input(type = "hidden", name = "step", value = step.as_str());
+                                        }, "Remove");
+                                        : errors;
+                                        div(class = "button-row") : button;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h3 : "Set time limit";
+                @let mut errors = defaults.set_errors();
+                : full_form(uri!(set_draft_timer(event.series, &*event.event)), csrf, html! {
+                    : form_field("step", &mut errors, html! {
+                        label(for = "step") : "Step:";
+                        select(name = "step") {
+                            @for step in all::<TimedStep>() {
+                                option(value = step.as_str(), selected? = defaults.field_value("step") == Some(step.as_str())) : step.display_name();
+                            }
+                        }
+                        label(class = "help") : "(If this kind of step already has a time limit, it will be replaced.)";
+                    });
+                    : form_field("time_limit", &mut errors, html! {
+                        label(for = "time_limit") : "Time limit:";
+                        input(type = "text", name = "time_limit", value = defaults.field_value("time_limit").map(Cow::Borrowed).unwrap_or_else(|| Cow::Owned(unparse_duration(Duration::from_secs(24 * 60 * 60)))));
+                        label(class = "help") : "(Formatted like “1:23:45” or “1h 23m 45s”.)";
+                    });
+                    : form_field("reminders", &mut errors, html! {
+                        label(for = "reminders") : "Reminders:";
+                        input(type = "text", name = "reminders", value? = defaults.field_value("reminders"));
+                        label(class = "help") : "(Optional. A comma-separated list of how long before the time limit runs out reminders should be posted, e.g. “6h, 1h”.)";
+                    });
+                    : form_field("default_action", &mut errors, html! {
+                        label(for = "default_action") : "Default action:";
+                        input(type = "text", name = "default_action", value? = defaults.field_value("default_action"));
+                        label(class = "help") : "(Optional. “first” or “second” for choosing whether to go first, the setting name for bans, “setting: value” for picks, or “yes” or “no” for yes/no choices. Leave blank to make a random choice.)";
+                    });
+                }, errors, "Save");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(draft_timers_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Manage settings draft time limits — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/draft-timers")]
+pub(crate) async fn draft_timers_get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(draft_timers_form(transaction, me, uri, csrf.as_ref(), data, DraftTimersFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct SetDraftTimerForm {
+    #[field(default = String::new())]
+    csrf: String,
+    step: TimedStep,
+    time_limit: String,
+    reminders: String,
+    default_action: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/draft-timers", data = "<form>")]
+pub(crate) async fn set_draft_timer(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, SetDraftTimerForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if data.draft_kind().is_none() {
+            form.context.push_error(form::Error::validation("This event has no settings draft."));
+        }
+        let time_limit = if let Some(time_limit) = parse_duration(&value.time_limit, None).filter(|time_limit| !time_limit.is_zero()) {
+            Some(time_limit)
+        } else {
+            form.context.push_error(form::Error::validation("Duration must be formatted like “1:23:45” or “1h 23m 45s”.").with_name("time_limit"));
+            None
+        };
+        let mut reminders = Vec::default();
+        for reminder in value.reminders.split(',').map(str::trim).filter(|reminder| !reminder.is_empty()) {
+            if let Some(reminder) = parse_duration(reminder, None) {
+                if time_limit.is_some_and(|time_limit| reminder >= time_limit) {
+                    form.context.push_error(form::Error::validation("Reminders must be shorter than the time limit.").with_name("reminders"));
+                    break
+                }
+                reminders.push(reminder);
+            } else {
+                form.context.push_error(form::Error::validation("Reminders must be formatted like “1:23:45” or “1h 23m 45s” and separated by commas.").with_name("reminders"));
+                break
+            }
+        }
+        let default_action = if value.default_action.trim().is_empty() {
+            None
+        } else if let Some(action) = value.step.parse_default_action(&value.default_action) {
+            Some(action)
+        } else {
+            form.context.push_error(form::Error::validation("This default action is not valid for this kind of step.").with_name("default_action"));
+            None
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(draft_timers_form(transaction, Some(me), uri, csrf.as_ref(), data, DraftTimersFormDefaults::SetContext(form.context)).await?)
+        } else {
+            let time_limit = time_limit.expect("validated");
+            sqlx::query!("INSERT INTO draft_timers (series, event, step_kind, time_limit, reminders, default_action) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (series, event, step_kind) DO UPDATE SET time_limit = EXCLUDED.time_limit, reminders = EXCLUDED.reminders, default_action = EXCLUDED.default_action",
+                data.series as _,
+                &data.event,
+                value.step as _,
+                time_limit as _,
+                &reminders as _,
+                default_action.map(Json) as _,
+            ).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(draft_timers_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(draft_timers_form(transaction, Some(me), uri, csrf.as_ref(), data, DraftTimersFormDefaults::SetContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct RemoveDraftTimerForm {
+    #[field(default = String::new())]
+    csrf: String,
+    step: TimedStep,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/draft-timers/remove", data = "<form>")]
+pub(crate) async fn remove_draft_timer(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, RemoveDraftTimerForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(draft_timers_form(transaction, Some(me), uri, csrf.as_ref(), data, DraftTimersFormDefaults::RemoveContext(value.step, form.context)).await?)
+        } else {
+            sqlx::query!("DELETE FROM draft_timers WHERE series = $1 AND event = $2 AND step_kind = $3", data.series as _, &data.event, value.step as _).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(draft_timers_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(draft_timers_form(transaction, Some(me), uri, csrf.as_ref(), data, DraftTimersFormDefaults::None).await?)
+    })
+}
+
+/// The requirement types that can be added to an enter flow, along with a short description for the type selector.
+const REQUIREMENT_TYPES: [(&str, &str); 20] = [
+    ("raceTime", "Connect a racetime.gg account"),
+    ("raceTimeInvite", "Be on a list of invited racetime.gg users"),
+    ("twitch", "Connect a Twitch account to racetime.gg"),
+    ("discord", "Connect a Discord account"),
+    ("discordGuild", "Join the event's Discord server"),
+    ("challonge", "Connect a Challonge account"),
+    ("startGG", "Connect a start.gg account"),
+    ("textField", "Fill a custom text field"),
+    ("textField2", "Fill a second custom text field"),
+    ("yesNo", "Answer a custom yes/no question"),
+    ("rules", "Agree to the event rules"),
+    ("hardSettingsOk", "Opt in to or out of hard settings"),
+    ("mqOk", "Opt in to or out of Master Quest"),
+    ("liteOk", "Opt in to or out of RSL-Lite"),
+    ("restreamConsent", "Consent to being restreamed"),
+    ("qualifier", "Play the qualifier, live or async"),
+    ("tripleQualifier", "Play one of 3 qualifiers, live or async"),
+    ("qualifierPlacement", "Place within the top players of the qualifiers"),
+    ("rslLeaderboard", "Finish races on the RSL leaderboard"),
+    ("external", "A requirement that is checked manually"),
+];
+
+/// The parameters a newly added requirement of the given type starts out with, to be filled in by the organizer.
+fn requirement_template(kind: &str) -> Option<serde_json::Value> {
+    let now = Utc::now().format("%Y-%m-%dT%H:00:00Z").to_string();
+    Some(match kind {
+        "raceTime" | "twitch" | "discord" | "challonge" | "hardSettingsOk" | "mqOk" | "liteOk" | "rslLeaderboard" => json!({"type": kind}),
+        "raceTimeInvite" => json!({"type": kind, "invites": [], "text": null, "errorText": null}),
+        "discordGuild" => json!({"type": kind, "name": ""}),
+        "startGG" => json!({"type": kind, "optional": false}),
+        "textField" | "textField2" => json!({"type": kind, "label": "", "long": false, "regex": "^.+$", "regexErrorMessages": {}, "fallbackErrorMessage": "This field is required."}),
+        "yesNo" => json!({"type": kind, "label": ""}),
+        "rules" => json!({"type": kind, "document": null}),
+        "restreamConsent" => json!({"type": kind, "optional": false, "note": null}),
+        "qualifier" => json!({"type": kind, "asyncStart": now, "asyncEnd": now, "liveStart": now}),
+        "tripleQualifier" => json!({"type": kind, "asyncStarts": [now, now, now], "asyncEnds": [now, now, now], "liveStarts": [now, now, now]}),
+        "qualifierPlacement" => json!({"type": kind, "numPlayers": 32, "minRaces": 0, "needFinish": false, "event": null, "excludePlayers": 0}),
+        "external" => json!({"type": kind, "html": null, "text": "", "blocksSubmit": true}),
+        _ => return None,
+    })
+}
+
+/// Checks that a requirement can be deserialized, which also checks its regexes.
+fn validate_requirement(series: Series, requirement: &serde_json::Value) -> Result<(), String> {
+    let Some(kind) = requirement.get("type").and_then(|kind| kind.as_str()) else {
+        return Err(format!("The requirement must have a type."))
+    };
+    if !REQUIREMENT_TYPES.iter().any(|&(iter_kind, _)| iter_kind == kind) {
+        return Err(format!("Unknown requirement type: {kind}"))
+    }
+    if matches!(kind, "qualifier" | "tripleQualifier") && series != Series::TriforceBlitz {
+        return Err(format!("Qualifier requirements are currently only supported for Triforce Blitz."))
+    }
+    if let Err(e) = serde_json::from_value::<enter::Requirement>(requirement.clone()) {
+        return Err(format!("Invalid requirement: {e}"))
+    }
+    Ok(())
+}
+
+/// Whether a requirement of the given type has any parameters that can be edited.
+fn has_parameters(kind: &str) -> bool {
+    matches!(kind, "raceTimeInvite" | "discordGuild" | "startGG" | "textField" | "textField2" | "yesNo" | "rules" | "restreamConsent" | "qualifier" | "tripleQualifier" | "qualifierPlacement" | "external")
+}
+
+/// The values to prefill the fields for a requirement's parameters with, taken from the submitted form if there was an error and from the stored requirement otherwise.
+struct RequirementValues<'a, 'v> {
+    requirement: &'a serde_json::Value,
+    ctx: Option<&'a Context<'v>>,
+}
+
+impl RequirementValues<'_, '_> {
+    fn submitted(&self, name: &str) -> Option<String> {
+        self.ctx.and_then(|ctx| ctx.field_value(name)).map(|value| value.to_owned())
+    }
+
+    fn text(&self, name: &str, key: &str) -> String {
+        self.submitted(name).unwrap_or_else(|| self.requirement.get(key).and_then(|value| value.as_str()).unwrap_or_default().to_owned())
+    }
+
+    fn number(&self, name: &str, key: &str) -> String {
+        self.submitted(name).unwrap_or_else(|| self.requirement.get(key).and_then(|value| value.as_u64()).map(|value| value.to_string()).unwrap_or_default())
+    }
+
+    fn checkbox(&self, name: &str, key: &str, default: bool) -> bool {
+        self.ctx.and_then(|ctx| ctx.field_value(name)).map_or_else(|| self.requirement.get(key).and_then(|value| value.as_bool()).unwrap_or(default), |value| value == "on")
+    }
+
+    /// `idx` is used for the triple qualifier, which stores its dates as arrays.
+    fn datetime(&self, name: &str, key: &str, idx: Option<usize>) -> String {
+        self.submitted(name).unwrap_or_else(|| {
+            let value = self.requirement.get(key);
+            let value = if let Some(idx) = idx { value.and_then(|value| value.get(idx)) } else { value };
+            value.and_then(|value| serde_json::from_value::<DateTime<Utc>>(value.clone()).ok()).map(|datetime| datetime.format("%Y-%m-%dT%H:%M").to_string()).unwrap_or_default()
+        })
+    }
+
+    fn lines(&self, name: &str, key: &str) -> String {
+        self.submitted(name).unwrap_or_else(|| self.requirement.get(key).and_then(|value| value.as_array()).into_iter().flatten().filter_map(|line| line.as_str()).join("\n"))
+    }
+
+    fn regex_error_messages(&self, name: &str, key: &str) -> String {
+        self.submitted(name).unwrap_or_else(|| self.requirement.get(key).and_then(|value| value.as_object()).into_iter().flatten().map(|(regex, message)| format!("{regex} => {}", message.as_str().unwrap_or_default())).join("\n"))
+    }
+}
+
+fn text_field(errors: &mut Vec<&form::Error<'_>>, name: &str, field_label: &str, value: String, help: Option<&str>) -> RawHtml<String> {
+    form_field(name, errors, html! {
+        label(for = name) : field_label;
+        input(type = "text", name = name, value = value);
+        @if let Some(help) = help {
+            label(class = "help") : help;
+        }
+    })
+}
+
+fn textarea_field(errors: &mut Vec<&form::Error<'_>>, name: &str, field_label: &str, value: String, help: Option<&str>) -> RawHtml<String> {
+    form_field(name, errors, html! {
+        label(for = name) : field_label;
+        textarea(name = name, rows = value.lines().count().max(2).to_string()) : value;
+        @if let Some(help) = help {
+            label(class = "help") : help;
+        }
+    })
+}
+
+fn checkbox_field(errors: &mut Vec<&form::Error<'_>>, name: &str, field_label: &str, checked: bool, help: Option<&str>) -> RawHtml<String> {
+    form_field(name, errors, html! {
+        input(type = "checkbox", id = name, name = name, checked? = checked);
+        label(for = name) : field_label;
+        @if let Some(help) = help {
+            label(class = "help") : help;
+        }
+    })
+}
+
+fn datetime_field(errors: &mut Vec<&form::Error<'_>>, name: &str, field_label: &str, value: String) -> RawHtml<String> {
+    form_field(name, errors, html! {
+        label(for = name) : field_label;
+        input(type = "datetime-local", name = name, value = value);
+    })
+}
+
+const HTML_HELP: &str = "(May contain basic HTML formatting like <strong> or <em>, and links. Anything else is removed.)";
+
+fn requirement_fields(kind: &str, values: &RequirementValues<'_, '_>, errors: &mut Vec<&form::Error<'_>>) -> RawHtml<String> {
+    match kind {
+        "raceTimeInvite" => html! {
+            : textarea_field(errors, "invites", "Invited players:", values.lines("invites", "invites"), Some("(racetime.gg user IDs, one per line.)"));
+            : textarea_field(errors, "text", "Text:", values.text("text", "text"), Some(HTML_HELP));
+            : text_field(errors, "error_text", "Text for players who aren't invited:", values.text("error_text", "errorText"), Some("(Optional.)"));
+        },
+        "discordGuild" => html! {
+            : text_field(errors, "name", "Server name:", values.text("name", "name"), None);
+        },
+        "startGG" | "restreamConsent" => html! {
+            : checkbox_field(errors, "optional", "Optional", values.checkbox("optional", "optional", false), Some("(Players can enter without meeting this requirement.)"));
+            @if kind == "restreamConsent" {
+                : textarea_field(errors, "note", "Note:", values.text("note", "note"), Some(HTML_HELP));
+            }
+        },
+        "textField" | "textField2" => html! {
+            : textarea_field(errors, "label", "Label:", values.text("label", "label"), Some(HTML_HELP));
+            : checkbox_field(errors, "long", "Multiple lines", values.checkbox("long", "long", false), None);
+            : text_field(errors, "regex", "Regular expression:", values.text("regex", "regex"), Some("(Answers must match this to be accepted.)"));
+            : textarea_field(errors, "regex_error_messages", "Error messages:", values.regex_error_messages("regex_error_messages", "regexErrorMessages"), Some("(One per line, formatted like “regex => message”. The message of the first regular expression matching an invalid answer is shown.)"));
+            : text_field(errors, "fallback_error_message", "Fallback error message:", values.text("fallback_error_message", "fallbackErrorMessage"), Some("(Shown for invalid answers which don't match any of the regular expressions above.)"));
+        },
+        "yesNo" => html! {
+            : textarea_field(errors, "label", "Question:", values.text("label", "label"), Some(HTML_HELP));
+        },
+        "rules" => html! {
+            : text_field(errors, "document", "Rules document URL:", values.text("document", "document"), Some("(Optional, defaults to the event's info page.)"));
+        },
+        "qualifier" => html! {
+            : datetime_field(errors, "async_start1", "Async start (UTC):", values.datetime("async_start1", "asyncStart", None));
+            : datetime_field(errors, "async_end1", "Async end (UTC):", values.datetime("async_end1", "asyncEnd", None));
+            : datetime_field(errors, "live_start1", "Live start (UTC):", values.datetime("live_start1", "liveStart", None));
+        },
+        "tripleQualifier" => html! {
+            : datetime_field(errors, "async_start1", "Qualifier 1 async start (UTC):", values.datetime("async_start1", "asyncStarts", Some(0)));
+            : datetime_field(errors, "async_end1", "Qualifier 1 async end (UTC):", values.datetime("async_end1", "asyncEnds", Some(0)));
+            : datetime_field(errors, "live_start1", "Qualifier 1 live start (UTC):", values.datetime("live_start1", "liveStarts", Some(0)));
+            : datetime_field(errors, "async_start2", "Qualifier 2 async start (UTC):", values.datetime("async_start2", "asyncStarts", Some(1)));
+            : datetime_field(errors, "async_end2", "Qualifier 2 async end (UTC):", values.datetime("async_end2", "asyncEnds", Some(1)));
+            : datetime_field(errors, "live_start2", "Qualifier 2 live start (UTC):", values.datetime("live_start2", "liveStarts", Some(1)));
+            : datetime_field(errors, "async_start3", "Qualifier 3 async start (UTC):", values.datetime("async_start3", "asyncStarts", Some(2)));
+            : datetime_field(errors, "async_end3", "Qualifier 3 async end (UTC):", values.datetime("async_end3", "asyncEnds", Some(2)));
+            : datetime_field(errors, "live_start3", "Qualifier 3 live start (UTC):", values.datetime("live_start3", "liveStarts", Some(2)));
+        },
+        "qualifierPlacement" => html! {
+            : text_field(errors, "num_players", "Number of players:", values.number("num_players", "numPlayers"), Some("(Players must place within this many players in the qualifiers.)"));
+            : text_field(errors, "min_races", "Minimum races:", values.number("min_races", "minRaces"), Some("(Players must have entered at least this many qualifiers.)"));
+            : checkbox_field(errors, "need_finish", "Only count finished races", values.checkbox("need_finish", "needFinish", false), None);
+            : text_field(errors, "qualifier_event", "Qualifier event:", values.text("qualifier_event", "event"), Some("(Optional slug of a different event in this series whose qualifiers should be checked.)"));
+            : text_field(errors, "exclude_players", "Excluded players:", values.number("exclude_players", "excludePlayers"), Some("(Players placing within this many players don't meet this requirement, e.g. because they qualified for a different event.)"));
+        },
+        "external" => html! {
+            : textarea_field(errors, "html", "Description:", values.text("html", "html"), Some(HTML_HELP));
+            : text_field(errors, "text", "Plain text description:", values.text("text", "text"), Some("(Optional, used on Discord.)"));
+            : checkbox_field(errors, "blocks_submit", "Blocks entering", values.checkbox("blocks_submit", "blocksSubmit", true), Some("(Players can't enter until an organizer marks this requirement as met.)"));
+        },
+        _ => html! {},
+    }
+}
+
+/// Builds a requirement of the given type from the submitted parameters, pushing errors for fields that are missing or invalid.
+fn requirement_from_form(kind: &str, value: &EditRequirementForm, ctx: &mut Context<'_>) -> serde_json::Value {
+    fn optional_text(text: &str) -> Option<&str> {
+        let text = text.trim();
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn optional_html(html: &str) -> Option<String> {
+        optional_text(html).map(enter::sanitize_html)
+    }
+
+    fn required_datetime(ctx: &mut Context<'_>, name: &'static str, value: &str) -> Option<DateTime<Utc>> {
+        if value.is_empty() {
+            ctx.push_error(form::Error::validation("This field is required.").with_name(name));
+        }
+        parse_datetime(ctx, name, value)
+    }
+
+    fn number(ctx: &mut Context<'_>, name: &'static str, value: &str, required: bool) -> usize {
+        let value = value.trim();
+        if value.is_empty() && !required { return 0 }
+        value.parse().unwrap_or_else(|_| {
+            ctx.push_error(form::Error::validation("Must be a whole number.").with_name(name));
+            0
+        })
+    }
+
+    match kind {
+        "raceTimeInvite" => json!({
+            "type": kind,
+            "invites": value.invites.lines().map(str::trim).filter(|line| !line.is_empty()).collect_vec(),
+            "text": optional_html(&value.text),
+            "errorText": optional_text(&value.error_text),
+        }),
+        "discordGuild" => {
+            if value.name.trim().is_empty() {
+                ctx.push_error(form::Error::validation("This field is required.").with_name("name"));
+            }
+            json!({"type": kind, "name": value.name.trim()})
+        }
+        "startGG" => json!({"type": kind, "optional": value.optional}),
+        "textField" | "textField2" => {
+            if let Err(e) = lazy_regex::Regex::new(&value.regex) {
+                ctx.push_error(form::Error::validation(format!("Invalid regular expression: {e}")).with_name("regex"));
+            }
+            let mut regex_error_messages = Vec::default();
+            for line in value.regex_error_messages.lines() {
+                let line = line.trim();
+                if line.is_empty() { continue }
+                if let Some((regex, message)) = line.split_once(" => ") {
+                    if let Err(e) = lazy_regex::Regex::new(regex.trim()) {
+                        ctx.push_error(form::Error::validation(format!("Invalid regular expression: {e}")).with_name("regex_error_messages"));
+                    }
+                    regex_error_messages.push((regex.trim().to_owned(), message.trim().to_owned()));
+                } else {
+                    ctx.push_error(form::Error::validation("Each line must be formatted like “regex => message”.").with_name("regex_error_messages"));
+                }
+            }
+            json!({
+                "type": kind,
+                "label": enter::sanitize_html(&value.label),
+                "long": value.long,
+                "regex": value.regex,
+                "regexErrorMessages": regex_error_messages.into_iter().collect::<serde_json::Value>(),
+                "fallbackErrorMessage": value.fallback_error_message.trim(),
+            })
+        }
+        "yesNo" => json!({"type": kind, "label": enter::sanitize_html(&value.label)}),
+        "rules" => {
+            let document = optional_text(&value.document);
+            if let Some(document) = document {
+                if let Err(e) = Url::parse(document) {
+                    ctx.push_error(form::Error::validation(format!("Invalid URL: {e}")).with_name("document"));
+                }
+            }
+            json!({"type": kind, "document": document})
+        }
+        "restreamConsent" => json!({"type": kind, "optional": value.optional, "note": optional_html(&value.note)}),
+        "qualifier" => json!({
+            "type": kind,
+            "asyncStart": required_datetime(ctx, "async_start1", &value.async_start1),
+            "asyncEnd": required_datetime(ctx, "async_end1", &value.async_end1),
+            "liveStart": required_datetime(ctx, "live_start1", &value.live_start1),
+        }),
+        "tripleQualifier" => json!({
+            "type": kind,
+            "asyncStarts": [
+                required_datetime(ctx, "async_start1", &value.async_start1),
+                required_datetime(ctx, "async_start2", &value.async_start2),
+                required_datetime(ctx, "async_start3", &value.async_start3),
+            ],
+            "asyncEnds": [
+                required_datetime(ctx, "async_end1", &value.async_end1),
+                required_datetime(ctx, "async_end2", &value.async_end2),
+                required_datetime(ctx, "async_end3", &value.async_end3),
+            ],
+            "liveStarts": [
+                required_datetime(ctx, "live_start1", &value.live_start1),
+                required_datetime(ctx, "live_start2", &value.live_start2),
+                required_datetime(ctx, "live_start3", &value.live_start3),
+            ],
+        }),
+        "qualifierPlacement" => json!({
+            "type": kind,
+            "numPlayers": number(ctx, "num_players", &value.num_players, true),
+            "minRaces": number(ctx, "min_races", &value.min_races, false),
+            "needFinish": value.need_finish,
+            "event": optional_text(&value.qualifier_event),
+            "excludePlayers": number(ctx, "exclude_players", &value.exclude_players, false),
+        }),
+        "external" => json!({
+            "type": kind,
+            "html": optional_html(&value.html),
+            "text": optional_text(&value.text),
+            "blocksSubmit": value.blocks_submit,
+        }),
+        _ => json!({"type": kind}),
+    }
+}
+
+/// An enter flow as stored in the database, keeping the requirements as JSON so they can be edited individually.
+#[derive(Default, Deserialize, Serialize)]
+struct RawEnterFlow {
+    requirements: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    closes: Option<DateTime<Utc>>,
+}
+
+impl RawEnterFlow {
+    async fn load(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>) -> sqlx::Result<Option<Self>> {
+        Ok(sqlx::query_scalar!(r#"SELECT enter_flow AS "enter_flow: Json<RawEnterFlow>" FROM events WHERE series = $1 AND event = $2"#, event.series as _, &event.event).fetch_one(&mut **transaction).await?.map(|Json(flow)| flow))
+    }
+
+    /// Saves the enter flow if it is valid. Otherwise, returns an error message.
+    async fn save(&self, transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>) -> Result<Result<(), String>, event::Error> {
+        let flow = serde_json::to_value(self)?;
+        if let Err(e) = serde_json::from_value::<enter::Flow>(flow.clone()) {
+            return Ok(Err(format!("The resulting enter flow would be invalid: {e}")))
+        }
+        sqlx::query!("UPDATE events SET enter_flow = $1 WHERE series = $2 AND event = $3", Json(&flow) as _, event.series as _, &event.event).execute(&mut **transaction).await?;
+        Ok(Ok(()))
+    }
+}
+
+enum EnterFlowFormDefaults<'v> {
+    None,
+    RequirementContext(usize, Context<'v>),
+    AddContext(Context<'v>),
+    DeadlineContext(Context<'v>),
+}
+
+impl<'v> EnterFlowFormDefaults<'v> {
+    fn requirement_errors(&self, for_idx: usize) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::RequirementContext(idx, ctx) if *idx == for_idx => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn requirement_context(&self, for_idx: usize) -> Option<&Context<'v>> {
+        match self {
+            Self::RequirementContext(idx, ctx) if *idx == for_idx => Some(ctx),
+            _ => None,
+        }
+    }
+
+    fn add_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::AddContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn add_value(&self) -> Option<&str> {
+        if let Self::AddContext(ctx) = self {
+            ctx.field_value("kind")
+        } else {
+            None
+        }
+    }
+
+    fn deadline_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::DeadlineContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn deadline_value(&self) -> Option<&str> {
+        if let Self::DeadlineContext(ctx) = self {
+            ctx.field_value("closes")
+        } else {
+            None
+        }
+    }
+}
+
+async fn enter_flow_form(mut transaction: Transaction<'_, Postgres>, http_client: &reqwest::Client, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: EnterFlowFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let flow = RawEnterFlow::load(&mut transaction, &event).await?;
+            let num_requirements = flow.as_ref().map_or(0, |flow| flow.requirements.len());
+            let mut requirements = Vec::with_capacity(num_requirements);
+            for (idx, requirement) in flow.iter().flat_map(|flow| &flow.requirements).enumerate() {
+                let kind = requirement.get("type").and_then(|kind| kind.as_str()).unwrap_or_default();
+                let description = REQUIREMENT_TYPES.iter().find(|&&(iter_kind, _)| iter_kind == kind).map_or(kind, |&(_, description)| description);
+                let values = RequirementValues { ctx: defaults.requirement_context(idx), requirement };
+                requirements.push((idx, kind, description, values));
+            }
+            let preview = enter::preview(http_client, &event).await;
+            html! {
+                h2 : "Enter flow";
+                p : "The enter flow is the list of requirements players have to meet to enter this event. Descriptions and labels may contain basic HTML formatting and links. Anything else is removed when saving.";
+                @if !matches!(event.team_config, TeamConfig::Solo) {
+                    p : "Note: The enter flow is currently only used for solo events.";
+                }
+                @if flow.is_none() {
+                    p : "This event does not have an enter flow yet, so signups are not open. Adding a requirement or setting a deadline will open signups.";
+                } else if num_requirements == 0 {
+                    p : "There are no requirements, so anyone can enter this event with a single click.";
+                }
+                @for (idx, kind, description, values) in requirements {
+                    h3 : format!("{}. {description}", idx + 1);
+                    @let mut errors = defaults.requirement_errors(idx);
+                    @if has_parameters(kind) {
+                        : full_form(uri!(edit_requirement(event.series, &*event.event, idx)), csrf, requirement_fields(kind, &values, &mut errors), errors, "Save");
+                    } else {
+                        @for error in errors {
+                            : render_form_error(error);
+                        }
+                        p : "This requirement has no parameters.";
+                    }
+                    div(class = "button-row") {
+                        @if idx > 0 {
+                            : button_form(uri!(move_requirement_up(event.series, &*event.event, idx)), csrf, Vec::default(), "Move up").1;
+                        }
+                        @if idx + 1 < num_requirements {
+                            : button_form(uri!(move_requirement_down(event.series, &*event.event, idx)), csrf, Vec::default(), "Move down").1;
+                        }
+                        : button_form(uri!(remove_requirement(event.series, &*event.event, idx)), csrf, Vec::default(), "Remove").1;
+                    }
+                }
+                h3 : "Add requirement";
+                @let mut errors = defaults.add_errors();
+                : full_form(uri!(add_requirement(event.series, &*event.event)), csrf, html! {
+                    : form_field("kind", &mut errors, html! {
+                        label(for = "kind") : "Type:";
+                        select(name = "kind") {
+                            @for (kind, description) in REQUIREMENT_TYPES {
+                                option(value = kind, selected? = defaults.add_value() == Some(kind)) : description;
+                            }
+                        }
+                        label(class = "help") : "(The requirement is added at the end with placeholder parameters, which can then be edited above.)";
+                    });
+                }, errors, "Add");
+                h3 : "Deadline";
+                @let mut errors = defaults.deadline_errors();
+                : full_form(uri!(enter_flow_deadline(event.series, &*event.event)), csrf, html! {
+                    : form_field("closes", &mut errors, html! {
+                        label(for = "closes") : "Signups close (UTC):";
+                        input(type = "datetime-local", name = "closes", value? = defaults.deadline_value().map(|value| value.to_owned()).or_else(|| flow.as_ref().and_then(|flow| flow.closes).map(|closes| closes.format("%Y-%m-%dT%H:%M").to_string())));
+                        label(class = "help") : "(Leave blank to keep signups open until the event starts.)";
+                    });
+                }, errors, "Save");
+                @if num_requirements > 0 {
+                    h3 : "Preview";
+                    p : "This is how the requirements appear on the Enter tab to a player who hasn't met any of them yet:";
+                    : preview;
+                }
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(enter_flow_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Enter flow — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/enter-flow")]
+pub(crate) async fn enter_flow_get(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(enter_flow_form(transaction, http_client, me, uri, csrf.as_ref(), data, EnterFlowFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct AddRequirementForm {
+    #[field(default = String::new())]
+    csrf: String,
+    kind: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow", data = "<form>")]
+pub(crate) async fn add_requirement(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, AddRequirementForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let mut flow = RawEnterFlow::load(&mut transaction, &data).await?.unwrap_or_default();
+        if let Some(requirement) = requirement_template(&value.kind) {
+            flow.requirements.push(requirement);
+        } else {
+            form.context.push_error(form::Error::validation("Unknown requirement type.").with_name("kind"));
+        }
+        if form.context.errors().next().is_none() {
+            if let Err(msg) = flow.save(&mut transaction, &data).await? {
+                form.context.push_error(form::Error::validation(msg).with_name("kind"));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::AddContext(form.context)).await?)
+        } else {
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(enter_flow_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::AddContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct EnterFlowDeadlineForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = String::new())]
+    closes: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow/deadline", data = "<form>")]
+pub(crate) async fn enter_flow_deadline(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, EnterFlowDeadlineForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let mut flow = RawEnterFlow::load(&mut transaction, &data).await?.unwrap_or_default();
+        flow.closes = parse_datetime(&mut form.context, "closes", &value.closes);
+        if form.context.errors().next().is_none() {
+            if let Err(msg) = flow.save(&mut transaction, &data).await? {
+                form.context.push_error(form::Error::validation(msg).with_name("closes"));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::DeadlineContext(form.context)).await?)
+        } else {
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(enter_flow_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::DeadlineContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct EditRequirementForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = String::new())]
+    invites: String,
+    #[field(default = String::new())]
+    text: String,
+    #[field(default = String::new())]
+    error_text: String,
+    #[field(default = String::new())]
+    name: String,
+    #[field(default = false)]
+    optional: bool,
+    #[field(default = String::new())]
+    label: String,
+    #[field(default = false)]
+    long: bool,
+    #[field(default = String::new())]
+    regex: String,
+    #[field(default = String::new())]
+    regex_error_messages: String,
+    #[field(default = String::new())]
+    fallback_error_message: String,
+    #[field(default = String::new())]
+    document: String,
+    #[field(default = String::new())]
+    note: String,
+    #[field(default = String::new())]
+    async_start1: String,
+    #[field(default = String::new())]
+    async_end1: String,
+    #[field(default = String::new())]
+    live_start1: String,
+    #[field(default = String::new())]
+    async_start2: String,
+    #[field(default = String::new())]
+    async_end2: String,
+    #[field(default = String::new())]
+    live_start2: String,
+    #[field(default = String::new())]
+    async_start3: String,
+    #[field(default = String::new())]
+    async_end3: String,
+    #[field(default = String::new())]
+    live_start3: String,
+    #[field(default = String::new())]
+    num_players: String,
+    #[field(default = String::new())]
+    min_races: String,
+    #[field(default = false)]
+    need_finish: bool,
+    #[field(default = String::new())]
+    qualifier_event: String,
+    #[field(default = String::new())]
+    exclude_players: String,
+    #[field(default = String::new())]
+    html: String,
+    #[field(default = false)]
+    blocks_submit: bool,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow/<idx>", data = "<form>")]
+pub(crate) async fn edit_requirement(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, idx: usize, form: Form<Contextual<'_, EditRequirementForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let mut flow = RawEnterFlow::load(&mut transaction, &data).await?.unwrap_or_default();
+        if let Some(slot) = flow.requirements.get_mut(idx) {
+            let kind = slot.get("type").and_then(|kind| kind.as_str()).unwrap_or_default().to_owned();
+            let requirement = requirement_from_form(&kind, value, &mut form.context);
+            if form.context.errors().next().is_none() {
+                if let Err(msg) = validate_requirement(data.series, &requirement) {
+                    form.context.push_error(form::Error::validation(msg));
+                } else {
+                    *slot = requirement;
+                }
+            }
+        } else {
+            form.context.push_error(form::Error::validation("This requirement no longer exists."));
+        }
+        if form.context.errors().next().is_none() {
+            if let Err(msg) = flow.save(&mut transaction, &data).await? {
+                form.context.push_error(form::Error::validation(msg));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::RequirementContext(idx, form.context)).await?)
+        } else {
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(enter_flow_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::RequirementContext(idx, form.context)).await?)
+    })
+}
+
+/// Common implementation of the buttons that move or remove a requirement, which only differ in how they change the list of requirements.
+async fn rearrange_requirements(pool: &PgPool, http_client: &reqwest::Client, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, idx: usize, form: Form<Contextual<'_, EmptyForm>>, rearrange: impl FnOnce(&mut Vec<serde_json::Value>) -> bool) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if form.value.is_some() {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let mut flow = RawEnterFlow::load(&mut transaction, &data).await?.unwrap_or_default();
+        if !rearrange(&mut flow.requirements) {
+            form.context.push_error(form::Error::validation("This requirement can't be moved there."));
+        }
+        if form.context.errors().next().is_none() {
+            if let Err(msg) = flow.save(&mut transaction, &data).await? {
+                form.context.push_error(form::Error::validation(msg));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::RequirementContext(idx, form.context)).await?)
+        } else {
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(enter_flow_get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(enter_flow_form(transaction, http_client, Some(me), uri, csrf.as_ref(), data, EnterFlowFormDefaults::RequirementContext(idx, form.context)).await?)
+    })
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow/<idx>/up", data = "<form>")]
+pub(crate) async fn move_requirement_up(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, idx: usize, form: Form<Contextual<'_, EmptyForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    rearrange_requirements(pool, http_client, me, uri, csrf, series, event, idx, form, |requirements| if idx > 0 && idx < requirements.len() {
+        requirements.swap(idx - 1, idx);
+        true
+    } else {
+        false
+    }).await
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow/<idx>/down", data = "<form>")]
+pub(crate) async fn move_requirement_down(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, idx: usize, form: Form<Contextual<'_, EmptyForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    rearrange_requirements(pool, http_client, me, uri, csrf, series, event, idx, form, |requirements| if idx + 1 < requirements.len() {
+        requirements.swap(idx, idx + 1);
+        true
+    } else {
+        false
+    }).await
+}
+
+#[rocket::post("/event/<series>/<event>/configure/enter-flow/<idx>/remove", data = "<form>")]
+pub(crate) async fn remove_requirement(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, idx: usize, form: Form<Contextual<'_, EmptyForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    rearrange_requirements(pool, http_client, me, uri, csrf, series, event, idx, form, |requirements| if idx < requirements.len() {
+        requirements.remove(idx);
+        true
+    } else {
+        false
+    }).await
+}
+
+async fn clone_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, ctx: Context<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let mut errors = ctx.errors().collect_vec();
+            html! {
+                h2 : "Create a new event based on this one";
+                p : "The new event will have the same settings, organizers, restream coordinators, round names, settings draft time limits, and signup requirements as this one, but no teams or races. Like any new event, it will be reviewed by an admin before it's listed on the front page.";
+                : full_form(uri!(clone_post(event.series, &*event.event)), csrf, html! {
+                    : form_field("event", &mut errors, html! {
+                        label(for = "event") : "Event slug:";
+                        input(type = "text", name = "event", maxlength = "8", value? = ctx.field_value("event"));
+                        label(class = "help") {
+                            : "(Used in URLs. Up to 8 lowercase letters, digits, or hyphens. This event's slug is ";
+                            code : &*event.event;
+                            : ".)";
+                        }
+                    });
+                    : form_field("display_name", &mut errors, html! {
+                        label(for = "display_name") : "Name:";
+                        input(type = "text", name = "display_name", value? = ctx.field_value("display_name"));
+                    });
+                    : form_field("short_name", &mut errors, html! {
+                        label(for = "short_name") : "Short name:";
+                        input(type = "text", name = "short_name", value? = ctx.field_value("short_name"));
+                        label(class = "help") : "(Optional, used in race room and calendar titles.)";
+                    });
+                    : form_field("start", &mut errors, html! {
+                        label(for = "start") : "Start (UTC):";
+                        input(type = "datetime-local", name = "start", value? = ctx.field_value("start"));
+                        label(class = "help") : "(Optional, can be added later.)";
+                    });
+                    : form_field("end", &mut errors, html! {
+                        label(for = "end") : "End (UTC):";
+                        input(type = "datetime-local", name = "end", value? = ctx.field_value("end"));
+                        label(class = "help") : "(Optional, can be added later.)";
+                    });
+                }, errors, "Create");
+                p : "Dates in the signup requirements, such as qualifier times, are copied unchanged. Remember to update them after creating the new event.";
+                p : "The Discord server is kept, but the race room, results, organizer, and scheduling channels are not copied, since each channel can only belong to one event. An admin can configure new channels for the new event.";
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(clone_get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to create a new event based on this one.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("New Event — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/clone")]
+pub(crate) async fn clone_get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(clone_form(transaction, me, uri, csrf.as_ref(), data, Context::default()).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct CloneForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = String::new())]
+    event: String,
+    #[field(default = String::new())]
+    display_name: String,
+    #[field(default = String::new())]
+    short_name: String,
+    #[field(default = String::new())]
+    start: String,
+    #[field(default = String::new())]
+    end: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/clone", data = "<form>")]
+pub(crate) async fn clone_post(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, CloneForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer of this event to create a new event based on it."));
+        }
+        if !is_valid_slug(&value.event) {
+            form.context.push_error(form::Error::validation("The event slug must consist of 1 to 8 lowercase letters, digits, or hyphens.").with_name("event"));
+        } else if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM events WHERE series = $1 AND event = $2) AS "exists!""#, data.series as _, &value.event).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is already an event with this slug in this series.").with_name("event"));
+        }
+        if value.display_name.trim().is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a name for the event.").with_name("display_name"));
+        }
+        let start = parse_datetime(&mut form.context, "start", &value.start);
+        let end = parse_datetime(&mut form.context, "end", &value.end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                form.context.push_error(form::Error::validation("The event must end after it starts.").with_name("end"));
+            }
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(clone_form(transaction, Some(me), uri, csrf.as_ref(), data, form.context).await?)
+        } else {
+            let short_name = Some(value.short_name.trim()).filter(|short_name| !short_name.is_empty());
+            // URLs and slugs that refer to a specific season elsewhere (bracket, signups, teams, restreams) are not copied.
+            // Neither are Discord channels, since events are looked up by channel and each channel must belong to at most one event.
+            sqlx::query!("INSERT INTO events (
+                series, event, display_name, short_name, start, end_time, listed, pending_approval,
+                discord_guild, discord_invite_url,
+                hide_teams_tab, hide_races_tab, enter_flow, show_qualifier_times, default_game_count, min_schedule_notice, language, show_opt_out, retime_window,
+                auto_import, team_config, challonge_community, open_stream_delay, invitational_stream_delay, rando_version, single_settings,
+                manual_reporting_with_breaks, emulator_settings_reminder, prevent_late_joins, draft_spec, reschedule_confirmation
+            ) SELECT
+                series, $3, $4, $5, $6, $7, FALSE, TRUE,
+                discord_guild, discord_invite_url,
+                hide_teams_tab, hide_races_tab, enter_flow, show_qualifier_times, default_game_count, min_schedule_notice, language, show_opt_out, retime_window,
+                auto_import, team_config, challonge_community, open_stream_delay, invitational_stream_delay, rando_version, single_settings,
+                manual_reporting_with_breaks, emulator_settings_reminder, prevent_late_joins, draft_spec, reschedule_confirmation
+            FROM events WHERE series = $1 AND event = $2", data.series as _, &data.event, &value.event, value.display_name.trim(), short_name, start, end).execute(&mut *transaction).await?;
+            sqlx::query!("INSERT INTO organizers (series, event, organizer) SELECT series, $3, organizer FROM organizers WHERE series = $1 AND event = $2", data.series as _, &data.event, &value.event).execute(&mut *transaction).await?;
+            sqlx::query!("INSERT INTO restreamers (series, event, restreamer) SELECT series, $3, restreamer FROM restreamers WHERE series = $1 AND event = $2", data.series as _, &data.event, &value.event).execute(&mut *transaction).await?;
+            sqlx::query!("INSERT INTO phase_round_options (series, event, phase, round, display_fr, deadline_reminder) SELECT series, $3, phase, round, display_fr, deadline_reminder FROM phase_round_options WHERE series = $1 AND event = $2", data.series as _, &data.event, &value.event).execute(&mut *transaction).await?;
+            sqlx::query!("INSERT INTO draft_timers (series, event, step_kind, time_limit, reminders, default_action) SELECT series, $3, step_kind, time_limit, reminders, default_action FROM draft_timers WHERE series = $1 AND event = $2", data.series as _, &data.event, &value.event).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            let msg = MessageBuilder::default()
+                .mention_user(&me)
+                .push(" requested a new event based on ")
+                .push_safe(&data.display_name)
+                .push(": ")
+                .push_safe(value.display_name.trim())
+                .push(" <")
+                .push(uri!(base_uri(), super::create::pending).to_string())
+                .push('>')
+                .build();
+            let discord_ctx = discord_ctx.read().await;
+            if let Err(e) = async { crate::discord_bot::FENHL.create_dm_channel(&*discord_ctx).await?.say(&*discord_ctx, msg).await }.await {
+                eprintln!("failed to notify admin of requested event {}/{}: {e} ({e:?})", series.slug(), value.event);
+            }
+            RedirectOrContent::Redirect(Redirect::to(uri!(super::info(series, &value.event))))
+        }
+    } else {
+        RedirectOrContent::Content(clone_form(transaction, Some(me), uri, csrf.as_ref(), data, form.context).await?)
+    })
+}
@@ -0,0 +1,534 @@
+//! Self-service event creation. Events created here stay unlisted until approved by an admin.
+
+use {
+    sqlx::types::Json,
+    crate::{
+        discord_bot::FENHL,
+        draft::spec::Spec,
+        event::enter,
+        prelude::*,
+    },
+};
+
+const TEAM_CONFIGS: [(TeamConfig, &str, &str); 5] = [
+    (TeamConfig::Solo, "solo", "Solo"),
+    (TeamConfig::CoOp, "coop", "Co-op (2 players, same world)"),
+    (TeamConfig::TfbCoOp, "tfbcoop", "Triforce Blitz co-op (2 players, 2 worlds)"),
+    (TeamConfig::Pictionary, "pictionary", "Pictionary (runner and pilot)"),
+    (TeamConfig::Multiworld, "multiworld", "Multiworld (3 players, 3 worlds)"),
+];
+
+const MATCH_SOURCES: [(&str, &str); 3] = [
+    ("manual", "Races are created manually on Mido's House"),
+    ("startgg", "Matches are imported from start.gg"),
+    ("challonge", "Matches are imported from Challonge"),
+];
+
+/// Whether new events in this series can be requested here.
+///
+/// The info or status pages of some series are only implemented for their existing events and would panic for new ones.
+fn supports_generic_events(series: Series) -> bool {
+    match series {
+        | Series::BattleRoyale
+        | Series::League
+        | Series::NineDaysOfSaws
+        | Series::Scrubs
+            => false,
+        | Series::CoOp
+        | Series::CopaDoBrasil
+        | Series::CopaLatinoamerica
+        | Series::MixedPools
+        | Series::Mq
+        | Series::Multiworld
+        | Series::Pictionary
+        | Series::PotsOfTime
+        | Series::Rsl
+        | Series::SongsOfHope
+        | Series::SpeedGaming
+        | Series::Standard
+        | Series::TournoiFrancophone
+        | Series::TriforceBlitz
+        | Series::WeTryToBeBetter
+            => true,
+    }
+}
+
+fn is_admin(me: &User) -> bool {
+    me.id == crate::id::FENHL
+}
+
+async fn create_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, ctx: Context<'_>) -> Result<RawHtml<String>, event::Error> {
+    let content = if let Some(ref me) = me {
+        let mut errors = ctx.errors().collect_vec();
+        let checkbox = |name: &str| ctx.field_value(name).is_some_and(|value| value == "on");
+        // the select field is always submitted, so this distinguishes a new form from one with errors
+        let is_submitted = ctx.field_value("series").is_some();
+        html! {
+            p : "If you are planning a tournament, community race, or other event for the Ocarina of Time randomizer community, you can request it here. Your event will be reviewed by an admin before it's listed on the front page. Until then, you can already configure it and share its link with others.";
+            @if is_admin(me) {
+                p {
+                    a(href = uri!(pending)) : "Review pending events";
+                }
+            }
+            : full_form(uri!(post), csrf, html! {
+                h2 : "Basic info";
+                : form_field("series", &mut errors, html! {
+                    label(for = "series") : "Series:";
+                    select(name = "series") {
+                        @for series in all::<Series>().filter(|&series| supports_generic_events(series)) {
+                            option(value = series.slug(), selected? = ctx.field_value("series") == Some(series.slug())) : series.display_name();
+                        }
+                    }
+                });
+                : form_field("event", &mut errors, html! {
+                    label(for = "event") : "Event slug:";
+                    input(type = "text", name = "event", maxlength = "8", value? = ctx.field_value("event"));
+                    label(class = "help") : "(Used in URLs. Up to 8 lowercase letters, digits, or hyphens, e.g. “2025” or “s3”.)";
+                });
+                : form_field("display_name", &mut errors, html! {
+                    label(for = "display_name") : "Name:";
+                    input(type = "text", name = "display_name", value? = ctx.field_value("display_name"));
+                });
+                : form_field("short_name", &mut errors, html! {
+                    label(for = "short_name") : "Short name:";
+                    input(type = "text", name = "short_name", value? = ctx.field_value("short_name"));
+                    label(class = "help") : "(Optional, used in race room and calendar titles.)";
+                });
+                : form_field("team_config", &mut errors, html! {
+                    label(for = "team_config") : "Team format:";
+                    select(name = "team_config") {
+                        @for (_, value, display) in TEAM_CONFIGS {
+                            option(value = value, selected? = ctx.field_value("team_config") == Some(value)) : display;
+                        }
+                    }
+                });
+                : form_field("language", &mut errors, html! {
+                    label(for = "language") : "Language:";
+                    select(name = "language") {
+                        @for language in all::<Language>() {
+                            option(value = language.short_code(), selected? = ctx.field_value("language") == Some(language.short_code())) : language;
+                        }
+                    }
+                });
+                : form_field("start", &mut errors, html! {
+                    label(for = "start") : "Start (UTC):";
+                    input(type = "datetime-local", name = "start", value? = ctx.field_value("start"));
+                    label(class = "help") : "(Optional, can be added later.)";
+                });
+                : form_field("end", &mut errors, html! {
+                    label(for = "end") : "End (UTC):";
+                    input(type = "datetime-local", name = "end", value? = ctx.field_value("end"));
+                    label(class = "help") : "(Optional, can be added later.)";
+                });
+                h2 : "Matches";
+                : form_field("match_source", &mut errors, html! {
+                    label(for = "match_source") : "Match source:";
+                    select(name = "match_source") {
+                        @for (value, display) in MATCH_SOURCES {
+                            option(value = value, selected? = ctx.field_value("match_source") == Some(value)) : display;
+                        }
+                    }
+                });
+                : form_field("url", &mut errors, html! {
+                    label(for = "url") : "Bracket URL:";
+                    input(type = "text", name = "url", value? = ctx.field_value("url"));
+                    label(class = "help") : "(Required if matches are imported from start.gg or Challonge.)";
+                });
+                : form_field("draft_spec", &mut errors, html! {
+                    label(for = "draft_spec") : "Settings draft:";
+                    textarea(name = "draft_spec") : ctx.field_value("draft_spec").unwrap_or_default();
+                    label(class = "help") : "(Optional. If your event uses a settings draft, enter its draft format as JSON. Leave empty for no settings draft.)";
+                });
+                h2 : "Discord";
+                p : "All optional. Enable developer mode in Discord to copy server and channel IDs. Mido's House must be added to the server for these to work.";
+                : form_field("discord_guild", &mut errors, html! {
+                    label(for = "discord_guild") : "Server ID:";
+                    input(type = "text", name = "discord_guild", value? = ctx.field_value("discord_guild"));
+                });
+                : form_field("discord_organizer_channel", &mut errors, html! {
+                    label(for = "discord_organizer_channel") : "Organizer channel ID:";
+                    input(type = "text", name = "discord_organizer_channel", value? = ctx.field_value("discord_organizer_channel"));
+                });
+                : form_field("discord_scheduling_channel", &mut errors, html! {
+                    label(for = "discord_scheduling_channel") : "Scheduling channel ID:";
+                    input(type = "text", name = "discord_scheduling_channel", value? = ctx.field_value("discord_scheduling_channel"));
+                });
+                : form_field("discord_race_room_channel", &mut errors, html! {
+                    label(for = "discord_race_room_channel") : "Race room channel ID:";
+                    input(type = "text", name = "discord_race_room_channel", value? = ctx.field_value("discord_race_room_channel"));
+                });
+                : form_field("discord_race_results_channel", &mut errors, html! {
+                    label(for = "discord_race_results_channel") : "Race results channel ID:";
+                    input(type = "text", name = "discord_race_results_channel", value? = ctx.field_value("discord_race_results_channel"));
+                });
+                h2 : "Entering";
+                p : "Entrants must:";
+                : form_field("require_racetime", &mut errors, html! {
+                    input(type = "checkbox", id = "require_racetime", name = "require_racetime", checked? = !is_submitted || checkbox("require_racetime"));
+                    label(for = "require_racetime") : "have a racetime.gg account connected to their Mido's House account";
+                });
+                : form_field("require_twitch", &mut errors, html! {
+                    input(type = "checkbox", id = "require_twitch", name = "require_twitch", checked? = checkbox("require_twitch"));
+                    label(for = "require_twitch") : "have a Twitch account connected to their racetime.gg account";
+                });
+                : form_field("require_discord", &mut errors, html! {
+                    input(type = "checkbox", id = "require_discord", name = "require_discord", checked? = checkbox("require_discord"));
+                    label(for = "require_discord") : "have a Discord account connected to their Mido's House account";
+                });
+                : form_field("require_discord_guild", &mut errors, html! {
+                    input(type = "checkbox", id = "require_discord_guild", name = "require_discord_guild", checked? = checkbox("require_discord_guild"));
+                    label(for = "require_discord_guild") : "be in the event's Discord server";
+                });
+                : form_field("discord_guild_name", &mut errors, html! {
+                    label(for = "discord_guild_name") : "Discord server name:";
+                    input(type = "text", name = "discord_guild_name", value? = ctx.field_value("discord_guild_name"));
+                    label(class = "help") : "(Required if entrants must be in the Discord server.)";
+                });
+                : form_field("require_startgg", &mut errors, html! {
+                    input(type = "checkbox", id = "require_startgg", name = "require_startgg", checked? = checkbox("require_startgg"));
+                    label(for = "require_startgg") : "have a start.gg account connected to their Mido's House account";
+                });
+                : form_field("require_challonge", &mut errors, html! {
+                    input(type = "checkbox", id = "require_challonge", name = "require_challonge", checked? = checkbox("require_challonge"));
+                    label(for = "require_challonge") : "have a Challonge account connected to their Mido's House account";
+                });
+                : form_field("require_rules", &mut errors, html! {
+                    input(type = "checkbox", id = "require_rules", name = "require_rules", checked? = checkbox("require_rules"));
+                    label(for = "require_rules") : "agree to the event rules";
+                });
+                : form_field("rules_url", &mut errors, html! {
+                    label(for = "rules_url") : "Rules document URL:";
+                    input(type = "text", name = "rules_url", value? = ctx.field_value("rules_url"));
+                    label(class = "help") : "(Optional.)";
+                });
+                : form_field("require_restream_consent", &mut errors, html! {
+                    input(type = "checkbox", id = "require_restream_consent", name = "require_restream_consent", checked? = checkbox("require_restream_consent"));
+                    label(for = "require_restream_consent") : "agree to be restreamed";
+                });
+            }, errors, "Request event");
+        }
+    } else {
+        html! {
+            p {
+                a(href = uri!(auth::login(Some(uri!(get))))) : "Sign in or create a Mido's House account";
+                : " to request a new event.";
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle::default(), "New Event — Mido's House", html! {
+        h1 : "New event";
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/new")]
+pub(crate) async fn get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    Ok(create_form(pool.begin().await?, me, uri, csrf.as_ref(), Context::default()).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct CreateForm {
+    #[field(default = String::new())]
+    csrf: String,
+    series: String,
+    #[field(default = String::new())]
+    event: String,
+    #[field(default = String::new())]
+    display_name: String,
+    #[field(default = String::new())]
+    short_name: String,
+    team_config: String,
+    language: Language,
+    #[field(default = String::new())]
+    start: String,
+    #[field(default = String::new())]
+    end: String,
+    match_source: String,
+    #[field(default = String::new())]
+    url: String,
+    #[field(default = String::new())]
+    draft_spec: String,
+    #[field(default = String::new())]
+    discord_guild: String,
+    #[field(default = String::new())]
+    discord_organizer_channel: String,
+    #[field(default = String::new())]
+    discord_scheduling_channel: String,
+    #[field(default = String::new())]
+    discord_race_room_channel: String,
+    #[field(default = String::new())]
+    discord_race_results_channel: String,
+    require_racetime: bool,
+    require_twitch: bool,
+    require_discord: bool,
+    require_discord_guild: bool,
+    #[field(default = String::new())]
+    discord_guild_name: String,
+    require_startgg: bool,
+    require_challonge: bool,
+    require_rules: bool,
+    #[field(default = String::new())]
+    rules_url: String,
+    require_restream_consent: bool,
+}
+
+pub(super) fn is_valid_slug(slug: &str) -> bool {
+    regex_is_match!("^[0-9a-z-]{1,8}$", slug)
+}
+
+/// Parses the value of a `datetime-local` input, interpreted as UTC.
+pub(super) fn parse_datetime(ctx: &mut Context<'_>, name: &'static str, value: &str) -> Option<DateTime<Utc>> {
+    if value.is_empty() { return None }
+    match NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M") {
+        Ok(datetime) => Some(datetime.and_utc()),
+        Err(_) => {
+            ctx.push_error(form::Error::validation("Dates must be formatted like “2025-01-31T18:00”.").with_name(name));
+            None
+        }
+    }
+}
+
+fn parse_snowflake(ctx: &mut Context<'_>, name: &'static str, value: &str) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() { return None }
+    match value.parse::<u64>() {
+        Ok(0) | Err(_) => {
+            ctx.push_error(form::Error::validation("Discord IDs consist of digits only.").with_name(name));
+            None
+        }
+        Ok(id) => Some(id),
+    }
+}
+
+#[rocket::post("/new", data = "<form>")]
+pub(crate) async fn post(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, form: Form<Contextual<'_, CreateForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        let series = value.series.parse::<Series>().ok();
+        match series {
+            None => form.context.push_error(form::Error::validation("Unknown series.").with_name("series")),
+            Some(series) if !supports_generic_events(series) => form.context.push_error(form::Error::validation("New events in this series can't be requested here. Please contact an admin.").with_name("series")),
+            Some(_) => {}
+        }
+        if !is_valid_slug(&value.event) {
+            form.context.push_error(form::Error::validation("The event slug must consist of 1 to 8 lowercase letters, digits, or hyphens.").with_name("event"));
+        } else if let Some(series) = series {
+            if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM events WHERE series = $1 AND event = $2) AS "exists!""#, series as _, &value.event).fetch_one(&mut *transaction).await? {
+                form.context.push_error(form::Error::validation("There is already an event with this slug in this series.").with_name("event"));
+            }
+        }
+        if value.display_name.trim().is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a name for the event.").with_name("display_name"));
+        }
+        let team_config = TEAM_CONFIGS.into_iter().find(|(_, slug, _)| *slug == value.team_config).map(|(team_config, _, _)| team_config);
+        if team_config.is_none() {
+            form.context.push_error(form::Error::validation("Unknown team format.").with_name("team_config"));
+        }
+        let start = parse_datetime(&mut form.context, "start", &value.start);
+        let end = parse_datetime(&mut form.context, "end", &value.end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                form.context.push_error(form::Error::validation("The event must end after it starts.").with_name("end"));
+            }
+        }
+        let url = if value.url.trim().is_empty() {
+            None
+        } else {
+            match Url::parse(value.url.trim()) {
+                Ok(url) => Some(url),
+                Err(e) => {
+                    form.context.push_error(form::Error::validation(format!("Failed to parse URL: {e}")).with_name("url"));
+                    None
+                }
+            }
+        };
+        match (&*value.match_source, url.as_ref().and_then(|url| url.host_str())) {
+            ("manual", None) => {}
+            ("manual", Some(_)) => form.context.push_error(form::Error::validation("A bracket URL is only used if matches are imported from start.gg or Challonge.").with_name("url")),
+            ("startgg", Some("start.gg" | "www.start.gg")) | ("challonge", Some("challonge.com" | "www.challonge.com")) => {}
+            ("startgg", _) => form.context.push_error(form::Error::validation("Please enter the URL of your start.gg tournament, e.g. https://start.gg/tournament/example").with_name("url")),
+            ("challonge", _) => form.context.push_error(form::Error::validation("Please enter the URL of your Challonge tournament, e.g. https://challonge.com/example").with_name("url")),
+            (_, _) => form.context.push_error(form::Error::validation("Unknown match source.").with_name("match_source")),
+        }
+        let draft_spec = if value.draft_spec.trim().is_empty() {
+            None
+        } else {
+            match serde_json::from_str::<Spec>(&value.draft_spec) {
+                Ok(spec) => if let Err(e) = spec.validate() {
+                    form.context.push_error(form::Error::validation(format!("Invalid draft format: {e}")).with_name("draft_spec"));
+                    None
+                } else {
+                    Some(serde_json::from_str::<serde_json::Value>(&value.draft_spec)?)
+                },
+                Err(e) => {
+                    form.context.push_error(form::Error::validation(format!("Invalid draft format: {e}")).with_name("draft_spec"));
+                    None
+                }
+            }
+        };
+        let discord_guild = parse_snowflake(&mut form.context, "discord_guild", &value.discord_guild).map(GuildId::new);
+        let discord_organizer_channel = parse_snowflake(&mut form.context, "discord_organizer_channel", &value.discord_organizer_channel).map(ChannelId::new);
+        let discord_scheduling_channel = parse_snowflake(&mut form.context, "discord_scheduling_channel", &value.discord_scheduling_channel).map(ChannelId::new);
+        let discord_race_room_channel = parse_snowflake(&mut form.context, "discord_race_room_channel", &value.discord_race_room_channel).map(ChannelId::new);
+        let discord_race_results_channel = parse_snowflake(&mut form.context, "discord_race_results_channel", &value.discord_race_results_channel).map(ChannelId::new);
+        if discord_guild.is_none() && (discord_organizer_channel.is_some() || discord_scheduling_channel.is_some() || discord_race_room_channel.is_some() || discord_race_results_channel.is_some()) {
+            form.context.push_error(form::Error::validation("Please enter the server ID if you want to use Discord channels.").with_name("discord_guild"));
+        }
+        let mut requirements = Vec::default();
+        if value.require_racetime {
+            requirements.push(json!({"type": "raceTime"}));
+        }
+        if value.require_twitch {
+            requirements.push(json!({"type": "twitch"}));
+        }
+        if value.require_discord {
+            requirements.push(json!({"type": "discord"}));
+        }
+        if value.require_discord_guild {
+            if discord_guild.is_none() {
+                form.context.push_error(form::Error::validation("Please enter the server ID if entrants must be in the Discord server.").with_name("discord_guild"));
+            }
+            if value.discord_guild_name.trim().is_empty() {
+                form.context.push_error(form::Error::validation("Please enter the server name if entrants must be in the Discord server.").with_name("discord_guild_name"));
+            }
+            requirements.push(json!({"type": "discordGuild", "name": value.discord_guild_name.trim()}));
+        }
+        if value.require_startgg {
+            requirements.push(json!({"type": "startGG"}));
+        }
+        if value.require_challonge {
+            requirements.push(json!({"type": "challonge"}));
+        }
+        if value.require_rules {
+            let document = if value.rules_url.trim().is_empty() {
+                None
+            } else if let Ok(url) = Url::parse(value.rules_url.trim()) {
+                Some(url)
+            } else {
+                form.context.push_error(form::Error::validation("Please enter a valid URL.").with_name("rules_url"));
+                None
+            };
+            requirements.push(json!({"type": "rules", "document": document}));
+        }
+        if value.require_restream_consent {
+            requirements.push(json!({"type": "restreamConsent"}));
+        }
+        let enter_flow = json!({"requirements": requirements});
+        if let Err(e) = serde_json::from_value::<enter::Flow>(enter_flow.clone()) {
+            form.context.push_error(form::Error::validation(format!("Invalid entry requirements: {e}")));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(create_form(transaction, Some(me), uri, csrf.as_ref(), form.context).await?)
+        } else {
+            let series = series.expect("checked above");
+            let short_name = Some(value.short_name.trim()).filter(|short_name| !short_name.is_empty());
+            sqlx::query!("INSERT INTO events (
+                series, event, display_name, short_name, start, end_time, listed, pending_approval, team_config, language, url, enter_flow, draft_spec,
+                discord_guild, discord_organizer_channel, discord_scheduling_channel, discord_race_room_channel, discord_race_results_channel
+            ) VALUES ($1, $2, $3, $4, $5, $6, FALSE, TRUE, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)",
+                series as _,
+                &value.event,
+                value.display_name.trim(),
+                short_name,
+                start,
+                end,
+                team_config.expect("checked above") as _,
+                value.language as _,
+                url.as_ref().map(|url| url.to_string()),
+                Json(&enter_flow) as _,
+                draft_spec.map(Json) as _,
+                discord_guild.map(PgSnowflake) as _,
+                discord_organizer_channel.map(PgSnowflake) as _,
+                discord_scheduling_channel.map(PgSnowflake) as _,
+                discord_race_room_channel.map(PgSnowflake) as _,
+                discord_race_results_channel.map(PgSnowflake) as _,
+            ).execute(&mut *transaction).await?;
+            sqlx::query!("INSERT INTO organizers (series, event, organizer) VALUES ($1, $2, $3)", series as _, &value.event, me.id as _).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            let msg = MessageBuilder::default()
+                .mention_user(&me)
+                .push(" requested a new event: ")
+                .push_safe(value.display_name.trim())
+                .push(" <")
+                .push(uri!(base_uri(), pending).to_string())
+                .push('>')
+                .build();
+            // the event has already been created at this point, so a failure to notify shouldn't be reported to the requester
+            let discord_ctx = discord_ctx.read().await;
+            if let Err(e) = async { FENHL.create_dm_channel(&*discord_ctx).await?.say(&*discord_ctx, msg).await }.await {
+                eprintln!("failed to notify admin of requested event {}/{}: {e} ({e:?})", series.slug(), value.event);
+            }
+            RedirectOrContent::Redirect(Redirect::to(uri!(super::info(series, &value.event))))
+        }
+    } else {
+        RedirectOrContent::Content(create_form(transaction, Some(me), uri, csrf.as_ref(), form.context).await?)
+    })
+}
+
+#[rocket::get("/new/pending")]
+pub(crate) async fn pending(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    if !me.as_ref().is_some_and(is_admin) { return Err(StatusOrError::Status(Status::Forbidden)) }
+    let rows = sqlx::query!(r#"SELECT series AS "series: Series", event FROM events WHERE pending_approval ORDER BY series, event"#).fetch_all(&mut *transaction).await?;
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        let event = event::Data::new(&mut transaction, row.series, row.event).await?.expect("event deleted during transaction");
+        let organizers = event.organizers(&mut transaction).await?;
+        events.push((event, organizers));
+    }
+    Ok(page(transaction, &me, &uri, PageStyle::default(), "Pending Events — Mido's House", html! {
+        h1 : "Pending events";
+        @if events.is_empty() {
+            p : "No events are currently awaiting approval.";
+        } else {
+            table {
+                thead {
+                    tr {
+                        th : "Event";
+                        th : "Organizers";
+                        th;
+                    }
+                }
+                tbody {
+                    @for (event, organizers) in events {
+                        tr {
+                            td : event;
+                            td : English.join_html_opt(organizers);
+                            td {
+                                div(class = "button-row") {
+                                    : button_form(uri!(approve(event.series, &*event.event)), csrf.as_ref(), Vec::default(), "Approve").1;
+                                    : button_form(uri!(reject(event.series, &*event.event)), csrf.as_ref(), Vec::default(), "Reject").1;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }).await?)
+}
+
+#[rocket::post("/new/pending/<series>/<event>/approve", data = "<form>")]
+pub(crate) async fn approve(pool: &State<PgPool>, me: User, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, EmptyForm>>) -> Result<Redirect, StatusOrError<event::Error>> {
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    if form.value.is_none() || !is_admin(&me) { return Err(StatusOrError::Status(Status::Forbidden)) }
+    sqlx::query!("UPDATE events SET pending_approval = FALSE, listed = TRUE WHERE series = $1 AND event = $2 AND pending_approval", series as _, event).execute(&**pool).await?;
+    Ok(Redirect::to(uri!(pending)))
+}
+
+#[rocket::post("/new/pending/<series>/<event>/reject", data = "<form>")]
+pub(crate) async fn reject(pool: &State<PgPool>, me: User, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, EmptyForm>>) -> Result<Redirect, StatusOrError<event::Error>> {
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    if form.value.is_none() || !is_admin(&me) { return Err(StatusOrError::Status(Status::Forbidden)) }
+    let mut transaction = pool.begin().await?;
+    if sqlx::query_scalar!(r#"SELECT pending_approval AS "pending_approval!" FROM events WHERE series = $1 AND event = $2"#, series as _, event).fetch_optional(&mut *transaction).await?.unwrap_or(false) {
+        // organizers may already have added teams, races, etc., so the event is kept but won't be listed
+        sqlx::query!("UPDATE events SET pending_approval = FALSE, rejected = TRUE WHERE series = $1 AND event = $2", series as _, event).execute(&mut *transaction).await?;
+    }
+    transaction.commit().await?;
+    Ok(Redirect::to(uri!(pending)))
+}
@@ -26,10 +26,24 @@ enum DeserializeRawHtml {}
 
 impl<'de> DeserializeAs<'de, RawHtml<String>> for DeserializeRawHtml {
     fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<RawHtml<String>, D::Error> {
-        String::deserialize(deserializer).map(RawHtml)
+        String::deserialize(deserializer).map(|html| RawHtml(sanitize_html(&html)))
     }
 }
 
+/// Removes everything except basic formatting and links to web pages or email addresses from HTML written by event organizers.
+///
+/// This is applied both when an enter flow is edited and when it's rendered, since organizers don't need to be trusted to create events.
+pub(crate) fn sanitize_html(html: &str) -> String {
+    ammonia::Builder::empty()
+        .add_tags(["a", "abbr", "b", "br", "code", "em", "i", "li", "ol", "p", "s", "small", "strong", "sub", "sup", "u", "ul"])
+        .add_tag_attributes("a", ["href"])
+        .add_tag_attributes("abbr", ["title"])
+        .add_url_schemes(["http", "https", "mailto"])
+        .link_rel(Some("noopener noreferrer"))
+        .clean(html)
+        .to_string()
+}
+
 enum DeserializeRegex {}
 
 impl<'de> DeserializeAs<'de, Regex> for DeserializeRegex {
@@ -891,6 +905,36 @@ pub(crate) struct EnterForm {
     text_field2: String,
 }
 
+/// Renders the requirements of an event's enter flow the way they appear to a user who hasn't met any of them yet, for organizers editing the enter flow.
+pub(crate) async fn preview(http_client: &reqwest::Client, data: &Data<'_>) -> RawHtml<String> {
+    let Some(Flow { ref requirements, .. }) = data.enter_flow else { return html! {} };
+    let defaults = pic::EnterFormDefaults::Context(Context::default());
+    let mut requirements_display = Vec::with_capacity(requirements.len());
+    for requirement in requirements {
+        let is_checked = if let Requirement::External { .. } = requirement { None } else { Some(false) };
+        let html_content = match requirement.check_get(http_client, data, is_checked, uri!(get(data.series, &*data.event, _, _)), &defaults).await {
+            Ok(status) => Ok((status.html_content)(&mut Vec::default())),
+            Err(e) => Err(e),
+        };
+        requirements_display.push((is_checked, html_content));
+    }
+    html! {
+        @for (is_checked, html_content) in requirements_display {
+            div(class = "check-item") {
+                div(class = "checkmark") {
+                    @if is_checked.is_none() {
+                        : "?";
+                    }
+                }
+                @match html_content {
+                    Ok(html_content) => div : html_content;
+                    Err(e) => div(class = "error") : format!("This requirement could not be previewed: {e}");
+                }
+            }
+        }
+    }
+}
+
 pub(crate) async fn enter_form(mut transaction: Transaction<'_, Postgres>, http_client: &reqwest::Client, discord_ctx: &RwFuture<DiscordCtx>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, data: Data<'_>, defaults: pic::EnterFormDefaults<'_>) -> Result<RawHtml<String>, Error> {
     //TODO if already entered, redirect to status page
     let my_invites = if let Some(ref me) = me {
@@ -0,0 +1,731 @@
+//! Round-robin group phases whose groups and pairings are generated by Mido's House.
+//!
+//! Teams are distributed into groups in snake order by seed, so each group gets a similar spread of seeds. Within each group, every team plays every other team once, and all races are created when the phase starts.
+//! Teams are ranked by points (1 for a win, ½ for a draw), then by the phase's configured tiebreakers, then by seed. The top teams of each group can then be seeded into a [bracket](super::bracket).
+
+use {
+    std::cmp::Reverse,
+    sqlx::{
+        PgPool,
+        types::Json,
+    },
+    crate::{
+        event::{
+            Data,
+            Tab,
+            swiss::{
+                self,
+                format_points,
+            },
+        },
+        prelude::*,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Discord(#[from] crate::discord_bot::Error),
+    #[error(transparent)] PgInterval(#[from] PgIntervalDecodeError),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+    #[error("a group match references a team that doesn't exist")]
+    TeamData,
+}
+
+impl IsNetworkError for Error {
+    fn is_network_error(&self) -> bool {
+        match self {
+            Self::Cal(e) => e.is_network_error(),
+            Self::Discord(_) => false,
+            Self::PgInterval(_) => false,
+            Self::Sql(_) => false,
+            Self::TeamData => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Tiebreaker {
+    /// Points from matches between the tied teams.
+    HeadToHead,
+    /// Fewest DNFs, then lowest sum of finish times.
+    TotalTime,
+}
+
+impl Tiebreaker {
+    fn display_name(&self) -> &'static str {
+        match self {
+            Self::HeadToHead => "head-to-head",
+            Self::TotalTime => "total time",
+        }
+    }
+}
+
+/// Tiebreaker orders offered when adding a group phase, as form values.
+const TIEBREAKER_OPTIONS: [(&str, &[Tiebreaker]); 5] = [
+    ("head_to_head,total_time", &[Tiebreaker::HeadToHead, Tiebreaker::TotalTime]),
+    ("total_time,head_to_head", &[Tiebreaker::TotalTime, Tiebreaker::HeadToHead]),
+    ("head_to_head", &[Tiebreaker::HeadToHead]),
+    ("total_time", &[Tiebreaker::TotalTime]),
+    ("none", &[]),
+];
+
+fn format_tiebreakers(tiebreakers: &[Tiebreaker]) -> String {
+    if tiebreakers.is_empty() {
+        format!("seed")
+    } else {
+        format!("{}, then seed", tiebreakers.iter().map(Tiebreaker::display_name).join(", then "))
+    }
+}
+
+/// Sort key for a single tiebreaker. Keys at the same index are always of the same variant.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum TiebreakerKey {
+    HeadToHead(Reverse<u32>),
+    TotalTime(u32, Duration),
+}
+
+pub(crate) fn group_name(group_number: i16) -> String {
+    format!("Group {}", char::from(b'A' + u8::try_from(group_number - 1).expect("at most 26 groups")))
+}
+
+/// A team's record in its group so far.
+pub(crate) struct Standing {
+    pub(crate) team: Id<Teams>,
+    /// Twice the team's points, since a draw is worth half a point.
+    pub(crate) half_points: u32,
+    pub(crate) wins: u32,
+    pub(crate) draws: u32,
+    pub(crate) losses: u32,
+    /// The number of this team's matches that haven't been reported yet.
+    pub(crate) remaining: u32,
+    pub(crate) dnfs: u32,
+    /// The sum of this team's finish times, not counting DNFs.
+    pub(crate) total_time: Duration,
+    seed: usize,
+}
+
+struct GroupMatch {
+    group_number: i16,
+    round: i16,
+    team1: Id<Teams>,
+    team2: Id<Teams>,
+    reported: bool,
+    winner: Option<Id<Teams>>,
+    team1_time: Option<Duration>,
+    team2_time: Option<Duration>,
+}
+
+async fn load_matches(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> Result<Vec<GroupMatch>, Error> {
+    let mut matches = Vec::default();
+    for row in sqlx::query!(r#"SELECT group_number, round, team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>", reported_at, winner AS "winner: Id<Teams>", team1_time, team2_time FROM group_matches WHERE series = $1 AND event = $2 AND phase = $3 ORDER BY group_number, round, team1"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await? {
+        matches.push(GroupMatch {
+            group_number: row.group_number,
+            round: row.round,
+            team1: row.team1,
+            team2: row.team2,
+            reported: row.reported_at.is_some(),
+            winner: row.winner,
+            team1_time: row.team1_time.map(decode_pginterval).transpose()?,
+            team2_time: row.team2_time.map(decode_pginterval).transpose()?,
+        });
+    }
+    Ok(matches)
+}
+
+/// Points the given team scored in matches between teams from the given set.
+fn head_to_head(team: Id<Teams>, tied: &HashSet<Id<Teams>>, matches: &[GroupMatch]) -> u32 {
+    matches.iter()
+        .filter(|m| m.reported && tied.contains(&m.team1) && tied.contains(&m.team2) && (m.team1 == team || m.team2 == team))
+        .map(|m| match m.winner {
+            Some(winner) if winner == team => 2,
+            Some(_) => 0,
+            None => 1,
+        })
+        .sum()
+}
+
+/// Sorts the standings of a single group from first to last place.
+fn sort_group(mut standings: Vec<Standing>, matches: &[GroupMatch], tiebreakers: &[Tiebreaker]) -> Vec<Standing> {
+    standings.sort_by_key(|standing| Reverse(standing.half_points));
+    let mut sorted = Vec::with_capacity(standings.len());
+    for (_, tied) in &standings.into_iter().chunk_by(|standing| standing.half_points) {
+        let mut tied = tied.collect_vec();
+        let tied_teams = tied.iter().map(|standing| standing.team).collect::<HashSet<_>>();
+        tied.sort_by_cached_key(|standing| (
+            tiebreakers.iter().map(|tiebreaker| match tiebreaker {
+                Tiebreaker::HeadToHead => TiebreakerKey::HeadToHead(Reverse(head_to_head(standing.team, &tied_teams, matches))),
+                Tiebreaker::TotalTime => TiebreakerKey::TotalTime(standing.dnfs, standing.total_time),
+            }).collect_vec(),
+            standing.seed,
+        ));
+        sorted.extend(tied);
+    }
+    sorted
+}
+
+/// The standings of each group of a group phase, sorted from first to last place. The outer list is indexed by group number minus 1.
+pub(crate) async fn standings(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> Result<Vec<Vec<Standing>>, Error> {
+    let Some(row) = sqlx::query!(r#"SELECT num_groups, tiebreakers AS "tiebreakers: Json<Vec<Tiebreaker>>" FROM group_phases WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, phase).fetch_optional(&mut **transaction).await? else { return Ok(Vec::default()) };
+    let seeds = swiss::seed_order(transaction, event, phase).await?
+        .into_iter()
+        .enumerate()
+        .map(|(seed, team)| (team, seed))
+        .collect::<HashMap<_, _>>();
+    let mut standings = HashMap::new();
+    for member in sqlx::query!(r#"SELECT team AS "team: Id<Teams>", group_number FROM group_members WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await? {
+        standings.insert(member.team, (member.group_number, Standing {
+            team: member.team,
+            half_points: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            remaining: 0,
+            dnfs: 0,
+            total_time: Duration::default(),
+            // resigned teams aren't included in the seed order
+            seed: seeds.get(&member.team).copied().unwrap_or(usize::MAX),
+        }));
+    }
+    let matches = load_matches(transaction, event, phase).await?;
+    for m in &matches {
+        for (team, time) in [(m.team1, m.team1_time), (m.team2, m.team2_time)] {
+            let Some((_, standing)) = standings.get_mut(&team) else { continue };
+            if !m.reported {
+                standing.remaining += 1;
+                continue
+            }
+            match m.winner {
+                Some(winner) if winner == team => {
+                    standing.half_points += 2;
+                    standing.wins += 1;
+                }
+                Some(_) => standing.losses += 1,
+                None => {
+                    standing.half_points += 1;
+                    standing.draws += 1;
+                }
+            }
+            if let Some(time) = time {
+                standing.total_time += time;
+            } else {
+                standing.dnfs += 1;
+            }
+        }
+    }
+    let mut groups = (0..row.num_groups).map(|_| Vec::default()).collect_vec();
+    for (group_number, standing) in standings.into_values() {
+        groups[usize::try_from(group_number - 1).expect("group numbers are positive")].push(standing);
+    }
+    Ok(groups.into_iter().map(|group| sort_group(group, &matches, &row.tiebreakers.0)).collect())
+}
+
+/// The teams advancing from a group phase, in the order they should be seeded into a bracket: first all group winners by group, then all runners-up, and so on.
+///
+/// Returns `None` if there is no such group phase.
+pub(crate) async fn advancing_teams(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> Result<Option<Vec<Id<Teams>>>, Error> {
+    let Some(advancing) = sqlx::query_scalar!("SELECT advancing FROM group_phases WHERE series = $1 AND event = $2 AND phase = $3", event.series as _, &event.event, phase).fetch_optional(&mut **transaction).await? else { return Ok(None) };
+    let groups = standings(transaction, event, phase).await?;
+    let mut teams = Vec::default();
+    for place in 0..usize::try_from(advancing).expect("checked by database constraint") {
+        teams.extend(groups.iter().filter_map(|group| group.get(place)).map(|standing| standing.team));
+    }
+    Ok(Some(teams))
+}
+
+/// Distributes the teams, given in seed order, into groups in snake order.
+fn snake(teams: Vec<Id<Teams>>, num_groups: usize) -> Vec<Vec<Id<Teams>>> {
+    let mut groups = vec![Vec::default(); num_groups];
+    for (idx, team) in teams.into_iter().enumerate() {
+        let (row, col) = (idx / num_groups, idx % num_groups);
+        groups[if row % 2 == 0 { col } else { num_groups - 1 - col }].push(team);
+    }
+    groups
+}
+
+/// Round-robin pairings for the given teams using the circle method, as a list of rounds. With an odd number of teams, one team sits out each round.
+fn round_robin(teams: &[Id<Teams>]) -> Vec<Vec<[Id<Teams>; 2]>> {
+    let mut slots = teams.iter().copied().map(Some).collect_vec();
+    if slots.len() % 2 == 1 {
+        slots.push(None);
+    }
+    let num_slots = slots.len();
+    let mut rounds = Vec::default();
+    for _ in 1..num_slots {
+        rounds.push((0..num_slots / 2).filter_map(|idx| Some([slots[idx]?, slots[num_slots - 1 - idx]?])).collect());
+        slots[1..].rotate_right(1);
+    }
+    rounds
+}
+
+/// Assigns the active teams to groups and creates the races for all matches of the phase.
+async fn start_phase<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, phase: &str, num_groups: i16) -> Result<Transaction<'a, Postgres>, Error> {
+    let teams = swiss::seed_order(&mut transaction, event, phase).await?;
+    for (group_number, group) in (1..).zip(snake(teams, usize::try_from(num_groups).expect("checked by database constraint"))) {
+        for &team in &group {
+            sqlx::query!("INSERT INTO group_members (series, event, phase, team, group_number) VALUES ($1, $2, $3, $4, $5)", event.series as _, &event.event, phase, team as _, group_number).execute(&mut *transaction).await?;
+        }
+        for (round, pairings) in (1..).zip(round_robin(&group)) {
+            for [team1, team2] in pairings {
+                let team1 = Team::from_id(&mut transaction, team1).await?.ok_or(Error::TeamData)?;
+                let team2 = Team::from_id(&mut transaction, team2).await?.ok_or(Error::TeamData)?;
+                let draft = if let Some(draft_kind) = event.draft_kind() {
+                    Some(Draft::for_game1(&mut transaction, http_client, draft_kind, event, Some(phase), [&team1, &team2]).await?)
+                } else {
+                    None
+                };
+                let mut race = Race::new_for_pairing(&mut transaction, event, phase, format!("{}, Round {round}", group_name(group_number)), None, [&team1, &team2], draft).await?;
+                transaction = crate::discord_bot::create_scheduling_thread(discord_ctx, transaction, &mut race, 1).await?;
+                race.save(&mut transaction).await?;
+                sqlx::query!("INSERT INTO group_matches (series, event, phase, group_number, round, team1, team2, race) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)", event.series as _, &event.event, phase, group_number, round, team1.id as _, team2.id as _, race.id as _).execute(&mut *transaction).await?;
+            }
+        }
+    }
+    Ok(transaction)
+}
+
+/// Records the result of a race if it's part of a group phase, replacing the previously reported result if any.
+///
+/// `winner` is `None` if the race was a draw. `finish_times` contains the finish times of the teams that finished; a missing team is counted as a DNF.
+pub(crate) async fn record_result(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, winner: Option<Id<Teams>>, finish_times: &HashMap<Id<Teams>, Duration>) -> sqlx::Result<()> {
+    let Some(row) = sqlx::query!(r#"SELECT team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>" FROM group_matches WHERE race = $1"#, race as _).fetch_optional(&mut **transaction).await? else { return Ok(()) };
+    sqlx::query!("UPDATE group_matches SET reported_at = COALESCE(reported_at, NOW()), winner = $1, team1_time = $2, team2_time = $3 WHERE race = $4", winner as _, finish_times.get(&row.team1).copied() as _, finish_times.get(&row.team2).copied() as _, race as _).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+enum GroupsFormDefaults<'v> {
+    None,
+    AddContext(Context<'v>),
+    StartContext(String, Context<'v>),
+    ReportContext(String, Id<Teams>, Id<Teams>, Context<'v>),
+}
+
+impl<'v> GroupsFormDefaults<'v> {
+    fn add_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::AddContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn add_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::AddContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+
+    fn start_errors(&self, for_phase: &str) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::StartContext(phase, ctx) if phase == for_phase => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn report_errors(&self, for_phase: &str, for_team1: Id<Teams>, for_team2: Id<Teams>) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::ReportContext(phase, team1, team2, ctx) if phase == for_phase && *team1 == for_team1 && *team2 == for_team2 => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+}
+
+async fn groups_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: GroupsFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let mut team_names = HashMap::new();
+            let mut phases = Vec::default();
+            for row in sqlx::query!(r#"SELECT phase, num_groups, advancing, tiebreakers AS "tiebreakers: Json<Vec<Tiebreaker>>" FROM group_phases WHERE series = $1 AND event = $2 ORDER BY phase"#, event.series as _, &event.event).fetch_all(&mut *transaction).await? {
+                let matches = load_matches(&mut transaction, &event, &row.phase).await?;
+                for team in matches.iter().flat_map(|m| [m.team1, m.team2]) {
+                    if !team_names.contains_key(&team) {
+                        let name = Team::from_id(&mut transaction, team).await?.ok_or(Error::TeamData)?.to_html(&mut transaction, false).await?;
+                        team_names.insert(team, name);
+                    }
+                }
+                let standings = standings(&mut transaction, &event, &row.phase).await?;
+                phases.push((row.phase, row.num_groups, row.advancing, row.tiebreakers.0, matches, standings));
+            }
+            html! {
+                h2 : "Group phases";
+                p {
+                    : "Mido's House can split the teams into round-robin groups and create the races for all matches of each group. Teams are assigned to groups in snake order by seed (see ";
+                    a(href = uri!(super::configure::seeding_get(event.series, &*event.event))) : "seeding";
+                    : "; teams without a seed are ordered by qualifier rank). Results are recorded automatically when a race room finishes, except for draws within the retime window, which can be reported below. A win is worth 1 point and a draw ½ point. Once the groups are finished, the advancing teams can be seeded into a ";
+                    a(href = uri!(super::bracket::configure_get(event.series, &*event.event))) : "bracket";
+                    : ".";
+                }
+                @for (phase, num_groups, advancing, tiebreakers, matches, standings) in phases {
+                    h3 : &phase;
+                    p : format!("{num_groups} group{}, top {advancing} of each group advance{}. Tiebreakers: {}.", if num_groups == 1 { "" } else { "s" }, if advancing == 1 { "s" } else { "" }, format_tiebreakers(&tiebreakers));
+                    @if matches.is_empty() {
+                        p : "Not started yet.";
+                        @let errors = defaults.start_errors(&phase);
+                        @let (errors, button) = button_form_ext(uri!(start(event.series, &*event.event)), csrf, errors, html! {
+                            input(type = "hidden", name = "phase", value = &phase);
+                        }, "Assign groups and create races");
+                        : errors;
+                        div(class = "button-row") : button;
+                    } else {
+                        @for (group_number, group) in (1..).zip(&standings) {
+                            h4 : group_name(group_number);
+                            table {
+                                thead {
+                                    tr {
+                                        th : "Rank";
+                                        @if let TeamConfig::Solo = event.team_config {
+                                            th : "Player";
+                                        } else {
+                                            th : "Team";
+                                        }
+                                        th : "Points";
+                                        th : "Wins";
+                                        th : "Draws";
+                                        th : "Losses";
+                                        th : "DNFs";
+                                        th : "Total time";
+                                        th : "Remaining";
+                                    }
+                                }
+                                tbody {
+                                    @for (rank, standing) in group.iter().enumerate() {
+                                        tr {
+                                            td : rank + 1;
+                                            td : team_names.get(&standing.team);
+                                            td : format_points(standing.half_points);
+                                            td : standing.wins;
+                                            td : standing.draws;
+                                            td : standing.losses;
+                                            td : standing.dnfs;
+                                            td : English.format_duration(standing.total_time, false);
+                                            td : standing.remaining;
+                                        }
+                                    }
+                                }
+                            }
+                            table {
+                                thead {
+                                    tr {
+                                        th : "Round";
+                                        th : "Pairing";
+                                        th : "Result";
+                                        th;
+                                    }
+                                }
+                                tbody {
+                                    @for m in matches.iter().filter(|m| m.group_number == group_number) {
+                                        tr {
+                                            td : m.round;
+                                            td {
+                                                : team_names.get(&m.team1);
+                                                : " vs ";
+                                                : team_names.get(&m.team2);
+                                            }
+                                            td {
+                                                @if !m.reported {
+                                                    : "pending";
+                                                } else if let Some(winner) = m.winner {
+                                                    : team_names.get(&winner);
+                                                    : " won";
+                                                } else {
+                                                    : "draw";
+                                                }
+                                            }
+                                            td {
+                                                @let errors = defaults.report_errors(&phase, m.team1, m.team2);
+                                                @let (errors, button) = button_form_ext(uri!(report(event.series, &*event.event)), csrf, errors, html! {
+                                                    input(type = "hidden", name = "phase", value = &phase);
+                                                    input(type = "hidden", name = "team1", value = m.team1.to_string());
+                                                    input(type = "hidden", name = "team2", value = m.team2.to_string());
+                                                    select(name = "result") {
+                                                        option(value = "team1") : team_names.get(&m.team1);
+                                                        option(value = "team2") : team_names.get(&m.team2);
+                                                        option(value = "draw") : "Draw";
+                                                    }
+                                                }, if m.reported { "Change result" } else { "Report" });
+                                                : errors;
+                                                div(class = "button-row") : button;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h3 : "Add group phase";
+                @let mut errors = defaults.add_errors();
+                : full_form(uri!(add_phase(event.series, &*event.event)), csrf, html! {
+                    : form_field("phase", &mut errors, html! {
+                        label(for = "phase") : "Phase:";
+                        input(type = "text", name = "phase", value = defaults.add_value("phase").unwrap_or("Groups"));
+                        label(class = "help") : "(Used as the phase of the created races.)";
+                    });
+                    : form_field("num_groups", &mut errors, html! {
+                        label(for = "num_groups") : "Number of groups:";
+                        input(type = "number", min = "1", max = "26", name = "num_groups", value? = defaults.add_value("num_groups"));
+                    });
+                    : form_field("advancing", &mut errors, html! {
+                        label(for = "advancing") : "Teams advancing per group:";
+                        input(type = "number", min = "1", name = "advancing", value = defaults.add_value("advancing").unwrap_or("2"));
+                    });
+                    : form_field("tiebreakers", &mut errors, html! {
+                        label(for = "tiebreakers") : "Tiebreakers:";
+                        select(name = "tiebreakers") {
+                            @for (value, tiebreakers) in TIEBREAKER_OPTIONS {
+                                option(value = value, selected? = defaults.add_value("tiebreakers") == Some(value)) : format_tiebreakers(tiebreakers);
+                            }
+                        }
+                        label(class = "help") : "(Applied in order to teams with the same number of points. Total time ranks teams by fewest DNFs, then by lowest sum of finish times.)";
+                    });
+                }, errors, "Add");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Group phases — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/groups")]
+pub(crate) async fn get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(groups_form(transaction, me, uri, csrf.as_ref(), data, GroupsFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct AddPhaseForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    num_groups: i16,
+    advancing: i16,
+    tiebreakers: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/groups", data = "<form>")]
+pub(crate) async fn add_phase(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, AddPhaseForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let phase = value.phase.trim();
+        if phase.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a phase.").with_name("phase"));
+        } else if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM group_phases WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, phase).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is already a group phase with this name.").with_name("phase"));
+        }
+        if !(1..=26).contains(&value.num_groups) {
+            form.context.push_error(form::Error::validation("The number of groups must be between 1 and 26.").with_name("num_groups"));
+        }
+        if value.advancing < 1 {
+            form.context.push_error(form::Error::validation("At least one team must advance from each group.").with_name("advancing"));
+        }
+        let tiebreakers = if let Some((_, tiebreakers)) = TIEBREAKER_OPTIONS.iter().find(|(option, _)| *option == value.tiebreakers) {
+            tiebreakers.to_vec()
+        } else {
+            form.context.push_error(form::Error::validation("Invalid tiebreakers.").with_name("tiebreakers"));
+            Vec::default()
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::AddContext(form.context)).await?)
+        } else {
+            sqlx::query!("INSERT INTO group_phases (series, event, phase, num_groups, advancing, tiebreakers) VALUES ($1, $2, $3, $4, $5, $6)", data.series as _, &data.event, phase, value.num_groups, value.advancing, Json(tiebreakers) as _).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::AddContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct StartForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/groups/start", data = "<form>")]
+pub(crate) async fn start(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, StartForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let num_groups = sqlx::query_scalar!("SELECT num_groups FROM group_phases WHERE series = $1 AND event = $2 AND phase = $3", data.series as _, &data.event, value.phase).fetch_optional(&mut *transaction).await?;
+        if let Some(num_groups) = num_groups {
+            if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM group_members WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, value.phase).fetch_one(&mut *transaction).await? {
+                form.context.push_error(form::Error::validation("This group phase has already started."));
+            }
+            if Team::for_event(&mut transaction, data.series, &data.event).await?.len() < 2 * usize::try_from(num_groups).expect("checked by database constraint") {
+                form.context.push_error(form::Error::validation("Each group must have at least 2 teams."));
+            }
+        } else {
+            form.context.push_error(form::Error::validation("There is no group phase with this name."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::StartContext(value.phase.clone(), form.context)).await?)
+        } else {
+            transaction = start_phase(transaction, &*discord_ctx.read().await, http_client, &data, &value.phase, num_groups.expect("checked above")).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::None).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct ReportForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    team1: Id<Teams>,
+    team2: Id<Teams>,
+    result: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/groups/report", data = "<form>")]
+pub(crate) async fn report(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, ReportForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if !sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM group_matches WHERE series = $1 AND event = $2 AND phase = $3 AND team1 = $4 AND team2 = $5) AS "exists!""#, data.series as _, &data.event, value.phase, value.team1 as _, value.team2 as _).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is no such group match."));
+        }
+        let winner = match &*value.result {
+            "team1" => Some(value.team1),
+            "team2" => Some(value.team2),
+            "draw" => None,
+            _ => {
+                form.context.push_error(form::Error::validation("Invalid result.").with_name("result"));
+                None
+            }
+        };
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::ReportContext(value.phase.clone(), value.team1, value.team2, form.context)).await?)
+        } else {
+            // finish times recorded from the race room are kept, so the total time tiebreaker stays accurate after a manual correction
+            sqlx::query!("UPDATE group_matches SET reported_at = NOW(), winner = $1 WHERE series = $2 AND event = $3 AND phase = $4 AND team1 = $5 AND team2 = $6", winner as _, data.series as _, &data.event, value.phase, value.team1 as _, value.team2 as _).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(groups_form(transaction, Some(me), uri, csrf.as_ref(), data, GroupsFormDefaults::None).await?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn teams(count: u64) -> Vec<Id<Teams>> {
+        (1..=count).map(Id::from).collect()
+    }
+
+    #[test]
+    fn snake_order() {
+        let ids = |ids: &[u64]| ids.iter().copied().map(Id::from).collect_vec();
+        assert_eq!(snake(teams(8), 3), [ids(&[1, 6, 7]), ids(&[2, 5, 8]), ids(&[3, 4])]);
+    }
+
+    #[test]
+    fn snake_single_group() {
+        assert_eq!(snake(teams(5), 1), [teams(5)]);
+    }
+
+    /// Checks that every pair of teams meets exactly once and that no team plays twice in the same round.
+    fn check_round_robin(count: u64) {
+        let teams = teams(count);
+        let rounds = round_robin(&teams);
+        let expected_rounds = if count % 2 == 0 { count - 1 } else { count };
+        assert_eq!(rounds.len() as u64, expected_rounds, "number of rounds for {count} teams");
+        let mut pairs = HashSet::new();
+        for (round_idx, round) in rounds.iter().enumerate() {
+            let mut playing = HashSet::new();
+            for &[team1, team2] in round {
+                assert_ne!(team1, team2);
+                assert!(playing.insert(team1) && playing.insert(team2), "a team plays twice in round {} with {count} teams", round_idx + 1);
+                assert!(pairs.insert(if team1 < team2 { (team1, team2) } else { (team2, team1) }), "rematch between {team1} and {team2} with {count} teams");
+            }
+            // with an odd number of teams, exactly one team has a bye each round
+            assert_eq!(playing.len() as u64, count - count % 2, "teams playing in round {} with {count} teams", round_idx + 1);
+        }
+        assert_eq!(pairs.len() as u64, count * (count - 1) / 2, "number of matches with {count} teams");
+    }
+
+    #[test]
+    fn round_robin_even() {
+        for count in [2, 4, 6, 8] {
+            check_round_robin(count);
+        }
+    }
+
+    #[test]
+    fn round_robin_odd() {
+        for count in [3, 5, 7] {
+            check_round_robin(count);
+        }
+        // with 5 teams, each team sits out exactly one of the 5 rounds
+        let teams = teams(5);
+        let rounds = round_robin(&teams);
+        for &team in &teams {
+            assert_eq!(rounds.iter().filter(|round| !round.iter().flatten().contains(&team)).count(), 1, "byes for team {team}");
+        }
+    }
+
+    #[test]
+    fn round_robin_trivial() {
+        assert!(round_robin(&teams(1)).iter().all(Vec::is_empty));
+        assert!(round_robin(&[]).is_empty());
+    }
+}
@@ -16,8 +16,13 @@ use {
     },
 };
 
+pub(crate) mod bracket;
 pub(crate) mod configure;
+pub(crate) mod create;
 pub(crate) mod enter;
+pub(crate) mod groups;
+pub(crate) mod standings;
+pub(crate) mod swiss;
 pub(crate) mod teams;
 
 #[derive(Debug, Clone, Copy, sqlx::Type)]
@@ -83,6 +88,8 @@ pub(crate) enum MatchSource<'a> {
         tournament: &'a str,
     },
     League,
+    /// A bracket generated and advanced by Mido's House, see the [`bracket`] module.
+    MidosHouse,
     StartGG(&'a str),
 }
 
@@ -182,12 +189,17 @@ pub(crate) struct Data<'a> {
     pub(crate) emulator_settings_reminder: bool,
     pub(crate) prevent_late_joins: bool,
     pub(crate) manual_reporting_with_breaks: bool,
+    /// Whether a team changing the start time of an already scheduled race needs the opponent's confirmation.
+    pub(crate) reschedule_confirmation: bool,
     pub(crate) language: Language,
     pub(crate) listed: bool,
+    draft_spec: Option<&'static draft::spec::Spec>,
+    has_brackets: bool,
 }
 
 #[derive(Debug, thiserror::Error, rocket_util::Error)]
 pub(crate) enum DataError {
+    #[error(transparent)] DraftSpec(#[from] draft::spec::SpecError),
     #[error(transparent)] PgInterval(#[from] PgIntervalDecodeError),
     #[error(transparent)] Sql(#[from] sqlx::Error),
     #[error(transparent)] Url(#[from] url::ParseError),
@@ -234,8 +246,11 @@ impl<'a> Data<'a> {
             emulator_settings_reminder,
             prevent_late_joins,
             manual_reporting_with_breaks,
+            reschedule_confirmation,
             language AS "language: Language",
-            listed
+            listed,
+            draft_spec AS "draft_spec: Json<draft::spec::Spec>",
+            EXISTS (SELECT 1 FROM brackets WHERE brackets.series = events.series AND brackets.event = events.event) AS "has_brackets!"
         FROM events WHERE series = $1 AND event = $2"#, series as _, &event).fetch_optional(&mut **transaction).await?
             .map(|row| Ok::<_, DataError>(Self {
                 display_name: row.display_name,
@@ -271,9 +286,12 @@ impl<'a> Data<'a> {
                 emulator_settings_reminder: row.emulator_settings_reminder,
                 prevent_late_joins: row.prevent_late_joins,
                 manual_reporting_with_breaks: row.manual_reporting_with_breaks,
+                reschedule_confirmation: row.reschedule_confirmation,
                 language: row.language,
+                draft_spec: row.draft_spec.map(|Json(spec)| spec.intern(series, &event)).transpose()?,
                 series, event,
                 listed: row.listed,
+                has_brackets: row.has_brackets,
             }))
             .transpose()
     }
@@ -403,8 +421,10 @@ impl<'a> Data<'a> {
                 },
                 Some("league.ootrandomizer.com") => MatchSource::League,
                 Some("start.gg" | "www.start.gg") => MatchSource::StartGG(&url.path()[1..]),
-                _ => MatchSource::Manual,
+                _ => if self.has_brackets { MatchSource::MidosHouse } else { MatchSource::Manual },
             }
+        } else if self.has_brackets {
+            MatchSource::MidosHouse
         } else {
             MatchSource::Manual
         }
@@ -440,6 +460,7 @@ impl<'a> Data<'a> {
     }
 
     pub(crate) fn draft_kind(&self) -> Option<draft::Kind> {
+        if let Some(spec) = self.draft_spec { return Some(draft::Kind::Custom(spec)) }
         match (self.series, &*self.event) {
             (Series::Multiworld, "3") => Some(draft::Kind::MultiworldS3),
             (Series::Multiworld, "4") => Some(draft::Kind::MultiworldS4),
@@ -661,6 +682,20 @@ impl<'a> Data<'a> {
                         a(class = "button", href = uri!(races(self.series, &*self.event))) : "Races";
                     }
                 }
+                @if !self.hide_races_tab && !self.is_single_race() && matches!(self.match_source(), MatchSource::Manual | MatchSource::MidosHouse) {
+                    @if let Tab::Standings = tab {
+                        a(class = "button selected", href? = is_subpage.then(|| uri!(standings::get(self.series, &*self.event)))) : "Standings";
+                    } else {
+                        a(class = "button", href = uri!(standings::get(self.series, &*self.event))) : "Standings";
+                    }
+                }
+                @if self.has_brackets {
+                    @if let Tab::Bracket = tab {
+                        a(class = "button selected", href? = is_subpage.then(|| uri!(bracket::get(self.series, &*self.event)))) : "Bracket";
+                    } else {
+                        a(class = "button", href = uri!(bracket::get(self.series, &*self.event))) : "Bracket";
+                    }
+                }
                 @if signed_up {
                     @if let Tab::MyStatus = tab {
                         a(class = "button selected", href? = is_subpage.then(|| uri!(status(self.series, &*self.event)))) : "My Status";
@@ -816,6 +851,8 @@ pub(crate) enum Tab {
     Info,
     Teams,
     Races,
+    Standings,
+    Bracket,
     MyStatus,
     Enter,
     FindTeam,
@@ -825,9 +862,12 @@ pub(crate) enum Tab {
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
+    #[error(transparent)] Bracket(#[from] bracket::Error),
     #[error(transparent)] Calendar(#[from] cal::Error),
     #[error(transparent)] Data(#[from] DataError),
     #[error(transparent)] Discord(#[from] crate::discord_bot::Error),
+    #[error(transparent)] Draft(#[from] draft::Error),
+    #[error(transparent)] Groups(#[from] groups::Error),
     #[error(transparent)] Json(#[from] serde_json::Error),
     #[error(transparent)] Page(#[from] PageError),
     #[error(transparent)] RaceTime(#[from] racetime::Error),
@@ -836,6 +876,7 @@ pub(crate) enum Error {
     #[error(transparent)] SeedData(#[from] seed::ExtraDataError),
     #[error(transparent)] Serenity(#[from] serenity::Error),
     #[error(transparent)] Sql(#[from] sqlx::Error),
+    #[error(transparent)] Swiss(#[from] swiss::Error),
     #[error(transparent)] Url(#[from] url::ParseError),
     #[error(transparent)] Wheel(#[from] wheel::Error),
     #[error("missing user data for an event organizer")]
@@ -853,9 +894,12 @@ impl<E: Into<Error>> From<E> for StatusOrError<Error> {
 impl IsNetworkError for Error {
     fn is_network_error(&self) -> bool {
         match self {
+            Self::Bracket(e) => e.is_network_error(),
             Self::Calendar(e) => e.is_network_error(),
             Self::Data(_) => false,
             Self::Discord(_) => false,
+            Self::Draft(e) => e.is_network_error(),
+            Self::Groups(e) => e.is_network_error(),
             Self::Json(_) => false,
             Self::Page(e) => e.is_network_error(),
             Self::RaceTime(e) => e.is_network_error(),
@@ -864,6 +908,7 @@ impl IsNetworkError for Error {
             Self::SeedData(e) => e.is_network_error(),
             Self::Serenity(_) => false,
             Self::Sql(_) => false,
+            Self::Swiss(e) => e.is_network_error(),
             Self::Url(_) => false,
             Self::Wheel(e) => e.is_network_error(),
             Self::OrganizerUserData => false,
@@ -969,7 +1014,7 @@ pub(crate) async fn races(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &Stat
         let is_organizer = data.organizers(&mut transaction).await?.contains(me);
         let can_create = is_organizer && match data.match_source() {
             MatchSource::League => false,
-            MatchSource::Manual | MatchSource::Challonge { .. } | MatchSource::StartGG(_) => true,
+            MatchSource::Manual | MatchSource::Challonge { .. } | MatchSource::MidosHouse | MatchSource::StartGG(_) => true,
         };
         let show_restream_consent = is_organizer || data.restreamers(&mut transaction).await?.contains(me);
         let can_edit = show_restream_consent || me.is_archivist;
@@ -980,6 +1025,11 @@ pub(crate) async fn races(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &Stat
     let content = html! {
         : header;
         //TODO copiable calendar link (with link to index for explanation?)
+        @if data.draft_kind().is_some() {
+            p {
+                a(href = uri!(crate::draft::stats::get(series, event))) : "Settings draft statistics";
+            }
+        }
         @if any_races_ongoing_or_upcoming {
             //TODO split into ongoing and upcoming, show headers for both
             : cal::race_table(&mut transaction, &*discord_ctx.read().await, http_client, &uri, Some(&data), cal::RaceTableOptions { game_count: false, show_multistreams: true, can_create, can_edit, show_restream_consent, challonge_import_ctx: None }, &ongoing_and_upcoming_races).await?;
@@ -992,7 +1042,7 @@ pub(crate) async fn races(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &Stat
         } else if can_create && !any_races_ongoing_or_upcoming {
             div(class = "button-row") {
                 @match data.match_source() {
-                    MatchSource::Manual | MatchSource::Challonge { .. } => a(class = "button", href = uri!(crate::cal::create_race(series, event, _))) : "New Race";
+                    MatchSource::Manual | MatchSource::Challonge { .. } | MatchSource::MidosHouse => a(class = "button", href = uri!(crate::cal::create_race(series, event, _))) : "New Race";
                     //MatchSource::Challonge { .. } => a(class = "button", href = uri!(crate::cal::import_races(series, event))) : "Import"; // disabled due to Challonge pagination bug
                     MatchSource::League => {}
                     MatchSource::StartGG(_) => @if !data.auto_import {
@@ -1979,7 +2029,7 @@ pub(crate) async fn opt_out_post(pool: &State<PgPool>, discord_ctx: &State<RwFut
     }
 }
 
-#[derive(Debug, sqlx::Type)]
+#[derive(Debug, Clone, Copy, sqlx::Type)]
 #[sqlx(type_name = "async_kind", rename_all = "lowercase")]
 pub(crate) enum AsyncKind {
     #[sqlx(rename = "qualifier")]
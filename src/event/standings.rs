@@ -0,0 +1,247 @@
+//! Live standings for events whose matches are races between Mido's House teams, computed from the recorded race results.
+
+use {
+    std::cmp::Reverse,
+    rocket::{
+        http::ContentType,
+        response::content::RawJson,
+    },
+    sqlx::PgPool,
+    crate::{
+        event::{
+            Data,
+            DataError,
+            Tab,
+            swiss::format_points,
+        },
+        prelude::*,
+    },
+};
+
+#[derive(Debug, thiserror::Error, rocket_util::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Csv(#[from] csv::Error),
+    #[error(transparent)] Data(#[from] DataError),
+    #[error(transparent)] Event(#[from] event::Error),
+    #[error(transparent)] IntoInner(#[from] csv::IntoInnerError<csv::Writer<Vec<u8>>>),
+    #[error(transparent)] Json(#[from] serde_json::Error),
+    #[error(transparent)] Page(#[from] PageError),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+}
+
+impl<E: Into<Error>> From<E> for StatusOrError<Error> {
+    fn from(e: E) -> Self {
+        Self::Err(e.into())
+    }
+}
+
+/// A team's record across all races of the event so far.
+pub(crate) struct Standing {
+    pub(crate) team: Team,
+    pub(crate) wins: u32,
+    pub(crate) draws: u32,
+    pub(crate) losses: u32,
+    /// Twice the team's points, since a draw is worth half a point.
+    pub(crate) half_points: u32,
+    /// The number of this team's races that haven't finished yet.
+    pub(crate) remaining: u32,
+    finish_times: Vec<Duration>,
+}
+
+impl Standing {
+    /// The team's average finish time, not counting DNFs.
+    pub(crate) fn average_finish_time(&self) -> Option<Duration> {
+        (!self.finish_times.is_empty()).then(|| self.finish_times.iter().sum::<Duration>() / u32::try_from(self.finish_times.len()).expect("too many races"))
+    }
+}
+
+/// The placement and finish time of each team in a race, or `None` if the race's results haven't been recorded (yet).
+///
+/// Results are read from those recorded by the race bot or entered by an organizer, so this doesn't query racetime.gg.
+async fn race_results(transaction: &mut Transaction<'_, Postgres>, race: &Race) -> Result<Option<HashMap<Id<Teams>, (Option<i16>, Option<Duration>)>>, Error> {
+    let mut results = HashMap::new();
+    for result in cal::RaceResult::for_race(&mut *transaction, race.id).await? {
+        if let Entrant::MidosHouseTeam(team) = result.entrant {
+            results.insert(team.id, (result.placement, result.placement.and(result.finish_time)));
+        }
+    }
+    if race.teams().any(|team| !results.contains_key(&team.id)) { return Ok(None) }
+    Ok(Some(results))
+}
+
+/// The standings of the active teams of the event, sorted from first to last place.
+///
+/// Every pair of teams in a race with recorded results counts as a match: the team with the better placement wins, and if both teams have the same placement or neither finished, it's a draw.
+pub(crate) async fn standings(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, event: &Data<'_>) -> Result<Vec<Standing>, Error> {
+    let mut standings = Team::for_event(transaction, event.series, &event.event).await?
+        .into_iter()
+        .map(|team| (team.id, Standing {
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            half_points: 0,
+            remaining: 0,
+            finish_times: Vec::default(),
+            team,
+        }))
+        .collect::<HashMap<_, _>>();
+    for race in Race::for_event(transaction, http_client, event).await? {
+        if race.teams().count() < 2 { continue }
+        if !race.is_ended() {
+            for team in race.teams() {
+                if let Some(standing) = standings.get_mut(&team.id) {
+                    standing.remaining += 1;
+                }
+            }
+            continue
+        }
+        let Some(results) = race_results(transaction, &race).await? else { continue };
+        for (&team, &(placement, time)) in &results {
+            let Some(standing) = standings.get_mut(&team) else { continue };
+            if let Some(time) = time {
+                standing.finish_times.push(time);
+            }
+            for (&opponent, &(opponent_placement, _)) in &results {
+                if opponent == team { continue }
+                match (placement.is_none(), placement).cmp(&(opponent_placement.is_none(), opponent_placement)) {
+                    Ordering::Less => {
+                        standing.wins += 1;
+                        standing.half_points += 2;
+                    }
+                    Ordering::Equal => {
+                        standing.draws += 1;
+                        standing.half_points += 1;
+                    }
+                    Ordering::Greater => standing.losses += 1,
+                }
+            }
+        }
+    }
+    let mut standings = standings.into_values().collect_vec();
+    standings.sort_by_key(|standing| (Reverse(standing.half_points), Reverse(standing.wins), standing.average_finish_time().is_none(), standing.average_finish_time(), standing.team.id));
+    Ok(standings)
+}
+
+#[rocket::get("/event/<series>/<event>/standings")]
+pub(crate) async fn get(pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, series: Series, event: &str) -> Result<RawHtml<String>, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let header = data.header(&mut transaction, me.as_ref(), Tab::Standings, false).await?;
+    let standings = standings(&mut transaction, http_client, &data).await?;
+    let mut team_names = Vec::with_capacity(standings.len());
+    for standing in &standings {
+        team_names.push(standing.team.to_html(&mut transaction, false).await?);
+    }
+    let content = html! {
+        @if standings.is_empty() {
+            article {
+                p : "No teams have entered this event yet.";
+            }
+        } else {
+            p : "Standings are computed from the recorded results of this event's races. A win is worth 1 point and a draw ½ point. The average finish time doesn't include DNFs.";
+            table {
+                thead {
+                    tr {
+                        th : "Rank";
+                        @if let TeamConfig::Solo = data.team_config {
+                            th : "Player";
+                        } else {
+                            th : "Team";
+                        }
+                        th : "Points";
+                        th : "Wins";
+                        th : "Draws";
+                        th : "Losses";
+                        th : "Average finish time";
+                        th : "Remaining";
+                    }
+                }
+                tbody {
+                    @for (rank, (standing, team_name)) in standings.iter().zip_eq(team_names).enumerate() {
+                        tr {
+                            td : rank + 1;
+                            td : team_name;
+                            td : format_points(standing.half_points);
+                            td : standing.wins;
+                            td : standing.draws;
+                            td : standing.losses;
+                            td {
+                                @if let Some(average_finish_time) = standing.average_finish_time() {
+                                    : English.format_duration(average_finish_time, false);
+                                } else {
+                                    : "—";
+                                }
+                            }
+                            td : standing.remaining;
+                        }
+                    }
+                }
+            }
+            p {
+                : "Export: ";
+                a(href = uri!(json(series, event))) : "JSON";
+                : " • ";
+                a(href = uri!(csv(series, event))) : "CSV";
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: data.chests().await?, ..PageStyle::default() }, &format!("Standings — {}", data.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[derive(Serialize)]
+struct Row {
+    rank: usize,
+    team_id: Id<Teams>,
+    name: Option<String>,
+    racetime_slug: Option<String>,
+    points: f64,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    average_finish_time_seconds: Option<u64>,
+    remaining: u32,
+}
+
+async fn rows(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, event: &Data<'_>) -> Result<Vec<Row>, Error> {
+    let mut rows = Vec::default();
+    for (rank, standing) in standings(transaction, http_client, event).await?.into_iter().enumerate() {
+        rows.push(Row {
+            rank: rank + 1,
+            team_id: standing.team.id,
+            name: standing.team.name(transaction).await?.map(|name| name.into_owned()),
+            racetime_slug: standing.team.racetime_slug.clone(),
+            points: f64::from(standing.half_points) / 2.0,
+            wins: standing.wins,
+            draws: standing.draws,
+            losses: standing.losses,
+            average_finish_time_seconds: standing.average_finish_time().map(|time| time.as_secs()),
+            remaining: standing.remaining,
+        });
+    }
+    Ok(rows)
+}
+
+#[rocket::get("/event/<series>/<event>/standings.json")]
+pub(crate) async fn json(pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series, event: &str) -> Result<RawJson<Vec<u8>>, StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let rows = rows(&mut transaction, http_client, &data).await?;
+    transaction.commit().await?;
+    Ok(RawJson(serde_json::to_vec_pretty(&rows)?))
+}
+
+#[rocket::get("/event/<series>/<event>/standings.csv")]
+pub(crate) async fn csv(pool: &State<PgPool>, http_client: &State<reqwest::Client>, series: Series, event: &str) -> Result<(ContentType, Vec<u8>), StatusOrError<Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut csv = csv::Writer::from_writer(Vec::default());
+    for row in rows(&mut transaction, http_client, &data).await? {
+        csv.serialize(row)?;
+    }
+    transaction.commit().await?;
+    Ok((ContentType::CSV, csv.into_inner()?))
+}
@@ -0,0 +1,745 @@
+//! Swiss phases whose pairings are generated by Mido's House rather than an external bracket.
+//!
+//! Each round is a single race per pairing. A win is worth 1 point and a draw (both teams finishing with the same time) ½ point, while a double DNF counts as a loss for both teams. Pairings are Monrad-style: teams are ranked by points, then by Buchholz score (the sum of their opponents' points), then by their seed for the phase, and each team is paired with the highest-ranked team it hasn't played yet.
+
+use {
+    std::cmp::Reverse,
+    sqlx::PgPool,
+    crate::{
+        event::{
+            Data,
+            Tab,
+        },
+        prelude::*,
+    },
+};
+
+/// Upper bound on the number of pairings tried while avoiding rematches, after which rematches are allowed.
+const MAX_PAIRING_STEPS: usize = 100_000;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Discord(#[from] crate::discord_bot::Error),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+    #[error("a Swiss match references a team that doesn't exist")]
+    TeamData,
+}
+
+impl IsNetworkError for Error {
+    fn is_network_error(&self) -> bool {
+        match self {
+            Self::Cal(e) => e.is_network_error(),
+            Self::Discord(_) => false,
+            Self::Sql(_) => false,
+            Self::TeamData => false,
+        }
+    }
+}
+
+/// The result of a Swiss match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Win(Id<Teams>),
+    /// Both teams finished with the same time.
+    Draw,
+    /// Neither team finished.
+    DoubleDnf,
+}
+
+impl Outcome {
+    fn winner(&self) -> Option<Id<Teams>> {
+        if let Self::Win(winner) = *self { Some(winner) } else { None }
+    }
+
+    fn is_double_dnf(&self) -> bool {
+        matches!(self, Self::DoubleDnf)
+    }
+}
+
+/// A team's record in a Swiss phase so far.
+pub(crate) struct Standing {
+    pub(crate) team: Id<Teams>,
+    /// Twice the team's Swiss points, since a draw is worth half a point.
+    pub(crate) half_points: u32,
+    pub(crate) wins: u32,
+    pub(crate) draws: u32,
+    pub(crate) losses: u32,
+    /// The sum of the half points of this team's opponents so far.
+    pub(crate) buchholz: u32,
+    had_bye: bool,
+    opponents: Vec<Id<Teams>>,
+    seed: usize,
+}
+
+/// Formats a number of half points as Swiss points.
+pub(crate) fn format_points(half_points: u32) -> String {
+    if half_points % 2 == 0 {
+        format!("{}", half_points / 2)
+    } else {
+        format!("{}.5", half_points / 2)
+    }
+}
+
+/// The active teams of the event in the order in which they're seeded for the given phase: teams with a configured seed for the phase first, then by qualifier rank.
+pub(crate) async fn seed_order(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> sqlx::Result<Vec<Id<Teams>>> {
+    let seeds = sqlx::query!(r#"SELECT team AS "team: Id<Teams>", seed FROM seeds WHERE series = $1 AND event = $2 AND phase = $3"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await?
+        .into_iter()
+        .map(|row| (row.team, row.seed))
+        .collect::<HashMap<_, _>>();
+    let mut teams = Team::for_event(transaction, event.series, &event.event).await?;
+    teams.sort_by_key(|team| (seeds.get(&team.id).copied().unwrap_or(i32::MAX), team.qualifier_rank.unwrap_or(i16::MAX), team.id));
+    Ok(teams.into_iter().map(|team| team.id).collect())
+}
+
+/// The standings of the active teams in a Swiss phase, sorted from first to last place.
+pub(crate) async fn standings(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> sqlx::Result<Vec<Standing>> {
+    let seed_order = seed_order(transaction, event, phase).await?;
+    let matches = sqlx::query!(r#"SELECT team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>", winner AS "winner: Id<Teams>", double_dnf FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3 AND reported_at IS NOT NULL"#, event.series as _, &event.event, phase).fetch_all(&mut **transaction).await?
+        .into_iter()
+        .map(|row| (row.team1, row.team2, match (row.winner, row.double_dnf) {
+            (Some(winner), _) => Outcome::Win(winner),
+            (None, true) => Outcome::DoubleDnf,
+            (None, false) => Outcome::Draw,
+        }));
+    Ok(compute_standings(seed_order, matches))
+}
+
+/// Computes the standings from the active teams in seed order and the reported matches. A match without a second team is a bye.
+fn compute_standings(seed_order: Vec<Id<Teams>>, matches: impl IntoIterator<Item = (Id<Teams>, Option<Id<Teams>>, Outcome)>) -> Vec<Standing> {
+    let mut standings = seed_order.into_iter().enumerate().map(|(seed, team)| (team, Standing {
+        half_points: 0,
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        buchholz: 0,
+        had_bye: false,
+        opponents: Vec::default(),
+        team, seed,
+    })).collect::<HashMap<_, _>>();
+    // resigned teams aren't ranked, but their points still count towards their opponents' Buchholz scores
+    let mut half_points = HashMap::<Id<Teams>, u32>::default();
+    for (team1, team2, outcome) in matches {
+        let Some(team2) = team2 else {
+            *half_points.entry(team1).or_default() += 2;
+            if let Some(standing) = standings.get_mut(&team1) {
+                standing.half_points += 2;
+                standing.wins += 1;
+                standing.had_bye = true;
+            }
+            continue
+        };
+        for (team, opponent) in [(team1, team2), (team2, team1)] {
+            let points = match outcome {
+                Outcome::Win(winner) if winner == team => 2,
+                Outcome::Win(_) | Outcome::DoubleDnf => 0,
+                Outcome::Draw => 1,
+            };
+            *half_points.entry(team).or_default() += points;
+            if let Some(standing) = standings.get_mut(&team) {
+                standing.half_points += points;
+                match points {
+                    2 => standing.wins += 1,
+                    1 => standing.draws += 1,
+                    _ => standing.losses += 1,
+                }
+                standing.opponents.push(opponent);
+            }
+        }
+    }
+    let mut standings = standings.into_values().collect_vec();
+    for standing in &mut standings {
+        standing.buchholz = standing.opponents.iter().map(|opponent| half_points.get(opponent).copied().unwrap_or_default()).sum();
+    }
+    standings.sort_by_key(|standing| (Reverse(standing.half_points), Reverse(standing.buchholz), standing.seed));
+    standings
+}
+
+/// Pairs the teams from the given list in order, avoiding rematches. Returns `None` if this is impossible or the step budget runs out.
+fn pair_without_rematches(teams: &[Id<Teams>], played: &HashSet<(Id<Teams>, Id<Teams>)>, steps: &mut usize) -> Option<Vec<[Id<Teams>; 2]>> {
+    let Some((&team, rest)) = teams.split_first() else { return Some(Vec::default()) };
+    for (idx, &opponent) in rest.iter().enumerate() {
+        if *steps == 0 { return None }
+        *steps -= 1;
+        if played.contains(&(team, opponent)) { continue }
+        let mut remaining = rest.to_owned();
+        remaining.remove(idx);
+        if let Some(mut pairings) = pair_without_rematches(&remaining, played, steps) {
+            pairings.insert(0, [team, opponent]);
+            return Some(pairings)
+        }
+    }
+    None
+}
+
+/// Computes the pairings for the next round from the current standings. Returns the pairings and the team receiving a bye, if any.
+///
+/// In the first round, the top half of the seeds is paired against the bottom half. After that, teams are paired from the top of the standings down, avoiding rematches where possible.
+/// The bye goes to the lowest-ranked team that hasn't had one yet.
+fn pair(standings: &[Standing], first_round: bool) -> (Vec<[Id<Teams>; 2]>, Option<Id<Teams>>) {
+    let mut teams = standings.iter().map(|standing| standing.team).collect_vec();
+    let bye = if teams.len() % 2 == 1 {
+        let idx = standings.iter().rposition(|standing| !standing.had_bye).unwrap_or(teams.len() - 1);
+        Some(teams.remove(idx))
+    } else {
+        None
+    };
+    let pairings = if first_round {
+        let (top, bottom) = teams.split_at(teams.len() / 2);
+        top.iter().zip_eq(bottom).map(|(&team1, &team2)| [team1, team2]).collect()
+    } else {
+        let played = standings.iter()
+            .flat_map(|standing| standing.opponents.iter().map(|&opponent| (standing.team, opponent)))
+            .collect::<HashSet<_>>();
+        let mut steps = MAX_PAIRING_STEPS;
+        pair_without_rematches(&teams, &played, &mut steps)
+            .unwrap_or_else(|| teams.chunks_exact(2).map(|pair| [pair[0], pair[1]]).collect())
+    };
+    (pairings, bye)
+}
+
+/// Generates the pairings for the given round and creates a race for each of them.
+async fn create_round<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, phase: &str, round: i16) -> Result<Transaction<'a, Postgres>, Error> {
+    let standings = standings(&mut transaction, event, phase).await?;
+    let (pairings, bye) = pair(&standings, round == 1);
+    if let Some(bye) = bye {
+        sqlx::query!("INSERT INTO swiss_matches (series, event, phase, round, team1, reported_at, winner) VALUES ($1, $2, $3, $4, $5, NOW(), $5)", event.series as _, &event.event, phase, round, bye as _).execute(&mut *transaction).await?;
+    }
+    for [team1, team2] in pairings {
+        let team1 = Team::from_id(&mut transaction, team1).await?.ok_or(Error::TeamData)?;
+        let team2 = Team::from_id(&mut transaction, team2).await?.ok_or(Error::TeamData)?;
+        let draft = if let Some(draft_kind) = event.draft_kind() {
+            Some(Draft::for_game1(&mut transaction, http_client, draft_kind, event, Some(phase), [&team1, &team2]).await?)
+        } else {
+            None
+        };
+        let mut race = Race::new_for_pairing(&mut transaction, event, phase, format!("Round {round}"), None, [&team1, &team2], draft).await?;
+        transaction = crate::discord_bot::create_scheduling_thread(discord_ctx, transaction, &mut race, 1).await?;
+        race.save(&mut transaction).await?;
+        sqlx::query!("INSERT INTO swiss_matches (series, event, phase, round, team1, team2, race) VALUES ($1, $2, $3, $4, $5, $6, $7)", event.series as _, &event.event, phase, round, team1.id as _, team2.id as _, race.id as _).execute(&mut *transaction).await?;
+    }
+    Ok(transaction)
+}
+
+/// Makes the teams of the highest and 2nd-highest Swiss point groups with more than one team eligible for the respective tiebreaker asyncs.
+async fn assign_tiebreakers(transaction: &mut Transaction<'_, Postgres>, event: &Data<'_>, phase: &str) -> sqlx::Result<()> {
+    let standings = standings(transaction, event, phase).await?;
+    let mut kinds = [AsyncKind::Tiebreaker1, AsyncKind::Tiebreaker2].into_iter();
+    for (_, group) in &standings.iter().chunk_by(|standing| standing.half_points) {
+        let group = group.collect_vec();
+        if group.len() < 2 { continue }
+        let Some(kind) = kinds.next() else { break };
+        for standing in group {
+            sqlx::query!("INSERT INTO async_teams (team, kind) VALUES ($1, $2) ON CONFLICT (team, kind) DO NOTHING", standing.team as _, kind as _).execute(&mut **transaction).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the next round of a Swiss phase once all matches of its latest round have been reported, or assigns the tiebreaker asyncs after the last round.
+async fn advance<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, phase: &str) -> Result<Transaction<'a, Postgres>, Error> {
+    let rounds = sqlx::query_scalar!("SELECT rounds FROM swiss_phases WHERE series = $1 AND event = $2 AND phase = $3", event.series as _, &event.event, phase).fetch_one(&mut *transaction).await?;
+    let Some(current_round) = sqlx::query_scalar!("SELECT MAX(round) FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3", event.series as _, &event.event, phase).fetch_one(&mut *transaction).await? else { return Ok(transaction) };
+    if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3 AND round = $4 AND reported_at IS NULL) AS "exists!""#, event.series as _, &event.event, phase, current_round).fetch_one(&mut *transaction).await? {
+        return Ok(transaction)
+    }
+    if current_round < rounds {
+        transaction = create_round(transaction, discord_ctx, http_client, event, phase, current_round + 1).await?;
+    } else {
+        assign_tiebreakers(&mut transaction, event, phase).await?;
+    }
+    Ok(transaction)
+}
+
+/// Records the result of a race if it's part of a Swiss phase, advancing the phase to the next round if this was the last outstanding match.
+///
+/// If the race had already been reported, the result is corrected in place. Standings reflect the correction, but rounds which have already been paired aren't changed.
+pub(crate) async fn record_result<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, event: &Data<'_>, race: Id<Races>, outcome: Outcome) -> Result<Transaction<'a, Postgres>, Error> {
+    if let Some(phase) = sqlx::query_scalar!("UPDATE swiss_matches SET reported_at = NOW(), winner = $1, double_dnf = $2 WHERE race = $3 AND reported_at IS NULL RETURNING phase", outcome.winner() as _, outcome.is_double_dnf(), race as _).fetch_optional(&mut *transaction).await? {
+        transaction = advance(transaction, discord_ctx, http_client, event, &phase).await?;
+    } else {
+        sqlx::query!("UPDATE swiss_matches SET winner = $1, double_dnf = $2 WHERE race = $3", outcome.winner() as _, outcome.is_double_dnf(), race as _).execute(&mut *transaction).await?;
+    }
+    Ok(transaction)
+}
+
+enum SwissFormDefaults<'v> {
+    None,
+    AddContext(Context<'v>),
+    StartContext(String, Context<'v>),
+    ReportContext(String, i16, Id<Teams>, Context<'v>),
+}
+
+impl<'v> SwissFormDefaults<'v> {
+    fn add_errors(&self) -> Vec<&form::Error<'v>> {
+        if let Self::AddContext(ctx) = self {
+            ctx.errors().collect()
+        } else {
+            Vec::default()
+        }
+    }
+
+    fn add_value(&self, field_name: &str) -> Option<&str> {
+        if let Self::AddContext(ctx) = self {
+            ctx.field_value(field_name)
+        } else {
+            None
+        }
+    }
+
+    fn start_errors(&self, for_phase: &str) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::StartContext(phase, ctx) if phase == for_phase => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+
+    fn report_errors(&self, for_phase: &str, for_round: i16, for_team1: Id<Teams>) -> Vec<&form::Error<'v>> {
+        match self {
+            Self::ReportContext(phase, round, team1, ctx) if phase == for_phase && *round == for_round && *team1 == for_team1 => ctx.errors().collect(),
+            _ => Vec::default(),
+        }
+    }
+}
+
+async fn swiss_form(mut transaction: Transaction<'_, Postgres>, me: Option<User>, uri: Origin<'_>, csrf: Option<&CsrfToken>, event: Data<'_>, defaults: SwissFormDefaults<'_>) -> Result<RawHtml<String>, event::Error> {
+    let header = event.header(&mut transaction, me.as_ref(), Tab::Configure, true).await?;
+    let content = if event.is_ended() {
+        html! {
+            article {
+                p : "This event has ended and can no longer be configured.";
+            }
+        }
+    } else if let Some(ref me) = me {
+        if event.organizers(&mut transaction).await?.contains(me) {
+            let mut team_names = HashMap::new();
+            for team in Team::for_event(&mut transaction, event.series, &event.event).await? {
+                team_names.insert(team.id, team.to_html(&mut transaction, false).await?);
+            }
+            let mut phases = Vec::default();
+            for row in sqlx::query!("SELECT phase, rounds FROM swiss_phases WHERE series = $1 AND event = $2 ORDER BY phase", event.series as _, &event.event).fetch_all(&mut *transaction).await? {
+                let matches = sqlx::query!(r#"SELECT round, team1 AS "team1: Id<Teams>", team2 AS "team2: Id<Teams>", reported_at, winner AS "winner: Id<Teams>", double_dnf FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3 ORDER BY round, team1"#, event.series as _, &event.event, &row.phase).fetch_all(&mut *transaction).await?;
+                for team in matches.iter().flat_map(|row| [Some(row.team1), row.team2]).flatten() {
+                    if !team_names.contains_key(&team) {
+                        // resigned teams aren't included in Team::for_event
+                        let name = Team::from_id(&mut transaction, team).await?.ok_or(Error::TeamData)?.to_html(&mut transaction, false).await?;
+                        team_names.insert(team, name);
+                    }
+                }
+                let standings = standings(&mut transaction, &event, &row.phase).await?;
+                let current_round = matches.iter().map(|row| row.round).max();
+                phases.push((row.phase, row.rounds, matches, standings, current_round));
+            }
+            html! {
+                h2 : "Swiss phases";
+                p : "Mido's House can pair the rounds of a Swiss phase and create the races for each round automatically. Each round is a single race per pairing. The next round is paired as soon as all results of the current round are in. Results are recorded automatically when a race room finishes, except for draws within the retime window, which can be reported below.";
+                p {
+                    : "Round 1 is paired top half against bottom half by seed (see ";
+                    a(href = uri!(super::configure::seeding_get(event.series, &*event.event))) : "seeding";
+                    : "; teams without a seed are ordered by qualifier rank). Later rounds pair teams from the top of the standings down while avoiding rematches. For an odd number of teams, the lowest-ranked team without a bye gets a bye, which counts as a win. After the last round, the highest and 2nd-highest point groups with more than one team become eligible for the 1st and 2nd tiebreaker asyncs.";
+                }
+                @for (phase, rounds, matches, standings, current_round) in phases {
+                    h3 : &phase;
+                    @if let Some(current_round) = current_round {
+                        p : format!("Round {current_round} of {rounds}");
+                        table {
+                            thead {
+                                tr {
+                                    th : "Rank";
+                                    @if let TeamConfig::Solo = event.team_config {
+                                        th : "Player";
+                                    } else {
+                                        th : "Team";
+                                    }
+                                    th : "Points";
+                                    th : "Wins";
+                                    th : "Draws";
+                                    th : "Losses";
+                                    th : "Buchholz";
+                                }
+                            }
+                            tbody {
+                                @for (rank, standing) in standings.iter().enumerate() {
+                                    tr {
+                                        td : rank + 1;
+                                        td : team_names.get(&standing.team);
+                                        td : format_points(standing.half_points);
+                                        td : standing.wins;
+                                        td : standing.draws;
+                                        td : standing.losses;
+                                        td : format_points(standing.buchholz);
+                                    }
+                                }
+                            }
+                        }
+                        @for (round, round_matches) in &matches.iter().chunk_by(|row| row.round) {
+                            h4 : format!("Round {round}");
+                            table {
+                                thead {
+                                    tr {
+                                        th : "Pairing";
+                                        th : "Result";
+                                        th;
+                                    }
+                                }
+                                tbody {
+                                    @for row in round_matches {
+                                        tr {
+                                            @if let Some(team2) = row.team2 {
+                                                td {
+                                                    : team_names.get(&row.team1);
+                                                    : " vs ";
+                                                    : team_names.get(&team2);
+                                                }
+                                                td {
+                                                    @if row.reported_at.is_none() {
+                                                        : "pending";
+                                                    } else if let Some(winner) = row.winner {
+                                                        : team_names.get(&winner);
+                                                        : " won";
+                                                    } else if row.double_dnf {
+                                                        : "both did not finish";
+                                                    } else {
+                                                        : "draw";
+                                                    }
+                                                }
+                                                td {
+                                                    @if round == current_round {
+                                                        @let errors = defaults.report_errors(&phase, round, row.team1);
+                                                        @let (errors, button) = button_form_ext(uri!(report(event.series, &*event.event)), csrf, errors, html! {
+                                                            input(type = "hidden", name = "phase", value = &phase);
+                                                            input(type = "hidden", name = "round", value = round.to_string());
+                                                            input(type = "hidden", name = "team1", value = row.team1.to_string());
+                                                            select(name = "result") {
+                                                                option(value = "team1") : team_names.get(&row.team1);
+                                                                option(value = "team2") : team_names.get(&team2);
+                                                                option(value = "draw") : "Draw";
+                                                                option(value = "double_dnf") : "Both did not finish";
+                                                            }
+                                                        }, if row.reported_at.is_none() { "Report" } else { "Change result" });
+                                                        : errors;
+                                                        div(class = "button-row") : button;
+                                                    }
+                                                }
+                                            } else {
+                                                td : team_names.get(&row.team1);
+                                                td : "bye";
+                                                td;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    } else {
+                        p : format!("{rounds} rounds, not started yet");
+                        @let errors = defaults.start_errors(&phase);
+                        @let (errors, button) = button_form_ext(uri!(start(event.series, &*event.event)), csrf, errors, html! {
+                            input(type = "hidden", name = "phase", value = &phase);
+                        }, "Pair round 1 and create races");
+                        : errors;
+                        div(class = "button-row") : button;
+                    }
+                }
+                h3 : "Add Swiss phase";
+                @let mut errors = defaults.add_errors();
+                : full_form(uri!(add_phase(event.series, &*event.event)), csrf, html! {
+                    : form_field("phase", &mut errors, html! {
+                        label(for = "phase") : "Phase:";
+                        input(type = "text", name = "phase", value = defaults.add_value("phase").unwrap_or("Swiss"));
+                        label(class = "help") : "(Used as the phase of the created races.)";
+                    });
+                    : form_field("rounds", &mut errors, html! {
+                        label(for = "rounds") : "Number of rounds:";
+                        input(type = "number", min = "1", name = "rounds", value? = defaults.add_value("rounds"));
+                    });
+                }, errors, "Add");
+            }
+        } else {
+            html! {
+                article {
+                    p : "This page is for organizers of this event only.";
+                }
+            }
+        }
+    } else {
+        html! {
+            article {
+                p {
+                    a(href = uri!(auth::login(Some(uri!(get(event.series, &*event.event)))))) : "Sign in or create a Mido's House account";
+                    : " to configure this event.";
+                }
+            }
+        }
+    };
+    Ok(page(transaction, &me, &uri, PageStyle { chests: event.chests().await?, ..PageStyle::default() }, &format!("Swiss phases — {}", event.display_name), html! {
+        : header;
+        : content;
+    }).await?)
+}
+
+#[rocket::get("/event/<series>/<event>/configure/swiss")]
+pub(crate) async fn get(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: String) -> Result<RawHtml<String>, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    Ok(swiss_form(transaction, me, uri, csrf.as_ref(), data, SwissFormDefaults::None).await?)
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct AddPhaseForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    rounds: i16,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/swiss", data = "<form>")]
+pub(crate) async fn add_phase(pool: &State<PgPool>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, AddPhaseForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let phase = value.phase.trim();
+        if phase.is_empty() {
+            form.context.push_error(form::Error::validation("Please enter a phase.").with_name("phase"));
+        } else if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM swiss_phases WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, phase).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is already a Swiss phase with this name.").with_name("phase"));
+        }
+        if value.rounds < 1 {
+            form.context.push_error(form::Error::validation("There must be at least one round.").with_name("rounds"));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::AddContext(form.context)).await?)
+        } else {
+            sqlx::query!("INSERT INTO swiss_phases (series, event, phase, rounds) VALUES ($1, $2, $3, $4)", data.series as _, &data.event, phase, value.rounds).execute(&mut *transaction).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::AddContext(form.context)).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct StartForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/swiss/start", data = "<form>")]
+pub(crate) async fn start(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, StartForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        if !sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM swiss_phases WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, value.phase).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("There is no Swiss phase with this name."));
+        } else if sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3) AS "exists!""#, data.series as _, &data.event, value.phase).fetch_one(&mut *transaction).await? {
+            form.context.push_error(form::Error::validation("This Swiss phase has already started."));
+        }
+        if Team::for_event(&mut transaction, data.series, &data.event).await?.len() < 2 {
+            form.context.push_error(form::Error::validation("At least 2 teams are required to start a Swiss phase."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::StartContext(value.phase.clone(), form.context)).await?)
+        } else {
+            transaction = create_round(transaction, &*discord_ctx.read().await, http_client, &data, &value.phase, 1).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::None).await?)
+    })
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct ReportForm {
+    #[field(default = String::new())]
+    csrf: String,
+    phase: String,
+    round: i16,
+    team1: Id<Teams>,
+    result: String,
+}
+
+#[rocket::post("/event/<series>/<event>/configure/swiss/report", data = "<form>")]
+pub(crate) async fn report(pool: &State<PgPool>, discord_ctx: &State<RwFuture<DiscordCtx>>, http_client: &State<reqwest::Client>, me: User, uri: Origin<'_>, csrf: Option<CsrfToken>, series: Series, event: &str, form: Form<Contextual<'_, ReportForm>>) -> Result<RedirectOrContent, StatusOrError<event::Error>> {
+    let mut transaction = pool.begin().await?;
+    let data = Data::new(&mut transaction, series, event).await?.ok_or(StatusOrError::Status(Status::NotFound))?;
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    Ok(if let Some(ref value) = form.value {
+        if data.is_ended() {
+            form.context.push_error(form::Error::validation("This event has ended and can no longer be configured"));
+        }
+        if !data.organizers(&mut transaction).await?.contains(&me) {
+            form.context.push_error(form::Error::validation("You must be an organizer to configure this event."));
+        }
+        let team2 = sqlx::query_scalar!(r#"SELECT team2 AS "team2: Id<Teams>" FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3 AND round = $4 AND team1 = $5"#, data.series as _, &data.event, value.phase, value.round, value.team1 as _).fetch_optional(&mut *transaction).await?;
+        let current_round = sqlx::query_scalar!("SELECT MAX(round) FROM swiss_matches WHERE series = $1 AND event = $2 AND phase = $3", data.series as _, &data.event, value.phase).fetch_one(&mut *transaction).await?;
+        let outcome = match team2 {
+            None => {
+                form.context.push_error(form::Error::validation("There is no such Swiss match."));
+                None
+            }
+            Some(None) => {
+                form.context.push_error(form::Error::validation("Byes don't have results to report."));
+                None
+            }
+            Some(Some(team2)) => match &*value.result {
+                "team1" => Some(Outcome::Win(value.team1)),
+                "team2" => Some(Outcome::Win(team2)),
+                "draw" => Some(Outcome::Draw),
+                "double_dnf" => Some(Outcome::DoubleDnf),
+                _ => {
+                    form.context.push_error(form::Error::validation("Invalid result.").with_name("result"));
+                    None
+                }
+            },
+        };
+        if current_round != Some(value.round) {
+            form.context.push_error(form::Error::validation("Only results from the latest round can be changed, since later rounds have already been paired."));
+        }
+        if form.context.errors().next().is_some() {
+            RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::ReportContext(value.phase.clone(), value.round, value.team1, form.context)).await?)
+        } else {
+            let outcome = outcome.expect("validated");
+            sqlx::query!("UPDATE swiss_matches SET reported_at = NOW(), winner = $1, double_dnf = $2 WHERE series = $3 AND event = $4 AND phase = $5 AND round = $6 AND team1 = $7", outcome.winner() as _, outcome.is_double_dnf(), data.series as _, &data.event, value.phase, value.round, value.team1 as _).execute(&mut *transaction).await?;
+            transaction = advance(transaction, &*discord_ctx.read().await, http_client, &data, &value.phase).await?;
+            transaction.commit().await?;
+            RedirectOrContent::Redirect(Redirect::to(uri!(get(series, event))))
+        }
+    } else {
+        RedirectOrContent::Content(swiss_form(transaction, Some(me), uri, csrf.as_ref(), data, SwissFormDefaults::None).await?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(id: u64) -> Id<Teams> {
+        Id::from(id)
+    }
+
+    fn teams(standings: &[Standing]) -> Vec<Id<Teams>> {
+        standings.iter().map(|standing| standing.team).collect()
+    }
+
+    #[test]
+    fn first_round_pairs_top_half_against_bottom_half() {
+        let standings = compute_standings((1..=8).map(team).collect(), []);
+        let (pairings, bye) = pair(&standings, true);
+        assert_eq!(pairings, [[team(1), team(5)], [team(2), team(6)], [team(3), team(7)], [team(4), team(8)]]);
+        assert_eq!(bye, None);
+    }
+
+    #[test]
+    fn bye_goes_to_lowest_ranked_team_without_one() {
+        let standings = compute_standings((1..=5).map(team).collect(), [(team(5), None, Outcome::Win(team(5)))]);
+        let (pairings, bye) = pair(&standings, false);
+        assert_eq!(bye, Some(team(4)));
+        assert_eq!(pairings.len(), 2);
+        assert!(pairings.iter().flatten().all(|&paired| paired != team(4)));
+    }
+
+    #[test]
+    fn later_rounds_avoid_rematches() {
+        let round1 = [
+            (team(1), Some(team(3)), Outcome::Win(team(1))),
+            (team(2), Some(team(4)), Outcome::Win(team(2))),
+        ];
+        let standings = compute_standings((1..=4).map(team).collect(), round1);
+        let (pairings, bye) = pair(&standings, false);
+        assert_eq!(bye, None);
+        // pairing by rank is possible since neither 1 vs 2 nor 3 vs 4 is a rematch
+        assert_eq!(pairings, [[team(1), team(2)], [team(3), team(4)]]);
+        let round2 = [
+            (team(1), Some(team(2)), Outcome::Win(team(1))),
+            (team(3), Some(team(4)), Outcome::Win(team(3))),
+        ];
+        let standings = compute_standings((1..=4).map(team).collect(), round1.into_iter().chain(round2));
+        let (pairings, _) = pair(&standings, false);
+        // the ranking is 1, 2, 3, 4 again, but now the only pairing without rematches is 1 vs 4 and 2 vs 3
+        assert_eq!(pairings.len(), 2);
+        assert!(pairings.contains(&[team(1), team(4)]));
+        assert!(pairings.iter().any(|pairing| pairing.contains(&team(2)) && pairing.contains(&team(3))));
+    }
+
+    #[test]
+    fn pair_without_rematches_gives_up() {
+        let all = [team(1), team(2), team(3), team(4)];
+        let played = all.iter().cartesian_product(&all).filter(|(a, b)| a != b).map(|(&a, &b)| (a, b)).collect::<HashSet<_>>();
+        let mut steps = MAX_PAIRING_STEPS;
+        assert_eq!(pair_without_rematches(&all, &played, &mut steps), None);
+        let mut steps = 0;
+        assert_eq!(pair_without_rematches(&all, &HashSet::default(), &mut steps), None);
+        let mut steps = MAX_PAIRING_STEPS;
+        assert_eq!(pair_without_rematches(&[], &played, &mut steps), Some(Vec::default()));
+    }
+
+    #[test]
+    fn standings_score_outcomes() {
+        let matches = [
+            (team(1), Some(team(2)), Outcome::Win(team(1))),
+            (team(3), Some(team(4)), Outcome::Draw),
+            (team(5), Some(team(6)), Outcome::DoubleDnf),
+            (team(7), None, Outcome::Win(team(7))),
+        ];
+        let standings = compute_standings((1..=7).map(team).collect(), matches);
+        let points = standings.iter().map(|standing| (standing.team, standing.half_points, standing.wins, standing.draws, standing.losses)).collect::<HashMap<_, _>>();
+        assert_eq!(points[&team(1)], (team(1), 2, 1, 0, 0));
+        assert_eq!(points[&team(2)], (team(2), 0, 0, 0, 1));
+        assert_eq!(points[&team(3)], (team(3), 1, 0, 1, 0));
+        assert_eq!(points[&team(5)], (team(5), 0, 0, 0, 1));
+        assert_eq!(points[&team(6)], (team(6), 0, 0, 0, 1));
+        assert_eq!(points[&team(7)], (team(7), 2, 1, 0, 0));
+        assert!(standings.iter().find(|standing| standing.team == team(7)).unwrap().had_bye);
+        // wins and byes are tied, seed breaks the tie
+        assert_eq!(teams(&standings)[..2], [team(1), team(7)]);
+    }
+
+    #[test]
+    fn standings_break_ties_by_buchholz() {
+        let matches = [
+            // round 1
+            (team(1), Some(team(3)), Outcome::Win(team(1))),
+            (team(2), Some(team(4)), Outcome::Win(team(4))),
+            // round 2
+            (team(1), Some(team(4)), Outcome::Draw),
+            (team(3), Some(team(2)), Outcome::Win(team(3))),
+        ];
+        let standings = compute_standings((1..=4).map(team).collect(), matches);
+        // 1 and 4 both have 1.5 points, but 1's opponents (3 and 4) have 1 + 1.5 points while 4's opponents (2 and 1) only have 0 + 1.5
+        assert_eq!(teams(&standings), [team(1), team(4), team(3), team(2)]);
+        assert_eq!(standings[0].buchholz, 5);
+        assert_eq!(standings[1].buchholz, 3);
+    }
+
+    #[test]
+    fn resigned_teams_count_for_buchholz() {
+        // team 3 resigned after round 1, so it's not in the seed order anymore
+        let matches = [
+            (team(1), Some(team(3)), Outcome::Win(team(3))),
+            (team(2), Some(team(4)), Outcome::Win(team(2))),
+        ];
+        let standings = compute_standings([1, 2, 4].into_iter().map(team).collect(), matches);
+        assert_eq!(teams(&standings), [team(2), team(1), team(4)]);
+        assert_eq!(standings.iter().find(|standing| standing.team == team(1)).unwrap().buchholz, 2);
+    }
+}
@@ -10,7 +10,7 @@ pub(crate) struct EmptyForm {
     csrf: String,
 }
 
-fn render_form_error(error: &form::Error<'_>) -> RawHtml<String> {
+pub(crate) fn render_form_error(error: &form::Error<'_>) -> RawHtml<String> {
     html! {
         p(class = "error") : error;
     }
@@ -17,6 +17,7 @@ use {
     rocket_util::Doctype,
     crate::{
         api,
+        availability,
         notification::{
             self,
             Notification,
@@ -360,7 +361,7 @@ async fn index(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>,
         p {
             a(href = uri!(archive(_))) : "Past events";
             : " • ";
-            a(href = uri!(new_event)) : "Planning an event?";
+            a(href = uri!(event::create::get)) : "Planning an event?";
         }
         h1 : "Ongoing/upcoming races";
         p {
@@ -454,19 +455,6 @@ async fn archive(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, sort:
     Ok(page(transaction, &me, &uri, PageStyle { chests, ..PageStyle::default() }, "Event Archive — Mido's House", page_content).await?)
 }
 
-#[rocket::get("/new")]
-async fn new_event(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>) -> PageResult {
-    let mut transaction = pool.begin().await?;
-    let fenhl = User::from_id(&mut *transaction, crate::id::FENHL).await?.ok_or(PageError::FenhlUserData)?;
-    page(transaction, &me, &uri, PageStyle::default(), "New Event — Mido's House", html! {
-        p {
-            : "If you are planning a tournament, community race, or other event for the Ocarina of Time randomizer community, or if you would like Mido's House to archive data about a past event you organized, please contact ";
-            : fenhl;
-            : " to determine the specific needs of the event.";
-        }
-    }).await
-}
-
 #[rocket::get("/robots.txt")]
 async fn robots_txt() -> RawText<&'static str> {
     RawText("User-agent: *\nDisallow: /seed/\nDisallow: /static/\n")
@@ -564,12 +552,12 @@ pub(crate) async fn rocket(pool: PgPool, discord_ctx: RwFuture<DiscordCtx>, http
     .mount("/", rocket::routes![
         index,
         archive,
-        new_event,
         robots_txt,
         api::graphql_request,
         api::graphql_query,
         api::graphql_playground,
         api::entrants_csv,
+        api::results_csv,
         auth::racetime_callback,
         auth::discord_callback,
         auth::challonge_callback,
@@ -583,19 +571,36 @@ pub(crate) async fn rocket(pool: PgPool, discord_ctx: RwFuture<DiscordCtx>, http
         auth::register_racetime,
         auth::register_discord,
         auth::merge_accounts,
+        availability::get,
+        availability::post,
+        availability::add_exception,
+        availability::remove_exception,
         cal::index_help,
         cal::index,
         cal::for_series,
         cal::for_event,
+        cal::for_user,
+        cal::index_json,
+        cal::for_series_json,
+        cal::for_event_json,
+        cal::index_atom,
+        cal::for_series_atom,
+        cal::for_event_atom,
         cal::create_race,
         cal::create_race_post,
         cal::import_races,
         cal::import_races_post,
         cal::practice_seed,
+        cal::race_info,
         cal::edit_race,
         cal::edit_race_post,
+        cal::edit_race_result,
         cal::add_file_hash,
         cal::add_file_hash_post,
+        draft::history::get,
+        draft::stats::get,
+        draft::web::live,
+        draft::web::post,
         event::info,
         event::races,
         event::status,
@@ -617,8 +622,47 @@ pub(crate) async fn rocket(pool: PgPool, discord_ctx: RwFuture<DiscordCtx>, http
         event::configure::get,
         event::configure::post,
         event::configure::restreamers_get,
+        event::configure::conflicts,
         event::configure::add_restreamer,
         event::configure::remove_restreamer,
+        event::configure::seeding_get,
+        event::configure::add_seed,
+        event::configure::remove_seed,
+        event::configure::deadlines_get,
+        event::configure::set_deadline,
+        event::configure::remove_deadline,
+        event::configure::draft_timers_get,
+        event::configure::set_draft_timer,
+        event::configure::remove_draft_timer,
+        event::configure::enter_flow_get,
+        event::configure::add_requirement,
+        event::configure::enter_flow_deadline,
+        event::configure::edit_requirement,
+        event::configure::move_requirement_up,
+        event::configure::move_requirement_down,
+        event::configure::remove_requirement,
+        event::swiss::get,
+        event::swiss::add_phase,
+        event::swiss::start,
+        event::swiss::report,
+        event::groups::get,
+        event::groups::add_phase,
+        event::groups::start,
+        event::groups::report,
+        event::standings::get,
+        event::standings::json,
+        event::standings::csv,
+        event::bracket::get,
+        event::bracket::configure_get,
+        event::bracket::create,
+        event::bracket::report,
+        event::configure::clone_get,
+        event::configure::clone_post,
+        event::create::get,
+        event::create::post,
+        event::create::pending,
+        event::create::approve,
+        event::create::reject,
         favicon::favicon_ico,
         favicon::favicon_png,
         crate::mw::index,
@@ -628,6 +672,8 @@ pub(crate) async fn rocket(pool: PgPool, discord_ctx: RwFuture<DiscordCtx>, http
         notification::dismiss,
         seed::get,
         user::profile,
+        user::reset_calendar_token,
+        user::set_timezone,
     ])
     .mount("/static", FileServer::without_index("assets/static"))
     .register("/", rocket::catchers![
@@ -41,6 +41,14 @@ impl Table for Races {
     }
 }
 
+pub(crate) enum RescheduleRequests {}
+
+impl Table for RescheduleRequests {
+    fn query_exists(id: i64) -> sqlx::query::QueryScalar<'static, Postgres, bool, <Postgres as Database>::Arguments<'static>> {
+        sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM reschedule_requests WHERE id = $1) AS "exists!""#, id)
+    }
+}
+
 pub(crate) enum Teams {}
 
 impl Table for Teams {
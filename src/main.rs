@@ -23,14 +23,17 @@ use {
         io::stdout,
         net::UnixStream,
     },
-    crate::unix_socket::ClientMessage as Subcommand,
+    crate::unix_socket::ClientMessage,
 };
 
 mod api;
 mod auth;
+mod availability;
 mod cal;
 mod challonge;
 mod config;
+mod conflict;
+mod deadline;
 mod discord_bot;
 mod draft;
 mod event;
@@ -45,6 +48,8 @@ mod notification;
 mod ootr_web;
 mod prelude;
 mod racetime_bot;
+mod rating;
+mod reschedule;
 mod seed;
 mod series;
 mod sheets;
@@ -113,9 +118,25 @@ fn parse_port(arg: &str) -> Result<u16, std::num::ParseIntError> {
     }
 }
 
-#[cfg(not(unix))]
 #[derive(clap::Subcommand)]
-enum Subcommand {}
+enum Subcommand {
+    /// Run random settings drafts offline and report any that fail to complete with valid settings
+    SimulateDraft {
+        /// A hardcoded draft format (s7, mw3, mw4, mw5, rsl7, fr3, fr4, or fr5) or the path to a draft spec JSON file
+        format: String,
+        #[clap(short = 'n', long, default_value_t = 100)]
+        runs: u32,
+        /// Seed for the first run, incremented for each subsequent run
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Print the actions and picks of each successful run
+        #[clap(short, long)]
+        verbose: bool,
+    },
+    #[cfg(unix)]
+    #[clap(flatten)]
+    Client(ClientMessage),
+}
 
 #[derive(clap::Parser)]
 #[clap(version = CLAP_VERSION)]
@@ -137,6 +158,7 @@ enum Error {
     #[error(transparent)] Reqwest(#[from] reqwest::Error),
     #[error(transparent)] Rocket(#[from] rocket::Error),
     #[error(transparent)] Serenity(#[from] serenity::Error),
+    #[error(transparent)] SimulateDraft(#[from] draft::simulate::SimulationError),
     #[error(transparent)] Sql(#[from] sqlx::Error),
     #[error(transparent)] Task(#[from] tokio::task::JoinError),
     #[cfg(unix)] #[error(transparent)] Wheel(#[from] wheel::Error),
@@ -146,38 +168,43 @@ enum Error {
 #[wheel::main(rocket)]
 async fn main(Args { port, subcommand }: Args) -> Result<bool, Error> {
     if let Some(subcommand) = subcommand {
-        #[cfg(unix)] let mut sock = UnixStream::connect(unix_socket::PATH).await?;
-        #[cfg(unix)] subcommand.write(&mut sock).await?;
         match subcommand {
-            #[cfg(unix)] Subcommand::CleanupRoles { .. } => {
-                u8::read(&mut sock).await?;
-            }
-            #[cfg(unix)] Subcommand::PrepareStop { async_proto: false, .. } => {
-                while let Some(update) = Option::<PrepareStopUpdate>::read(&mut sock).await? {
-                    println!("{} preparing to stop Mido's House: {update}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+            Subcommand::SimulateDraft { format, runs, seed, verbose } => return Ok(draft::simulate::cli(&format, runs, seed, verbose).await?),
+            #[cfg(unix)] Subcommand::Client(msg) => {
+                let mut sock = UnixStream::connect(unix_socket::PATH).await?;
+                msg.write(&mut sock).await?;
+                match msg {
+                    ClientMessage::CleanupRoles { .. } => {
+                        u8::read(&mut sock).await?;
+                    }
+                    ClientMessage::PrepareStop { async_proto: false, .. } => {
+                        while let Some(update) = Option::<PrepareStopUpdate>::read(&mut sock).await? {
+                            println!("{} preparing to stop Mido's House: {update}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                        }
+                        println!("{} preparing to stop Mido's House: done", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                    }
+                    ClientMessage::PrepareStop { async_proto: true, .. } => {
+                        let mut stdout = stdout();
+                        while let Some(update) = Option::<PrepareStopUpdate>::read(&mut sock).await? {
+                            update.write(&mut stdout).await?;
+                            stdout.flush().await?;
+                        }
+                    }
+                    ClientMessage::Roll { .. } | ClientMessage::RollRsl { .. } | ClientMessage::Seed { .. } => while let Some(update) = Option::<SeedRollUpdate>::read(&mut sock).await? {
+                        println!("{} {update:#?}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                    },
+                    ClientMessage::UpdateRegionalVc { .. } => {
+                        println!("{} Mido's House: updating regional voice chat", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                        u8::read(&mut sock).await?;
+                        println!("{} Mido's House: done updating regional voice chat", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                    }
+                    ClientMessage::CheckEosmwAccess { .. } => {
+                        println!("{} Mido's House: checking end-of-season multiworld access", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                        let authorized = bool::read(&mut sock).await?;
+                        println!("{} Mido's House: done checking end-of-season multiworld access", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+                        return Ok(authorized)
+                    }
                 }
-                println!("{} preparing to stop Mido's House: done", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-            }
-            #[cfg(unix)] Subcommand::PrepareStop { async_proto: true, .. } => {
-                let mut stdout = stdout();
-                while let Some(update) = Option::<PrepareStopUpdate>::read(&mut sock).await? {
-                    update.write(&mut stdout).await?;
-                    stdout.flush().await?;
-                }
-            }
-            #[cfg(unix)] Subcommand::Roll { .. } | Subcommand::RollRsl { .. } | Subcommand::Seed { .. } => while let Some(update) = Option::<SeedRollUpdate>::read(&mut sock).await? {
-                println!("{} {update:#?}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-            },
-            #[cfg(unix)] Subcommand::UpdateRegionalVc { .. } => {
-                println!("{} Mido's House: updating regional voice chat", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-                u8::read(&mut sock).await?;
-                println!("{} Mido's House: done updating regional voice chat", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-            }
-            #[cfg(unix)] Subcommand::CheckEosmwAccess { .. } => {
-                println!("{} Mido's House: checking end-of-season multiworld access", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-                let authorized = bool::read(&mut sock).await?;
-                println!("{} Mido's House: done checking end-of-season multiworld access", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-                return Ok(authorized)
             }
         }
     } else {
@@ -249,6 +276,26 @@ async fn main(Args { port, subcommand }: Args) -> Result<bool, Error> {
             Ok(Err(e)) => Err(Error::from(e)),
             Err(e) => Err(Error::from(e)),
         });
+        let draft_timer_task = tokio::spawn(draft::timer::run(db_pool.clone(), http_client.clone(), discord_builder.ctx_fut.clone(), rocket.shutdown())).map(|res| match res {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(Error::from(e)),
+            Err(e) => Err(Error::from(e)),
+        });
+        let deadline_task = tokio::spawn(deadline::run(db_pool.clone(), http_client.clone(), discord_builder.ctx_fut.clone(), rocket.shutdown())).map(|res| match res {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(Error::from(e)),
+            Err(e) => Err(Error::from(e)),
+        });
+        let reschedule_task = tokio::spawn(reschedule::run(db_pool.clone(), discord_builder.ctx_fut.clone(), rocket.shutdown())).map(|res| match res {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(Error::from(e)),
+            Err(e) => Err(Error::from(e)),
+        });
+        let rating_task = tokio::spawn(rating::run(db_pool.clone(), http_client.clone(), rocket.shutdown())).map(|res| match res {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(Error::from(e)),
+            Err(e) => Err(Error::from(e)),
+        });
         let import_task = tokio::spawn(cal::auto_import_races(db_pool, http_client, config, rocket.shutdown(), discord_builder.ctx_fut.clone(), new_room_lock)).map(|res| match res {
             Ok(Ok(())) => Ok(()),
             Ok(Err(e)) => Err(Error::from(e)),
@@ -270,7 +317,7 @@ async fn main(Args { port, subcommand }: Args) -> Result<bool, Error> {
             Err(e) => Err(Error::from(e)),
         });
         #[cfg(not(unix))] let unix_socket_task = future::ok(());
-        let ((), (), (), (), ()) = tokio::try_join!(discord_task, import_task, racetime_task, rocket_task, unix_socket_task)?;
+        let ((), (), (), (), (), (), (), (), ()) = tokio::try_join!(discord_task, import_task, deadline_task, draft_timer_task, reschedule_task, rating_task, racetime_task, rocket_task, unix_socket_task)?;
     }
     Ok(true)
 }
@@ -253,6 +253,7 @@ pub(crate) use {
             Id,
             Notifications,
             Races,
+            RescheduleRequests,
             Teams,
             Users,
         },
@@ -35,7 +35,7 @@ use {
 #[cfg(unix)] use async_proto::Protocol;
 #[cfg(windows)] use directories::UserDirs;
 
-mod report;
+pub(crate) mod report;
 
 #[cfg(unix)] pub(crate) const PYTHON: &str = "python3";
 #[cfg(windows)] pub(crate) const PYTHON: &str = "py";
@@ -367,6 +367,7 @@ impl Goal {
         }
     }
 
+    /// The hardcoded draft format of this goal. Race rooms should use [`Handler::draft_kind`] instead, which also takes events with a custom draft spec into account.
     fn draft_kind(&self) -> Option<draft::Kind> {
         match self {
             Self::Cc7 => Some(draft::Kind::S7),
@@ -2451,6 +2452,19 @@ impl Handler {
 
     fn is_official(&self) -> bool { self.official_data.is_some() }
 
+    /// Events with a configured draft spec use it regardless of their goal's hardcoded draft format.
+    fn draft_kind(&self, goal: Goal) -> Option<draft::Kind> {
+        if_chain! {
+            if let Some(OfficialRaceData { ref event, .. }) = self.official_data;
+            if let Some(draft_kind @ draft::Kind::Custom(_)) = event.draft_kind();
+            then {
+                Some(draft_kind)
+            } else {
+                goal.draft_kind()
+            }
+        }
+    }
+
     async fn goal(&self, ctx: &RaceContext<GlobalState>) -> Result<Goal, GoalFromStrError> {
         if let Some(OfficialRaceData { goal, .. }) = self.official_data {
             Ok(goal)
@@ -2473,7 +2487,7 @@ impl Handler {
 
     async fn send_settings(&self, ctx: &RaceContext<GlobalState>, preface: &str, reply_to: &str) -> Result<(), Error> {
         let goal = self.goal(ctx).await.to_racetime()?;
-        if let Some(draft_kind) = goal.draft_kind() {
+        if let Some(draft_kind) = self.draft_kind(goal) {
             let available_settings = lock!(@read state = self.race_state; if let RaceState::Draft { state: ref draft, .. } = *state {
                 match draft.next_step(draft_kind, self.official_data.as_ref().and_then(|OfficialRaceData { cal_event, .. }| cal_event.race.game), &mut draft::MessageContext::RaceTime { high_seed_name: &self.high_seed_name, low_seed_name: &self.low_seed_name, reply_to }).await.to_racetime()?.kind {
                     draft::StepKind::GoFirst => None,
@@ -2495,6 +2509,7 @@ impl Handler {
                 draft::Kind::TournoiFrancoS3 => fr::S3_SETTINGS.into_iter().map(|fr::Setting { description, .. }| Cow::Borrowed(description)).collect(),
                 draft::Kind::TournoiFrancoS4 => fr::S4_SETTINGS.into_iter().map(|fr::Setting { description, .. }| Cow::Borrowed(description)).collect(),
                 draft::Kind::TournoiFrancoS5 => fr::S5_SETTINGS.into_iter().map(|fr::Setting { description, .. }| Cow::Borrowed(description)).collect(),
+                draft::Kind::Custom(spec) => spec.all_settings().map(|setting| Cow::Borrowed(&*setting.description)).collect(),
             });
             if available_settings.is_empty() {
                 ctx.say(if let French = goal.language() {
@@ -2516,7 +2531,7 @@ impl Handler {
 
     async fn advance_draft(&self, ctx: &RaceContext<GlobalState>, state: &RaceState) -> Result<(), Error> {
         let goal = self.goal(ctx).await.to_racetime()?;
-        let Some(draft_kind) = goal.draft_kind() else { unreachable!() };
+        let Some(draft_kind) = self.draft_kind(goal) else { unreachable!() };
         let RaceState::Draft { state: ref draft, unlock_spoiler_log } = *state else { unreachable!() };
         let step = draft.next_step(draft_kind, self.official_data.as_ref().and_then(|OfficialRaceData { cal_event, .. }| cal_event.race.game), &mut draft::MessageContext::RaceTime { high_seed_name: &self.high_seed_name, low_seed_name: &self.low_seed_name, reply_to: "friend" }).await.to_racetime()?;
         match step.kind {
@@ -2546,10 +2561,10 @@ impl Handler {
         let goal = self.goal(ctx).await.to_racetime()?;
         let reply_to = sender.map_or("friend", |user| &user.name);
         if let RaceStatusValue::Open | RaceStatusValue::Invitational = ctx.data().await.status.value {
-            lock!(@write state = self.race_state; if let Some(draft_kind) = goal.draft_kind() {
+            lock!(@write state = self.race_state; if let Some(draft_kind) = self.draft_kind(goal) {
                 match *state {
                     RaceState::Init => match draft_kind {
-                        draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => ctx.say(format!("Sorry {reply_to}, no draft has been started. Use “!seed draft” to start one.")).await?,
+                        draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => ctx.say(format!("Sorry {reply_to}, no draft has been started. Use “!seed draft” to start one.")).await?,
                         draft::Kind::RslS7 => ctx.say(format!("Sorry {reply_to}, no draft has been started. Use “!seed draft” to start one. For more info about these options, use !presets")).await?,
                         draft::Kind::TournoiFrancoS3 => ctx.say(format!("Désolé {reply_to}, le draft n'a pas débuté. Utilisez “!seed draft” pour en commencer un. Pour plus d'infos, utilisez !presets")).await?,
                         draft::Kind::TournoiFrancoS4 | draft::Kind::TournoiFrancoS5 => ctx.say(format!("Sorry {reply_to}, no draft has been started. Use “!seed draft” to start one. For more info about these options, use !presets / le draft n'a pas débuté. Utilisez “!seed draft” pour en commencer un. Pour plus d'infos, utilisez !presets")).await?,
@@ -2573,8 +2588,18 @@ impl Handler {
                             true
                         };
                         if is_active_team {
+                            let state_before = draft.clone();
+                            let logged_action = action.clone();
                             match draft.apply(draft_kind, self.official_data.as_ref().and_then(|OfficialRaceData { cal_event, .. }| cal_event.race.game), &mut draft::MessageContext::RaceTime { high_seed_name: &self.high_seed_name, low_seed_name: &self.low_seed_name, reply_to }, action).await.to_racetime()? {
-                                Ok(_) => self.advance_draft(ctx, &state).await?,
+                                Ok(_) => {
+                                    if let Some(OfficialRaceData { ref cal_event, .. }) = self.official_data {
+                                        let mut transaction = ctx.global_state.db_pool.begin().await.to_racetime()?;
+                                        Draft::lock(&mut transaction, cal_event.race.id).await.to_racetime()?;
+                                        draft::history::record(&mut transaction, cal_event.race.id, draft::history::Source::RaceTime, sender.map(|sender| draft::history::Actor::RaceTime(sender.id.clone())), &state_before, &logged_action).await.to_racetime()?;
+                                        transaction.commit().await.to_racetime()?;
+                                    }
+                                    self.advance_draft(ctx, &state).await?;
+                                }
                                 Err(mut error_msg) => {
                                     unlock!();
                                     // can't send messages longer than 1000 characters
@@ -2590,7 +2615,7 @@ impl Handler {
                             }
                         } else {
                             match draft_kind {
-                                draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 => ctx.say(format!("Sorry {reply_to}, it's not your turn in the settings draft.")).await?,
+                                draft::Kind::S7 | draft::Kind::MultiworldS3 | draft::Kind::MultiworldS4 | draft::Kind::MultiworldS5 | draft::Kind::Custom(_) => ctx.say(format!("Sorry {reply_to}, it's not your turn in the settings draft.")).await?,
                                 draft::Kind::RslS7 => ctx.say(format!("Sorry {reply_to}, it's not your turn in the weights draft.")).await?,
                                 draft::Kind::TournoiFrancoS3 => ctx.say(format!("Désolé {reply_to}, mais ce n'est pas votre tour.")).await?,
                                 draft::Kind::TournoiFrancoS4 | draft::Kind::TournoiFrancoS5 => ctx.say(format!("Sorry {reply_to}, it's not your turn in the settings draft. / mais ce n'est pas votre tour.")).await?,
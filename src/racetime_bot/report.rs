@@ -4,10 +4,11 @@ use {
     crate::{
         prelude::*,
         racetime_bot::*,
+        rating,
     },
 };
 
-trait Score {
+pub(crate) trait Score {
     type SortKey: Ord;
 
     fn is_dnf(&self) -> bool;
@@ -74,14 +75,58 @@ impl Score for tfb::Score {
     }
 }
 
-async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ctx: &RaceContext<GlobalState>, cal_event: &cal::Event, event: &event::Data<'_>, mut entrants: [(Entrant, S, Url); 2]) -> Result<Transaction<'a, Postgres>, Error> {
+/// Starts a results announcement with the race's phase, round, and game number, if any.
+fn push_info_prefix(builder: &mut MessageBuilder, race: &Race) {
+    let info_prefix = match (&race.phase, &race.round) {
+        (Some(phase), Some(round)) => Some(format!("{phase} {round}")),
+        (Some(phase), None) => Some(phase.clone()),
+        (None, Some(round)) => Some(round.clone()),
+        (None, None) => None,
+    };
+    match (info_prefix, race.game) {
+        (Some(prefix), Some(game)) => {
+            builder.push_safe(prefix);
+            builder.push(", game ");
+            builder.push(game.to_string());
+            builder.push(": ");
+        }
+        (Some(prefix), None) => {
+            builder.push_safe(prefix);
+            builder.push(": ");
+        }
+        (None, Some(game)) => {
+            builder.push("game ");
+            builder.push(game.to_string());
+            builder.push(": ");
+        }
+        (None, None) => {}
+    }
+}
+
+/// Records the results of a race in the database. Entrants who did not finish are listed without a placement.
+pub(crate) async fn record_results<S: Score>(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, entrants: &[(Entrant, S, Option<Url>)], retimed: bool) -> sqlx::Result<()> {
+    let results = entrants.iter().map(|(entrant, score, room)| cal::RaceResult {
+        entrant: entrant.clone(),
+        room: room.clone(),
+        finish_time: score.as_duration().flatten(),
+        placement: (!score.is_dnf()).then(|| i16::try_from(entrants.iter().filter(|(_, other, _)| other.sort_key() < score.sort_key()).count() + 1).expect("too many entrants")),
+        retimed,
+    }).collect_vec();
+    cal::RaceResult::save(transaction, race, &results).await
+}
+
+/// Announces and reports the result of a race between two entrants, and advances any Mido's House-managed phases the race is part of.
+///
+/// `retimed` should be set if the times were entered or corrected by an organizer, in which case they're considered final even if they're within the event's retime window.
+pub(crate) async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, league_api_key: &str, startgg_token: &str, race: &Race, event: &event::Data<'_>, mut entrants: [(Entrant, S, Option<Url>); 2], retimed: bool) -> Result<Transaction<'a, Postgres>, Error> {
     entrants.sort_unstable_by_key(|(_, time, _)| time.sort_key());
+    record_results(&mut transaction, race.id, &entrants, retimed).await.to_racetime()?;
     let [(winner, winning_time, winning_room), (loser, losing_time, losing_room)] = entrants;
     if winning_time.is_dnf() && losing_time.is_dnf() {
         if let Some(results_channel) = event.discord_race_results_channel.or(event.discord_organizer_channel) {
             let msg = if_chain! {
                 if let French = event.language;
-                if let Some(phase_round) = match (&cal_event.race.phase, &cal_event.race.round) {
+                if let Some(phase_round) = match (&race.phase, &race.round) {
                     (Some(phase), Some(round)) => if let Some(Some(phase_round)) = sqlx::query_scalar!("SELECT display_fr FROM phase_round_options WHERE series = $1 AND event = $2 AND phase = $3 AND round = $4", event.series as _, &event.event, phase, round).fetch_optional(&mut *transaction).await.to_racetime()? {
                         Some(Some(phase_round))
                     } else {
@@ -90,7 +135,7 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                     (Some(_), None) | (None, Some(_)) => None, // no translation
                     (None, None) => Some(None), // no phase/round
                 };
-                if cal_event.race.game.is_none();
+                if race.game.is_none();
                 then {
                     let mut builder = MessageBuilder::default();
                     if let Some(phase_round) = phase_round {
@@ -99,92 +144,96 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                     }
                     builder.push("Ni ");
                     builder.mention_entrant(&mut transaction, event.discord_guild, &winner).await.to_racetime()?;
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(winning_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref winning_room) = winning_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(winning_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(" ni ");
                     builder.mention_entrant(&mut transaction, event.discord_guild, &loser).await.to_racetime()?;
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(losing_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref losing_room) = losing_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(losing_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(" n'ont fini");
-                    if winning_room == losing_room {
-                        builder.push(" <");
-                        builder.push(winning_room);
-                        builder.push('>');
+                    if_chain! {
+                        if winning_room == losing_room;
+                        if let Some(winning_room) = winning_room;
+                        then {
+                            builder.push(" <");
+                            builder.push(winning_room);
+                            builder.push('>');
+                        }
                     }
                     builder.build()
                 } else {
                     let mut builder = MessageBuilder::default();
-                    let info_prefix = match (&cal_event.race.phase, &cal_event.race.round) {
-                        (Some(phase), Some(round)) => Some(format!("{phase} {round}")),
-                        (Some(phase), None) => Some(phase.clone()),
-                        (None, Some(round)) => Some(round.clone()),
-                        (None, None) => None,
-                    };
-                    match (info_prefix, cal_event.race.game) {
-                        (Some(prefix), Some(game)) => {
-                            builder.push_safe(prefix);
-                            builder.push(", game ");
-                            builder.push(game.to_string());
-                            builder.push(": ");
-                        }
-                        (Some(prefix), None) => {
-                            builder.push_safe(prefix);
-                            builder.push(": ");
-                        }
-                        (None, Some(game)) => {
-                            builder.push("game ");
-                            builder.push(game.to_string());
-                            builder.push(": ");
-                        }
-                        (None, None) => {}
-                    }
+                    push_info_prefix(&mut builder, race);
                     builder.mention_entrant(&mut transaction, event.discord_guild, &winner).await.to_racetime()?;
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(winning_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref winning_room) = winning_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(winning_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(" and ");
                     builder.mention_entrant(&mut transaction, event.discord_guild, &loser).await.to_racetime()?;
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(losing_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref losing_room) = losing_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(losing_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(" both did not finish");
-                    if winning_room == losing_room {
-                        builder.push(" <");
-                        builder.push(winning_room);
-                        builder.push('>');
+                    if_chain! {
+                        if winning_room == losing_room;
+                        if let Some(winning_room) = winning_room;
+                        then {
+                            builder.push(" <");
+                            builder.push(winning_room);
+                            builder.push('>');
+                        }
                     }
                     builder.build()
                 }
             };
-            results_channel.say(&*ctx.global_state.discord_ctx.read().await, msg).await.to_racetime()?;
+            results_channel.say(discord_ctx, msg).await.to_racetime()?;
         }
-    } else if losing_time.time_window(&winning_time).is_some_and(|time_window| time_window <= event.retime_window) {
+        transaction = event::swiss::record_result(transaction, discord_ctx, http_client, event, race.id, event::swiss::Outcome::DoubleDnf).await.to_racetime()?;
+        // a first report can't change the outcome of a decided match, so there's no notification for the organizers
+        (transaction, _) = event::bracket::record_result(transaction, discord_ctx, http_client, event, race.id, None).await.to_racetime()?;
+        event::groups::record_result(&mut transaction, race.id, None, &HashMap::default()).await.to_racetime()?;
+    } else if losing_time.time_window(&winning_time).is_some_and(|time_window| time_window <= if retimed { Duration::ZERO } else { event.retime_window }) {
         if let Some(organizer_channel) = event.discord_organizer_channel {
             let mut msg = MessageBuilder::default();
-            msg.push("race finished as a draw: <");
-            msg.push(winning_room.to_string());
-            if winning_room != losing_room {
-                msg.push("> and <");
-                msg.push(losing_room);
+            msg.push("race finished as a draw");
+            for (idx, room) in winning_room.iter().chain(losing_room.iter().filter(|&losing_room| winning_room.as_ref() != Some(losing_room))).enumerate() {
+                msg.push(if idx == 0 { ": <" } else { " and <" });
+                msg.push(room.to_string());
+                msg.push('>');
             }
-            msg.push('>');
-            if event.discord_race_results_channel.is_some() || matches!(cal_event.race.source, cal::Source::StartGG { .. }) {
+            if event.discord_race_results_channel.is_some() || matches!(race.source, cal::Source::StartGG { .. }) {
                 msg.push(" — please manually ");
                 if let Some(results_channel) = event.discord_race_results_channel {
                     msg.push("post the announcement in ");
                     msg.mention(&results_channel);
                 }
-                if let Some(startgg_set_url) = cal_event.race.startgg_set_url().to_racetime()? {
+                if let Some(startgg_set_url) = race.startgg_set_url().to_racetime()? {
                     if event.discord_race_results_channel.is_some() {
                         msg.push(" and ");
                     }
@@ -193,13 +242,13 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                 msg.push(" after adjusting the times");
             }
             //TODO note to manually initialize high seed for next game's draft (if any) and use `/post-status`
-            organizer_channel.say(&*ctx.global_state.discord_ctx.read().await, msg.build()).await.to_racetime()?;
+            organizer_channel.say(discord_ctx, msg.build()).await.to_racetime()?;
         }
     } else {
         if let Some(results_channel) = event.discord_race_results_channel.or(event.discord_organizer_channel) {
             let msg = if_chain! {
                 if let French = event.language;
-                if let Some(phase_round) = match (&cal_event.race.phase, &cal_event.race.round) {
+                if let Some(phase_round) = match (&race.phase, &race.round) {
                     (Some(phase), Some(round)) => if let Some(Some(phase_round)) = sqlx::query_scalar!("SELECT display_fr FROM phase_round_options WHERE series = $1 AND event = $2 AND phase = $3 AND round = $4", event.series as _, &event.event, phase, round).fetch_optional(&mut *transaction).await.to_racetime()? {
                         Some(Some(phase_round))
                     } else {
@@ -208,7 +257,7 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                     (Some(_), None) | (None, Some(_)) => None, // no translation
                     (None, None) => Some(None), // no phase/round
                 };
-                if cal_event.race.game.is_none();
+                if race.game.is_none();
                 then {
                     let mut builder = MessageBuilder::default();
                     if let Some(phase_round) = phase_round {
@@ -219,67 +268,58 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                     builder.push(" (");
                     builder.push(winning_time.format(French));
                     builder.push(')');
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(winning_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref winning_room) = winning_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(winning_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(if winner.name_is_plural() { " ont battu " } else { " a battu " });
                     builder.mention_entrant(&mut transaction, event.discord_guild, &loser).await.to_racetime()?;
                     builder.push(" (");
                     builder.push(losing_time.format(French));
-                    builder.push(if winning_room == losing_room { ") <" } else { ") [<" });
-                    builder.push(losing_room.to_string());
-                    builder.push(if winning_room == losing_room { ">" } else { ">]" });
+                    builder.push(')');
+                    if let Some(ref losing_room) = losing_room {
+                        builder.push(if winning_room.as_ref() == Some(losing_room) { " <" } else { " [<" });
+                        builder.push(losing_room.to_string());
+                        builder.push(if winning_room.as_ref() == Some(losing_room) { ">" } else { ">]" });
+                    }
                     builder.build()
                 } else {
                     let mut builder = MessageBuilder::default();
-                    let info_prefix = match (&cal_event.race.phase, &cal_event.race.round) {
-                        (Some(phase), Some(round)) => Some(format!("{phase} {round}")),
-                        (Some(phase), None) => Some(phase.clone()),
-                        (None, Some(round)) => Some(round.clone()),
-                        (None, None) => None,
-                    };
-                    match (info_prefix, cal_event.race.game) {
-                        (Some(prefix), Some(game)) => {
-                            builder.push_safe(prefix);
-                            builder.push(", game ");
-                            builder.push(game.to_string());
-                            builder.push(": ");
-                        }
-                        (Some(prefix), None) => {
-                            builder.push_safe(prefix);
-                            builder.push(": ");
-                        }
-                        (None, Some(game)) => {
-                            builder.push("game ");
-                            builder.push(game.to_string());
-                            builder.push(": ");
-                        }
-                        (None, None) => {}
-                    }
+                    push_info_prefix(&mut builder, race);
                     builder.mention_entrant(&mut transaction, event.discord_guild, &winner).await.to_racetime()?;
                     builder.push(" (");
                     builder.push(winning_time.format(English));
                     builder.push(')');
-                    if winning_room != losing_room {
-                        builder.push(" [<");
-                        builder.push(winning_room.to_string());
-                        builder.push(">]");
+                    if_chain! {
+                        if winning_room != losing_room;
+                        if let Some(ref winning_room) = winning_room;
+                        then {
+                            builder.push(" [<");
+                            builder.push(winning_room.to_string());
+                            builder.push(">]");
+                        }
                     }
                     builder.push(if winner.name_is_plural() { " defeat " } else { " defeats " });
                     builder.mention_entrant(&mut transaction, event.discord_guild, &loser).await.to_racetime()?;
                     builder.push(" (");
                     builder.push(losing_time.format(English));
-                    builder.push(if winning_room == losing_room { ") <" } else { ") [<" });
-                    builder.push(losing_room.to_string());
-                    builder.push(if winning_room == losing_room { ">" } else { ">]" });
+                    builder.push(')');
+                    if let Some(ref losing_room) = losing_room {
+                        builder.push(if winning_room.as_ref() == Some(losing_room) { " <" } else { " [<" });
+                        builder.push(losing_room.to_string());
+                        builder.push(if winning_room.as_ref() == Some(losing_room) { ">" } else { ">]" });
+                    }
                     builder.build()
                 }
             };
-            results_channel.say(&*ctx.global_state.discord_ctx.read().await, msg).await.to_racetime()?;
+            results_channel.say(discord_ctx, msg).await.to_racetime()?;
         }
-        match cal_event.race.source {
+        match race.source {
             cal::Source::Manual | cal::Source::Sheet { .. } => {}
             cal::Source::Challonge { .. } => {} //TODO
             cal::Source::League { id } => if let (Some(winner), Some(loser), Some(winning_time), Some(losing_time)) = (
@@ -296,47 +336,69 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
             ) {
                 let mut form = collect![as HashMap<_, _>:
                     "id" => id.to_string(),
-                    "racetimeRoom" => format!("https://{}{}", racetime_host(), ctx.data().await.url),
                     "fpa" => format!("0"), //TODO also report races with FPA calls
                     "winner" => winner,
                     "loser" => loser,
                 ];
+                if let Some(ref room) = winning_room {
+                    form.insert("racetimeRoom", room.to_string());
+                }
                 if let Some(winning_time) = winning_time {
                     form.insert("winningTime", winning_time.as_secs().to_string());
                 }
                 if let Some(losing_time) = losing_time {
                     form.insert("losingTime", losing_time.as_secs().to_string());
                 }
-                let request = ctx.global_state.http_client.post("https://league.ootrandomizer.com/reportResultFromMidoHouse")
-                    .bearer_auth(&ctx.global_state.league_api_key)
+                let request = http_client.post("https://league.ootrandomizer.com/reportResultFromMidoHouse")
+                    .bearer_auth(league_api_key)
                     .form(&form);
                 println!("reporting result to League website: {:?}", serde_urlencoded::to_string(&form));
                 request.send().await?.detailed_error_for_status().await.to_racetime()?;
             },
-            cal::Source::StartGG { ref set, .. } => if cal_event.race.game.is_none() { //TODO also auto-report multi-game matches (report all games but the last as match progress)
+            cal::Source::StartGG { ref set, .. } => if race.game.is_none() { //TODO also auto-report multi-game matches (report all games but the last as match progress)
                 if let Entrant::MidosHouseTeam(Team { startgg_id: Some(winner_entrant_id), .. }) = &winner {
-                    startgg::query_uncached::<startgg::ReportOneGameResultMutation>(&ctx.global_state.http_client, &ctx.global_state.startgg_token, startgg::report_one_game_result_mutation::Variables {
+                    startgg::query_uncached::<startgg::ReportOneGameResultMutation>(http_client, startgg_token, startgg::report_one_game_result_mutation::Variables {
                         set_id: set.clone(),
                         winner_entrant_id: winner_entrant_id.clone(),
                     }).await.to_racetime()?;
                 } else {
                     if let Some(organizer_channel) = event.discord_organizer_channel {
                         let mut msg = MessageBuilder::default();
-                        msg.push("failed to report race result to start.gg: <https://");
-                        msg.push(racetime_host());
-                        msg.push(&ctx.data().await.url);
+                        msg.push("failed to report race result to start.gg: <");
+                        if let Some(ref room) = winning_room {
+                            msg.push(room.to_string());
+                        } else {
+                            msg.push(uri!(base_uri(), cal::race_info(event.series, &*event.event, race.id)).to_string());
+                        }
                         msg.push("> (winner has no start.gg entrant ID)");
-                        organizer_channel.say(&*ctx.global_state.discord_ctx.read().await, msg.build()).await.to_racetime()?;
+                        organizer_channel.say(discord_ctx, msg.build()).await.to_racetime()?;
                     }
                 }
             },
             cal::Source::SpeedGaming { .. } => {} //TODO
         }
+        if let Entrant::MidosHouseTeam(ref winner) = winner {
+            transaction = event::swiss::record_result(transaction, discord_ctx, http_client, event, race.id, event::swiss::Outcome::Win(winner.id)).await.to_racetime()?;
+            // a first report can't change the outcome of a decided match, so there's no notification for the organizers
+            (transaction, _) = event::bracket::record_result(transaction, discord_ctx, http_client, event, race.id, Some(winner.id)).await.to_racetime()?;
+            let mut finish_times = HashMap::default();
+            if let Some(Some(winning_time)) = winning_time.as_duration() {
+                finish_times.insert(winner.id, winning_time);
+            }
+            if_chain! {
+                if let Entrant::MidosHouseTeam(ref loser) = loser;
+                if let Some(Some(losing_time)) = losing_time.as_duration();
+                then {
+                    finish_times.insert(loser.id, losing_time);
+                }
+            }
+            event::groups::record_result(&mut transaction, race.id, Some(winner.id), &finish_times).await.to_racetime()?;
+        }
         if_chain! {
             if let Entrant::MidosHouseTeam(winner) = winner;
             if let Entrant::MidosHouseTeam(loser) = loser;
             if let Some(draft_kind) = event.draft_kind();
-            if let Some(next_game) = cal_event.race.next_game(&mut transaction, &ctx.global_state.http_client).await.to_racetime()?;
+            if let Some(next_game) = race.next_game(&mut transaction, http_client).await.to_racetime()?;
             then {
                 //TODO if this game decides the match, delete next game instead of initializing draft
                 let draft = Draft::for_next_game(&mut transaction, draft_kind, loser.id, winner.id).await.to_racetime()?;
@@ -346,8 +408,7 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
                     if let Some(scheduling_thread) = next_game.scheduling_thread;
                     // not automatically posting if the match might already be decided
                     //TODO remove this condition after implementing handling for decided matches (see TODO comment above)
-                    if cal_event.race.game.expect("found next game for race without game number") <= cal_event.race.game_count(&mut transaction).await.to_racetime()? / 2;
-                    let discord_ctx = ctx.global_state.discord_ctx.read().await;
+                    if race.game.expect("found next game for race without game number") <= race.game_count(&mut transaction).await.to_racetime()? / 2;
                     let data = discord_ctx.data.read().await;
                     if let Some(Some(command_ids)) = data.get::<CommandIds>().and_then(|command_ids| command_ids.get(&guild_id).copied());
                     then {
@@ -366,33 +427,90 @@ async fn report_1v1<'a, S: Score>(mut transaction: Transaction<'a, Postgres>, ct
     Ok(transaction)
 }
 
-async fn report_ffa(ctx: &RaceContext<GlobalState>, cal_event: &cal::Event, event: &event::Data<'_>, room: Url) -> Result<(), Error> {
+/// Records and announces results entered by an organizer for a race with more than two entrants.
+pub(crate) async fn report_multi(transaction: &mut Transaction<'_, Postgres>, discord_ctx: &DiscordCtx, race: &Race, event: &event::Data<'_>, mut entrants: Vec<(Entrant, Option<Duration>, Option<Url>)>) -> Result<(), Error> {
+    entrants.sort_unstable_by_key(|(_, time, _)| time.sort_key());
+    record_results(&mut *transaction, race.id, &entrants, true).await.to_racetime()?;
     if let Some(results_channel) = event.discord_race_results_channel.or(event.discord_organizer_channel) {
         let mut builder = MessageBuilder::default();
-        let info_prefix = match (&cal_event.race.phase, &cal_event.race.round) {
-            (Some(phase), Some(round)) => Some(format!("{phase} {round}")),
-            (Some(phase), None) => Some(phase.clone()),
-            (None, Some(round)) => Some(round.clone()),
-            (None, None) => None,
-        };
-        match (info_prefix, cal_event.race.game) {
-            (Some(prefix), Some(game)) => {
-                builder.push_safe(prefix);
-                builder.push(", game ");
-                builder.push(game.to_string());
-                builder.push(": ");
-            }
-            (Some(prefix), None) => {
-                builder.push_safe(prefix);
-                builder.push(": ");
+        push_info_prefix(&mut builder, race);
+        builder.push("race finished:");
+        for (entrant, time, _) in &entrants {
+            builder.push_line("");
+            if !time.is_dnf() {
+                builder.push((entrants.iter().filter(|(_, other, _)| other.sort_key() < time.sort_key()).count() + 1).to_string());
+                builder.push(". ");
             }
-            (None, Some(game)) => {
-                builder.push("game ");
-                builder.push(game.to_string());
-                builder.push(": ");
+            builder.mention_entrant(&mut *transaction, event.discord_guild, entrant).await.to_racetime()?;
+            builder.push(" (");
+            builder.push(time.format(event.language));
+            builder.push(')');
+        }
+        results_channel.say(discord_ctx, builder.build()).await.to_racetime()?;
+    }
+    Ok(())
+}
+
+/// Records results corrected by an organizer after the race has already been reported, without announcing them again or reporting them to external sites.
+///
+/// Any Mido's House-managed phases the race is part of are updated with the corrected outcome, and ratings are recomputed if the race has already been rated.
+/// If the correction changes the outcome of a decided bracket match, a notification for the organizers is returned, to be sent once the transaction has been committed.
+pub(crate) async fn correct_results<'a>(mut transaction: Transaction<'a, Postgres>, discord_ctx: &DiscordCtx, http_client: &reqwest::Client, race: &Race, event: &event::Data<'_>, mut entrants: Vec<(Entrant, Option<Duration>, Option<Url>)>) -> Result<(Transaction<'a, Postgres>, Option<(ChannelId, String)>), Error> {
+    entrants.sort_unstable_by_key(|(_, time, _)| time.sort_key());
+    let mut bracket_notification = None;
+    record_results(&mut transaction, race.id, &entrants, true).await.to_racetime()?;
+    if let [(ref winning_entrant, winning_time, _), (ref losing_entrant, losing_time, _)] = *entrants {
+        let winner = if winning_time.sort_key() == losing_time.sort_key() {
+            Some(None) // both teams have the same time or neither finished
+        } else if let Entrant::MidosHouseTeam(ref team) = *winning_entrant {
+            Some(Some(team.id))
+        } else {
+            None
+        };
+        if let Some(winner) = winner {
+            let swiss_outcome = match winner {
+                Some(winner) => event::swiss::Outcome::Win(winner),
+                None if winning_time.is_none() => event::swiss::Outcome::DoubleDnf,
+                None => event::swiss::Outcome::Draw,
+            };
+            transaction = event::swiss::record_result(transaction, discord_ctx, http_client, event, race.id, swiss_outcome).await.to_racetime()?;
+            (transaction, bracket_notification) = event::bracket::record_result(transaction, discord_ctx, http_client, event, race.id, winner).await.to_racetime()?;
+            let mut finish_times = HashMap::default();
+            for (entrant, time) in [(winning_entrant, winning_time), (losing_entrant, losing_time)] {
+                if let (Entrant::MidosHouseTeam(team), Some(time)) = (entrant, time) {
+                    finish_times.insert(team.id, time);
+                }
             }
-            (None, None) => {}
+            event::groups::record_result(&mut transaction, race.id, winner, &finish_times).await.to_racetime()?;
         }
+    }
+    rating::invalidate(&mut transaction, race.id).await.to_racetime()?;
+    Ok((transaction, bracket_notification))
+}
+
+async fn report_ffa(transaction: &mut Transaction<'_, Postgres>, ctx: &RaceContext<GlobalState>, cal_event: &cal::Event, event: &event::Data<'_>, data: &RaceData, room: Url) -> Result<(), Error> {
+    let mut entrants = Vec::with_capacity(data.entrants.len());
+    for entrant in &data.entrants {
+        entrants.push((if_chain! {
+            if let TeamConfig::Solo = event.team_config;
+            if let Some(user) = User::from_racetime(&mut **transaction, &entrant.user.id).await.to_racetime()?;
+            if let Some(team) = Team::from_event_and_member(&mut *transaction, event.series, &event.event, user.id).await.to_racetime()?;
+            then {
+                Entrant::MidosHouseTeam(team)
+            } else {
+                Entrant::Named {
+                    name: entrant.user.full_name.clone(),
+                    racetime_id: Some(entrant.user.id.clone()),
+                    twitch_username: entrant.user.twitch_name.clone(),
+                }
+            }
+        }, entrant.finish_time, Some(room.clone())));
+    }
+    entrants.sort_unstable_by_key(|(_, time, _)| time.sort_key());
+    record_results(transaction, cal_event.race.id, &entrants, false).await.to_racetime()?;
+    if let Some(results_channel) = event.discord_race_results_channel.or(event.discord_organizer_channel) {
+        let mut builder = MessageBuilder::default();
+        push_info_prefix(&mut builder, &cal_event.race);
         builder.push("race finished: <");
         builder.push(room.to_string());
         builder.push('>');
@@ -501,7 +619,7 @@ impl Handler {
                 TeamConfig::Solo => match cal_event.race.entrants {
                     Entrants::Open | Entrants::Count { .. } => {
                         let room = Url::parse(&format!("https://{}{}", racetime_host(), data.url)).to_racetime()?;
-                        report_ffa(ctx, cal_event, event, room).await?;
+                        report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                     }
                     Entrants::Named(_) => unimplemented!(),
                     Entrants::Two(_) | Entrants::Three(_) => {
@@ -521,12 +639,12 @@ impl Handler {
                                             twitch_username: entrant.user.twitch_name.clone(),
                                         }
                                     }
-                                }, tfb_scores.remove(&entrant.user.id).expect("missing TFB score"), room.clone()));
+                                }, tfb_scores.remove(&entrant.user.id).expect("missing TFB score"), Some(room.clone())));
                             }
                             if let Ok(teams) = teams.try_into() {
-                                transaction = report_1v1(transaction, ctx, cal_event, event, teams).await?;
+                                transaction = report_1v1(transaction, &*ctx.global_state.discord_ctx.read().await, &ctx.global_state.http_client, &ctx.global_state.league_api_key, &ctx.global_state.startgg_token, &cal_event.race, event, teams, false).await?;
                             } else { //TODO separate function for reporting 3-entrant results
-                                report_ffa(ctx, cal_event, event, room).await?;
+                                report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                             }
                         } else {
                             let mut teams = Vec::with_capacity(data.entrants.len());
@@ -543,12 +661,12 @@ impl Handler {
                                             twitch_username: entrant.user.twitch_name.clone(),
                                         }
                                     }
-                                }, entrant.finish_time, room.clone()));
+                                }, entrant.finish_time, Some(room.clone())));
                             }
                             if let Ok(teams) = teams.try_into() {
-                                transaction = report_1v1(transaction, ctx, cal_event, event, teams).await?;
+                                transaction = report_1v1(transaction, &*ctx.global_state.discord_ctx.read().await, &ctx.global_state.http_client, &ctx.global_state.league_api_key, &ctx.global_state.startgg_token, &cal_event.race, event, teams, false).await?;
                             } else { //TODO separate function for reporting 3-entrant results
-                                report_ffa(ctx, cal_event, event, room).await?;
+                                report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                             }
                         }
                     }
@@ -557,7 +675,7 @@ impl Handler {
                 _ => match cal_event.race.entrants {
                     Entrants::Open | Entrants::Count { .. } => {
                         let room = Url::parse(&format!("https://{}{}", racetime_host(), data.url)).to_racetime()?;
-                        report_ffa(ctx, cal_event, event, room).await?;
+                        report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                     }
                     Entrants::Named(_) => unimplemented!(),
                     Entrants::Two(_) | Entrants::Three(_) => {
@@ -606,7 +724,7 @@ impl Handler {
                                     teams.push((
                                         Entrant::MidosHouseTeam(team),
                                         tfb_scores.remove(team_slug).expect("missing TFB score"),
-                                        team_rooms.remove(team_slug).expect("each team should have a room"),
+                                        Some(team_rooms.remove(team_slug).expect("each team should have a room")),
                                     ));
                                 } else {
                                     all_teams_found = false;
@@ -616,10 +734,10 @@ impl Handler {
                                 if all_teams_found;
                                 if let Ok(teams) = teams.try_into();
                                 then {
-                                    transaction = report_1v1(transaction, ctx, cal_event, event, teams).await?;
+                                    transaction = report_1v1(transaction, &*ctx.global_state.discord_ctx.read().await, &ctx.global_state.http_client, &ctx.global_state.league_api_key, &ctx.global_state.startgg_token, &cal_event.race, event, teams, false).await?;
                                 } else { //TODO separate function for reporting 3-entrant results
                                     let room = Url::parse(&format!("https://{}{}", racetime_host(), data.url)).to_racetime()?;
-                                    report_ffa(ctx, cal_event, event, room).await?;
+                                    report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                                 }
                             }
                         } else {
@@ -630,7 +748,7 @@ impl Handler {
                                     teams.push((
                                         Entrant::MidosHouseTeam(team),
                                         times.iter().try_fold(Duration::default(), |acc, &time| Some(acc + time?)).map(|total| total / u32::try_from(times.len()).expect("too many team members")),
-                                        team_rooms.remove(&team_slug).expect("each team should have a room"),
+                                        Some(team_rooms.remove(&team_slug).expect("each team should have a room")),
                                     ));
                                 } else {
                                     all_teams_found = false;
@@ -640,10 +758,10 @@ impl Handler {
                                 if all_teams_found;
                                 if let Ok(teams) = teams.try_into();
                                 then {
-                                    transaction = report_1v1(transaction, ctx, cal_event, event, teams).await?;
+                                    transaction = report_1v1(transaction, &*ctx.global_state.discord_ctx.read().await, &ctx.global_state.http_client, &ctx.global_state.league_api_key, &ctx.global_state.startgg_token, &cal_event.race, event, teams, false).await?;
                                 } else { //TODO separate function for reporting 3-entrant results
                                     let room = Url::parse(&format!("https://{}{}", racetime_host(), data.url)).to_racetime()?;
-                                    report_ffa(ctx, cal_event, event, room).await?;
+                                    report_ffa(&mut transaction, ctx, cal_event, event, &data, room).await?;
                                 }
                             }
                         }
@@ -0,0 +1,499 @@
+//! Glicko-2 player ratings across all Mido's House events, computed from finished races and qualifier asyncs.
+//!
+//! A background task rates each race (and each qualifier async once it's closed) as its own rating period, in chronological order.
+//! Each player's rating before each race or async is kept as a history, so a corrected race result only rewinds the ratings in its category to before that race.
+//! Races and asyncs that can't be rated (e.g. because the room was cancelled, or because of an error) are marked as skipped so they aren't checked again.
+//! To avoid flooding racetime.gg with requests while catching up on past races, only a limited number of races is processed per check.
+//! Every pair of teams in a race counts as a game: the team with the better time wins, and if both teams have the same time or neither finished, it's a draw.
+//! Team members are rated individually against the average rating of the opposing team.
+
+use {
+    racetime::model::RaceStatusValue,
+    sqlx::PgPool,
+    crate::{
+        event::{
+            AsyncKind,
+            TeamConfig,
+            teams,
+        },
+        prelude::*,
+    },
+};
+
+const DEFAULT_RATING: f64 = 1500.0;
+const DEFAULT_DEVIATION: f64 = 350.0;
+const DEFAULT_VOLATILITY: f64 = 0.06;
+/// The system constant τ, which constrains the change in volatility over time.
+const TAU: f64 = 0.5;
+/// The conversion factor between the Glicko and Glicko-2 scales.
+const SCALE: f64 = 173.7178;
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+/// The maximum number of races and asyncs processed per check.
+const BATCH_SIZE: usize = 100;
+/// How long after a race has ended to keep waiting for its room to finish before skipping it.
+const PENDING_TIMEOUT: TimeDelta = TimeDelta::days(1);
+
+/// Ratings are tracked separately for each kind of team format since skill in one doesn't necessarily carry over to the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence, sqlx::Type)]
+#[sqlx(type_name = "rating_category", rename_all = "snake_case")]
+pub(crate) enum Category {
+    Solo,
+    CoOp,
+    Multiworld,
+}
+
+impl Category {
+    /// Returns `None` for team formats where finish times don't reflect the skill of individual players in a comparable way.
+    pub(crate) fn from_team_config(team_config: TeamConfig) -> Option<Self> {
+        match team_config {
+            TeamConfig::Solo => Some(Self::Solo),
+            TeamConfig::CoOp | TeamConfig::TfbCoOp => Some(Self::CoOp),
+            TeamConfig::Multiworld => Some(Self::Multiworld),
+            TeamConfig::Pictionary => None,
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solo => write!(f, "Solo"),
+            Self::CoOp => write!(f, "Co-op"),
+            Self::Multiworld => write!(f, "Multiworld"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rating {
+    pub(crate) rating: f64,
+    pub(crate) deviation: f64,
+    volatility: f64,
+    /// The number of races and asyncs that went into this rating.
+    pub(crate) races: i32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_RATING,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+            races: 0,
+        }
+    }
+}
+
+impl Rating {
+    pub(crate) async fn for_user(transaction: &mut Transaction<'_, Postgres>, user: Id<Users>, category: Category) -> sqlx::Result<Self> {
+        Ok(sqlx::query_as!(Self, "SELECT rating, deviation, volatility, races FROM ratings WHERE user_id = $1 AND category = $2", user as _, category as _).fetch_optional(&mut **transaction).await?.unwrap_or_default())
+    }
+
+    /// All of the user's ratings, skipping categories in which they haven't been rated yet.
+    pub(crate) async fn all_for_user(transaction: &mut Transaction<'_, Postgres>, user: Id<Users>) -> sqlx::Result<Vec<(Category, Self)>> {
+        let mut ratings = Vec::default();
+        for category in all::<Category>() {
+            let rating = Self::for_user(&mut *transaction, user, category).await?;
+            if rating.races > 0 {
+                ratings.push((category, rating));
+            }
+        }
+        Ok(ratings)
+    }
+
+    /// A lower bound on the player's skill which we're about 95% confident in. Used for seeding so that players with few races aren't overrated.
+    pub(crate) fn conservative(&self) -> f64 {
+        self.rating - 2.0 * self.deviation
+    }
+
+    async fn save(&self, transaction: &mut Transaction<'_, Postgres>, user: Id<Users>, category: Category) -> sqlx::Result<()> {
+        sqlx::query!("INSERT INTO ratings (user_id, category, rating, deviation, volatility, races) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (user_id, category) DO UPDATE SET rating = EXCLUDED.rating, deviation = EXCLUDED.deviation, volatility = EXCLUDED.volatility, races = EXCLUDED.races",
+            user as _, category as _, self.rating, self.deviation, self.volatility, self.races,
+        ).execute(&mut **transaction).await?;
+        Ok(())
+    }
+
+    /// Returns the rating after a rating period with the given games, each given as the opponent's rating and deviation and a score of 1 for a win, 0.5 for a draw, or 0 for a loss.
+    fn update(self, games: &[(f64, f64, f64)]) -> Self {
+        fn g(phi: f64) -> f64 {
+            1.0 / (1.0 + 3.0 * phi.powi(2) / std::f64::consts::PI.powi(2)).sqrt()
+        }
+
+        fn e(mu: f64, mu_j: f64, phi_j: f64) -> f64 {
+            1.0 / (1.0 + (-g(phi_j) * (mu - mu_j)).exp())
+        }
+
+        if games.is_empty() { return self }
+        let mu = (self.rating - DEFAULT_RATING) / SCALE;
+        let phi = self.deviation / SCALE;
+        let sigma = self.volatility;
+        let mut v_inv = 0.0;
+        let mut delta_sum = 0.0;
+        for &(rating_j, deviation_j, score) in games {
+            let mu_j = (rating_j - DEFAULT_RATING) / SCALE;
+            let phi_j = deviation_j / SCALE;
+            let expected = e(mu, mu_j, phi_j);
+            v_inv += g(phi_j).powi(2) * expected * (1.0 - expected);
+            delta_sum += g(phi_j) * (score - expected);
+        }
+        let v = 1.0 / v_inv;
+        let delta = v * delta_sum;
+        // new volatility using the Illinois algorithm
+        let a = sigma.powi(2).ln();
+        let f = |x: f64| x.exp() * (delta.powi(2) - phi.powi(2) - v - x.exp()) / (2.0 * (phi.powi(2) + v + x.exp()).powi(2)) - (x - a) / TAU.powi(2);
+        let mut big_a = a;
+        let mut big_b = if delta.powi(2) > phi.powi(2) + v {
+            (delta.powi(2) - phi.powi(2) - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * TAU) < 0.0 {
+                k += 1.0;
+            }
+            a - k * TAU
+        };
+        let mut f_a = f(big_a);
+        let mut f_b = f(big_b);
+        while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+            big_b = big_c;
+            f_b = f_c;
+        }
+        let sigma = (big_a / 2.0).exp();
+        let phi_star = (phi.powi(2) + sigma.powi(2)).sqrt();
+        let phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let mu = mu + phi.powi(2) * delta_sum;
+        Self {
+            rating: mu * SCALE + DEFAULT_RATING,
+            deviation: phi * SCALE,
+            volatility: sigma,
+            races: self.races + 1,
+        }
+    }
+}
+
+/// The average conservative rating of the team's members, used as a seeding source. Members who haven't been rated yet count with the default rating.
+pub(crate) async fn team_rating(transaction: &mut Transaction<'_, Postgres>, team_config: TeamConfig, team: &Team) -> sqlx::Result<f64> {
+    let members = team.members(&mut *transaction).await?;
+    if members.is_empty() { return Ok(Rating::default().conservative()) }
+    let mut total = 0.0;
+    for member in &members {
+        total += if let Some(category) = Category::from_team_config(team_config) {
+            Rating::for_user(&mut *transaction, member.id, category).await?.conservative()
+        } else {
+            Rating::default().conservative()
+        };
+    }
+    Ok(total / members.len() as f64)
+}
+
+/// One side of a rated race: the members of a team (or a single player) and their finish time, or `None` if they didn't finish.
+struct Side {
+    players: Vec<Id<Users>>,
+    time: Option<Duration>,
+}
+
+/// Combines the members' times the same way as the standings: the average of all members' times, or `None` if any member did not finish.
+fn team_time(times: &[Option<Duration>]) -> Option<Duration> {
+    times.iter().try_fold(Duration::default(), |acc, &time| Some(acc + time?)).map(|total| total / u32::try_from(times.len()).expect("too many team members"))
+}
+
+enum RaceSides {
+    Finished(Vec<Side>),
+    /// A race room hasn't finished yet.
+    Pending,
+    /// A race room was cancelled, so there are no results to rate.
+    Cancelled,
+}
+
+async fn race_sides(transaction: &mut Transaction<'_, Postgres>, cache: &mut teams::Cache, race: &Race) -> Result<RaceSides, event::Error> {
+    let results = cal::RaceResult::for_race(&mut *transaction, race.id).await?;
+    if !results.is_empty() {
+        // results reported by the bot or entered by an organizer take precedence over the room data
+        let mut sides = Vec::with_capacity(results.len());
+        for result in results {
+            let players = match result.entrant {
+                Entrant::MidosHouseTeam(ref team) => team.members(&mut *transaction).await?.into_iter().map(|member| member.id).collect(),
+                Entrant::Discord { id, ref racetime_id, .. } => if let Some(user) = User::from_discord(&mut **transaction, id).await? {
+                    vec![user.id]
+                } else if let Some(user) = if let Some(racetime_id) = racetime_id { User::from_racetime(&mut **transaction, racetime_id).await? } else { None } {
+                    vec![user.id]
+                } else {
+                    Vec::default()
+                },
+                Entrant::Named { racetime_id: Some(ref racetime_id), .. } => User::from_racetime(&mut **transaction, racetime_id).await?.into_iter().map(|user| user.id).collect(),
+                Entrant::Named { racetime_id: None, .. } => Vec::default(),
+            };
+            sides.push(Side { time: result.placement.and(result.finish_time), players });
+        }
+        return Ok(RaceSides::Finished(sides))
+    }
+    let mut team_members = HashMap::new();
+    for team in race.teams() {
+        for member in team.members(&mut *transaction).await? {
+            if let Some(racetime) = member.racetime {
+                team_members.insert(racetime.id, team.id.to_string());
+            }
+        }
+    }
+    let mut side_times = HashMap::<_, (Vec<_>, Vec<_>)>::default();
+    for room in race.rooms() {
+        let room_data = cache.race_data(&room).await?;
+        match room_data.status.value {
+            RaceStatusValue::Finished => {}
+            RaceStatusValue::Cancelled => return Ok(RaceSides::Cancelled),
+            _ => return Ok(RaceSides::Pending),
+        }
+        for entrant in &room_data.entrants {
+            let Some(user) = User::from_racetime(&mut **transaction, &entrant.user.id).await? else { continue };
+            let key = team_members.get(&entrant.user.id).cloned()
+                .or_else(|| entrant.team.as_ref().map(|team| team.slug.clone()))
+                .unwrap_or_else(|| entrant.user.id.clone());
+            let (players, times) = side_times.entry(key).or_default();
+            players.push(user.id);
+            times.push(entrant.finish_time);
+        }
+    }
+    Ok(RaceSides::Finished(side_times.into_values().map(|(players, times)| Side { time: team_time(&times), players }).collect()))
+}
+
+/// The sides of a closed qualifier async, grouping players by team.
+async fn async_sides(transaction: &mut Transaction<'_, Postgres>, series: Series, event: &str, kind: AsyncKind) -> Result<Vec<Side>, event::Error> {
+    let rows = sqlx::query!(r#"SELECT player AS "player: Id<Users>", time, team AS "team?: Id<Teams>" FROM async_players LEFT JOIN team_members ON member = player AND team IN (SELECT id FROM teams WHERE series = $1 AND event = $2 AND NOT resigned) WHERE series = $1 AND event = $2 AND kind = $3"#, series as _, event, kind as _).fetch_all(&mut **transaction).await?;
+    let mut side_times = HashMap::<_, (Vec<_>, Vec<_>)>::default();
+    for row in rows {
+        let (players, times) = side_times.entry(row.team.map_or_else(|| Either::Left(row.player), Either::Right)).or_default();
+        players.push(row.player);
+        times.push(row.time.map(decode_pginterval).transpose().map_err(event::DataError::PgInterval)?);
+    }
+    Ok(side_times.into_values().map(|(players, times)| Side { time: team_time(&times), players }).collect())
+}
+
+/// Updates the ratings of all players in a single race or async, which is rated as the given rating period.
+///
+/// The players' previous ratings are kept in the rating history so the update can be rewound by [`invalidate`].
+async fn rate(transaction: &mut Transaction<'_, Postgres>, category: Category, period: i64, sides: &[Side]) -> sqlx::Result<()> {
+    let mut ratings = HashMap::new();
+    for side in sides {
+        for &player in &side.players {
+            ratings.insert(player, Rating::for_user(&mut *transaction, player, category).await?);
+        }
+    }
+    for (&player, rating) in &ratings {
+        sqlx::query!("INSERT INTO rating_history (period, category, user_id, rating, deviation, volatility, races) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            period, category as _, player as _, rating.rating, rating.deviation, rating.volatility, rating.races,
+        ).execute(&mut **transaction).await?;
+    }
+    let side_ratings = sides.iter().map(|side| {
+        let count = side.players.len().max(1) as f64;
+        let rating = side.players.iter().map(|player| ratings[player].rating).sum::<f64>() / count;
+        let deviation = (side.players.iter().map(|player| ratings[player].deviation.powi(2)).sum::<f64>() / count).sqrt();
+        (rating, deviation)
+    }).collect_vec();
+    for (idx, side) in sides.iter().enumerate() {
+        if side.players.is_empty() { continue }
+        let games = sides.iter().zip(&side_ratings).enumerate()
+            .filter(|&(opponent_idx, (opponent, _))| opponent_idx != idx && !opponent.players.is_empty())
+            .map(|(_, (opponent, &(rating, deviation)))| (rating, deviation, match (side.time.is_none(), side.time).cmp(&(opponent.time.is_none(), opponent.time)) {
+                Less => 1.0,
+                Equal => 0.5,
+                Greater => 0.0,
+            }))
+            .collect_vec();
+        for &player in &side.players {
+            ratings[&player].update(&games).save(&mut *transaction, player, category).await?;
+        }
+    }
+    Ok(())
+}
+
+/// If the given race has already been rated, rewinds the ratings in its category to before the race, so it and everything rated after it are recomputed in chronological order with its corrected results on the next check.
+///
+/// Glicko-2 updates can't be undone individually since each rating period depends on the ratings resulting from the previous ones.
+pub(crate) async fn invalidate(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>) -> sqlx::Result<()> {
+    let Some(row) = sqlx::query!(r#"SELECT period, category AS "category!: Category" FROM rated_races WHERE race = $1 AND NOT skipped"#, race as _).fetch_optional(&mut **transaction).await? else { return Ok(()) };
+    // restore each affected player's rating from before the earliest rewound rating period they were in
+    sqlx::query!("UPDATE ratings SET rating = history.rating, deviation = history.deviation, volatility = history.volatility, races = history.races FROM (
+        SELECT DISTINCT ON (user_id) user_id, rating, deviation, volatility, races FROM rating_history WHERE category = $1 AND period >= $2 ORDER BY user_id, period
+    ) AS history WHERE ratings.user_id = history.user_id AND ratings.category = $1", row.category as _, row.period).execute(&mut **transaction).await?;
+    // players who hadn't been rated before
+    sqlx::query!("DELETE FROM ratings WHERE category = $1 AND races = 0", row.category as _).execute(&mut **transaction).await?;
+    sqlx::query!("DELETE FROM rating_history WHERE category = $1 AND period >= $2", row.category as _, row.period).execute(&mut **transaction).await?;
+    sqlx::query!("DELETE FROM rated_races WHERE category = $1 AND period >= $2", row.category as _, row.period).execute(&mut **transaction).await?;
+    sqlx::query!("DELETE FROM rated_asyncs WHERE category = $1 AND period >= $2", row.category as _, row.period).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+enum Unrated {
+    Race(Id<Races>),
+    Async {
+        series: Series,
+        event: String,
+        kind: AsyncKind,
+    },
+}
+
+impl fmt::Display for Unrated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Race(id) => write!(f, "race {id}"),
+            Self::Async { series, event, kind } => write!(f, "{kind:?} async of {}/{event}", series.slug()),
+        }
+    }
+}
+
+enum Outcome {
+    Rated(Category),
+    /// The race or async won't be rated, e.g. because its room was cancelled or its event's team format isn't rated.
+    Skipped,
+    /// The race room hasn't finished yet, so the race and everything after it will be checked again later.
+    Pending,
+}
+
+async fn rate_unrated(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, cache: &mut teams::Cache, ended_at: DateTime<Utc>, period: i64, unrated: &Unrated) -> Result<Outcome, event::Error> {
+    Ok(match *unrated {
+        Unrated::Race(id) => {
+            let race = Race::from_id(&mut *transaction, http_client, id).await?;
+            let Some(event) = event::Data::new(&mut *transaction, race.series, &race.event).await? else { return Ok(Outcome::Skipped) };
+            let Some(category) = Category::from_team_config(event.team_config) else { return Ok(Outcome::Skipped) };
+            match race_sides(&mut *transaction, cache, &race).await? {
+                RaceSides::Finished(sides) => {
+                    rate(&mut *transaction, category, period, &sides).await?;
+                    Outcome::Rated(category)
+                }
+                RaceSides::Pending => if Utc::now() - ended_at > PENDING_TIMEOUT { Outcome::Skipped } else { Outcome::Pending },
+                RaceSides::Cancelled => Outcome::Skipped,
+            }
+        }
+        Unrated::Async { series, ref event, kind } => {
+            let Some(data) = event::Data::new(&mut *transaction, series, event).await? else { return Ok(Outcome::Skipped) };
+            let Some(category) = Category::from_team_config(data.team_config) else { return Ok(Outcome::Skipped) };
+            let sides = async_sides(&mut *transaction, series, event, kind).await?;
+            rate(&mut *transaction, category, period, &sides).await?;
+            Outcome::Rated(category)
+        }
+    })
+}
+
+/// Marks a race or async as processed, either as rated in the given category or as skipped if `category` is `None`.
+async fn mark(transaction: &mut Transaction<'_, Postgres>, unrated: &Unrated, period: i64, category: Option<Category>) -> sqlx::Result<()> {
+    let skipped = category.is_none();
+    match *unrated {
+        Unrated::Race(id) => { sqlx::query!("INSERT INTO rated_races (race, skipped, period, category) VALUES ($1, $2, $3, $4)", id as _, skipped, period, category as _).execute(&mut **transaction).await?; }
+        Unrated::Async { series, ref event, kind } => { sqlx::query!("INSERT INTO rated_asyncs (series, event, kind, skipped, period, category) VALUES ($1, $2, $3, $4, $5, $6)", series as _, event, kind as _, skipped, period, category as _).execute(&mut **transaction).await?; }
+    }
+    Ok(())
+}
+
+/// Returns whether there are more races or asyncs left to process than fit in one batch.
+async fn update_all(db_pool: &PgPool, http_client: &reqwest::Client) -> Result<bool, event::Error> {
+    let mut transaction = db_pool.begin().await?;
+    let mut unrated = sqlx::query!(r#"SELECT id AS "id: Id<Races>", GREATEST(end_time, async_end1, async_end2, async_end3) AS "ended_at!" FROM races WHERE NOT ignored AND GREATEST(end_time, async_end1, async_end2, async_end3) IS NOT NULL AND NOT EXISTS (SELECT 1 FROM rated_races WHERE race = races.id)"#).fetch(&mut *transaction)
+        .map_ok(|row| (row.ended_at, Unrated::Race(row.id)))
+        .try_collect::<Vec<_>>().await?;
+    unrated.extend(sqlx::query!(r#"SELECT series AS "series: Series", event, kind AS "kind: AsyncKind", end_time AS "end_time!" FROM asyncs WHERE end_time <= NOW() AND EXISTS (SELECT 1 FROM async_players WHERE async_players.series = asyncs.series AND async_players.event = asyncs.event AND async_players.kind = asyncs.kind) AND NOT EXISTS (SELECT 1 FROM rated_asyncs WHERE rated_asyncs.series = asyncs.series AND rated_asyncs.event = asyncs.event AND rated_asyncs.kind = asyncs.kind)"#).fetch(&mut *transaction)
+        .map_ok(|row| (row.end_time, Unrated::Async { series: row.series, event: row.event, kind: row.kind }))
+        .try_collect::<Vec<_>>().await?);
+    transaction.commit().await?;
+    unrated.sort_by_key(|&(ended_at, _)| ended_at);
+    let mut cache = teams::Cache::new(http_client.clone());
+    let mut processed = 0;
+    for (ended_at, unrated) in unrated {
+        if processed >= BATCH_SIZE { return Ok(true) }
+        let mut transaction = db_pool.begin().await?;
+        let period = sqlx::query_scalar!(r#"SELECT nextval('rating_periods') AS "period!""#).fetch_one(&mut *transaction).await?;
+        match rate_unrated(&mut transaction, http_client, &mut cache, ended_at, period, &unrated).await {
+            Ok(Outcome::Rated(category)) => mark(&mut transaction, &unrated, period, Some(category)).await?,
+            Ok(Outcome::Skipped) => mark(&mut transaction, &unrated, period, None).await?,
+            Ok(Outcome::Pending) => {
+                // rating anything newer first would break the chronological order, so wait for this race
+                transaction.rollback().await?;
+                return Ok(false)
+            }
+            Err(e) if e.is_network_error() => return Err(e),
+            Err(e) => {
+                // a single broken race (e.g. a deleted room) shouldn't stop ratings from being updated
+                eprintln!("failed to rate {unrated}, skipping: {e} ({e:?})");
+                transaction.rollback().await?;
+                transaction = db_pool.begin().await?;
+                mark(&mut transaction, &unrated, period, None).await?;
+            }
+        }
+        transaction.commit().await?;
+        processed += 1;
+    }
+    Ok(false)
+}
+
+async fn run_inner(db_pool: &PgPool, http_client: &reqwest::Client, shutdown: &mut rocket::Shutdown) -> Result<(), event::Error> {
+    loop {
+        let backlog = update_all(db_pool, http_client).await?;
+        select! {
+            () = &mut *shutdown => break,
+            () = sleep(Duration::from_secs(if backlog { 60 } else { 10 * 60 })) => {}
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run(db_pool: PgPool, http_client: reqwest::Client, mut shutdown: rocket::Shutdown) -> Result<(), event::Error> {
+    let mut last_crash = Instant::now();
+    let mut wait_time = Duration::from_secs(1);
+    loop {
+        match run_inner(&db_pool, &http_client, &mut shutdown).await {
+            Ok(()) => break Ok(()),
+            Err(e) if e.is_network_error() => {
+                if last_crash.elapsed() >= Duration::from_secs(60 * 60 * 24) {
+                    wait_time = Duration::from_secs(1); // reset wait time after no crash for a day
+                } else {
+                    wait_time *= 2; // exponential backoff
+                }
+                if wait_time >= Duration::from_secs(2 * 60) {
+                    eprintln!("failed to update player ratings (retrying in {}): {e} ({e:?})", English.format_duration(wait_time, true));
+                }
+                sleep(wait_time).await;
+                last_crash = Instant::now();
+            }
+            Err(e) => {
+                if let Environment::Production = Environment::default() {
+                    wheel::night_report(&format!("{}/error", night_path()), Some(&format!("failed to update player ratings: {e} ({e:?})"))).await?;
+                }
+                break Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example calculation from Mark Glickman's “Example of the Glicko-2 system”. The paper rounds intermediate results, hence the tolerance.
+    #[test]
+    fn glickman_example() {
+        let rating = Rating { rating: 1500.0, deviation: 200.0, volatility: 0.06, races: 0 };
+        let updated = rating.update(&[
+            (1400.0, 30.0, 1.0),
+            (1550.0, 100.0, 0.0),
+            (1700.0, 300.0, 0.0),
+        ]);
+        assert!((updated.rating - 1464.06).abs() < 0.05, "rating: {}", updated.rating);
+        assert!((updated.deviation - 151.52).abs() < 0.05, "deviation: {}", updated.deviation);
+        assert!((updated.volatility - 0.05999).abs() < 0.00001, "volatility: {}", updated.volatility);
+        assert_eq!(updated.races, 1);
+    }
+
+    #[test]
+    fn no_games() {
+        let rating = Rating::default();
+        let updated = rating.update(&[]);
+        assert_eq!(updated.rating, rating.rating);
+        assert_eq!(updated.races, 0);
+    }
+}
@@ -0,0 +1,137 @@
+//! Reschedule requests for events where changing the start time of an already scheduled race requires the opponent's confirmation, configured using the `reschedule_confirmation` column of the `events` table.
+//!
+//! A background task marks requests as expired once they can no longer be acted upon and posts a note to the scheduling thread.
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "reschedule_request_state", rename_all = "snake_case")]
+pub(crate) enum State {
+    Pending,
+    Accepted,
+    Declined,
+    Withdrawn,
+    /// The race was rescheduled some other way, e.g. by an organizer or by a newer request.
+    Superseded,
+    Expired,
+}
+
+pub(crate) struct Request {
+    pub(crate) id: Id<RescheduleRequests>,
+    pub(crate) race: Id<Races>,
+    /// The team which asked for the race to be rescheduled. Only its opponents (or an organizer) can accept the request.
+    pub(crate) requester: Id<Teams>,
+    pub(crate) start: DateTime<Utc>,
+    pub(crate) requested_at: DateTime<Utc>,
+    pub(crate) expires_at: DateTime<Utc>,
+    pub(crate) state: State,
+}
+
+impl Request {
+    /// Loads the request and locks it for the remainder of the transaction.
+    pub(crate) async fn for_update(transaction: &mut Transaction<'_, Postgres>, id: Id<RescheduleRequests>) -> sqlx::Result<Option<Self>> {
+        Ok(sqlx::query!(r#"SELECT race AS "race: Id<Races>", requester AS "requester: Id<Teams>", start, requested_at, expires_at, state AS "state: State" FROM reschedule_requests WHERE id = $1 FOR UPDATE"#, id as _).fetch_optional(&mut **transaction).await?
+            .map(|row| Self {
+                race: row.race,
+                requester: row.requester,
+                start: row.start,
+                requested_at: row.requested_at,
+                expires_at: row.expires_at,
+                state: row.state,
+                id,
+            }))
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.state == State::Pending && self.expires_at > Utc::now()
+    }
+
+    pub(crate) async fn resolve(&self, transaction: &mut Transaction<'_, Postgres>, state: State, resolved_by: Option<Id<Users>>) -> sqlx::Result<()> {
+        sqlx::query!("UPDATE reschedule_requests SET state = $1, resolved_by = $2, resolved_at = NOW() WHERE id = $3", state as _, resolved_by as _, self.id as _).execute(&mut **transaction).await?;
+        Ok(())
+    }
+}
+
+/// Returns when a request to move a race from `current_start` to `new_start` should expire.
+///
+/// Requests stay open for at most a day, and must be resolved while both the current and the requested start time are still at least the event's minimum scheduling notice away.
+pub(crate) fn expiry(now: DateTime<Utc>, current_start: Option<DateTime<Utc>>, new_start: DateTime<Utc>, min_schedule_notice: Duration) -> DateTime<Utc> {
+    let min_schedule_notice = TimeDelta::from_std(min_schedule_notice).expect("minimum scheduling notice too long");
+    let mut expiry = (now + TimeDelta::days(1)).min(new_start - min_schedule_notice);
+    if let Some(current_start) = current_start {
+        expiry = expiry.min(current_start - min_schedule_notice);
+    }
+    expiry
+}
+
+/// Creates a new pending request, superseding any open requests for the same race.
+pub(crate) async fn create(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>, requester: Id<Teams>, requested_by: Id<Users>, channel: ChannelId, start: DateTime<Utc>, expires_at: DateTime<Utc>) -> sqlx::Result<Id<RescheduleRequests>> {
+    supersede(&mut *transaction, race).await?;
+    let id = Id::<RescheduleRequests>::new(&mut *transaction).await?;
+    sqlx::query!("INSERT INTO reschedule_requests (id, race, requester, requested_by, channel, start, requested_at, expires_at) VALUES ($1, $2, $3, $4, $5, $6, NOW(), $7)", id as _, race as _, requester as _, requested_by as _, PgSnowflake(channel) as _, start, expires_at).execute(&mut **transaction).await?;
+    Ok(id)
+}
+
+/// Marks any open requests for the given race as superseded. Should be called whenever the race's start time is changed.
+pub(crate) async fn supersede(transaction: &mut Transaction<'_, Postgres>, race: Id<Races>) -> sqlx::Result<()> {
+    sqlx::query!("UPDATE reschedule_requests SET state = 'superseded', resolved_at = NOW() WHERE race = $1 AND state = 'pending'", race as _).execute(&mut **transaction).await?;
+    Ok(())
+}
+
+async fn expire_all(db_pool: &PgPool, discord_ctx: &DiscordCtx) -> Result<(), event::Error> {
+    let mut transaction = db_pool.begin().await?;
+    let expired = sqlx::query!(r#"UPDATE reschedule_requests SET state = 'expired', resolved_at = NOW() FROM races WHERE races.id = reschedule_requests.race AND state = 'pending' AND expires_at <= NOW() RETURNING channel AS "channel: PgSnowflake<ChannelId>", reschedule_requests.start, races.game"#).fetch_all(&mut *transaction).await?;
+    transaction.commit().await?;
+    for row in expired {
+        let PgSnowflake(channel) = row.channel;
+        let mut msg = MessageBuilder::default();
+        msg.push("The request to reschedule ");
+        msg.push(if let Some(game) = row.game { format!("game {game}") } else { format!("this race") });
+        msg.push(" for ");
+        msg.push_timestamp(row.start, serenity_utils::message::TimestampStyle::LongDateTime);
+        msg.push(" has expired without a response. The race remains scheduled as before.");
+        if let Err(e) = channel.say(discord_ctx, msg.build()).await {
+            eprintln!("failed to post reschedule request expiry in {channel}: {e} ({e:?})");
+        }
+    }
+    Ok(())
+}
+
+async fn run_inner(db_pool: &PgPool, discord_ctx: &RwFuture<DiscordCtx>, shutdown: &mut rocket::Shutdown) -> Result<(), event::Error> {
+    loop {
+        expire_all(db_pool, &*discord_ctx.read().await).await?;
+        select! {
+            () = &mut *shutdown => break,
+            () = sleep(Duration::from_secs(60)) => {}
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn run(db_pool: PgPool, discord_ctx: RwFuture<DiscordCtx>, mut shutdown: rocket::Shutdown) -> Result<(), event::Error> {
+    let mut last_crash = Instant::now();
+    let mut wait_time = Duration::from_secs(1);
+    loop {
+        match run_inner(&db_pool, &discord_ctx, &mut shutdown).await {
+            Ok(()) => break Ok(()),
+            Err(e) if e.is_network_error() => {
+                if last_crash.elapsed() >= Duration::from_secs(60 * 60 * 24) {
+                    wait_time = Duration::from_secs(1); // reset wait time after no crash for a day
+                } else {
+                    wait_time *= 2; // exponential backoff
+                }
+                if wait_time >= Duration::from_secs(2 * 60) {
+                    eprintln!("failed to expire reschedule requests (retrying in {}): {e} ({e:?})", English.format_duration(wait_time, true));
+                }
+                sleep(wait_time).await;
+                last_crash = Instant::now();
+            }
+            Err(e) => {
+                if let Environment::Production = Environment::default() {
+                    wheel::night_report(&format!("{}/error", night_path()), Some(&format!("failed to expire reschedule requests: {e} ({e:?})"))).await?;
+                }
+                break Err(e)
+            }
+        }
+    }
+}
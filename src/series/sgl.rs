@@ -1,9 +1,11 @@
 use crate::{
+    conflict,
     event::{
         Data,
         InfoError,
     },
     prelude::*,
+    reschedule,
 };
 
 /// Rate limit once per minute according to DMs with tsigma6
@@ -126,6 +128,7 @@ impl Restream {
             };
             cal_event.race.schedule.set_live_start(self.when_countdown);
             if let Some(was_scheduled) = schedule_changed {
+                reschedule::supersede(&mut transaction, cal_event.race.id).await?;
                 use {
                     serenity::all::{
                         CreateAllowedMentions,
@@ -189,37 +192,40 @@ impl Restream {
                     } else {
                         Vec::default()
                     };
+                    let conflicts = conflict::for_race(&mut transaction, &cal_event.race).await?;
                     transaction.commit().await?;
                     transaction = db_pool.begin().await?;
                     if let Some(thread) = cal_event.race.scheduling_thread {
-                        let msg = if_chain! {
-                            if let French = event.language;
-                            if cal_event.race.game.is_none();
-                            if overlapping_maintenance_windows.is_empty();
-                            then {
-                                MessageBuilder::default()
-                                    .push("Votre race a été planifiée pour le ")
-                                    .push_timestamp(self.when_countdown, serenity_utils::message::TimestampStyle::LongDateTime)
-                                    .push('.')
-                                    .build()
+                        let french = matches!(event.language, French) && cal_event.race.game.is_none();
+                        let mut response_content = MessageBuilder::default();
+                        if french {
+                            response_content.push("Votre race a été planifiée pour le ");
+                        } else {
+                            response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
+                            response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
+                        }
+                        response_content.push_timestamp(self.when_countdown, serenity_utils::message::TimestampStyle::LongDateTime);
+                        response_content.push('.');
+                        for window in overlapping_maintenance_windows {
+                            response_content.push_line("");
+                            if french {
+                                response_content.push_bold("Attention :");
+                                response_content.push(" cette race pourrait coïncider avec une maintenance de racetime.gg prévue du ");
+                                response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+                                response_content.push(" au ");
                             } else {
-                                let mut response_content = MessageBuilder::default();
-                                response_content.push(if let Some(game) = cal_event.race.game { format!("Game {game}") } else { format!("This race") });
-                                response_content.push(if was_scheduled { " has been rescheduled for " } else { " is now scheduled for " });
-                                response_content.push_timestamp(self.when_countdown, serenity_utils::message::TimestampStyle::LongDateTime);
-                                response_content.push('.');
-                                for window in overlapping_maintenance_windows {
-                                    response_content.push_line("");
-                                    response_content.push_bold("Warning:");
-                                    response_content.push(" this race may overlap with racetime.gg maintenance planned for ");
-                                    response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                    response_content.push(" until ");
-                                    response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
-                                    response_content.push('.');
-                                }
-                                response_content.build()
+                                response_content.push_bold("Warning:");
+                                response_content.push(" this race may overlap with racetime.gg maintenance planned for ");
+                                response_content.push_timestamp(window.start, serenity_utils::message::TimestampStyle::ShortDateTime);
+                                response_content.push(" until ");
                             }
-                        };
+                            response_content.push_timestamp(window.end, serenity_utils::message::TimestampStyle::ShortDateTime);
+                            response_content.push('.');
+                        }
+                        for conflict in &conflicts {
+                            conflict.push_warning(&mut response_content, if french { French } else { English });
+                        }
+                        let msg = response_content.build();
                         thread.say(discord_ctx, msg).await?;
                     }
                 }
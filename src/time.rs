@@ -152,6 +152,119 @@ pub(crate) fn unparse_duration(duration: Duration) -> String {
     buf
 }
 
+/// A point in time entered by a user, e.g. as the start time of a race.
+#[derive(Clone, Copy)]
+pub(crate) enum ParsedTime {
+    /// A Discord timestamp, which can be used as-is.
+    Exact(DateTime<Utc>),
+    /// A human-readable time which should be echoed back to the user for confirmation, since it may have been misinterpreted.
+    Interpreted(DateTime<Utc>),
+}
+
+impl ParsedTime {
+    pub(crate) fn time(&self) -> DateTime<Utc> {
+        match *self {
+            Self::Exact(time) | Self::Interpreted(time) => time,
+        }
+    }
+}
+
+fn parse_timezone(s: &str) -> Option<Either<Tz, FixedOffset>> {
+    if regex_is_match!("^(?:utc|gmt|z)$"i, s) {
+        Some(Either::Left(Tz::UTC))
+    } else if let Some((_, sign, hours, minutes)) = regex_captures!("^(?:utc|gmt)? *([+-])([0-9]{1,2})(?::?([0-9]{2}))?$"i, s) {
+        let secs = 60 * 60 * hours.parse::<i32>().ok()? + if minutes.is_empty() { 0 } else { 60 * minutes.parse::<i32>().ok()? };
+        Some(Either::Right(if sign == "-" { FixedOffset::west_opt(secs)? } else { FixedOffset::east_opt(secs)? }))
+    } else {
+        s.parse().ok().map(Either::Left)
+    }
+}
+
+fn parse_time_of_day(hour: &str, minute: &str, meridiem: &str) -> Option<NaiveTime> {
+    let mut hour = hour.parse::<u32>().ok()?;
+    let minute = if minute.is_empty() { 0 } else { minute.parse().ok()? };
+    match &*meridiem.to_ascii_lowercase() {
+        "" => {}
+        "am" => if hour == 0 || hour > 12 { return None } else if hour == 12 { hour = 0 },
+        "pm" => if hour == 0 || hour > 12 { return None } else if hour < 12 { hour += 12 },
+        _ => return None,
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn local_to_utc<Z: TimeZone>(timezone: &Z, datetime: NaiveDateTime) -> Option<DateTime<Utc>> {
+    // during a DST transition, earliest picks the first of two ambiguous times and rejects nonexistent ones
+    timezone.from_local_datetime(&datetime).earliest().map(|datetime| datetime.to_utc())
+}
+
+fn next_occurrence<Z: TimeZone>(timezone: &Z, now: DateTime<Utc>, day: &str, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(timezone).date_naive();
+    let days_ahead = match &*day.to_ascii_lowercase() {
+        "" => {
+            let today_at = local_to_utc(timezone, today.and_time(time))?;
+            return if today_at > now { Some(today_at) } else { local_to_utc(timezone, today.succ_opt()?.and_time(time)) }
+        }
+        "today" | "tonight" => 0,
+        "tomorrow" => 1,
+        day => {
+            let weekday = day.get(..3)?.parse::<Weekday>().ok()?;
+            let days_ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+            let candidate = local_to_utc(timezone, (today + TimeDelta::days(days_ahead.into())).and_time(time))?;
+            return if candidate > now { Some(candidate) } else { local_to_utc(timezone, (today + TimeDelta::days(i64::from(days_ahead) + 7)).and_time(time)) }
+        }
+    };
+    local_to_utc(timezone, (today + TimeDelta::days(days_ahead)).and_time(time))
+}
+
+/// Parses a point in time entered by a user.
+///
+/// Accepts Discord timestamps, dates like `2026-11-03 19:30 Europe/Berlin`, days and times of day like `tomorrow 8pm` or `saturday 18:00 UTC`, and offsets like `in 2 hours` or `+90m`.
+/// Times without an explicit time zone are interpreted in `timezone`.
+pub(crate) fn parse_user_time(input: &str, now: DateTime<Utc>, timezone: Tz) -> Option<ParsedTime> {
+    let input = input.trim();
+    if let Some((_, timestamp)) = regex_captures!("^<t:(-?[0-9]+)(?::[tTdDfFR])?>$", input) {
+        return Utc.timestamp_opt(timestamp.parse().ok()?, 0).single().map(ParsedTime::Exact)
+    }
+    Some(ParsedTime::Interpreted(if let Some((_, days, rest)) = regex_captures!("^(?:in +|\\+ *)(?:([0-9]+) *d(?:ays?)?,? *)?(.*?)(?: +from now)?$"i, input) {
+        let mut offset = TimeDelta::days(if days.is_empty() { 0 } else { days.parse().ok()? });
+        if !rest.is_empty() {
+            // require explicit units so minutes can't be mistaken for hours
+            if !rest.contains(|c: char| c.is_ascii_alphabetic()) { return None }
+            offset += TimeDelta::from_std(parse_duration(rest, None)?).ok()?;
+        } else if days.is_empty() {
+            return None
+        }
+        now.checked_add_signed(offset)?
+    } else if let Some((_, year, month, day, hour, minute, zone)) = regex_captures!("^([0-9]{4})-([0-9]{1,2})-([0-9]{1,2})(?:,? +|T)([0-9]{1,2}):([0-9]{2})(?: *(.+))?$"i, input) {
+        let datetime = NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)?.and_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)?;
+        let zone = if zone.is_empty() { Either::Left(timezone) } else { parse_timezone(zone)? };
+        either::for_both!(zone, zone => local_to_utc(&zone, datetime))?
+    } else if let Some((_, day, hour, minute, meridiem, zone)) = regex_captures!("^(?:(today|tonight|tomorrow|mon(?:day)?|tue(?:s(?:day)?)?|wed(?:nesday)?|thu(?:r(?:s(?:day)?)?)?|fri(?:day)?|sat(?:urday)?|sun(?:day)?),?(?: +at)? +)?([0-9]{1,2})(?::([0-9]{2}))? *(am|pm)?(?: +(.+))?$"i, input) {
+        // a bare number is more likely a typo than a time of day
+        if minute.is_empty() && meridiem.is_empty() { return None }
+        let time = parse_time_of_day(hour, minute, meridiem)?;
+        let zone = if zone.is_empty() { Either::Left(timezone) } else { parse_timezone(zone)? };
+        either::for_both!(zone, zone => next_occurrence(&zone, now, day, time))?
+    } else {
+        return None
+    }))
+}
+
+/// Describes a point in time in UTC and, if given, the user's time zone, for echoing a parsed time back to the user.
+pub(crate) fn describe_time(time: DateTime<Utc>, timezone: Option<Tz>) -> String {
+    let utc = time.format("%A, %B %-d, %Y, %H:%M UTC");
+    if let Some(timezone) = timezone.filter(|&timezone| timezone != Tz::UTC) {
+        format!("{utc} ({} {})", time.with_timezone(&timezone).format("%A, %B %-d, %Y, %H:%M"), timezone.name())
+    } else {
+        utc.to_string()
+    }
+}
+
+/// Formats a point in time such that [`parse_user_time`] reads it back as the same minute.
+pub(crate) fn unparse_time(time: DateTime<Utc>, timezone: Tz) -> String {
+    format!("{} {}", time.with_timezone(&timezone).format("%Y-%m-%d %H:%M"), timezone.name())
+}
+
 pub(crate) struct DateTimeFormat {
     pub(crate) long: bool,
     pub(crate) running_text: bool,
@@ -223,3 +336,102 @@ where Z::Offset: fmt::Display {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).single().expect("invalid test date")
+    }
+
+    fn parse(input: &str, now: DateTime<Utc>, timezone: Tz) -> Option<DateTime<Utc>> {
+        parse_user_time(input, now, timezone).map(|parsed| parsed.time())
+    }
+
+    #[test]
+    fn discord_timestamps_are_exact() {
+        assert!(matches!(parse_user_time("<t:1792000000:F>", utc(2026, 10, 21, 18, 0), Tz::UTC), Some(ParsedTime::Exact(time)) if time.timestamp() == 1792000000));
+    }
+
+    #[test]
+    fn relative_times() {
+        let now = utc(2026, 10, 21, 18, 0);
+        assert_eq!(parse("in 2 hours", now, Tz::UTC), Some(utc(2026, 10, 21, 20, 0)));
+        assert_eq!(parse("+90m", now, Tz::UTC), Some(utc(2026, 10, 21, 19, 30)));
+        assert_eq!(parse("in 1 day", now, Tz::UTC), Some(utc(2026, 10, 22, 18, 0)));
+        assert_eq!(parse("+1d 2h", now, Tz::UTC), Some(utc(2026, 10, 22, 20, 0)));
+        // without units, 90 could mean minutes or hours
+        assert_eq!(parse("+90", now, Tz::UTC), None);
+    }
+
+    #[test]
+    fn past_times_and_weekdays() {
+        // a Wednesday
+        let now = utc(2026, 10, 21, 18, 0);
+        assert_eq!(parse("wednesday 17:00", now, Tz::UTC), Some(utc(2026, 10, 28, 17, 0)));
+        assert_eq!(parse("wed 19:00", now, Tz::UTC), Some(utc(2026, 10, 21, 19, 0)));
+        assert_eq!(parse("tuesday 12:00", now, Tz::UTC), Some(utc(2026, 10, 27, 12, 0)));
+        assert_eq!(parse("monday 8pm", now, Tz::UTC), Some(utc(2026, 10, 26, 20, 0)));
+        assert_eq!(parse("17:00", now, Tz::UTC), Some(utc(2026, 10, 22, 17, 0)));
+        assert_eq!(parse("19:00", now, Tz::UTC), Some(utc(2026, 10, 21, 19, 0)));
+        // an explicit “today” is taken literally even if it's in the past, so the caller can reject it
+        assert_eq!(parse("today 17:00", now, Tz::UTC), Some(utc(2026, 10, 21, 17, 0)));
+        assert_eq!(parse("tomorrow 5pm", now, Tz::UTC), Some(utc(2026, 10, 22, 17, 0)));
+        // a bare number is rejected rather than guessed
+        assert_eq!(parse("17", now, Tz::UTC), None);
+        assert_eq!(parse("13pm", now, Tz::UTC), None);
+    }
+
+    #[test]
+    fn weekdays_use_the_local_date() {
+        // still Wednesday in UTC, but already Thursday morning in Tokyo
+        let now = utc(2026, 10, 21, 23, 30);
+        let tokyo = "Asia/Tokyo".parse::<Tz>().expect("invalid test time zone");
+        assert_eq!(parse("thursday 9:00", now, tokyo), Some(utc(2026, 10, 22, 0, 0)));
+        assert_eq!(parse("thursday 8:00", now, tokyo), Some(utc(2026, 10, 28, 23, 0)));
+    }
+
+    #[test]
+    fn dst_transitions() {
+        let berlin = Europe::Berlin;
+        // clocks go forward from 02:00 to 03:00 on Sunday, March 29, 2026
+        let now = utc(2026, 3, 28, 12, 0);
+        assert_eq!(parse("tomorrow 8pm", now, berlin), Some(utc(2026, 3, 29, 18, 0)));
+        assert_eq!(parse("2026-03-29 03:30", now, berlin), Some(utc(2026, 3, 29, 1, 30)));
+        assert_eq!(parse("2026-03-29 02:30", now, berlin), None);
+        assert_eq!(parse("sunday 2:30am", now, berlin), None);
+        // a time without a day that has already passed today moves to tomorrow, which has a different UTC offset
+        assert_eq!(parse("20:00", utc(2026, 3, 28, 22, 0), berlin), Some(utc(2026, 3, 29, 18, 0)));
+        // clocks go back from 03:00 to 02:00 on Sunday, October 25, 2026, so 02:30 happens twice and the earlier one is used
+        let now = utc(2026, 10, 24, 20, 0);
+        assert_eq!(parse("tomorrow 2:30", now, berlin), Some(utc(2026, 10, 25, 0, 30)));
+        assert_eq!(parse("2026-10-25 02:30", now, berlin), Some(utc(2026, 10, 25, 0, 30)));
+        assert_eq!(parse("2026-10-25 03:30", now, berlin), Some(utc(2026, 10, 25, 2, 30)));
+    }
+
+    #[test]
+    fn time_zones() {
+        assert_eq!(parse_timezone("UTC"), Some(Either::Left(Tz::UTC)));
+        assert_eq!(parse_timezone("z"), Some(Either::Left(Tz::UTC)));
+        assert_eq!(parse_timezone("Europe/Berlin"), Some(Either::Left(Europe::Berlin)));
+        assert_eq!(parse_timezone("+5:30"), FixedOffset::east_opt(5 * 60 * 60 + 30 * 60).map(Either::Right));
+        assert_eq!(parse_timezone("GMT-3"), FixedOffset::west_opt(3 * 60 * 60).map(Either::Right));
+        assert_eq!(parse_timezone("utc+0100"), FixedOffset::east_opt(60 * 60).map(Either::Right));
+        let now = utc(2026, 10, 21, 18, 0);
+        assert_eq!(parse("2026-11-03 19:30 +5:30", now, Tz::UTC), Some(utc(2026, 11, 3, 14, 0)));
+        assert_eq!(parse("saturday 18:00 Europe/Berlin", now, Tz::UTC), Some(utc(2026, 10, 24, 16, 0)));
+        // explicit time zones take precedence over the user's time zone
+        assert_eq!(parse("2026-11-03 19:30 UTC", now, Europe::Berlin), Some(utc(2026, 11, 3, 19, 30)));
+    }
+
+    #[test]
+    fn invalid_time_zones() {
+        assert_eq!(parse_timezone("Mars/Olympus_Mons"), None);
+        assert_eq!(parse_timezone("UTC+25"), None);
+        assert_eq!(parse_timezone("+5:3"), None);
+        let now = utc(2026, 10, 21, 18, 0);
+        assert_eq!(parse("2026-11-03 19:30 Mars/Olympus_Mons", now, Tz::UTC), None);
+        assert_eq!(parse("saturday 18:00 UTC+25", now, Tz::UTC), None);
+    }
+}
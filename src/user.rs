@@ -1,8 +1,13 @@
 use {
+    std::cmp::Reverse,
     convert_case::{
         Case,
         Casing as _,
     },
+    rand::distr::{
+        Alphanumeric,
+        SampleString as _,
+    },
     sqlx::PgExecutor,
     crate::{
         auth::{
@@ -10,10 +15,37 @@ use {
             Discriminator,
             RaceTimeUser,
         },
+        availability,
+        discord_bot,
+        event::AsyncKind,
+        form::{
+            EmptyForm,
+            button_form,
+        },
         prelude::*,
+        rating::Rating,
+        seed::HashIconExt as _,
     },
 };
 
+const HISTORY_PAGE_SIZE: usize = 25;
+
+#[derive(Debug, thiserror::Error, rocket_util::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Cal(#[from] cal::Error),
+    #[error(transparent)] Data(#[from] event::DataError),
+    #[error(transparent)] Discord(#[from] discord_bot::Error),
+    #[error(transparent)] Page(#[from] PageError),
+    #[error(transparent)] Sql(#[from] sqlx::Error),
+    #[error(transparent)] Wheel(#[from] wheel::Error),
+}
+
+impl<E: Into<Error>> From<E> for StatusOrError<Error> {
+    fn from(e: E) -> Self {
+        Self::Err(e.into())
+    }
+}
+
 /// User preference that determines which external account a user's display name is be based on.
 #[derive(Debug, Clone, Copy, sqlx::Type)]
 #[sqlx(type_name = "user_display_source", rename_all = "lowercase")]
@@ -213,6 +245,11 @@ impl User {
         )
     }
 
+    /// The time zone chosen on the user's profile, used to interpret times entered without an explicit time zone.
+    pub(crate) async fn timezone(&self, pool: impl PgExecutor<'_>) -> sqlx::Result<Option<Tz>> {
+        Ok(sqlx::query_scalar!("SELECT timezone FROM users WHERE id = $1", self.id as _).fetch_one(pool).await?.and_then(|timezone| timezone.parse().ok()))
+    }
+
     pub(crate) fn display_name(&self) -> &str {
         match self.display_source {
             DisplaySource::RaceTime => &self.racetime.as_ref().expect("user with racetime.gg display preference but no racetime.gg display name").display_name,
@@ -289,7 +326,7 @@ impl fmt::Display for User {
 impl ToHtml for User {
     fn to_html(&self) -> RawHtml<String> {
         html! {
-            a(href = uri!(profile(self.id))) {
+            a(href = uri!(profile(self.id, _, _))) {
                 bdi : self.display_name();
             }
         }
@@ -304,8 +341,312 @@ impl PartialEq for User {
 
 impl Eq for User {}
 
-#[rocket::get("/user/<id>")]
-pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<'_>, racetime_user: Option<RaceTimeUser>, discord_user: Option<DiscordUser>, id: Id<Users>) -> Result<RawHtml<String>, StatusOrError<PageError>> {
+/// How a user did in a race or async of their match history.
+#[derive(Clone, Copy)]
+enum Outcome {
+    Win,
+    Draw,
+    Loss,
+    Placement {
+        place: usize,
+        entrants: usize,
+    },
+    Dnf,
+    /// No results have been recorded for this race.
+    Unknown,
+}
+
+enum HistoryKind {
+    Race(Race),
+    Async(AsyncKind),
+}
+
+struct HistoryEntry {
+    start: Option<DateTime<Utc>>,
+    event: event::Data<'static>,
+    kind: HistoryKind,
+    /// The other entrants of a race. Empty for asyncs.
+    opponents: Vec<Entrant>,
+    outcome: Outcome,
+    finish_time: Option<Duration>,
+    file_hash: Option<[HashIcon; 5]>,
+    vods: Vec<Url>,
+}
+
+/// A user's record in one event, summarized from their match history.
+struct EventSummary {
+    event: event::Data<'static>,
+    races: usize,
+    wins: usize,
+    draws: usize,
+    losses: usize,
+    asyncs: usize,
+    best_time: Option<Duration>,
+}
+
+/// Whether the entrant is the given user or one of the given teams, which should be the teams the user is a member of.
+pub(crate) fn is_user_entrant(user: &User, teams: &HashSet<Id<Teams>>, entrant: &Entrant) -> bool {
+    match entrant {
+        Entrant::MidosHouseTeam(team) => teams.contains(&team.id),
+        Entrant::Discord { id, .. } => user.discord.as_ref().is_some_and(|discord| discord.id == *id),
+        Entrant::Named { racetime_id, .. } => racetime_id.as_ref().is_some_and(|racetime_id| user.racetime.as_ref().is_some_and(|racetime| racetime.id == *racetime_id)),
+    }
+}
+
+/// One entrant's result in a race from a user's match history, without the entrant's details.
+struct HistoryResult {
+    team: Option<Id<Teams>>,
+    discord_id: Option<UserId>,
+    racetime_id: Option<String>,
+    finish_time: Option<Duration>,
+    placement: Option<i16>,
+}
+
+impl HistoryResult {
+    /// Equivalent to [`is_user_entrant`] for the entrant this result belongs to.
+    fn is_user(&self, user: &User, teams: &HashSet<Id<Teams>>) -> bool {
+        if let Some(team) = self.team {
+            teams.contains(&team)
+        } else if let Some(discord_id) = self.discord_id {
+            user.discord.as_ref().is_some_and(|discord| discord.id == discord_id)
+        } else {
+            self.racetime_id.as_ref().is_some_and(|racetime_id| user.racetime.as_ref().is_some_and(|racetime| racetime.id == *racetime_id))
+        }
+    }
+}
+
+fn race_outcome(user: &User, teams: &HashSet<Id<Teams>>, results: &[HistoryResult]) -> (Outcome, Option<Duration>) {
+    let Some((idx, result)) = results.iter().enumerate().find(|(_, result)| result.is_user(user, teams)) else { return (Outcome::Unknown, None) };
+    let outcome = if let [ref result1, ref result2] = *results {
+        let opponent = if idx == 0 { result2 } else { result1 };
+        match (result.placement.is_none(), result.placement).cmp(&(opponent.placement.is_none(), opponent.placement)) {
+            Less => Outcome::Win,
+            Equal => Outcome::Draw,
+            Greater => Outcome::Loss,
+        }
+    } else if let Some(place) = result.placement {
+        Outcome::Placement { place: usize::try_from(place).expect("placement is positive"), entrants: results.len() }
+    } else {
+        Outcome::Dnf
+    };
+    (outcome, result.finish_time)
+}
+
+enum HistoryItemKind {
+    Race(Id<Races>),
+    Async {
+        kind: AsyncKind,
+        submitted: DateTime<Utc>,
+        file_hash: Option<[HashIcon; 5]>,
+        vod: Option<Url>,
+    },
+}
+
+/// A race or async from a user's match history, with only the data needed to summarize it.
+struct HistoryItem {
+    start: Option<DateTime<Utc>>,
+    series: Series,
+    event: String,
+    kind: HistoryItemKind,
+    outcome: Outcome,
+    finish_time: Option<Duration>,
+}
+
+/// All ended races and closed qualifier asyncs in listed events the user has played in, including as a member of a team that later resigned, from newest to oldest.
+///
+/// This doesn't load the races themselves, see [`history_page`] for that.
+async fn history_items(transaction: &mut Transaction<'_, Postgres>, user: &User, teams: &HashSet<Id<Teams>>) -> Result<Vec<HistoryItem>, Error> {
+    let race_rows = sqlx::query!(r#"SELECT races.id AS "id: Id<Races>", races.series AS "series: Series", races.event, LEAST(races.start, races.async_start1, races.async_start2, races.async_start3) AS start FROM races JOIN events ON events.series = races.series AND events.event = races.event WHERE events.listed AND NOT races.ignored AND (
+        EXISTS (SELECT 1 FROM team_members WHERE member = $1 AND team IN (team1, team2, team3))
+        OR p1_discord = $2 OR p2_discord = $2
+        OR p1_racetime = $3 OR p2_racetime = $3
+    ) AND (
+        (races.start IS NOT NULL AND races.end_time IS NOT NULL)
+        OR (races.async_end1 IS NOT NULL AND races.async_end2 IS NOT NULL AND (races.async_end3 IS NOT NULL OR (races.team3 IS NULL AND races.p3 IS NULL)))
+    )"#, user.id as _, user.discord.as_ref().map(|discord| PgSnowflake(discord.id)) as _, user.racetime.as_ref().map(|racetime| &racetime.id)).fetch_all(&mut **transaction).await?;
+    let race_ids = race_rows.iter().map(|row| i64::from(row.id)).collect_vec();
+    let mut results = HashMap::<_, Vec<_>>::default();
+    for row in sqlx::query!(r#"SELECT
+        race AS "race: Id<Races>",
+        team AS "team: Id<Teams>",
+        discord_id AS "discord_id: PgSnowflake<UserId>",
+        racetime_id,
+        finish_time,
+        placement
+    FROM race_results WHERE race = ANY($1) ORDER BY placement ASC NULLS LAST, finish_time ASC NULLS LAST"#, &race_ids).fetch_all(&mut **transaction).await? {
+        results.entry(row.race).or_default().push(HistoryResult {
+            team: row.team,
+            discord_id: row.discord_id.map(|PgSnowflake(id)| id),
+            racetime_id: row.racetime_id,
+            finish_time: row.finish_time.map(decode_pginterval).transpose().map_err(event::DataError::PgInterval)?,
+            placement: row.placement,
+        });
+    }
+    let mut items = Vec::default();
+    for row in race_rows {
+        let (outcome, finish_time) = race_outcome(user, teams, results.get(&row.id).map(Vec::as_slice).unwrap_or_default());
+        items.push(HistoryItem {
+            start: row.start,
+            series: row.series,
+            event: row.event,
+            kind: HistoryItemKind::Race(row.id),
+            outcome, finish_time,
+        });
+    }
+    let async_rows = sqlx::query!(r#"SELECT
+        teams.series AS "series: Series",
+        teams.event,
+        async_teams.kind AS "kind: AsyncKind",
+        async_teams.submitted AS "submitted!",
+        async_players.time AS "time?",
+        async_players.vod AS "vod?",
+        asyncs.hash1 AS "hash1: HashIcon",
+        asyncs.hash2 AS "hash2: HashIcon",
+        asyncs.hash3 AS "hash3: HashIcon",
+        asyncs.hash4 AS "hash4: HashIcon",
+        asyncs.hash5 AS "hash5: HashIcon",
+        (SELECT COUNT(*) FROM async_players AS others WHERE others.series = teams.series AND others.event = teams.event AND others.kind = async_teams.kind AND others.time < async_players.time) + 1 AS "place!",
+        (SELECT COUNT(*) FROM async_players AS others WHERE others.series = teams.series AND others.event = teams.event AND others.kind = async_teams.kind) AS "entrants!",
+        async_players.player IS NOT NULL AS "played!"
+    FROM async_teams
+    JOIN team_members ON team_members.team = async_teams.team
+    JOIN teams ON teams.id = async_teams.team
+    JOIN asyncs ON asyncs.series = teams.series AND asyncs.event = teams.event AND asyncs.kind = async_teams.kind
+    JOIN events ON events.series = teams.series AND events.event = teams.event
+    LEFT JOIN async_players ON async_players.series = teams.series AND async_players.event = teams.event AND async_players.kind = async_teams.kind AND async_players.player = team_members.member
+    WHERE events.listed AND team_members.member = $1 AND async_teams.submitted IS NOT NULL AND (asyncs.end_time <= NOW() OR events.end_time <= NOW())"#, user.id as _).fetch_all(&mut **transaction).await?;
+    for row in async_rows {
+        let finish_time = row.time.map(decode_pginterval).transpose().map_err(event::DataError::PgInterval)?;
+        items.push(HistoryItem {
+            start: Some(row.submitted),
+            series: row.series,
+            event: row.event,
+            kind: HistoryItemKind::Async {
+                kind: row.kind,
+                submitted: row.submitted,
+                file_hash: match (row.hash1, row.hash2, row.hash3, row.hash4, row.hash5) {
+                    (Some(hash1), Some(hash2), Some(hash3), Some(hash4), Some(hash5)) => Some([hash1, hash2, hash3, hash4, hash5]),
+                    (_, _, _, _, _) => None,
+                },
+                vod: row.vod.and_then(|vod| vod.parse().ok()),
+            },
+            outcome: if !row.played {
+                Outcome::Unknown
+            } else if finish_time.is_some() {
+                Outcome::Placement { place: usize::try_from(row.place).expect("negative count"), entrants: usize::try_from(row.entrants).expect("negative count") }
+            } else {
+                Outcome::Dnf
+            },
+            finish_time,
+        });
+    }
+    items.sort_by_key(|item| (item.start.is_none(), Reverse(item.start)));
+    Ok(items)
+}
+
+/// Loads one page of the user's match history, from newest to oldest. `items` must be the user's [`history_items`].
+async fn history_page(transaction: &mut Transaction<'_, Postgres>, http_client: &reqwest::Client, user: &User, teams: &HashSet<Id<Teams>>, items: &[HistoryItem], series: Option<Series>, page: usize) -> Result<Vec<HistoryEntry>, Error> {
+    let rows = sqlx::query!(r#"SELECT race AS "race: Id<Races>", series AS "series!: Series", event AS "event!", kind AS "kind: AsyncKind" FROM (
+        SELECT races.id AS race, races.series, races.event, NULL::async_kind AS kind, LEAST(races.start, races.async_start1, races.async_start2, races.async_start3) AS start
+        FROM races JOIN events ON events.series = races.series AND events.event = races.event
+        WHERE events.listed AND NOT races.ignored AND (
+            EXISTS (SELECT 1 FROM team_members WHERE member = $1 AND team IN (team1, team2, team3))
+            OR p1_discord = $2 OR p2_discord = $2
+            OR p1_racetime = $3 OR p2_racetime = $3
+        ) AND (
+            (races.start IS NOT NULL AND races.end_time IS NOT NULL)
+            OR (races.async_end1 IS NOT NULL AND races.async_end2 IS NOT NULL AND (races.async_end3 IS NOT NULL OR (races.team3 IS NULL AND races.p3 IS NULL)))
+        )
+        UNION ALL
+        SELECT NULL, teams.series, teams.event, async_teams.kind, async_teams.submitted
+        FROM async_teams
+        JOIN team_members ON team_members.team = async_teams.team
+        JOIN teams ON teams.id = async_teams.team
+        JOIN asyncs ON asyncs.series = teams.series AND asyncs.event = teams.event AND asyncs.kind = async_teams.kind
+        JOIN events ON events.series = teams.series AND events.event = teams.event
+        WHERE events.listed AND team_members.member = $1 AND async_teams.submitted IS NOT NULL AND (asyncs.end_time <= NOW() OR events.end_time <= NOW())
+    ) AS history WHERE $4::VARCHAR IS NULL OR series = $4 ORDER BY start DESC NULLS LAST LIMIT $5 OFFSET $6"#,
+        user.id as _,
+        user.discord.as_ref().map(|discord| PgSnowflake(discord.id)) as _,
+        user.racetime.as_ref().map(|racetime| &racetime.id),
+        series.map(|series| series.slug()),
+        HISTORY_PAGE_SIZE as i64,
+        ((page - 1) * HISTORY_PAGE_SIZE) as i64,
+    ).fetch_all(&mut **transaction).await?;
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let Some(item) = items.iter().find(|item| match (&item.kind, row.race, row.kind) {
+            (HistoryItemKind::Race(id), Some(race), _) => *id == race,
+            (HistoryItemKind::Async { kind, .. }, None, Some(row_kind)) => item.series == row.series && item.event == row.event && *kind == row_kind,
+            (_, _, _) => false,
+        }) else { continue }; // match history changed between the queries
+        let event = event::Data::new(&mut *transaction, row.series, row.event).await?.expect("foreign key constraint violated");
+        entries.push(match item.kind {
+            HistoryItemKind::Race(id) => {
+                let race = Race::from_id(&mut *transaction, http_client, id).await?;
+                HistoryEntry {
+                    start: race.cal_events().filter_map(|cal_event| cal_event.start()).min(),
+                    file_hash: if race.show_seed() { race.seed.file_hash } else { None },
+                    vods: race.player_video_urls(&mut *transaction).await?.into_iter().filter(|(player, _)| player == user).map(|(_, video)| video).collect(),
+                    opponents: race.entrants.as_slice().unwrap_or_default().iter().filter(|entrant| !is_user_entrant(user, teams, entrant)).cloned().collect(),
+                    kind: HistoryKind::Race(race),
+                    outcome: item.outcome,
+                    finish_time: item.finish_time,
+                    event,
+                }
+            }
+            HistoryItemKind::Async { kind, submitted, file_hash, ref vod } => HistoryEntry {
+                start: Some(submitted),
+                kind: HistoryKind::Async(kind),
+                opponents: Vec::default(),
+                outcome: item.outcome,
+                finish_time: item.finish_time,
+                vods: vod.iter().cloned().collect(),
+                event, file_hash,
+            },
+        });
+    }
+    Ok(entries)
+}
+
+async fn event_summaries(transaction: &mut Transaction<'_, Postgres>, items: &[&HistoryItem]) -> Result<Vec<EventSummary>, Error> {
+    let mut summaries = Vec::<EventSummary>::default();
+    for item in items {
+        let summary = if let Some(summary) = summaries.iter_mut().find(|summary| summary.event.series == item.series && summary.event.event == item.event) {
+            summary
+        } else {
+            summaries.push(EventSummary {
+                event: event::Data::new(&mut *transaction, item.series, item.event.clone()).await?.expect("foreign key constraint violated"),
+                races: 0,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                asyncs: 0,
+                best_time: None,
+            });
+            summaries.last_mut().expect("just pushed")
+        };
+        match item.kind {
+            HistoryItemKind::Race(_) => summary.races += 1,
+            HistoryItemKind::Async { .. } => summary.asyncs += 1,
+        }
+        match item.outcome {
+            Outcome::Win => summary.wins += 1,
+            Outcome::Draw => summary.draws += 1,
+            Outcome::Loss => summary.losses += 1,
+            Outcome::Placement { .. } | Outcome::Dnf | Outcome::Unknown => {}
+        }
+        if let Some(finish_time) = item.finish_time {
+            if summary.best_time.is_none_or(|best_time| finish_time < best_time) {
+                summary.best_time = Some(finish_time);
+            }
+        }
+    }
+    Ok(summaries)
+}
+
+#[rocket::get("/user/<id>?<series>&<page>")]
+pub(crate) async fn profile(discord_ctx: &State<RwFuture<DiscordCtx>>, pool: &State<PgPool>, http_client: &State<reqwest::Client>, me: Option<User>, uri: Origin<'_>, csrf: Option<CsrfToken>, racetime_user: Option<RaceTimeUser>, discord_user: Option<DiscordUser>, id: Id<Users>, series: Option<&str>, page: Option<usize>) -> Result<RawHtml<String>, StatusOrError<Error>> {
     let mut transaction = pool.begin().await?;
     let user = if let Some(user) = User::from_id(&mut *transaction, id).await? {
         user
@@ -374,7 +715,7 @@ pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<
         } else {
             html! {
                 p {
-                    a(href = uri!(crate::auth::racetime_login(Some(uri!(profile(id)))))) : "Connect a racetime.gg account";
+                    a(href = uri!(crate::auth::racetime_login(Some(uri!(profile(id, _, _)))))) : "Connect a racetime.gg account";
                 }
             }
         }
@@ -464,7 +805,7 @@ pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<
         } else {
             html! {
                 p {
-                    a(href = uri!(crate::auth::discord_login(Some(uri!(profile(id)))))) : "Connect a Discord account";
+                    a(href = uri!(crate::auth::discord_login(Some(uri!(profile(id, _, _)))))) : "Connect a Discord account";
                 }
             }
         }
@@ -475,13 +816,78 @@ pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<
     events_organized.retain(|event| event.listed);
     let mut events_participated = user.events_participated(&mut transaction).await?;
     events_participated.retain(|event| event.listed);
+    let calendar = if me.as_ref().is_some_and(|me| me.id == user.id) {
+        let calendar_token = sqlx::query_scalar!("SELECT calendar_token FROM users WHERE id = $1", user.id as _).fetch_one(&mut *transaction).await?;
+        html! {
+            h2 : "Calendar";
+            @if let Some(calendar_token) = calendar_token {
+                p {
+                    : "Your personal calendar contains the races of your teams and the races you're restreaming, across all events. Subscribe to it in your calendar app using this link, and don't share it with others: ";
+                    code : uri!(base_uri(), cal::for_user(user.id, &*calendar_token));
+                }
+                : button_form(uri!(reset_calendar_token(user.id)), csrf.as_ref(), Vec::default(), "Reset Link").1;
+            } else {
+                p : "You can get a personal calendar containing the races of your teams and the races you're restreaming, across all events.";
+                : button_form(uri!(reset_calendar_token(user.id)), csrf.as_ref(), Vec::default(), "Create Calendar Link").1;
+            }
+        }
+    } else {
+        html! {}
+    };
+    let time_zone = if me.as_ref().is_some_and(|me| me.id == user.id) {
+        let timezone = user.timezone(&mut *transaction).await?;
+        html! {
+            h2 : "Time zone";
+            p : "Times you enter without a time zone, for example when scheduling races, are interpreted in this time zone.";
+            form(action = uri!(set_timezone(user.id)).to_string(), method = "post") {
+                : csrf.as_ref();
+                select(name = "timezone") {
+                    option(value = "", selected? = timezone.is_none()) : "UTC (not set)";
+                    @for tz in chrono_tz::TZ_VARIANTS {
+                        option(value = tz.name(), selected? = timezone == Some(tz)) : tz.name();
+                    }
+                }
+                : " ";
+                input(type = "submit", value = "Save");
+            }
+            p {
+                : "You can also enter your ";
+                a(href = uri!(availability::get)) : "availability";
+                : " so the times when all players are available can be suggested when scheduling races.";
+            }
+        }
+    } else {
+        html! {}
+    };
+    let ratings = Rating::all_for_user(&mut transaction, user.id).await?;
+    let teams = sqlx::query_scalar!(r#"SELECT team AS "team: Id<Teams>" FROM team_members WHERE member = $1"#, user.id as _).fetch_all(&mut *transaction).await?.into_iter().collect::<HashSet<_>>();
+    let history_items = history_items(&mut transaction, &user, &teams).await?;
+    let history_series = history_items.iter().map(|item| item.series).collect::<HashSet<_>>().into_iter().sorted_by_key(|series| series.display_name()).collect_vec();
+    let series_filter = series.map(Series::from_str).transpose().map_err(|()| StatusOrError::Status(Status::NotFound))?;
+    let filtered_items = history_items.iter().filter(|item| series_filter.is_none_or(|series| item.series == series)).collect_vec();
+    let summaries = event_summaries(&mut transaction, &filtered_items).await?;
+    let history_page = page.unwrap_or(1);
+    let history_pages = filtered_items.len().div_ceil(HISTORY_PAGE_SIZE).max(1);
+    if history_page == 0 || history_page > history_pages { return Err(StatusOrError::Status(Status::NotFound)) }
+    let history = self::history_page(&mut transaction, http_client, &user, &teams, &history_items, series_filter, history_page).await?;
+    let mut opponent_names = Vec::with_capacity(history.len());
+    {
+        let discord_ctx = discord_ctx.read().await;
+        for entry in &history {
+            let mut names = Vec::with_capacity(entry.opponents.len());
+            for opponent in &entry.opponents {
+                names.push(opponent.to_html(&mut transaction, &discord_ctx, false).await?);
+            }
+            opponent_names.push(names);
+        }
+    }
     let mut chests_events = events_organized.clone();
     chests_events.extend_from_slice(&events_participated);
     chests_events.sort_unstable_by(|e1, e2| e1.series.cmp(&e2.series).then_with(|| e1.event.cmp(&e2.event)));
     chests_events.dedup_by(|e1, e2| e1.series == e2.series && e1.event == e2.event);
     let chests_event = chests_events.choose(&mut rng());
     let chests = if let Some(event) = chests_event { event.chests().await? } else { ChestAppearances::random() };
-    Ok(page(transaction, &me, &uri, PageStyle { kind: if me.as_ref().is_some_and(|me| *me == user) { PageKind::MyProfile } else { PageKind::Other }, chests, ..PageStyle::default() }, &format!("{} — Mido's House", user.display_name()), html! {
+    Ok(crate::http::page(transaction, &me, &uri, PageStyle { kind: if me.as_ref().is_some_and(|me| *me == user) { PageKind::MyProfile } else { PageKind::Other }, chests, ..PageStyle::default() }, &format!("{} — Mido's House", user.display_name()), html! {
         h1 {
             bdi : user.display_name();
         }
@@ -491,6 +897,8 @@ pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<
         }
         : racetime;
         : discord;
+        : calendar;
+        : time_zone;
         @if user.is_archivist {
             p {
                 : "This user is an archivist: ";
@@ -519,5 +927,222 @@ pub(crate) async fn profile(pool: &State<PgPool>, me: Option<User>, uri: Origin<
                 }
             }
         }
+        @if !ratings.is_empty() {
+            h2 : "Ratings";
+            p : "Glicko-2 ratings computed from this user's races and qualifier asyncs across all Mido's House events.";
+            table {
+                thead {
+                    tr {
+                        th : "Category";
+                        th : "Rating";
+                        th : "Deviation";
+                        th : "Races";
+                    }
+                }
+                tbody {
+                    @for (category, rating) in ratings {
+                        tr {
+                            td : category.to_string();
+                            td : format!("{:.0}", rating.rating);
+                            td : format!("±{:.0}", rating.deviation);
+                            td : rating.races;
+                        }
+                    }
+                }
+            }
+        }
+        @if !history_series.is_empty() {
+            h2 : "Match history";
+            @if history_series.len() > 1 {
+                p {
+                    : "Series: ";
+                    @if series.is_some() {
+                        a(href = uri!(profile(id, _, _))) : "All";
+                    } else {
+                        : "All";
+                    }
+                    @for history_series in &history_series {
+                        : " • ";
+                        @if series == Some(history_series.slug()) {
+                            : history_series.display_name();
+                        } else {
+                            a(href = uri!(profile(id, Some(history_series.slug()), _))) : history_series.display_name();
+                        }
+                    }
+                }
+            }
+            table {
+                thead {
+                    tr {
+                        th : "Event";
+                        th : "Races";
+                        th : "Wins";
+                        th : "Draws";
+                        th : "Losses";
+                        th : "Asyncs";
+                        th : "Best finish time";
+                    }
+                }
+                tbody {
+                    @for summary in &summaries {
+                        tr {
+                            td : summary.event;
+                            td : summary.races;
+                            td : summary.wins;
+                            td : summary.draws;
+                            td : summary.losses;
+                            td : summary.asyncs;
+                            td {
+                                @if let Some(best_time) = summary.best_time {
+                                    : English.format_duration(best_time, false);
+                                } else {
+                                    : "—";
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            table {
+                thead {
+                    tr {
+                        th : "Date";
+                        th : "Event";
+                        th : "Round";
+                        th : "Opponents";
+                        th : "Result";
+                        th : "Finish time";
+                        th : "Seed";
+                        th : "VoD";
+                    }
+                }
+                tbody {
+                    @for (entry, opponent_names) in history.iter().zip_eq(opponent_names) {
+                        tr {
+                            td {
+                                @if let Some(start) = entry.start {
+                                    : format_datetime(start, DateTimeFormat { long: false, running_text: false });
+                                }
+                            }
+                            td : entry.event;
+                            @match entry.kind {
+                                HistoryKind::Race(ref race) => {
+                                    td {
+                                        a(href = uri!(cal::race_info(race.series, &*race.event, race.id))) {
+                                            : race.phase.iter().chain(&race.round).join(" ");
+                                            @if let Some(game) = race.game {
+                                                : ", game ";
+                                                : game;
+                                            }
+                                            @if race.phase.is_none() && race.round.is_none() && race.game.is_none() {
+                                                : "Race";
+                                            }
+                                        }
+                                    }
+                                }
+                                HistoryKind::Async(kind) => {
+                                    td {
+                                        @match kind {
+                                            AsyncKind::Qualifier1 | AsyncKind::Qualifier2 | AsyncKind::Qualifier3 => : "Qualifier async";
+                                            AsyncKind::Tiebreaker1 | AsyncKind::Tiebreaker2 => : "Tiebreaker async";
+                                        }
+                                    }
+                                }
+                            }
+                            td {
+                                @if opponent_names.is_empty() {
+                                    : "—";
+                                } else {
+                                    @for (idx, opponent_name) in opponent_names.into_iter().enumerate() {
+                                        @if idx > 0 {
+                                            : ", ";
+                                        }
+                                        : opponent_name;
+                                    }
+                                }
+                            }
+                            td {
+                                @match entry.outcome {
+                                    Outcome::Win => : "Win";
+                                    Outcome::Draw => : "Draw";
+                                    Outcome::Loss => : "Loss";
+                                    Outcome::Placement { place, entrants } => : format!("{place} of {entrants}");
+                                    Outcome::Dnf => : "DNF";
+                                    Outcome::Unknown => : "—";
+                                }
+                            }
+                            td {
+                                @if let Some(finish_time) = entry.finish_time {
+                                    : English.format_duration(finish_time, false);
+                                }
+                            }
+                            td {
+                                @if let Some(file_hash) = entry.file_hash {
+                                    div(class = "hash") {
+                                        @for hash_icon in file_hash {
+                                            : hash_icon.to_html();
+                                        }
+                                    }
+                                }
+                            }
+                            td {
+                                @for (idx, vod) in entry.vods.iter().enumerate() {
+                                    @if idx > 0 {
+                                        : " ";
+                                    }
+                                    a(href = vod.to_string()) : "VoD";
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if history_pages > 1 {
+                p {
+                    @if history_page > 1 {
+                        a(href = uri!(profile(id, series, Some(history_page - 1)))) : "Newer";
+                        : " • ";
+                    }
+                    : format!("Page {history_page} of {history_pages}");
+                    @if history_page < history_pages {
+                        : " • ";
+                        a(href = uri!(profile(id, series, Some(history_page + 1)))) : "Older";
+                    }
+                }
+            }
+        }
     }).await?)
 }
+
+/// Creates a new token for the user's personal calendar, invalidating the previous link if any.
+#[rocket::post("/user/<id>/calendar-token", data = "<form>")]
+pub(crate) async fn reset_calendar_token(pool: &State<PgPool>, me: User, csrf: Option<CsrfToken>, id: Id<Users>, form: Form<Contextual<'_, EmptyForm>>) -> Result<Redirect, StatusOrError<Error>> {
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    if form.value.is_none() || me.id != id { return Err(StatusOrError::Status(Status::Forbidden)) }
+    sqlx::query!("UPDATE users SET calendar_token = $1 WHERE id = $2", Alphanumeric.sample_string(&mut rng(), 32), id as _).execute(&**pool).await?;
+    Ok(Redirect::to(uri!(profile(id, _, _))))
+}
+
+#[derive(FromForm, CsrfForm)]
+pub(crate) struct TimezoneForm {
+    #[field(default = String::new())]
+    csrf: String,
+    #[field(default = String::new())]
+    timezone: String,
+}
+
+#[rocket::post("/user/<id>/timezone", data = "<form>")]
+pub(crate) async fn set_timezone(pool: &State<PgPool>, me: User, csrf: Option<CsrfToken>, id: Id<Users>, form: Form<Contextual<'_, TimezoneForm>>) -> Result<Redirect, StatusOrError<Error>> {
+    let mut form = form.into_inner();
+    form.verify(&csrf);
+    let Some(ref value) = form.value else { return Err(StatusOrError::Status(Status::Forbidden)) };
+    if me.id != id { return Err(StatusOrError::Status(Status::Forbidden)) }
+    let timezone = if value.timezone.is_empty() {
+        None
+    } else {
+        Some(value.timezone.parse::<Tz>().map_err(|_| StatusOrError::Status(Status::BadRequest))?)
+    };
+    sqlx::query!("UPDATE users SET timezone = $1 WHERE id = $2", timezone.map(|timezone| timezone.name()), id as _).execute(&**pool).await?;
+    Ok(Redirect::to(uri!(profile(id, _, _))))
+}